@@ -0,0 +1,183 @@
+## Errors returned by commands
+
+access-denied = Zugriff verweigert: Pfad liegt außerhalb der erlaubten Ordner
+file-not-found = Datei existiert nicht
+file-not-found-path = Datei existiert nicht: { $path }
+round-not-finished = Die Runde ist noch nicht beendet
+invalid-argument = Externes Programm nicht gestartet: { $reason }
+rate-limited = Zu viele Fenster auf einmal geöffnet; bitte in ein paar Sekunden erneut versuchen
+program-missing = { $program } ist nicht installiert oder nicht im PATH
+read-only-mode = Schreibschutz ist aktiv; Dateien werden nicht kopiert, verschoben oder verworfen
+session-conflict = Die Sitzung wurde an anderer Stelle geändert (jetzt Revision { $revision }); bitte neu laden, bevor weitere Änderungen folgen
+reel-too-long = Die Vorhörrolle würde die WAV-Grenze von 4 GB überschreiten; bitte die Mindestpunktzahl erhöhen
+file-too-large = { $file } ist { $size } MB groß; Dateien über { $limit } MB werden nicht dekodiert
+undo-copy-changed = { $path } wurde nach dem Kopieren geändert und deshalb nicht gelöscht
+undo-copy-unverified = Kein Export-Manifest führt { $path } auf; die Datei wurde nicht gelöscht
+undo-link-changed = { $path } verweist nicht mehr auf das Original und wurde deshalb nicht gelöscht
+vote-needs-voter = Ein Stimmen-Quorum ist gesetzt; bitte vor dem Abstimmen der Sitzung beitreten
+hook-shell-unsupported = Shell-Hooks werden nicht mehr ausgeführt; bitte Programm und Argumente einzeln angeben
+sample-not-in-tournament = Das Sample gehört nicht zu diesem Turnier
+redemption-too-few = Nicht genug ausgeschiedene Samples für eine Hoffnungsrunde
+merge-into-itself = Ein Sample kann nicht mit sich selbst zusammengeführt werden
+merge-not-in-tournament = Beide Samples müssen noch im Turnier sein
+merge-no-saves = Keine Spielstände zum Zusammenführen angegeben
+unknown-preset = Unbekannte Voreinstellung
+unknown-tournament = Unbekanntes Turnier
+unknown-session = Keine offene Sitzung { $id }
+unknown-log-level = Unbekannte Protokollstufe: { $level }
+name-empty = Der Name darf nicht leer sein
+threshold-out-of-range = Schwellenwerte müssen zwischen 0 und 1 liegen
+unsupported-audio-file = Keine unterstützte Audiodatei
+directory-not-found = Ordner existiert nicht
+invalid-save-path = Ungültiger Speicherpfad
+invalid-archive-entry = Ungültiger Archiveintrag
+invalid-video-path = Ungültiger Videopfad
+archive-unsafe-entry = Unsicherer Eintragsname im Archiv: { $name }
+backup-not-found = Sicherung existiert nicht
+save-not-passphrase = Die Datei ist nicht mit einer Passphrase verschlüsselt
+save-password-required = Die Sitzungsdatei ist verschlüsselt; ein Passwort wird benötigt
+not-save-file = Keine vorhandene VS-1-Sitzungsdatei
+not-music-library = Keine Musik-Mediathek
+not-export-manifest = Kein Export-Manifest: { $error }
+manifest-with-password = Ein verschlüsselter Export kann kein Manifest haben; es würde jede Datei im Klartext auflisten
+undo-already-undone = Der Vorgang wurde bereits rückgängig gemacht
+undo-restore-exists = Wiederherstellen nicht möglich, die Datei existiert bereits: { $path }
+undo-unknown-operation = Unbekannter Vorgang
+csv-column-missing = Spalte „{ $column }“ fehlt in der CSV-Kopfzeile
+mapping-needs-column = Die Zuordnung braucht eine Pfad- oder Dateinamenspalte
+deep-link-invalid = Kein VS-1-Link: { $url }
+deep-link-missing-parameter = Dem Link fehlt der Parameter „{ $name }“
+vote-already-decided = Dieser Vergleich ist bereits entschieden
+vote-unknown-voter = Unbekannte Stimme; bitte zuerst der Sitzung beitreten
+vote-not-current = Dieser Vergleich ist nicht mehr aktuell
+vote-not-in-pair = Der Gewinner muss eines der beiden verglichenen Samples sein
+plugin-invalid-name = Ungültiger Plugin-Name: { $name }
+plugin-not-found = Plugin nicht gefunden: { $name }
+plugin-no-memory = Plugin { $name } exportiert keinen Speicher
+plugin-failed = Plugin { $name } ist fehlgeschlagen: { $error }
+plugin-out-of-bounds = Plugin { $name } hat ein Ergebnis außerhalb seines Speichers geliefert
+plugin-invalid-json = Plugin { $name } hat ungültiges JSON geliefert: { $error }
+plugin-invalid-pairings = Plugin { $name } hat ungültige Paarungen geliefert
+reorder-too-many = In dieser Runde sind nur noch { $count } Vergleiche übrig
+reorder-invalid = Die neue Reihenfolge muss jede Position genau einmal enthalten
+no-audio-track = Keine Audiospur
+unknown-audio-format = Das Audioformat konnte nicht bestimmt werden
+audio-format-changes = Das Audioformat ändert sich innerhalb der Datei
+video-not-analyzed = Videodateien werden nicht analysiert
+video-extract-failed = ffmpeg konnte den Ton nicht extrahieren: { $error }
+program-failed = { $program } konnte nicht ausgeführt werden: { $error }
+cloud-download-timeout = Zeitüberschreitung beim Herunterladen der Datei aus iCloud
+copy-verification-failed = Prüfung fehlgeschlagen: Die Kopie stimmt nicht mit dem Original überein
+link-archive-entry = Archiveinträge können nicht verlinkt werden; bitte stattdessen kopieren
+ableton-library-not-found = Keine Ableton User Library gefunden; bitte den Ordner auswählen
+export-script-failed = Exportskript fehlgeschlagen: { $error }
+export-script-not-found = Exportskript nicht gefunden: { $name }
+export-script-not-string = Das Exportskript muss einen Text liefern, nicht { $type }
+hook-failed = Hook konnte nicht ausgeführt werden: { $error }
+hook-exited = Hook endete mit { $status }: { $error }
+standings-not-published = Die Wertung dieser Runde wurde noch nicht veröffentlicht
+server-failed = Server konnte nicht gestartet werden: { $error }
+server-no-address = Der Server hat keine IP-Adresse
+template-unclosed = Nicht geschlossener Platzhalter in „{ $template }“
+template-unknown-placeholder = Unbekannter Platzhalter {"{"}{ $name }{"}"}; möglich sind { $fields }
+template-invalid-format = Ungültiges Format „{ $format }“ für {"{"}{ $name }{"}"}
+template-outside-folder = Vorlagen müssen innerhalb des Exportordners bleiben
+preflight-no-parent = Das Ziel hat keinen vorhandenen übergeordneten Ordner
+preflight-not-writable = Das Ziel ist nicht beschreibbar: { $error }
+preflight-free-space-unknown = Freier Speicherplatz konnte nicht ermittelt werden: { $error }
+preflight-not-enough-space = Nicht genug freier Speicherplatz: { $needed } Bytes benötigt, { $available } Bytes verfügbar
+
+## Native confirmations for requests that may not come from the user
+
+confirm-allow = Erlauben
+confirm-cancel = Abbrechen
+confirm-open-save-title = Sitzung öffnen?
+confirm-open-save = Ein Link möchte { $path } öffnen. Das laufende Turnier wird ersetzt und nicht gespeicherte Stimmen gehen verloren.
+confirm-source-folder-title = Sample-Ordner erlauben?
+confirm-source-folder = Die Samples dieser Sitzung liegen in { $path }. Darf VS-1 diesen Ordner lesen?
+
+## Notifications for long-running jobs
+
+scan-complete = Scan abgeschlossen
+scan-complete-body = { $count ->
+    [one] Ein Sample
+   *[other] { $count } Samples
+} gefunden
+analysis-complete = Analyse abgeschlossen
+analysis-complete-body = { $count ->
+    [one] Ein Sample
+   *[other] { $count } Samples
+} analysiert
+export-complete = Export abgeschlossen
+export-complete-body = { $count ->
+    [one] Ein Sample
+   *[other] { $count } Samples
+} kopiert ({ $failed } fehlgeschlagen)
+reel-complete = Vorhörrolle bereit
+reel-complete-body = { $count ->
+    [one] Ein Sample
+   *[other] { $count } Samples
+} gerendert ({ $failed } fehlgeschlagen)
+archive-complete = Sitzungsarchiv bereit
+archive-complete-body = { $count ->
+    [one] Ein Sample
+   *[other] { $count } Samples
+} gepackt
+
+## Report headings
+
+heading-rank = Rang
+heading-filename = Dateiname
+heading-score = Punkte
+heading-comparisons = Vergleiche
+heading-win-rate = Siegquote
+heading-brightness = Helligkeit
+heading-flags = Auffälligkeiten
+heading-pack = Pack
+heading-samples = Samples
+heading-mean-score = Mittlere Punkte
+heading-best = Beste
+heading-top-ten = Top 10
+heading-round = Runde
+heading-advanced = Weiter
+heading-eliminated = Ausgeschieden
+report-title = Shootout-Bericht
+report-overview = Runde { $round }, { $samples } Samples, { $comparisons } Vergleiche
+report-standings = Rangliste
+report-comparisons-per-round = Vergleiche pro Runde
+report-advanced-per-round = Weiter und ausgeschieden pro Runde
+report-packs = Packs
+
+## Summary card; the card's font only covers ASCII
+
+summary-title = Sample-Shootout: Ergebnisse
+summary-subtitle = { $date } - { $count } Samples bewertet
+
+## Spoken sample descriptions for screen readers
+
+describe-category = Vermutlich: { $category }.
+describe-overview = { $duration }, { $loudness }, { $brightness }.
+describe-root-tempo = Grundton { $note }, { $bpm } BPM.
+describe-root = Grundton { $note }.
+describe-tempo = { $bpm } BPM.
+describe-mono = Mono.
+describe-clipping = Übersteuert.
+duration-milliseconds = { $milliseconds } Millisekunden
+duration-seconds = { $seconds } Sekunden
+duration-minutes = { $minutes } Minuten { $seconds } Sekunden
+loudness-very-loud = sehr laut
+loudness-loud = laut
+loudness-moderate = mittellaut
+loudness-quiet = leise
+loudness-very-quiet = sehr leise
+brightness-dark = dunkel
+brightness-balanced = ausgewogen
+brightness-bright = hell
+category-loop = Loop
+category-sustained-tonal = gehaltener tonaler Klang
+category-bass-or-kick = Bass oder Kick
+category-pitched-one-shot = tonaler One-Shot
+category-hi-hat-or-cymbal = Hi-Hat oder Becken
+category-noise-or-texture = Rauschen oder Textur
+category-low-percussion = Kick oder tiefe Percussion
+category-percussive-one-shot = perkussiver One-Shot
@@ -0,0 +1,183 @@
+## Errors returned by commands
+
+access-denied = Access denied: path is outside allowed directories
+file-not-found = File does not exist
+file-not-found-path = File does not exist: { $path }
+round-not-finished = The round is not finished yet
+invalid-argument = Refused to launch external program: { $reason }
+rate-limited = Too many windows opened at once; try again in a few seconds
+program-missing = { $program } is not installed or not on the PATH
+read-only-mode = Read-only mode is on; files are not copied, moved or discarded
+session-conflict = The session was changed elsewhere (now at revision { $revision }); reload it before making more changes
+reel-too-long = The audition reel would pass the 4 GB WAV limit; raise the minimum score
+file-too-large = { $file } is { $size } MB; files over { $limit } MB are not decoded
+undo-copy-changed = { $path } changed after it was copied, so it was left in place
+undo-copy-unverified = No export manifest lists { $path }, so it was left in place
+undo-link-changed = { $path } no longer links to the original, so it was left in place
+vote-needs-voter = A vote quorum is set; join the session before voting
+hook-shell-unsupported = Shell hooks are no longer run; list the program and its arguments instead
+sample-not-in-tournament = Sample is not part of this tournament
+redemption-too-few = Not enough eliminated samples for a redemption round
+merge-into-itself = Cannot merge a sample into itself
+merge-not-in-tournament = Both samples must still be in the tournament
+merge-no-saves = No saves given to merge
+unknown-preset = Unknown preset
+unknown-tournament = Unknown tournament
+unknown-session = No open session { $id }
+unknown-log-level = Unknown log level: { $level }
+name-empty = Name must not be empty
+threshold-out-of-range = Thresholds must be between 0 and 1
+unsupported-audio-file = Not a supported audio file
+directory-not-found = Directory does not exist
+invalid-save-path = Invalid save path
+invalid-archive-entry = Invalid archive entry
+invalid-video-path = Invalid video path
+archive-unsafe-entry = Unsafe entry name in archive: { $name }
+backup-not-found = Backup does not exist
+save-not-passphrase = File is not passphrase-encrypted
+save-password-required = Save file is encrypted; a password is required
+not-save-file = Not an existing VS-1 save file
+not-music-library = Not a Music library file
+not-export-manifest = Not an export manifest: { $error }
+manifest-with-password = An encrypted export can't have a manifest; it would list every file in plain text
+undo-already-undone = Operation was already undone
+undo-restore-exists = Cannot restore, file already exists: { $path }
+undo-unknown-operation = Unknown operation
+csv-column-missing = Column '{ $column }' not found in CSV header
+mapping-needs-column = Mapping needs a path or filename column
+deep-link-invalid = Not a VS-1 link: { $url }
+deep-link-missing-parameter = The link is missing its '{ $name }' parameter
+vote-already-decided = This comparison has already been decided
+vote-unknown-voter = Unknown voter; join the session first
+vote-not-current = This comparison is no longer current
+vote-not-in-pair = The winner must be one of the two samples being compared
+plugin-invalid-name = Invalid plugin name: { $name }
+plugin-not-found = Plugin not found: { $name }
+plugin-no-memory = Plugin { $name } does not export memory
+plugin-failed = Plugin { $name } failed: { $error }
+plugin-out-of-bounds = Plugin { $name } returned an out-of-bounds result
+plugin-invalid-json = Plugin { $name } returned invalid JSON: { $error }
+plugin-invalid-pairings = Plugin { $name } returned invalid pairings
+reorder-too-many = Only { $count } comparisons are left this round
+reorder-invalid = The new order must list each position once
+no-audio-track = No audio track
+unknown-audio-format = Could not determine the audio format
+audio-format-changes = The audio format changes partway through the file
+video-not-analyzed = Video files are not analyzed
+video-extract-failed = ffmpeg could not extract audio: { $error }
+program-failed = Failed to run { $program }: { $error }
+cloud-download-timeout = Timed out waiting for iCloud to download the file
+copy-verification-failed = Verification failed: copied file does not match the source
+link-archive-entry = Archive entries can't be linked; export copies instead
+ableton-library-not-found = No Ableton User Library found; choose its folder instead
+export-script-failed = Export script failed: { $error }
+export-script-not-found = Export script not found: { $name }
+export-script-not-string = Export script must return a string, got { $type }
+hook-failed = Failed to run hook: { $error }
+hook-exited = Hook exited with { $status }: { $error }
+standings-not-published = Standings for this round haven't been published
+server-failed = Failed to start server: { $error }
+server-no-address = Server has no IP address
+template-unclosed = Unclosed placeholder in "{ $template }"
+template-unknown-placeholder = Unknown placeholder {"{"}{ $name }{"}"}; use one of { $fields }
+template-invalid-format = Invalid format "{ $format }" for {"{"}{ $name }{"}"}
+template-outside-folder = Templates must stay inside the export folder
+preflight-no-parent = Destination has no existing parent directory
+preflight-not-writable = Destination is not writable: { $error }
+preflight-free-space-unknown = Could not determine free space: { $error }
+preflight-not-enough-space = Not enough free space: { $needed } bytes needed, { $available } bytes available
+
+## Native confirmations for requests that may not come from the user
+
+confirm-allow = Allow
+confirm-cancel = Cancel
+confirm-open-save-title = Open session?
+confirm-open-save = A link asks to open { $path }. The current tournament is replaced and votes that were not saved are lost.
+confirm-source-folder-title = Allow sample folder?
+confirm-source-folder = This session's samples are in { $path }. Allow VS-1 to read that folder?
+
+## Notifications for long-running jobs
+
+scan-complete = Scan complete
+scan-complete-body = Found { $count ->
+    [one] one sample
+   *[other] { $count } samples
+}
+analysis-complete = Analysis complete
+analysis-complete-body = Analyzed { $count ->
+    [one] one sample
+   *[other] { $count } samples
+}
+export-complete = Export complete
+export-complete-body = Copied { $count ->
+    [one] one sample
+   *[other] { $count } samples
+} ({ $failed } failed)
+reel-complete = Audition reel ready
+reel-complete-body = Rendered { $count ->
+    [one] one sample
+   *[other] { $count } samples
+} ({ $failed } failed)
+archive-complete = Session archive ready
+archive-complete-body = Packed { $count ->
+    [one] one sample
+   *[other] { $count } samples
+}
+
+## Report headings
+
+heading-rank = Rank
+heading-filename = Filename
+heading-score = Score
+heading-comparisons = Comparisons
+heading-win-rate = Win Rate
+heading-brightness = Brightness
+heading-flags = Flags
+heading-pack = Pack
+heading-samples = Samples
+heading-mean-score = Mean Score
+heading-best = Best
+heading-top-ten = Top 10
+heading-round = Round
+heading-advanced = Advanced
+heading-eliminated = Eliminated
+report-title = Shootout Report
+report-overview = Round { $round }, { $samples } samples, { $comparisons } comparisons
+report-standings = Standings
+report-comparisons-per-round = Comparisons per Round
+report-advanced-per-round = Advanced and Eliminated per Round
+report-packs = Packs
+
+## Summary card; the card's font only covers ASCII
+
+summary-title = Sample Shootout Results
+summary-subtitle = { $date } - { $count } samples ranked
+
+## Spoken sample descriptions for screen readers
+
+describe-category = Probably a { $category }.
+describe-overview = { $duration }, { $loudness }, { $brightness }.
+describe-root-tempo = Root { $note }, { $bpm } BPM.
+describe-root = Root { $note }.
+describe-tempo = { $bpm } BPM.
+describe-mono = Mono.
+describe-clipping = Clips.
+duration-milliseconds = { $milliseconds } milliseconds
+duration-seconds = { $seconds } seconds
+duration-minutes = { $minutes } minutes { $seconds } seconds
+loudness-very-loud = very loud
+loudness-loud = loud
+loudness-moderate = moderate
+loudness-quiet = quiet
+loudness-very-quiet = very quiet
+brightness-dark = dark
+brightness-balanced = balanced
+brightness-bright = bright
+category-loop = loop
+category-sustained-tonal = sustained tonal sound
+category-bass-or-kick = bass or kick
+category-pitched-one-shot = pitched one-shot
+category-hi-hat-or-cymbal = hi-hat or cymbal
+category-noise-or-texture = noise or texture
+category-low-percussion = kick or low percussion
+category-percussive-one-shot = percussive one-shot
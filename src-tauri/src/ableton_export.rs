@@ -0,0 +1,284 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+use crate::copy_export::{self, CopyFailure};
+use crate::export::{passes_min_score, sorted_results};
+use crate::jobs::{self, JobKind};
+use crate::journal::{Journal, OperationKind};
+use crate::path_template::{self, PathTemplate};
+use crate::settings::SettingsStore;
+use crate::{archive, audit, i18n, manifest, variations, AllowedPaths, Sample};
+
+/// Copies are named `001 kick.wav` and so on, so Live's browser lists them by rank
+const RANKED_NAME: &str = "{rank:03} {filename}";
+
+/// Where collections go inside the User Library
+const SAMPLES_FOLDER: &str = "Samples";
+
+/// How winners are put into the User Library
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaceMode {
+    #[default]
+    Copy,
+    /// Links back to the originals; takes no space but breaks when they move
+    Symlink,
+}
+
+/// Which winners `export_to_ableton` places, where and how
+#[derive(Debug, Deserialize, Clone)]
+pub struct AbletonExportOptions {
+    /// Defaults to the detected User Library
+    pub library_dir: Option<String>,
+    pub collection_name: String,
+    pub min_score: i32,
+    pub mode: Option<PlaceMode>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AbletonExport {
+    pub folder: String,
+    pub placed: usize,
+    pub failed: Vec<CopyFailure>,
+}
+
+/// The User Library Live creates on first launch, if there is one
+fn default_user_library(app: &AppHandle) -> Option<PathBuf> {
+    let paths = app.path();
+    [paths.audio_dir(), paths.document_dir()]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join("Ableton").join("User Library"))
+        .find(|dir| dir.is_dir())
+}
+
+/// Star rating for the sample at `index` of `total`: the top fifth gets five stars
+fn stars(index: usize, total: usize) -> u8 {
+    5 - (index * 5 / total.max(1)).min(4) as u8
+}
+
+/// Colour label matching the stars, using the names Bridge and Lightroom use
+fn label(stars: u8) -> &'static str {
+    match stars {
+        5 => "Red",
+        4 => "Orange",
+        3 => "Yellow",
+        2 => "Green",
+        _ => "Blue",
+    }
+}
+
+/// Standard XMP sidecar with the rating and colour label. Live ignores it, but tag
+/// browsers and most sample managers read it, and it costs nothing beside a copy.
+fn write_sidecar(file: &Path, stars: u8) -> Result<(), String> {
+    let content = format!(
+        concat!(
+            "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n",
+            " <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n",
+            "  <rdf:Description rdf:about=\"\" xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\"",
+            " xmp:Rating=\"{}\" xmp:Label=\"{}\"/>\n",
+            " </rdf:RDF>\n",
+            "</x:xmpmeta>\n"
+        ),
+        stars,
+        label(stars)
+    );
+    let mut sidecar = file.as_os_str().to_owned();
+    sidecar.push(".xmp");
+    fs::write(&sidecar, content).map_err(|e| e.to_string())
+}
+
+#[cfg(unix)]
+fn link(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(source, target)
+}
+
+#[cfg(windows)]
+fn link(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(source, target)
+}
+
+/// Link ranked samples into `folder`, replacing links left by an earlier export
+fn link_samples(
+    samples: &[&Sample],
+    allowed_paths: &AllowedPaths,
+    folder: &Path,
+    template: &PathTemplate,
+    on_progress: impl Fn(u64),
+) -> (Vec<(String, String)>, Vec<CopyFailure>) {
+    let mut linked = Vec::new();
+    let mut failed = Vec::new();
+    for (index, sample) in samples.iter().enumerate() {
+        on_progress(index as u64);
+        if !allowed_paths.is_path_allowed(Path::new(&sample.path)) {
+            failed.push(CopyFailure {
+                source: sample.path.clone(),
+                error: i18n::t("access-denied"),
+            });
+            continue;
+        }
+        if archive::split_archive_path(&sample.path).is_some() {
+            failed.push(CopyFailure {
+                source: sample.path.clone(),
+                error: i18n::t("link-archive-entry"),
+            });
+            continue;
+        }
+        let target = folder.join(template.render(&path_template::TemplateValues {
+            sample,
+            rank: index + 1,
+            filename: &sample.filename,
+            note: None,
+        }));
+        if fs::symlink_metadata(&target).is_ok_and(|m| m.file_type().is_symlink()) {
+            let _ = fs::remove_file(&target);
+        }
+        match link(Path::new(&sample.path), &target) {
+            Ok(()) => {
+                audit::record_write(&target, Some(&sample.path));
+                linked.push((sample.path.clone(), target.to_string_lossy().to_string()));
+            }
+            Err(e) => failed.push(CopyFailure {
+                source: sample.path.clone(),
+                error: e.to_string(),
+            }),
+        }
+    }
+    (linked, failed)
+}
+
+/// Path of Live's User Library when it is where Live puts it by default
+#[tauri::command]
+pub fn ableton_user_library(app: AppHandle) -> Option<String> {
+    default_user_library(&app).map(|dir| dir.to_string_lossy().to_string())
+}
+
+/// Put the winners into `Samples/<collection>` of Live's User Library, named by rank so
+/// the browser lists them best first, each with an XMP sidecar carrying a star rating
+/// and colour label by rank fifth.
+#[tauri::command]
+pub async fn export_to_ableton(
+    samples: Vec<Sample>,
+    options: AbletonExportOptions,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+    settings: State<'_, SettingsStore>,
+) -> Result<AbletonExport, String> {
+    settings.ensure_writable()?;
+    let AbletonExportOptions {
+        library_dir,
+        collection_name,
+        min_score,
+        mode,
+    } = options;
+    let library = match library_dir {
+        Some(dir) => {
+            let dir = PathBuf::from(dir);
+            allowed_paths.check_file(&dir)?;
+            dir
+        }
+        // Found by the backend itself, so there is nothing to check
+        None => default_user_library(&app).ok_or_else(|| i18n::t("ableton-library-not-found"))?,
+    };
+    // The library the user exported into is allowed from now on
+    allowed_paths.add_allowed_path(library.clone());
+
+    let name = path_template::sanitize(collection_name.trim());
+    let name = if name.is_empty() {
+        "vs1".to_string()
+    } else {
+        name
+    };
+    let folder = library.join(SAMPLES_FOLDER).join(name);
+    let mode = mode.unwrap_or_default();
+
+    let job_app = app.clone();
+    let (result, written) = tauri::async_runtime::spawn_blocking(move || {
+        let template = PathTemplate::parse(RANKED_NAME)?;
+        let ranked: Vec<&Sample> = sorted_results(&samples)
+            .into_iter()
+            .filter(|s| passes_min_score(s, min_score))
+            .collect();
+        let members = variations::expand(&ranked);
+        let members: Vec<&Sample> = members.iter().collect();
+        fs::create_dir_all(&folder).map_err(|e| e.to_string())?;
+        let job = jobs::start(&job_app, JobKind::Export, Some(members.len() as u64));
+        let allowed_paths = job_app.state::<AllowedPaths>();
+
+        // `placed` also holds copies resumed from an earlier run, `written` only new ones
+        let (placed, written, mut failed) = match mode {
+            PlaceMode::Copy => {
+                let report = copy_export::copy_samples(
+                    &members,
+                    &allowed_paths,
+                    &folder,
+                    None,
+                    Some(&template),
+                    |done| job.set_done(done),
+                )?;
+                let placed: Vec<(String, String)> =
+                    manifest::load(Path::new(&report.manifest_path))
+                        .files
+                        .into_iter()
+                        .map(|entry| {
+                            let target = folder.join(&entry.destination);
+                            (entry.source, target.to_string_lossy().to_string())
+                        })
+                        .collect();
+                (placed, report.written, report.failed)
+            }
+            PlaceMode::Symlink => {
+                let (linked, failed) =
+                    link_samples(&members, &allowed_paths, &folder, &template, |done| {
+                        job.set_done(done)
+                    });
+                (linked.clone(), linked, failed)
+            }
+        };
+
+        // Ratings follow rank in the full list, so a failed file doesn't shift the rest
+        let placed: Vec<(usize, &str)> = placed
+            .iter()
+            .filter_map(|(source, target)| {
+                let index = members.iter().position(|s| &s.path == source)?;
+                Some((index, target.as_str()))
+            })
+            .collect();
+        for &(index, target) in &placed {
+            let stars = stars(index, members.len());
+            if let Err(error) = write_sidecar(Path::new(target), stars) {
+                tracing::warn!(rank = index + 1, %error, "xmp sidecar not written");
+                failed.push(CopyFailure {
+                    source: members[index].path.clone(),
+                    error,
+                });
+            }
+        }
+
+        tracing::info!(
+            placed = placed.len(),
+            failed = failed.len(),
+            ?mode,
+            "ableton export finished"
+        );
+        let result = AbletonExport {
+            folder: folder.to_string_lossy().to_string(),
+            placed: placed.len(),
+            failed,
+        };
+        Ok::<_, String>((result, written))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    let kind = match mode {
+        PlaceMode::Copy => OperationKind::Copy,
+        PlaceMode::Symlink => OperationKind::Link,
+    };
+    if !written.is_empty() {
+        app.state::<Journal>().record_batch(kind, &written)?;
+    }
+    Ok(result)
+}
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+
+use crate::export::ELIMINATED_SCORE;
+use crate::pins::Pin;
+use crate::{rematches, Sample, TournamentState};
+
+/// Percentage carry is worth at most this many wins, so the next round's +1 per win
+/// still decides it
+const CARRY_POINTS: f32 = 2.0;
+
+/// What advancing samples take into the next round; applied by `advance` after the cut
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ScoreCarry {
+    /// Wins keep adding up, so an early blowout counts for the whole tournament
+    #[default]
+    Raw,
+    /// The share of this round's matches won, worth at most two wins
+    Percentage,
+    /// Everyone who advances starts the next round level
+    Reset,
+}
+
+/// How many samples advance from the current round.
+///
+/// With `adaptive_cutoff` set, everything scoring at least that many standard
+/// deviations above the mean advances (0 keeps whatever beats the average), so a
+/// round with a few clear favourites cuts deeper than one where scores are close.
+/// Without it, or when the scores are too even to separate anything, the fixed
+/// threshold applies. Pinned and benched samples and the calibration reference aren't
+/// counted.
+pub fn keep_count(state: &TournamentState) -> usize {
+    let reference = state.reference.as_ref().map(|r| r.path.as_str());
+    let competing: Vec<i32> = state
+        .samples
+        .iter()
+        .filter(|s| s.pin.is_none() && !s.benched && Some(s.path.as_str()) != reference)
+        .map(|s| s.score)
+        .collect();
+
+    // The schedule's last entry repeats
+    let threshold = match state.threshold_schedule.as_slice() {
+        [] => state.advancement_threshold,
+        schedule => schedule[(state.current_round.max(1) as usize).min(schedule.len()) - 1],
+    };
+    let fixed = ((competing.len() as f32 * threshold).ceil() as usize).max(2);
+
+    let Some(deviations) = state.adaptive_cutoff else {
+        return fixed;
+    };
+    let scores: Vec<f32> = competing
+        .iter()
+        .filter(|&&score| score > ELIMINATED_SCORE)
+        .map(|&score| score as f32)
+        .collect();
+    if scores.is_empty() {
+        return fixed;
+    }
+
+    let mean = scores.iter().sum::<f32>() / scores.len() as f32;
+    let variance = scores.iter().map(|s| (s - mean).powi(2)).sum::<f32>() / scores.len() as f32;
+    let cutoff = mean + deviations * variance.sqrt();
+    let above = scores.iter().filter(|&&s| s >= cutoff).count();
+
+    if above >= competing.len() {
+        fixed
+    } else {
+        above.max(2)
+    }
+}
+
+/// Samples advancing from the current round and the ones cut. Samples pinned as
+/// rejects and benched samples are in neither list.
+pub fn split(state: &TournamentState) -> (Vec<&Sample>, Vec<&Sample>) {
+    let reference = state.reference.as_ref().map(|r| r.path.as_str());
+    let mut competing: Vec<&Sample> = state
+        .samples
+        .iter()
+        .filter(|s| s.pin.is_none() && !s.benched && Some(s.path.as_str()) != reference)
+        .collect();
+    competing.sort_by(|a, b| b.score.cmp(&a.score));
+
+    let keep = keep_count(state).min(competing.len());
+    let cutoff = keep
+        .checked_sub(1)
+        .and_then(|last| competing.get(last))
+        .map_or(0, |s| s.score);
+    let (kept, below) = competing.split_at(keep);
+
+    // Stale samples that might really reach the cut are re-verified rather than cut
+    let (reverified, cut): (Vec<&Sample>, Vec<&Sample>) = below.iter().copied().partition(|s| {
+        s.uncertainty > 0.0
+            && s.score > ELIMINATED_SCORE
+            && s.score as f32 + s.uncertainty >= cutoff as f32
+    });
+
+    let keepers = state
+        .samples
+        .iter()
+        .filter(|s| s.pin == Some(Pin::AlwaysAdvance) && Some(s.path.as_str()) != reference);
+    let advancing = kept
+        .iter()
+        .copied()
+        .chain(reverified)
+        .chain(keepers)
+        .collect();
+    (advancing, cut)
+}
+
+/// Score a sample takes into the next round. Raw keeps every win, so an early blowout
+/// never fades; percentage keeps only how well it did this round, as up to `CARRY_POINTS` wins.
+fn carried_score(state: &TournamentState, sample: &Sample) -> i32 {
+    match state.score_carry {
+        _ if sample.score <= ELIMINATED_SCORE || sample.benched => sample.score,
+        ScoreCarry::Raw => sample.score,
+        ScoreCarry::Reset => 0,
+        ScoreCarry::Percentage => {
+            let (won, played) = state
+                .history
+                .iter()
+                .filter(|m| m.round == state.current_round)
+                .fold((0, 0), |(won, played), m| {
+                    if m.winner == sample.path {
+                        (won + 1, played + 1)
+                    } else if m.loser == sample.path {
+                        (won, played + 1)
+                    } else {
+                        (won, played)
+                    }
+                });
+            // Samples that sat the round out count as breaking even
+            let win_rate = if played > 0 {
+                won as f32 / played as f32
+            } else {
+                0.5
+            };
+            (win_rate * CARRY_POINTS).round() as i32
+        }
+    }
+}
+
+/// Close the current round: cut the samples below the line, carry scores over and
+/// pair the next round
+pub fn advance(state: &mut TournamentState) {
+    let reference = state.reference.as_ref().map(|r| r.path.clone());
+    let is_reference = |s: &Sample| Some(&s.path) == reference.as_ref();
+
+    let (advancing, cut) = split(state);
+    // Rejects skip the cut so they stay listed, benched samples wait to be brought back
+    let set_aside = state.samples.iter().filter(|s| {
+        !is_reference(s)
+            && (s.pin == Some(Pin::Eliminate) || (s.benched && s.pin != Some(Pin::AlwaysAdvance)))
+    });
+    let mut samples: Vec<Sample> = advancing
+        .into_iter()
+        .chain(set_aside)
+        .map(|s| Sample {
+            score: carried_score(state, s),
+            ..s.clone()
+        })
+        .collect();
+    // The reference isn't competing, so it is never cut
+    samples.extend(state.samples.iter().filter(|s| is_reference(s)).cloned());
+    let cut: Vec<Sample> = cut.into_iter().cloned().collect();
+
+    state.samples = samples;
+    // Kept so a redemption round can bring the best of them back
+    state.eliminated.extend(cut);
+    state.current_round += 1;
+    state.current_comparison_index = 0;
+    state.comparisons_this_round = rematches::pair_round(state);
+}
@@ -0,0 +1,1120 @@
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{self, Cursor};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, FormatReader, Track};
+use symphonia::core::io::{MediaSource, MediaSourceStream};
+use symphonia::core::meta::{MetadataOptions, MetadataRevision, StandardTagKey};
+use symphonia::core::probe::{Hint, ProbeResult};
+use tauri::{AppHandle, State};
+
+use crate::jobs::{self, JobHandle, JobKind};
+use crate::{archive, i18n, notifications, video, AllowedPaths, Sample};
+
+/// Samples at or above this magnitude count as full scale
+const CLIP_LEVEL: f32 = 0.999;
+/// Consecutive full-scale samples needed before calling it clipping rather than a loud peak
+const CLIP_RUN: usize = 3;
+/// Mean level (fraction of full scale) above which a channel has a DC offset
+const DC_OFFSET_THRESHOLD: f32 = 0.01;
+/// Oversampling factor and half-width (in input samples) of the true-peak interpolator
+const OVERSAMPLE: usize = 4;
+const INTERPOLATION_TAPS: isize = 8;
+/// Floor for level readings so silence doesn't serialize as -inf
+const MIN_DBFS: f32 = -120.0;
+/// Windows quieter than this count as silence
+const SILENCE_DBFS: f32 = -60.0;
+/// RMS window for silence and noise-floor measurements
+const LEVEL_WINDOW_SECS: f32 = 0.01;
+/// Noise floor is taken as this percentile of window levels that aren't digital silence
+const NOISE_FLOOR_PERCENTILE: f32 = 0.1;
+/// Frame size and hop for spectral descriptors
+const SPECTRAL_FRAME: usize = 2048;
+const SPECTRAL_HOP: usize = 1024;
+/// Share of spectral energy below the rolloff frequency
+const ROLLOFF_ENERGY: f32 = 0.85;
+/// Pitch range for root-note detection (A0 to C8)
+const MIN_PITCH_HZ: f32 = 27.5;
+const MAX_PITCH_HZ: f32 = 4186.0;
+/// YIN aperiodicity threshold; higher accepts less clearly pitched material
+const YIN_THRESHOLD: f32 = 0.15;
+/// Skip the attack transient before measuring pitch
+const PITCH_ONSET_SKIP_SECS: f32 = 0.05;
+/// Tempo range considered when estimating BPM
+const MIN_BPM: f32 = 60.0;
+const MAX_BPM: f32 = 180.0;
+/// Shorter material is treated as a one-shot without a tempo
+const MIN_TEMPO_SECS: f32 = 4.0;
+const TEMPO_HOP: usize = 512;
+const NOTE_NAMES: [&str; 12] = [
+    "C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B",
+];
+/// Cap for side/mid ratios so near-silent mids don't produce huge numbers
+const MAX_STEREO_WIDTH: f32 = 100.0;
+/// Audio handed to `decode_chunks` callbacks at a time
+const CHUNK_SECS: usize = 10;
+/// Files larger than this are analyzed a chunk at a time instead of decoded whole
+const STREAM_ABOVE_BYTES: u64 = 64 * 1024 * 1024;
+/// Whole-file decoding (spectrograms, loop slicing, transcoding) refuses larger files
+const MAX_DECODE_BYTES: u64 = 256 * 1024 * 1024;
+/// Even chunked, larger files are refused; this is the WAV format's own limit
+const MAX_STREAM_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+/// Pitch and tempo of streamed files are measured on this much audio from the start
+const EXCERPT_SECS: usize = 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityFlag {
+    /// Runs of samples stuck at full scale
+    Clipping,
+    /// Reconstructed waveform exceeds 0 dBFS between samples
+    InterSampleClipping,
+    DcOffset,
+}
+
+/// Per-sample measurements; new fields must default so older saves still load
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Analysis {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub duration_secs: f32,
+    pub peak_dbfs: f32,
+    pub true_peak_dbfs: f32,
+    /// Largest per-channel mean, as a fraction of full scale
+    pub dc_offset: f32,
+    pub flags: Vec<QualityFlag>,
+    pub leading_silence_secs: f32,
+    pub trailing_silence_secs: f32,
+    /// Fraction of the file below the silence threshold
+    pub silence_ratio: f32,
+    pub noise_floor_dbfs: f32,
+    /// Pearson correlation between left and right: 1 = mono, 0 = unrelated, < 0 = out of phase
+    pub stereo_correlation: f32,
+    /// Side level relative to mid level (0 = mono)
+    pub stereo_width: f32,
+    /// Level drop when folded down to mono, in dB (0 for mono files)
+    pub mono_loss_db: f32,
+    /// Brightness: energy-weighted mean frequency
+    pub spectral_centroid_hz: f32,
+    /// Frequency below which 85% of the energy lies
+    pub spectral_rolloff_hz: f32,
+    /// 0 = tonal, 1 = white noise
+    pub spectral_flatness: f32,
+    /// Detected fundamental, `None` for unpitched material
+    pub root_hz: Option<f32>,
+    /// Nearest note to `root_hz`, e.g. "F#2"
+    pub root_note: Option<String>,
+    /// Estimated tempo of loops and longer files
+    pub tempo_bpm: Option<f32>,
+}
+
+/// Decoded audio, interleaved
+pub struct DecodedAudio {
+    pub sample_rate: u32,
+    pub channels: usize,
+    pub samples: Vec<f32>,
+}
+
+impl DecodedAudio {
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.channels
+    }
+
+    pub fn channel(&self, index: usize) -> impl Iterator<Item = f32> + '_ {
+        self.samples
+            .iter()
+            .skip(index)
+            .step_by(self.channels)
+            .copied()
+    }
+}
+
+fn open_media(path: &str) -> Result<(Box<dyn MediaSource>, Option<String>), String> {
+    let extension = |name: &str| {
+        Path::new(name)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+    };
+
+    if let Some((archive_path, entry)) = archive::split_archive_path(path) {
+        let bytes = archive::read_entry(Path::new(archive_path), entry)?;
+        Ok((Box::new(Cursor::new(bytes)), extension(entry)))
+    } else {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        Ok((Box::new(file), extension(path)))
+    }
+}
+
+fn open_format(path: &str) -> Result<Box<dyn FormatReader>, String> {
+    Ok(probe(path)?.format)
+}
+
+fn probe(path: &str) -> Result<ProbeResult, String> {
+    let (source, extension) = open_media(path)?;
+    let stream = MediaSourceStream::new(source, Default::default());
+    let mut hint = Hint::new();
+    if let Some(extension) = &extension {
+        hint.with_extension(extension);
+    }
+
+    symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| e.to_string())
+}
+
+/// A rating as the file's tags carry it
+pub struct RatingTag {
+    pub value: String,
+    /// Came from an ID3 popularimeter (POPM), which rates on its own 0–255 scale
+    pub popularimeter: bool,
+}
+
+/// The rating the file's own tags carry (ID3 POPM, Vorbis or APE `RATING`), as written.
+/// Tags ahead of the audio (ID3v2) come with the probe; the rest from the container.
+pub fn rating_tag(path: &str) -> Option<RatingTag> {
+    let mut probed = probe(path).ok()?;
+    let rating = |revision: &MetadataRevision| {
+        revision
+            .tags()
+            .iter()
+            .find(|tag| {
+                tag.std_key == Some(StandardTagKey::Rating)
+                    || tag.key.eq_ignore_ascii_case("rating")
+                    || is_popularimeter(&tag.key)
+            })
+            .map(|tag| RatingTag {
+                value: tag.value.to_string(),
+                popularimeter: is_popularimeter(&tag.key),
+            })
+    };
+
+    let leading = probed
+        .metadata
+        .get()
+        .and_then(|metadata| metadata.current().and_then(rating));
+    leading.or_else(|| probed.format.metadata().current().and_then(rating))
+}
+
+/// Symphonia names POPM frames (and ID3v2.2 POP ones) `POPM:<email>`, after the player
+/// that wrote them
+fn is_popularimeter(key: &str) -> bool {
+    key.split(':').next() == Some("POPM")
+}
+
+fn audio_track(format: &dyn FormatReader) -> Result<&Track, String> {
+    format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| i18n::t("no-audio-track"))
+}
+
+/// Format details read from the container header, without decoding
+#[derive(Debug, Clone, Copy)]
+pub struct StreamInfo {
+    pub sample_rate: Option<u32>,
+    pub channels: Option<usize>,
+}
+
+pub fn stream_info(path: &str) -> Result<StreamInfo, String> {
+    let format = open_format(path)?;
+    let params = &audio_track(format.as_ref())?.codec_params;
+    Ok(StreamInfo {
+        sample_rate: params.sample_rate,
+        channels: params.channels.map(|c| c.count()),
+    })
+}
+
+/// Size on disk, or the uncompressed size of an archive entry
+fn file_size(path: &str) -> Option<u64> {
+    if let Some((archive_path, entry)) = archive::split_archive_path(path) {
+        return archive::entry_size(Path::new(archive_path), entry).ok();
+    }
+    fs::metadata(path).ok().map(|m| m.len())
+}
+
+fn check_size(path: &str, limit: u64) -> Result<(), String> {
+    let Some(size) = file_size(path).filter(|&size| size > limit) else {
+        return Ok(());
+    };
+    let megabytes = |bytes: u64| bytes / (1024 * 1024);
+    let file = Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string());
+    Err(i18n::t_args(
+        "file-too-large",
+        [
+            ("file", file.into()),
+            ("size", megabytes(size).into()),
+            ("limit", megabytes(limit).into()),
+        ],
+    ))
+}
+
+/// Whether `path` is big enough that it should go through `decode_chunks`
+pub fn needs_streaming(path: &str) -> bool {
+    file_size(path).is_some_and(|size| size > STREAM_ABOVE_BYTES)
+}
+
+/// Decode the whole first audio track of a file or archive entry into memory
+pub fn decode(path: &str) -> Result<DecodedAudio, String> {
+    check_size(path, MAX_DECODE_BYTES)?;
+    let mut samples = Vec::new();
+    let (sample_rate, channels) = decode_chunks(path, |chunk| {
+        samples.extend_from_slice(&chunk.samples);
+        Ok(())
+    })?;
+
+    Ok(DecodedAudio {
+        sample_rate,
+        channels,
+        samples,
+    })
+}
+
+/// Decode the first audio track about `CHUNK_SECS` at a time, so memory stays bounded
+/// however long the file is. Returns the sample rate and channel count.
+pub fn decode_chunks(
+    path: &str,
+    mut on_chunk: impl FnMut(&DecodedAudio) -> Result<(), String>,
+) -> Result<(u32, usize), String> {
+    if video::is_video(Path::new(path)) {
+        return Err(i18n::t("video-not-analyzed"));
+    }
+    check_size(path, MAX_STREAM_BYTES)?;
+
+    let mut format = open_format(path)?;
+    let track = audio_track(format.as_ref())?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+    let mut channels = track.codec_params.channels.map(|c| c.count()).unwrap_or(0);
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| e.to_string())?;
+
+    let mut chunk = DecodedAudio {
+        sample_rate,
+        channels,
+        samples: Vec::new(),
+    };
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(e)) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+            Err(SymphoniaError::ResetRequired) => break,
+            Err(e) => return Err(e.to_string()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                sample_rate = spec.rate;
+                channels = spec.channels.count();
+                // A chunk never mixes formats
+                if (sample_rate, channels) != (chunk.sample_rate, chunk.channels) {
+                    if !chunk.samples.is_empty() {
+                        on_chunk(&chunk)?;
+                        chunk.samples.clear();
+                    }
+                    chunk.sample_rate = sample_rate;
+                    chunk.channels = channels;
+                }
+
+                let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+                buffer.copy_interleaved_ref(decoded);
+                chunk.samples.extend_from_slice(buffer.samples());
+                if chunk.samples.len() >= CHUNK_SECS * sample_rate as usize * channels {
+                    on_chunk(&chunk)?;
+                    chunk.samples.clear();
+                }
+            }
+            // Skip corrupt frames the way players do
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    if channels == 0 || sample_rate == 0 {
+        return Err(i18n::t("unknown-audio-format"));
+    }
+    if !chunk.samples.is_empty() {
+        on_chunk(&chunk)?;
+    }
+
+    Ok((sample_rate, channels))
+}
+
+pub fn to_dbfs(level: f32) -> f32 {
+    (20.0 * level.log10()).max(MIN_DBFS)
+}
+
+fn sinc(x: f32) -> f32 {
+    if x.abs() < 1e-6 {
+        1.0
+    } else {
+        let px = std::f32::consts::PI * x;
+        px.sin() / px
+    }
+}
+
+/// Windowed-sinc coefficients for each fractional phase between two input samples
+fn interpolation_kernel() -> Vec<Vec<f32>> {
+    let width = INTERPOLATION_TAPS as f32;
+    (1..OVERSAMPLE)
+        .map(|phase| {
+            let fraction = phase as f32 / OVERSAMPLE as f32;
+            (-INTERPOLATION_TAPS + 1..=INTERPOLATION_TAPS)
+                .map(|m| {
+                    let x = fraction - m as f32;
+                    let window = 0.5 + 0.5 * (std::f32::consts::PI * x / width).cos();
+                    sinc(x) * window
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Peak of the band-limited signal, estimated by 4x oversampling (BS.1770 style)
+fn true_peak(channel: &[f32], kernel: &[Vec<f32>]) -> f32 {
+    let mut peak = channel.iter().fold(0.0f32, |p, x| p.max(x.abs()));
+    let len = channel.len() as isize;
+
+    for i in 0..len {
+        for taps in kernel {
+            let mut value = 0.0;
+            for (tap, m) in taps
+                .iter()
+                .zip(-INTERPOLATION_TAPS + 1..=INTERPOLATION_TAPS)
+            {
+                let index = i + m;
+                if (0..len).contains(&index) {
+                    value += channel[index as usize] * tap;
+                }
+            }
+            peak = peak.max(value.abs());
+        }
+    }
+
+    peak
+}
+
+fn has_clipped_run(channel: &[f32]) -> bool {
+    let mut run = 0;
+    for x in channel {
+        if x.abs() >= CLIP_LEVEL {
+            run += 1;
+            if run >= CLIP_RUN {
+                return true;
+            }
+        } else {
+            run = 0;
+        }
+    }
+    false
+}
+
+fn frames_per_window(sample_rate: u32) -> usize {
+    ((sample_rate as f32 * LEVEL_WINDOW_SECS) as usize).max(1)
+}
+
+/// RMS level (dBFS) of one window
+fn window_level(window: &[f32]) -> f32 {
+    let power = window.iter().map(|&x| x * x).sum::<f32>() / window.len() as f32;
+    to_dbfs(power.sqrt())
+}
+
+/// RMS level (dBFS) of consecutive short windows across all channels
+fn window_levels(audio: &DecodedAudio) -> Vec<f32> {
+    audio
+        .samples
+        .chunks(frames_per_window(audio.sample_rate) * audio.channels)
+        .map(window_level)
+        .collect()
+}
+
+struct SilenceStats {
+    leading_secs: f32,
+    trailing_secs: f32,
+    ratio: f32,
+    noise_floor_dbfs: f32,
+}
+
+fn silence_stats(audio: &DecodedAudio) -> SilenceStats {
+    silence_from_levels(&window_levels(audio))
+}
+
+fn silence_from_levels(levels: &[f32]) -> SilenceStats {
+    if levels.is_empty() {
+        return SilenceStats {
+            leading_secs: 0.0,
+            trailing_secs: 0.0,
+            ratio: 1.0,
+            noise_floor_dbfs: MIN_DBFS,
+        };
+    }
+
+    let silent = |level: &&f32| **level < SILENCE_DBFS;
+    let leading = levels.iter().take_while(silent).count();
+    let trailing = if leading == levels.len() {
+        0
+    } else {
+        levels.iter().rev().take_while(silent).count()
+    };
+
+    let mut audible: Vec<f32> = levels.iter().copied().filter(|&l| l > MIN_DBFS).collect();
+    audible.sort_by(|a, b| a.total_cmp(b));
+    let noise_floor_dbfs = audible
+        .get((audible.len() as f32 * NOISE_FLOOR_PERCENTILE) as usize)
+        .copied()
+        .unwrap_or(MIN_DBFS);
+
+    SilenceStats {
+        leading_secs: leading as f32 * LEVEL_WINDOW_SECS,
+        trailing_secs: trailing as f32 * LEVEL_WINDOW_SECS,
+        ratio: levels.iter().filter(silent).count() as f32 / levels.len() as f32,
+        noise_floor_dbfs,
+    }
+}
+
+struct StereoStats {
+    correlation: f32,
+    width: f32,
+    mono_loss_db: f32,
+}
+
+/// Running sums behind `StereoStats`; left empty for mono, which reads as fully correlated
+#[derive(Default)]
+struct StereoSums {
+    lr: f64,
+    ll: f64,
+    rr: f64,
+    mid: f64,
+    side: f64,
+}
+
+impl StereoSums {
+    /// Add the first two channels of `audio`
+    fn add(&mut self, audio: &DecodedAudio) {
+        if audio.channels < 2 {
+            return;
+        }
+        for frame in audio.samples.chunks_exact(audio.channels) {
+            let (l, r) = (frame[0] as f64, frame[1] as f64);
+            self.lr += l * r;
+            self.ll += l * l;
+            self.rr += r * r;
+            self.mid += ((l + r) / 2.0).powi(2);
+            self.side += ((l - r) / 2.0).powi(2);
+        }
+    }
+
+    fn finish(self) -> StereoStats {
+        let StereoSums {
+            lr,
+            ll,
+            rr,
+            mid,
+            side,
+        } = self;
+        let correlation = if ll > 0.0 && rr > 0.0 {
+            (lr / (ll * rr).sqrt()) as f32
+        } else {
+            1.0
+        };
+        let width = if mid > 0.0 {
+            (side / mid).sqrt() as f32
+        } else if side > 0.0 {
+            // Pure side signal: vanishes entirely in mono
+            MAX_STEREO_WIDTH
+        } else {
+            0.0
+        };
+        // Mono mid power vs. the average power of the two channels
+        let stereo_power = (ll + rr) / 2.0;
+        let mono_loss_db = if stereo_power > 0.0 {
+            (10.0 * (stereo_power / mid.max(1e-12)).log10()).clamp(0.0, -MIN_DBFS as f64) as f32
+        } else {
+            0.0
+        };
+
+        StereoStats {
+            correlation,
+            width: width.min(MAX_STEREO_WIDTH),
+            mono_loss_db,
+        }
+    }
+}
+
+/// Correlation and mid/side balance of the first two channels
+fn stereo_stats(audio: &DecodedAudio) -> StereoStats {
+    let mut sums = StereoSums::default();
+    sums.add(audio);
+    sums.finish()
+}
+
+struct SpectralStats {
+    centroid_hz: f32,
+    rolloff_hz: f32,
+    flatness: f32,
+}
+
+pub fn mono_mix(audio: &DecodedAudio) -> Vec<f32> {
+    audio
+        .samples
+        .chunks(audio.channels)
+        .map(|frame| frame.iter().sum::<f32>() / audio.channels as f32)
+        .collect()
+}
+
+/// Running per-frame sums behind `SpectralStats`
+#[derive(Default)]
+struct SpectralSums {
+    centroid: f32,
+    rolloff: f32,
+    flatness: f32,
+    frames: usize,
+}
+
+impl SpectralSums {
+    /// Add the non-silent frames of a mono signal
+    fn add(&mut self, mono: &[f32], sample_rate: u32) {
+        let fft = FftPlanner::<f32>::new().plan_fft_forward(SPECTRAL_FRAME);
+        let window: Vec<f32> = (0..SPECTRAL_FRAME)
+            .map(|i| {
+                0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / SPECTRAL_FRAME as f32).cos()
+            })
+            .collect();
+        let bin_hz = sample_rate as f32 / SPECTRAL_FRAME as f32;
+        let silence_power = 10f32.powf(SILENCE_DBFS / 10.0);
+
+        let mut buffer = vec![Complex::new(0.0f32, 0.0); SPECTRAL_FRAME];
+
+        let mut start = 0;
+        while start < mono.len() {
+            let frame = &mono[start..(start + SPECTRAL_FRAME).min(mono.len())];
+            start += SPECTRAL_HOP;
+
+            let frame_power = frame.iter().map(|x| x * x).sum::<f32>() / frame.len() as f32;
+            if frame_power < silence_power {
+                continue;
+            }
+
+            for (i, value) in buffer.iter_mut().enumerate() {
+                let sample = frame.get(i).copied().unwrap_or(0.0);
+                *value = Complex::new(sample * window[i], 0.0);
+            }
+            fft.process(&mut buffer);
+
+            let power: Vec<f32> = buffer[1..SPECTRAL_FRAME / 2]
+                .iter()
+                .map(|c| c.norm_sqr())
+                .collect();
+            let total: f32 = power.iter().sum();
+            if total <= 0.0 {
+                continue;
+            }
+
+            let weighted: f32 = power
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (i + 1) as f32 * bin_hz * p)
+                .sum();
+            self.centroid += weighted / total;
+
+            let mut cumulative = 0.0;
+            let rolloff_bin = power
+                .iter()
+                .position(|p| {
+                    cumulative += p;
+                    cumulative >= ROLLOFF_ENERGY * total
+                })
+                .unwrap_or(power.len() - 1);
+            self.rolloff += (rolloff_bin + 1) as f32 * bin_hz;
+
+            let log_mean = power.iter().map(|p| (p + 1e-12).ln()).sum::<f32>() / power.len() as f32;
+            self.flatness += log_mean.exp() / (total / power.len() as f32);
+            self.frames += 1;
+        }
+    }
+
+    fn finish(self) -> SpectralStats {
+        if self.frames == 0 {
+            return SpectralStats {
+                centroid_hz: 0.0,
+                rolloff_hz: 0.0,
+                flatness: 0.0,
+            };
+        }
+
+        SpectralStats {
+            centroid_hz: self.centroid / self.frames as f32,
+            rolloff_hz: self.rolloff / self.frames as f32,
+            flatness: (self.flatness / self.frames as f32).clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// Spectral descriptors averaged over all non-silent frames of the mono mix
+fn spectral_stats(audio: &DecodedAudio, mono: &[f32]) -> SpectralStats {
+    let mut sums = SpectralSums::default();
+    sums.add(mono, audio.sample_rate);
+    sums.finish()
+}
+
+/// Fundamental frequency of the sustained part of the sound (YIN), if it has one
+fn detect_pitch(mono: &[f32], sample_rate: u32) -> Option<f32> {
+    let rate = sample_rate as f32;
+    let max_lag = (rate / MIN_PITCH_HZ) as usize;
+    let min_lag = ((rate / MAX_PITCH_HZ) as usize).max(2);
+
+    let threshold = 10f32.powf(SILENCE_DBFS / 20.0);
+    let onset = mono.iter().position(|x| x.abs() > threshold)?;
+    let start = onset + (PITCH_ONSET_SKIP_SECS * rate) as usize;
+    // Short one-shots may not last past the attack; measure from the onset then
+    let window = mono
+        .get(start..start + 2 * max_lag)
+        .or_else(|| mono.get(onset..onset + 2 * max_lag))?;
+
+    let mut normalized = vec![1.0f32; max_lag + 1];
+    let mut running = 0.0f32;
+    for lag in 1..=max_lag {
+        let difference: f32 = (0..max_lag)
+            .map(|i| {
+                let delta = window[i] - window[i + lag];
+                delta * delta
+            })
+            .sum();
+        running += difference;
+        if running > 0.0 {
+            normalized[lag] = difference * lag as f32 / running;
+        }
+    }
+
+    let mut lag = (min_lag..max_lag).find(|&lag| normalized[lag] < YIN_THRESHOLD)?;
+    while lag + 1 < max_lag && normalized[lag + 1] < normalized[lag] {
+        lag += 1;
+    }
+
+    // Parabolic interpolation around the minimum for sub-sample accuracy
+    let (before, at, after) = (normalized[lag - 1], normalized[lag], normalized[lag + 1]);
+    let curvature = before - 2.0 * at + after;
+    let shift = if curvature.abs() > f32::EPSILON {
+        0.5 * (before - after) / curvature
+    } else {
+        0.0
+    };
+
+    Some(rate / (lag as f32 + shift))
+}
+
+/// Tempo from the autocorrelation of the onset strength (rises in energy)
+pub fn detect_tempo(mono: &[f32], sample_rate: u32) -> Option<f32> {
+    let rate = sample_rate as f32;
+    if (mono.len() as f32) < MIN_TEMPO_SECS * rate {
+        return None;
+    }
+
+    let energies: Vec<f32> = mono
+        .chunks(TEMPO_HOP)
+        .map(|chunk| (chunk.iter().map(|x| x * x).sum::<f32>() / chunk.len() as f32 + 1e-10).ln())
+        .collect();
+    let flux: Vec<f32> = energies
+        .windows(2)
+        .map(|w| (w[1] - w[0]).max(0.0))
+        .collect();
+    let mean = flux.iter().sum::<f32>() / flux.len() as f32;
+    let flux: Vec<f32> = flux.iter().map(|f| f - mean).collect();
+
+    let autocorrelation = |lag: usize| {
+        flux.iter()
+            .zip(&flux[lag..])
+            .map(|(a, b)| a * b)
+            .sum::<f32>()
+            / (flux.len() - lag) as f32
+    };
+
+    let frame_rate = rate / TEMPO_HOP as f32;
+    let min_lag = ((frame_rate * 60.0 / MAX_BPM).floor() as usize).max(2);
+    let max_lag = ((frame_rate * 60.0 / MIN_BPM).ceil() as usize).min(flux.len() / 2);
+    if min_lag >= max_lag {
+        return None;
+    }
+
+    let (lag, strength) = (min_lag..max_lag)
+        .map(|lag| (lag, autocorrelation(lag)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+    if strength <= 0.0 {
+        return None;
+    }
+
+    // Parabolic interpolation; one hop is several BPM at typical tempos
+    let (before, after) = (autocorrelation(lag - 1), autocorrelation(lag + 1));
+    let curvature = before - 2.0 * strength + after;
+    let shift = if curvature.abs() > f32::EPSILON {
+        (0.5 * (before - after) / curvature).clamp(-0.5, 0.5)
+    } else {
+        0.0
+    };
+
+    Some(60.0 * frame_rate / (lag as f32 + shift))
+}
+
+/// Seconds before the sound starts
+pub fn onset_secs(audio: &DecodedAudio) -> f32 {
+    silence_stats(audio).leading_secs
+}
+
+/// Nearest equal-tempered note name (A4 = 440 Hz), e.g. "F#2"
+pub fn note_name(hz: f32) -> String {
+    let midi = (69.0 + 12.0 * (hz / 440.0).log2()).round() as i32;
+    format!(
+        "{}{}",
+        NOTE_NAMES[midi.rem_euclid(12) as usize],
+        midi.div_euclid(12) - 1
+    )
+}
+
+fn quality_flags(clipped: bool, true_peak_level: f32, dc_offset: f32) -> Vec<QualityFlag> {
+    let mut flags = Vec::new();
+    if clipped {
+        flags.push(QualityFlag::Clipping);
+    }
+    if true_peak_level > 1.0 {
+        flags.push(QualityFlag::InterSampleClipping);
+    }
+    if dc_offset > DC_OFFSET_THRESHOLD {
+        flags.push(QualityFlag::DcOffset);
+    }
+    flags
+}
+
+pub fn analyze_audio(audio: &DecodedAudio) -> Analysis {
+    let kernel = interpolation_kernel();
+    let mut peak = 0.0f32;
+    let mut true_peak_level = 0.0f32;
+    let mut dc_offset = 0.0f32;
+    let mut clipped = false;
+
+    for index in 0..audio.channels {
+        let channel: Vec<f32> = audio.channel(index).collect();
+        if channel.is_empty() {
+            continue;
+        }
+
+        peak = channel.iter().fold(peak, |p, x| p.max(x.abs()));
+        true_peak_level = true_peak_level.max(true_peak(&channel, &kernel));
+        let mean = channel.iter().map(|&x| x as f64).sum::<f64>() / channel.len() as f64;
+        dc_offset = dc_offset.max(mean.abs() as f32);
+        clipped |= has_clipped_run(&channel);
+    }
+
+    let silence = silence_stats(audio);
+    let stereo = stereo_stats(audio);
+    let mono = mono_mix(audio);
+    let spectral = spectral_stats(audio, &mono);
+    let root_hz = detect_pitch(&mono, audio.sample_rate);
+
+    Analysis {
+        sample_rate: audio.sample_rate,
+        channels: audio.channels as u16,
+        duration_secs: audio.frames() as f32 / audio.sample_rate as f32,
+        peak_dbfs: to_dbfs(peak),
+        true_peak_dbfs: to_dbfs(true_peak_level),
+        dc_offset,
+        flags: quality_flags(clipped, true_peak_level, dc_offset),
+        leading_silence_secs: silence.leading_secs,
+        trailing_silence_secs: silence.trailing_secs,
+        silence_ratio: silence.ratio,
+        noise_floor_dbfs: silence.noise_floor_dbfs,
+        stereo_correlation: stereo.correlation,
+        stereo_width: stereo.width,
+        mono_loss_db: stereo.mono_loss_db,
+        spectral_centroid_hz: spectral.centroid_hz,
+        spectral_rolloff_hz: spectral.rolloff_hz,
+        spectral_flatness: spectral.flatness,
+        root_hz,
+        root_note: root_hz.map(note_name),
+        tempo_bpm: detect_tempo(&mono, audio.sample_rate),
+    }
+}
+
+/// `analyze_audio` for files too long to hold in memory, fed one chunk at a time. Pitch
+/// and tempo come from the first `EXCERPT_SECS`, and the few interpolated true-peak
+/// points that straddle chunk boundaries are not checked.
+struct StreamingAnalysis {
+    sample_rate: u32,
+    channels: usize,
+    frames: usize,
+    kernel: Vec<Vec<f32>>,
+    peak: f32,
+    true_peak: f32,
+    /// Per channel, for the DC offset
+    sums: Vec<f64>,
+    /// Per channel, full-scale samples in a row so far
+    clip_runs: Vec<usize>,
+    clipped: bool,
+    levels: Vec<f32>,
+    /// Start of a level window that continues in the next chunk
+    partial_window: Vec<f32>,
+    stereo: StereoSums,
+    spectral: SpectralSums,
+    excerpt: Vec<f32>,
+}
+
+impl StreamingAnalysis {
+    fn new(sample_rate: u32, channels: usize) -> Self {
+        Self {
+            sample_rate,
+            channels,
+            frames: 0,
+            kernel: interpolation_kernel(),
+            peak: 0.0,
+            true_peak: 0.0,
+            sums: vec![0.0; channels],
+            clip_runs: vec![0; channels],
+            clipped: false,
+            levels: Vec::new(),
+            partial_window: Vec::new(),
+            stereo: StereoSums::default(),
+            spectral: SpectralSums::default(),
+            excerpt: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, chunk: &DecodedAudio) -> Result<(), String> {
+        if (chunk.sample_rate, chunk.channels) != (self.sample_rate, self.channels) {
+            return Err(i18n::t("audio-format-changes"));
+        }
+        self.frames += chunk.frames();
+
+        for index in 0..self.channels {
+            let channel: Vec<f32> = chunk.channel(index).collect();
+            self.peak = channel.iter().fold(self.peak, |p, x| p.max(x.abs()));
+            self.true_peak = self.true_peak.max(true_peak(&channel, &self.kernel));
+            self.sums[index] += channel.iter().map(|&x| x as f64).sum::<f64>();
+
+            let run = &mut self.clip_runs[index];
+            for x in &channel {
+                *run = if x.abs() >= CLIP_LEVEL { *run + 1 } else { 0 };
+                self.clipped |= *run >= CLIP_RUN;
+            }
+        }
+
+        let window_len = frames_per_window(self.sample_rate) * self.channels;
+        let mut samples = chunk.samples.as_slice();
+        if !self.partial_window.is_empty() {
+            let take = (window_len - self.partial_window.len()).min(samples.len());
+            self.partial_window.extend_from_slice(&samples[..take]);
+            samples = &samples[take..];
+            if self.partial_window.len() == window_len {
+                self.levels.push(window_level(&self.partial_window));
+                self.partial_window.clear();
+            }
+        }
+        let mut windows = samples.chunks_exact(window_len);
+        self.levels.extend(windows.by_ref().map(window_level));
+        self.partial_window.extend_from_slice(windows.remainder());
+
+        self.stereo.add(chunk);
+        let mono = mono_mix(chunk);
+        self.spectral.add(&mono, self.sample_rate);
+        let excerpt_len = EXCERPT_SECS * self.sample_rate as usize;
+        let wanted = excerpt_len
+            .saturating_sub(self.excerpt.len())
+            .min(mono.len());
+        self.excerpt.extend_from_slice(&mono[..wanted]);
+        Ok(())
+    }
+
+    fn finish(mut self) -> Analysis {
+        if !self.partial_window.is_empty() {
+            self.levels.push(window_level(&self.partial_window));
+        }
+        let dc_offset = self
+            .sums
+            .iter()
+            .map(|sum| (sum / self.frames.max(1) as f64).abs() as f32)
+            .fold(0.0f32, f32::max);
+        let silence = silence_from_levels(&self.levels);
+        let stereo = self.stereo.finish();
+        let spectral = self.spectral.finish();
+        let root_hz = detect_pitch(&self.excerpt, self.sample_rate);
+
+        Analysis {
+            sample_rate: self.sample_rate,
+            channels: self.channels as u16,
+            duration_secs: self.frames as f32 / self.sample_rate as f32,
+            peak_dbfs: to_dbfs(self.peak),
+            true_peak_dbfs: to_dbfs(self.true_peak),
+            dc_offset,
+            flags: quality_flags(self.clipped, self.true_peak, dc_offset),
+            leading_silence_secs: silence.leading_secs,
+            trailing_silence_secs: silence.trailing_secs,
+            silence_ratio: silence.ratio,
+            noise_floor_dbfs: silence.noise_floor_dbfs,
+            stereo_correlation: stereo.correlation,
+            stereo_width: stereo.width,
+            mono_loss_db: stereo.mono_loss_db,
+            spectral_centroid_hz: spectral.centroid_hz,
+            spectral_rolloff_hz: spectral.rolloff_hz,
+            spectral_flatness: spectral.flatness,
+            root_hz,
+            root_note: root_hz.map(note_name),
+            tempo_bpm: detect_tempo(&self.excerpt, self.sample_rate),
+        }
+    }
+}
+
+/// Analyze a file without ever holding all of it in memory
+fn analyze_streamed(path: &str) -> Result<Analysis, String> {
+    let mut analysis: Option<StreamingAnalysis> = None;
+    let (sample_rate, channels) = decode_chunks(path, |chunk| {
+        analysis
+            .get_or_insert_with(|| StreamingAnalysis::new(chunk.sample_rate, chunk.channels))
+            .add(chunk)
+    })?;
+    Ok(analysis
+        .unwrap_or_else(|| StreamingAnalysis::new(sample_rate, channels))
+        .finish())
+}
+
+pub fn analyze_file(path: &str) -> Result<Analysis, String> {
+    if needs_streaming(path) {
+        return analyze_streamed(path);
+    }
+    decode(path).map(|audio| analyze_audio(&audio))
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnalysisFailure {
+    pub path: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnalysisReport {
+    pub samples: Vec<Sample>,
+    pub failed: Vec<AnalysisFailure>,
+}
+
+/// Analyze every sample that has no analysis yet, spread across CPU cores. With a
+/// job, progress is reported to it and its throttle settings are followed; without
+/// one (the CLI) every core runs flat out.
+pub fn analyze_batch(mut samples: Vec<Sample>, job: Option<&JobHandle>) -> AnalysisReport {
+    let pending: Vec<usize> = samples
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| s.analysis.is_none())
+        .map(|(i, _)| i)
+        .collect();
+
+    let throttle = job.map(|job| job.throttle()).unwrap_or_default();
+    let workers = throttle.workers(pending.len());
+    let done = AtomicU64::new(0);
+    let results = Mutex::new(Vec::with_capacity(pending.len()));
+
+    std::thread::scope(|scope| {
+        for chunk in pending.chunks(pending.len().div_ceil(workers).max(1)) {
+            let (samples, done, results) = (&samples, &done, &results);
+            scope.spawn(move || {
+                for &index in chunk {
+                    let started = Instant::now();
+                    let result = analyze_file(&samples[index].path);
+                    results.lock().unwrap().push((index, result));
+                    let done = done.fetch_add(1, Ordering::Relaxed) + 1;
+                    if let Some(job) = job {
+                        job.set_done(done);
+                        job.pace(started.elapsed());
+                    }
+                }
+            });
+        }
+    });
+
+    let mut failed = Vec::new();
+    for (index, result) in results.into_inner().unwrap() {
+        match result {
+            Ok(analysis) => {
+                // Kept on the sample, so the results can mark surround files
+                samples[index].channels = Some(analysis.channels);
+                samples[index].analysis = Some(analysis);
+            }
+            Err(error) => failed.push(AnalysisFailure {
+                path: samples[index].path.clone(),
+                error,
+            }),
+        }
+    }
+
+    AnalysisReport { samples, failed }
+}
+
+/// Seconds of leading silence before the sound starts, for replaying from the onset
+#[tauri::command]
+pub async fn get_onset(
+    file_path: String,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<f32, String> {
+    if !allowed_paths.is_path_allowed(Path::new(&file_path)) {
+        return Err(i18n::t("access-denied"));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || decode(&file_path).map(|audio| onset_secs(&audio)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Samples outside the allowed folders come back unanalyzed, listed as failures
+#[tauri::command]
+pub async fn analyze_samples(
+    samples: Vec<Sample>,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<AnalysisReport, String> {
+    let started = Instant::now();
+    let (samples, refused): (Vec<Sample>, Vec<Sample>) = samples
+        .into_iter()
+        .partition(|s| s.analysis.is_some() || allowed_paths.is_path_allowed(Path::new(&s.path)));
+    let pending = samples.iter().filter(|s| s.analysis.is_none()).count();
+    let job = jobs::start(&app, JobKind::Analysis, Some(pending as u64));
+
+    let mut report =
+        tauri::async_runtime::spawn_blocking(move || analyze_batch(samples, Some(&job)))
+            .await
+            .map_err(|e| e.to_string())?;
+    let analyzed = pending - report.failed.len();
+    report
+        .failed
+        .extend(refused.iter().map(|sample| AnalysisFailure {
+            path: sample.path.clone(),
+            error: i18n::t("access-denied"),
+        }));
+    report.samples.extend(refused);
+
+    tracing::info!(analyzed, failed = report.failed.len(), "analysis finished");
+    notifications::job_finished(
+        &app,
+        started,
+        &i18n::t("analysis-complete"),
+        &i18n::t_args("analysis-complete-body", [("count", analyzed.into())]),
+    );
+    Ok(report)
+}
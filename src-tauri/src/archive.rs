@@ -0,0 +1,155 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use crate::cache::cache_key;
+use crate::scan::is_audio_name;
+use crate::{i18n, Sample};
+
+/// Separates the archive file from the entry name in `Sample.path`,
+/// e.g. `/packs/drums.zip!/Kicks/kick_01.wav`
+pub const ARCHIVE_SEPARATOR: &str = "!/";
+
+/// Plain archives, opened by the scan when archives are included
+const ARCHIVE_EXTENSIONS: &[&str] = &["zip"];
+
+/// Zip-based containers whose audio entries can be played directly: archives, and
+/// Renoise `.xrni` instruments (zips with their samples inside), which the scan only
+/// opens when instruments are included
+const CONTAINER_EXTENSIONS: &[&str] = &["zip", "xrni"];
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .map(|ext| {
+            extensions
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+        .unwrap_or(false)
+}
+
+pub fn is_archive(path: &Path) -> bool {
+    has_extension(path, ARCHIVE_EXTENSIONS)
+}
+
+pub fn is_container(path: &Path) -> bool {
+    has_extension(path, CONTAINER_EXTENSIONS)
+}
+
+/// Split an archive sample path into the archive file and the entry name
+pub fn split_archive_path(path: &str) -> Option<(&str, &str)> {
+    // Matched on the original bytes: lowercasing can change the length of non-ASCII
+    // names, and the marker is ASCII, so a match always starts on a char boundary
+    let bytes = path.as_bytes();
+    let archive_end = CONTAINER_EXTENSIONS
+        .iter()
+        .filter_map(|ext| {
+            let marker = format!(".{}{}", ext, ARCHIVE_SEPARATOR);
+            bytes
+                .windows(marker.len())
+                .position(|window| window.eq_ignore_ascii_case(marker.as_bytes()))
+                .map(|idx| idx + 1 + ext.len())
+        })
+        .min()?;
+    Some((
+        &path[..archive_end],
+        &path[archive_end + ARCHIVE_SEPARATOR.len()..],
+    ))
+}
+
+/// List audio entries of a zip archive as samples with archive-relative paths
+pub fn list_audio_entries(archive_path: &Path) -> Result<Vec<Sample>, String> {
+    let file = File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let mut samples = Vec::new();
+    for i in 0..zip.len() {
+        let entry = zip.by_index(i).map_err(|e| e.to_string())?;
+        if entry.is_dir() || !is_audio_name(entry.name()) {
+            continue;
+        }
+
+        // Skip entries with absolute or `..` components
+        let Some(relative) = entry.enclosed_name() else {
+            continue;
+        };
+
+        let filename = relative
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        samples.push(Sample {
+            path: format!(
+                "{}{}{}",
+                archive_path.to_string_lossy(),
+                ARCHIVE_SEPARATOR,
+                entry.name()
+            ),
+            filename,
+            score: 0,
+            comparisons: 0,
+            provisional: false,
+            online_only: false,
+            analysis: None,
+            variants: Vec::new(),
+            pack: None,
+            instrument: None,
+            channels: None,
+            pin: None,
+            benched: false,
+            uncertainty: 0.0,
+            tags: Vec::new(),
+        });
+    }
+
+    Ok(samples)
+}
+
+/// Extract a single archive entry into `cache_dir`, reusing a previous extraction
+pub fn extract_entry(
+    archive_path: &Path,
+    entry_name: &str,
+    cache_dir: &Path,
+) -> Result<PathBuf, String> {
+    let file_name = Path::new(entry_name)
+        .file_name()
+        .ok_or_else(|| i18n::t("invalid-archive-entry"))?;
+    let target_dir = cache_dir.join(cache_key(archive_path, entry_name));
+    let target = target_dir.join(file_name);
+    if target.exists() {
+        return Ok(target);
+    }
+
+    let file = File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let mut entry = zip.by_name(entry_name).map_err(|e| e.to_string())?;
+
+    fs::create_dir_all(&target_dir).map_err(|e| e.to_string())?;
+
+    // Write to a temp name first so an interrupted extraction is never reused
+    let partial = target_dir.join(format!("{}.partial", file_name.to_string_lossy()));
+    let mut out = File::create(&partial).map_err(|e| e.to_string())?;
+    std::io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+    fs::rename(&partial, &target).map_err(|e| e.to_string())?;
+
+    Ok(target)
+}
+
+/// Uncompressed size of an archive entry, as recorded in the archive
+pub fn entry_size(archive_path: &Path, entry_name: &str) -> Result<u64, String> {
+    let file = File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let entry = zip.by_name(entry_name).map_err(|e| e.to_string())?;
+    Ok(entry.size())
+}
+
+/// Read an archive entry fully into memory
+pub fn read_entry(archive_path: &Path, entry_name: &str) -> Result<Vec<u8>, String> {
+    let file = File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    let mut entry = zip.by_name(entry_name).map_err(|e| e.to_string())?;
+
+    let mut bytes = Vec::with_capacity(entry.size() as usize);
+    std::io::copy(&mut entry, &mut bytes).map_err(|e| e.to_string())?;
+    Ok(bytes)
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use crate::journal::now_secs;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditKind {
+    /// A folder or file became accessible (scan source, dialog pick, save location)
+    Grant,
+    /// A path outside the allowed folders was refused
+    Denial,
+    Write,
+    Move,
+    /// An external program was started
+    Launch,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub kind: AuditKind,
+    /// The path concerned, or the program for launches
+    pub subject: String,
+    /// Where a move or copy came from, or the arguments of a launch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Entries returned when the frontend doesn't ask for a number
+const DEFAULT_LIMIT: usize = 500;
+/// Past this size the log moves to `audit.log.1`, replacing the one before
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+/// The same path refused again within this many seconds isn't recorded again
+const DENIAL_REPEAT_SECS: u64 = 60;
+
+/// JSON Lines file in the app data dir; lines are only ever appended, never rewritten,
+/// until the file is rotated
+static LOG_FILE: OnceLock<Mutex<PathBuf>> = OnceLock::new();
+/// Recently recorded denials and when, so a retry loop can't flood the log
+static RECENT_DENIALS: Mutex<Vec<(String, u64)>> = Mutex::new(Vec::new());
+
+pub fn init(data_dir: &Path) {
+    let _ = LOG_FILE.set(Mutex::new(data_dir.join("audit.log")));
+}
+
+fn rotated(file: &Path) -> PathBuf {
+    file.with_extension("log.1")
+}
+
+fn append(file: &Path, entry: &AuditEntry) -> io::Result<()> {
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::metadata(file).is_ok_and(|m| m.len() >= MAX_LOG_BYTES) {
+        fs::rename(file, rotated(file))?;
+    }
+    let line = serde_json::to_string(entry)?;
+    let mut out = OpenOptions::new().create(true).append(true).open(file)?;
+    writeln!(out, "{}", line)
+}
+
+/// Append an entry; before `init` (and in the CLI) nothing is recorded
+pub fn record(kind: AuditKind, subject: impl Into<String>, detail: Option<String>) {
+    let Some(file) = LOG_FILE.get() else {
+        return;
+    };
+    let entry = AuditEntry {
+        timestamp: now_secs(),
+        kind,
+        subject: subject.into(),
+        detail,
+    };
+    if kind == AuditKind::Denial && repeated_denial(&entry) {
+        return;
+    }
+
+    // Holding the lock keeps concurrent entries on separate lines
+    let file = file.lock().unwrap();
+    if let Err(e) = append(&file, &entry) {
+        tracing::warn!(error = %e, "could not write audit log");
+    }
+}
+
+fn repeated_denial(entry: &AuditEntry) -> bool {
+    let mut recent = RECENT_DENIALS.lock().unwrap();
+    recent.retain(|(_, at)| entry.timestamp.saturating_sub(*at) < DENIAL_REPEAT_SECS);
+    if recent.iter().any(|(subject, _)| *subject == entry.subject) {
+        return true;
+    }
+    recent.push((entry.subject.clone(), entry.timestamp));
+    false
+}
+
+/// Record a file the app wrote, with the file it was copied from if any
+pub fn record_write(path: &Path, source: Option<&str>) {
+    record(
+        AuditKind::Write,
+        path.to_string_lossy(),
+        source.map(str::to_string),
+    );
+}
+
+fn read_log(path: &Path) -> Result<String, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Most recent entries first, a page at a time: `offset` entries are skipped, so the
+/// next page starts where the last one ended
+#[tauri::command]
+pub fn get_audit_log(
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> Result<Vec<AuditEntry>, String> {
+    let Some(file) = LOG_FILE.get() else {
+        return Ok(Vec::new());
+    };
+    let path = file.lock().unwrap().clone();
+    let current = read_log(&path)?;
+    let previous = read_log(&rotated(&path))?;
+
+    // A line cut short by a crash is skipped rather than failing the whole log
+    Ok(current
+        .lines()
+        .rev()
+        .chain(previous.lines().rev())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .skip(offset.unwrap_or(0))
+        .take(limit.unwrap_or(DEFAULT_LIMIT))
+        .collect())
+}
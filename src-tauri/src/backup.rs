@@ -0,0 +1,113 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use tauri::State;
+
+use crate::{audit, i18n, AllowedPaths};
+
+/// Number of previous versions kept for each save file
+pub const BACKUP_COUNT: usize = 5;
+
+const BACKUP_DIR_NAME: &str = ".vs1-backups";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BackupInfo {
+    /// 1 is the most recent backup
+    pub index: usize,
+    pub path: String,
+    pub size: u64,
+    pub modified: u64,
+}
+
+fn backup_path(save_path: &Path, index: usize) -> Option<PathBuf> {
+    let name = save_path.file_name()?.to_string_lossy();
+    let dir = save_path.parent()?.join(BACKUP_DIR_NAME);
+    Some(dir.join(format!("{}.bak{}", name, index)))
+}
+
+/// Shift existing backups down by one and copy the current save into `.bak1`
+pub fn rotate(save_path: &Path) -> Result<(), String> {
+    if !save_path.is_file() {
+        return Ok(());
+    }
+
+    let first = backup_path(save_path, 1).ok_or_else(|| i18n::t("invalid-save-path"))?;
+    if let Some(dir) = first.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    for index in (1..BACKUP_COUNT).rev() {
+        if let (Some(from), Some(to)) = (
+            backup_path(save_path, index),
+            backup_path(save_path, index + 1),
+        ) {
+            if from.exists() {
+                fs::rename(&from, &to).map_err(|e| e.to_string())?;
+            }
+        }
+    }
+
+    fs::copy(save_path, &first).map_err(|e| e.to_string())?;
+    audit::record_write(&first, Some(&save_path.to_string_lossy()));
+    Ok(())
+}
+
+pub fn list(save_path: &Path) -> Vec<BackupInfo> {
+    (1..=BACKUP_COUNT)
+        .filter_map(|index| {
+            let path = backup_path(save_path, index)?;
+            let metadata = fs::metadata(&path).ok()?;
+            let modified = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+
+            Some(BackupInfo {
+                index,
+                path: path.to_string_lossy().to_string(),
+                size: metadata.len(),
+                modified,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn list_backups(
+    file_path: &str,
+    allowed_paths: State<AllowedPaths>,
+) -> Result<Vec<BackupInfo>, String> {
+    let path = Path::new(file_path);
+    if !allowed_paths.is_path_allowed(path.parent().unwrap_or(path)) {
+        return Err(i18n::t("access-denied"));
+    }
+
+    Ok(list(path))
+}
+
+/// Replace a save file with one of its backups; the current version becomes `.bak1`
+#[tauri::command]
+pub fn restore_backup(
+    file_path: &str,
+    index: usize,
+    allowed_paths: State<AllowedPaths>,
+) -> Result<(), String> {
+    let path = Path::new(file_path);
+    if !allowed_paths.is_path_allowed(path.parent().unwrap_or(path)) {
+        return Err(i18n::t("access-denied"));
+    }
+
+    let backup = backup_path(path, index)
+        .filter(|p| p.is_file())
+        .ok_or_else(|| i18n::t("backup-not-found"))?;
+
+    // Read first: rotating renumbers the backups
+    let bytes = fs::read(&backup).map_err(|e| e.to_string())?;
+    rotate(path)?;
+    fs::write(path, bytes).map_err(|e| e.to_string())?;
+    audit::record_write(path, Some(&backup.to_string_lossy()));
+    Ok(())
+}
@@ -0,0 +1,19 @@
+use crate::{i18n, pins, TournamentState};
+
+/// Take `path` out of scheduling without losing it, e.g. when it turns out to be the
+/// wrong take, or put it back in. A benched sample keeps its score and history, skips
+/// every cut and has its comparisons still to come this round dropped; brought back,
+/// it is paired again from the next round.
+pub fn set_benched(state: &mut TournamentState, path: &str, benched: bool) -> Result<(), String> {
+    let index = state
+        .samples
+        .iter()
+        .position(|s| s.path == path)
+        .ok_or_else(|| i18n::t("sample-not-in-tournament"))?;
+    state.samples[index].benched = benched;
+    if benched {
+        pins::unschedule(state, index);
+    }
+    tracing::info!(path, benched, "bench changed");
+    Ok(())
+}
@@ -0,0 +1,3 @@
+fn main() {
+    std::process::exit(vs_1_lib::cli::run())
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+use crate::rematches::{pairable, Played};
+use crate::TournamentState;
+
+/// Who sits out when a round has an odd number of samples to pair
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ByePolicy {
+    /// The sample with the fewest comparisons so far, never the same one two rounds running
+    #[default]
+    Fair,
+    /// Whoever the pairing left over, usually the lowest-ranked sample
+    Lowest,
+}
+
+/// A sample that sat a round out
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Bye {
+    pub round: i32,
+    pub path: String,
+    /// Comparisons it had played when the bye was given
+    pub comparisons: i32,
+}
+
+/// The sample that should sit out this round, from `candidates`
+fn choose(state: &TournamentState, candidates: &[usize]) -> Option<usize> {
+    let previous = state
+        .byes
+        .iter()
+        .find(|bye| bye.round == state.current_round - 1)
+        .map(|bye| bye.path.as_str());
+    let eligible: Vec<usize> = candidates
+        .iter()
+        .copied()
+        .filter(|&i| Some(state.samples[i].path.as_str()) != previous)
+        .collect();
+    // With a single candidate left, a repeat bye beats leaving it unpaired
+    let pool: &[usize] = if eligible.is_empty() {
+        candidates
+    } else {
+        &eligible
+    };
+
+    // Ties go to the lower score, which is who sat out before byes were assigned
+    pool.iter().copied().min_by(|&a, &b| {
+        let (a, b) = (&state.samples[a], &state.samples[b]);
+        (a.comparisons, a.score, &a.path).cmp(&(b.comparisons, b.score, &b.path))
+    })
+}
+
+/// Drop the candidates whose place `left_over` can't take without a rematch against
+/// their opponent
+fn without_rematches(
+    state: &TournamentState,
+    candidates: &[usize],
+    left_over: usize,
+    pending: usize,
+) -> Vec<usize> {
+    if state.allow_rematches {
+        return candidates.to_vec();
+    }
+    let played = Played::new(state);
+    let path = |i: usize| state.samples[i].path.as_str();
+    candidates
+        .iter()
+        .copied()
+        .filter(|&candidate| {
+            let opponent =
+                state.comparisons_this_round[pending..]
+                    .iter()
+                    .find_map(|&(a, b)| match (a == candidate, b == candidate) {
+                        (true, _) => Some(b),
+                        (_, true) => Some(a),
+                        _ => None,
+                    });
+            opponent.is_none_or(|opponent| !played.met(path(left_over), path(opponent)))
+        })
+        .collect()
+}
+
+/// Give the current round's bye and record it in `byes`. The pairings are kept; when
+/// the fair choice is already paired it swaps places with the sample left over, so
+/// its opponent plays that one instead. Rounds with an even count get no bye.
+pub fn assign(state: &mut TournamentState) {
+    let round = state.current_round;
+    state.byes.retain(|bye| bye.round != round);
+
+    let pairable = pairable(state);
+    if pairable.len() < 3 || pairable.len() % 2 == 0 {
+        return;
+    }
+
+    let pending = state
+        .current_comparison_index
+        .min(state.comparisons_this_round.len());
+    let paired: Vec<usize> = state.comparisons_this_round[pending..]
+        .iter()
+        .flat_map(|&(a, b)| [a, b])
+        .collect();
+    let unpaired: Vec<usize> = pairable
+        .iter()
+        .copied()
+        .filter(|i| !paired.contains(i))
+        .collect();
+    // Plugins may pair the round differently; only a single sample left over is a bye
+    let &[left_over] = unpaired.as_slice() else {
+        return;
+    };
+
+    let sitting_out = match state.bye_policy {
+        ByePolicy::Lowest => left_over,
+        ByePolicy::Fair => choose(
+            state,
+            &without_rematches(state, &pairable, left_over, pending),
+        )
+        .unwrap_or(left_over),
+    };
+    if sitting_out != left_over {
+        for pair in &mut state.comparisons_this_round[pending..] {
+            if pair.0 == sitting_out {
+                pair.0 = left_over;
+            } else if pair.1 == sitting_out {
+                pair.1 = left_over;
+            }
+        }
+    }
+
+    let sample = &state.samples[sitting_out];
+    tracing::debug!(round, path = %sample.path, "bye assigned");
+    state.byes.push(Bye {
+        round,
+        path: sample.path.clone(),
+        comparisons: sample.comparisons,
+    });
+}
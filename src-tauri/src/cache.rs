@@ -0,0 +1,22 @@
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Stable directory name for derived files of `source`; changes when the source is modified
+pub fn cache_key(source: &Path, extra: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(source.to_string_lossy().as_bytes());
+    // Separators keep "a" + "bc" from hashing like "ab" + "c"
+    hasher.update([0]);
+    hasher.update(extra.as_bytes());
+    hasher.update([0]);
+    let modified = fs::metadata(source)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok());
+    if let Some(modified) = modified {
+        hasher.update(modified.as_nanos().to_le_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::State;
+
+use crate::export::ELIMINATED_SCORE;
+use crate::rng::Rng;
+use crate::sessions::Sessions;
+use crate::{i18n, scan, AllowedPaths, TournamentState};
+
+/// Regular comparisons between two calibration matches
+const DEFAULT_INTERVAL: usize = 10;
+/// Most recent calibration matches compared against the ones before them
+const DRIFT_WINDOW: usize = 6;
+/// Change in the reference's win rate that counts as drift
+const DRIFT_TOLERANCE: f32 = 0.3;
+/// Random stream calibration opponents are drawn from, apart from the pairing one
+const CALIBRATION_STREAM: u32 = 2;
+
+/// A known-good sample replayed against the pool at intervals. Its matches don't
+/// change any scores; they only show whether judgments stay consistent over a session.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Reference {
+    pub path: String,
+    pub interval: usize,
+    #[serde(default)]
+    pub checks: Vec<CalibrationCheck>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalibrationCheck {
+    pub round: i32,
+    pub opponent: String,
+    /// Opponent's score when the match was played
+    pub opponent_score: i32,
+    pub reference_won: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CalibrationReport {
+    pub checks: usize,
+    /// Reference win rate before the recent window (`None` until there are enough checks)
+    pub baseline_win_rate: Option<f32>,
+    pub recent_win_rate: Option<f32>,
+    pub drifting: bool,
+}
+
+fn win_rate(checks: &[CalibrationCheck]) -> Option<f32> {
+    if checks.is_empty() {
+        return None;
+    }
+    let won = checks.iter().filter(|c| c.reference_won).count();
+    Some(won as f32 / checks.len() as f32)
+}
+
+/// Compare the latest calibration matches with the earlier ones; the reference
+/// suddenly winning (or losing) much more often means the judging standard moved
+pub fn report(reference: &Reference) -> CalibrationReport {
+    let checks = &reference.checks;
+    let split = checks.len().saturating_sub(DRIFT_WINDOW);
+    let (baseline, recent) = checks.split_at(split);
+
+    let baseline_win_rate = (baseline.len() >= DRIFT_WINDOW)
+        .then(|| win_rate(baseline))
+        .flatten();
+    let recent_win_rate = (recent.len() >= DRIFT_WINDOW)
+        .then(|| win_rate(recent))
+        .flatten();
+    let drifting = matches!(
+        (baseline_win_rate, recent_win_rate),
+        (Some(before), Some(now)) if (now - before).abs() > DRIFT_TOLERANCE
+    );
+
+    CalibrationReport {
+        checks: checks.len(),
+        baseline_win_rate,
+        recent_win_rate,
+        drifting,
+    }
+}
+
+/// Index of the calibration reference among the samples
+pub fn reference_index(state: &TournamentState) -> Option<usize> {
+    let path = &state.reference.as_ref()?.path;
+    state.samples.iter().position(|s| &s.path == path)
+}
+
+/// Whether the comparison on screen is a calibration match
+pub fn is_calibration_pairing(state: &TournamentState) -> bool {
+    let pairing = state
+        .comparisons_this_round
+        .get(state.current_comparison_index);
+    matches!((pairing, reference_index(state)), (Some(&(a, b)), Some(r)) if a == r || b == r)
+}
+
+/// Put the reference against a random active sample after every `interval` regular
+/// comparisons still to come this round. Earlier calibration matches still to come
+/// are dropped first, so this can run again after the schedule changed.
+pub fn schedule(state: &mut TournamentState) {
+    let next = state
+        .current_comparison_index
+        .min(state.comparisons_this_round.len());
+    let reference = reference_index(state);
+    let pending: Vec<(usize, usize)> = state.comparisons_this_round[next..]
+        .iter()
+        .copied()
+        .filter(|&(a, b)| reference.is_none_or(|r| a != r && b != r))
+        .collect();
+    state.comparisons_this_round.truncate(next);
+
+    let (Some(reference), Some(interval)) =
+        (reference, state.reference.as_ref().map(|r| r.interval))
+    else {
+        state.comparisons_this_round.extend(pending);
+        return;
+    };
+    let opponents: Vec<usize> = (0..state.samples.len())
+        .filter(|&i| {
+            let sample = &state.samples[i];
+            i != reference
+                && sample.score > ELIMINATED_SCORE
+                && sample.pin.is_none()
+                && !sample.benched
+        })
+        .collect();
+    if opponents.is_empty() {
+        state.comparisons_this_round.extend(pending);
+        return;
+    }
+
+    let mut rng = Rng::stream(
+        state.seed,
+        &[CALIBRATION_STREAM, state.current_round as u32, next as u32],
+    );
+    let mut scheduled = Vec::with_capacity(pending.len() + pending.len() / interval);
+    for (i, pair) in pending.into_iter().enumerate() {
+        scheduled.push(pair);
+        if (i + 1) % interval == 0 {
+            let opponent = opponents[rng.below(opponents.len() as u64) as usize];
+            // Alternate sides so the reference isn't always heard first
+            scheduled.push(if scheduled.len() % 2 == 0 {
+                (reference, opponent)
+            } else {
+                (opponent, reference)
+            });
+        }
+    }
+    state.comparisons_this_round.extend(scheduled);
+}
+
+/// Make `path` the tournament's calibration opponent, or clear it with `None`, and
+/// schedule its matches. Samples outside the pool are added to it so their matches
+/// can be scheduled.
+pub fn set_reference(
+    state: &mut TournamentState,
+    path: Option<String>,
+    interval: Option<usize>,
+    allowed_paths: &AllowedPaths,
+) -> Result<(), String> {
+    let Some(path) = path else {
+        state.reference = None;
+        schedule(state);
+        return Ok(());
+    };
+
+    if !state.samples.iter().any(|s| s.path == path) {
+        let file = Path::new(&path);
+        if !allowed_paths.is_path_allowed(file) {
+            return Err(i18n::t("access-denied"));
+        }
+        if !file.exists() {
+            return Err(i18n::t("file-not-found"));
+        }
+        let sample =
+            scan::sample_from_path(file).ok_or_else(|| i18n::t("unsupported-audio-file"))?;
+        state.samples.push(sample);
+    }
+
+    // Keep earlier checks when only the interval changes
+    let checks = match state.reference.take() {
+        Some(previous) if previous.path == path => previous.checks,
+        _ => Vec::new(),
+    };
+    state.reference = Some(Reference {
+        path,
+        interval: interval.unwrap_or(DEFAULT_INTERVAL).max(1),
+        checks,
+    });
+    schedule(state);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_calibration(
+    session_id: String,
+    sessions: State<Sessions>,
+) -> Result<Option<CalibrationReport>, String> {
+    sessions.read(&session_id, |state| state.reference.as_ref().map(report))
+}
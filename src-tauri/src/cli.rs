@@ -0,0 +1,246 @@
+use clap::{Parser, Subcommand};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::analysis::analyze_batch;
+use crate::dataset::write_parquet;
+use crate::duplicates::duplicate_groups;
+use crate::export::{format_results, ResultsFormat};
+use crate::scan::{scan_samples, scan_samples_with_progress, ScanOptions};
+use crate::search::{search, SampleQuery, SortKey};
+use crate::statistics::{format_pack_stats, statistics};
+use crate::{i18n, savefile, variations};
+
+/// Headless access to the VS-1 scanner and exporters
+#[derive(Parser)]
+#[command(name = "vs1", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the audio samples found in a directory
+    Scan {
+        directory: PathBuf,
+        /// Also list audio entries inside .zip archives
+        #[arg(long)]
+        archives: bool,
+        /// Decode every file and report clipping / DC offset
+        #[arg(long)]
+        analyze: bool,
+        /// Skip files silent for more than this fraction of their length (0-1; implies --analyze)
+        #[arg(long)]
+        max_silence: Option<f32>,
+        /// Collapse filename variations (kick_01a, kick_01b) into one entry
+        #[arg(long)]
+        group_variations: bool,
+        /// Include samples referenced by .sfz / .xrni instruments
+        #[arg(long)]
+        instruments: bool,
+        /// Folder levels to descend (1 = only the directory's own files)
+        #[arg(long)]
+        max_depth: Option<usize>,
+        /// Skip dotfiles and hidden folders
+        #[arg(long)]
+        skip_hidden: bool,
+        /// List a random subset of at most this many files
+        #[arg(long)]
+        sample_limit: Option<usize>,
+        /// Seed for --sample-limit; the same seed picks the same files
+        #[arg(long)]
+        sample_seed: Option<u32>,
+        #[arg(long, value_enum, default_value = "paths")]
+        export: ResultsFormat,
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Export the standings of a saved tournament
+    Export {
+        save_file: PathBuf,
+        #[arg(long, value_enum, default_value = "csv")]
+        format: ResultsFormat,
+        #[arg(long, default_value_t = i32::MIN)]
+        min_score: i32,
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Only filenames containing this text (case-insensitive)
+        #[arg(long)]
+        contains: Option<String>,
+        /// Order by a descriptor instead of the standings (low to high)
+        #[arg(long, value_enum)]
+        sort: Option<SortKey>,
+        /// Reverse the order given by --sort
+        #[arg(long)]
+        descending: bool,
+        /// Password for encrypted save files
+        #[arg(long)]
+        password: Option<String>,
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Per-pack aggregate scores of a saved tournament
+    Packs {
+        save_file: PathBuf,
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: ResultsFormat,
+        /// Password for encrypted save files
+        #[arg(long)]
+        password: Option<String>,
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Write every sample of a saved tournament with its analysis as a Parquet file
+    Dataset {
+        save_file: PathBuf,
+        out: PathBuf,
+        /// Password for encrypted save files
+        #[arg(long)]
+        password: Option<String>,
+    },
+    /// Find byte-identical audio files in a directory
+    Dedupe {
+        directory: PathBuf,
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+fn emit(text: &str, out: Option<PathBuf>) -> Result<(), String> {
+    match out {
+        Some(path) => fs::write(path, text).map_err(|e| e.to_string()),
+        None => {
+            println!("{}", text);
+            Ok(())
+        }
+    }
+}
+
+fn run_command(command: Command) -> Result<(), String> {
+    match command {
+        Command::Scan {
+            directory,
+            archives,
+            analyze,
+            max_silence,
+            group_variations,
+            instruments,
+            max_depth,
+            skip_hidden,
+            sample_limit,
+            sample_seed,
+            export,
+            out,
+        } => {
+            let options = ScanOptions {
+                include_archives: archives,
+                analyze,
+                max_silence_ratio: max_silence,
+                group_variations,
+                include_instruments: instruments,
+                max_depth,
+                include_hidden: !skip_hidden,
+                sample_limit,
+                sample_seed,
+                ..ScanOptions::default()
+            };
+            let (mut samples, report) = scan_samples_with_progress(&directory, &options, |_| {});
+            if let Some(sampling) = report.sampling {
+                eprintln!(
+                    "Sampled {} of {} files (seed {})",
+                    samples.len(),
+                    sampling.population,
+                    sampling.seed
+                );
+            }
+            if options.needs_analysis() {
+                samples = analyze_batch(samples, None).samples;
+                samples.retain(|sample| options.keep(sample));
+            }
+            if options.group_variations {
+                samples = variations::group(samples);
+            }
+            let refs: Vec<_> = samples.iter().collect();
+            emit(&format_results(&refs, export)?, out)
+        }
+        Command::Export {
+            save_file,
+            format,
+            min_score,
+            limit,
+            contains,
+            sort,
+            descending,
+            password,
+            out,
+        } => {
+            let bytes = fs::read(&save_file).map_err(|e| e.to_string())?;
+            let state = savefile::decode(bytes, password.as_deref())?;
+            let query = SampleQuery {
+                text: contains,
+                min_score: Some(min_score),
+                sort,
+                descending,
+                limit,
+                ..SampleQuery::default()
+            };
+            let ranked = variations::expand(&search(&state.samples, &query));
+            let ranked: Vec<_> = ranked.iter().collect();
+            emit(&format_results(&ranked, format)?, out)
+        }
+        Command::Packs {
+            save_file,
+            format,
+            password,
+            out,
+        } => {
+            let bytes = fs::read(&save_file).map_err(|e| e.to_string())?;
+            let state = savefile::decode(bytes, password.as_deref())?;
+            emit(
+                &format_pack_stats(
+                    &statistics(&state.samples, &state.history, &state.votes),
+                    format,
+                )?,
+                out,
+            )
+        }
+        Command::Dataset {
+            save_file,
+            out,
+            password,
+        } => {
+            let bytes = fs::read(&save_file).map_err(|e| e.to_string())?;
+            let state = savefile::decode(bytes, password.as_deref())?;
+            write_parquet(&state.samples, &out)
+        }
+        Command::Dedupe { directory, out } => {
+            let samples = scan_samples(&directory, &ScanOptions::default());
+            let groups = duplicate_groups(samples.iter().map(|s| s.path.as_str()), |path, e| {
+                eprintln!("Skipping {}: {}", path, e)
+            });
+
+            // One block per duplicate group, separated by blank lines
+            let text = groups
+                .iter()
+                .map(|paths| paths.join("\n"))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            emit(&text, out)
+        }
+    }
+}
+
+/// Entry point of the `vs1` binary; returns the process exit code
+pub fn run() -> i32 {
+    let cli = Cli::parse();
+    i18n::set_language(None);
+    match run_command(cli.command) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::error::CommandError;
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use crate::process;
+
+/// Put plain text on the OS clipboard
+pub fn set_text(text: &str) -> Result<(), CommandError> {
+    #[cfg(target_os = "macos")]
+    {
+        use cocoa::appkit::{NSPasteboard, NSPasteboardTypeString};
+        use cocoa::base::{id, nil};
+        use cocoa::foundation::NSString;
+
+        unsafe {
+            let pasteboard: id = NSPasteboard::generalPasteboard(nil);
+            NSPasteboard::clearContents(pasteboard);
+
+            let string: id = NSString::alloc(nil).init_str(text);
+            NSPasteboard::setString_forType(pasteboard, string, NSPasteboardTypeString);
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // Pipe through stdin so multi-line text and quotes survive intact
+        process::pipe(
+            "powershell",
+            &[
+                "-NoProfile",
+                "-Command",
+                "[Console]::InputEncoding = [Text.Encoding]::UTF8; Set-Clipboard -Value ([Console]::In.ReadToEnd())",
+            ],
+            text,
+        )?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        process::pipe("xclip", &["-selection", "clipboard"], text)?;
+    }
+
+    Ok(())
+}
+
+/// Put files on the OS clipboard as real file objects, so pasting into Finder,
+/// Explorer or a DAW copies them all at once
+pub fn set_files(paths: &[&str]) -> Result<(), CommandError> {
+    process::check_values(paths)?;
+
+    #[cfg(target_os = "macos")]
+    {
+        use cocoa::appkit::NSPasteboard;
+        use cocoa::base::{id, nil};
+        use cocoa::foundation::{NSArray, NSString, NSURL};
+
+        unsafe {
+            let pasteboard: id = NSPasteboard::generalPasteboard(nil);
+            NSPasteboard::clearContents(pasteboard);
+
+            let urls: Vec<id> = paths
+                .iter()
+                .map(|path| NSURL::fileURLWithPath_(nil, NSString::alloc(nil).init_str(path)))
+                .collect();
+            let array: id = NSArray::arrayWithObjects(nil, &urls);
+            NSPasteboard::writeObjects(pasteboard, array);
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // One path per line on stdin; quoting every path on the command line gets fragile
+        process::pipe(
+            "powershell",
+            &[
+                "-NoProfile",
+                "-Command",
+                "[Console]::InputEncoding = [Text.Encoding]::UTF8; Set-Clipboard -LiteralPath ([Console]::In.ReadToEnd() -split \"`r?`n\" | Where-Object { $_ })",
+            ],
+            &paths.join("\n"),
+        )?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let uris: Vec<String> = paths
+            .iter()
+            .filter_map(|path| process::file_uri(path))
+            .collect();
+        process::pipe(
+            "xclip",
+            &["-selection", "clipboard", "-t", "text/uri-list"],
+            &uris.join("\r\n"),
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::i18n;
+
+/// How long `materialize` waits for a sync client to finish a download
+const MATERIALIZE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// If `path` is an iCloud eviction stub (`.Name.wav.icloud`), return the real file path
+pub fn icloud_stub_target(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_string_lossy();
+    let inner = name.strip_prefix('.')?.strip_suffix(".icloud")?;
+    Some(path.with_file_name(inner))
+}
+
+fn icloud_stub_for(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_string_lossy();
+    Some(path.with_file_name(format!(".{}.icloud", name)))
+}
+
+/// Whether a file exists locally only as an online-only placeholder
+/// (Dropbox, OneDrive, iCloud file provider)
+pub fn is_placeholder(path: &Path) -> bool {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return false;
+    };
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_OFFLINE: u32 = 0x1000;
+        const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x40000;
+        const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x400000;
+
+        metadata.file_attributes()
+            & (FILE_ATTRIBUTE_OFFLINE
+                | FILE_ATTRIBUTE_RECALL_ON_OPEN
+                | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS)
+            != 0
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        use std::os::macos::fs::MetadataExt;
+        const SF_DATALESS: u32 = 0x4000_0000;
+
+        metadata.st_flags() & SF_DATALESS != 0 || (metadata.len() > 0 && metadata.st_blocks() == 0)
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        // A non-empty file without any allocated blocks has no local content
+        metadata.len() > 0 && metadata.blocks() == 0
+    }
+}
+
+/// Trigger a download of an online-only file and wait until it is available locally
+pub fn materialize(path: &Path) -> Result<(), String> {
+    if let Some(stub) = icloud_stub_for(path).filter(|stub| stub.exists()) {
+        #[cfg(target_os = "macos")]
+        {
+            use crate::audit::{self, AuditKind};
+
+            audit::record(
+                AuditKind::Launch,
+                "brctl",
+                Some(format!("download {}", path.display())),
+            );
+            std::process::Command::new("brctl")
+                .arg("download")
+                .arg(path)
+                .output()
+                .map_err(|e| {
+                    i18n::t_args(
+                        "program-failed",
+                        [("program", "brctl".into()), ("error", e.to_string().into())],
+                    )
+                })?;
+        }
+
+        let started = Instant::now();
+        while stub.exists() || !path.exists() {
+            if started.elapsed() > MATERIALIZE_TIMEOUT {
+                return Err(i18n::t("cloud-download-timeout"));
+            }
+            std::thread::sleep(Duration::from_millis(250));
+        }
+        return Ok(());
+    }
+
+    if !path.exists() {
+        return Err(i18n::t("file-not-found"));
+    }
+
+    // Reading the content makes Dropbox/OneDrive hydrate the file
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    let mut buffer = vec![0u8; 1 << 20];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+    }
+
+    Ok(())
+}
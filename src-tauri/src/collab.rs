@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n;
+use crate::remote::random_token;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Choice {
+    A,
+    B,
+    Skip,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Voter {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Ballot {
+    pub voter_id: String,
+    pub voter_name: String,
+    pub choice: Choice,
+}
+
+/// Aggregated outcome of one comparison
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Decision {
+    pub pair_id: String,
+    /// Majority choice; ties go to the voter who joined first
+    pub winner: Choice,
+    /// Individual ballots (empty for a single direct vote)
+    pub ballots: Vec<Ballot>,
+    /// Fraction of A/B ballots that preferred A, for averaged rating updates
+    pub share_a: f32,
+}
+
+/// Per-comparison vote history kept in the tournament state
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct VoteRecord {
+    pub pair_id: String,
+    pub round: i32,
+    pub a: String,
+    pub b: String,
+    pub winner: Choice,
+    pub ballots: Vec<Ballot>,
+}
+
+/// Voters who joined over the remote API and the ballots cast on the current pair
+#[derive(Debug, Default)]
+pub struct CollabSession {
+    pub voters: Vec<Voter>,
+    ballots: Vec<Ballot>,
+    decided: bool,
+    /// Ballots needed before a pair is decided; `None` means every joined voter
+    pub quorum: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SessionStatus {
+    pub voters: Vec<Voter>,
+    pub ballots_cast: usize,
+    pub ballots_needed: usize,
+}
+
+impl CollabSession {
+    pub fn join(&mut self, name: &str) -> Result<Voter, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(i18n::t("name-empty"));
+        }
+
+        let voter = Voter {
+            id: random_token()?,
+            name: name.to_string(),
+        };
+        self.voters.push(voter.clone());
+        Ok(voter)
+    }
+
+    /// Called whenever the main window moves on to another comparison
+    pub fn reset_pair(&mut self) {
+        self.ballots.clear();
+        self.decided = false;
+    }
+
+    fn ballots_needed(&self) -> usize {
+        let voters = self.voters.len().max(1);
+        self.quorum.unwrap_or(voters).clamp(1, voters)
+    }
+
+    pub fn status(&self) -> SessionStatus {
+        SessionStatus {
+            voters: self.voters.clone(),
+            ballots_cast: self.ballots.len(),
+            ballots_needed: self.ballots_needed(),
+        }
+    }
+
+    /// Record (or replace) a voter's ballot; returns the decision once the quorum is met
+    pub fn cast(
+        &mut self,
+        pair_id: &str,
+        voter_id: &str,
+        choice: Choice,
+    ) -> Result<Option<Decision>, String> {
+        if self.decided {
+            return Err(i18n::t("vote-already-decided"));
+        }
+
+        let voter = self
+            .voters
+            .iter()
+            .find(|v| v.id == voter_id)
+            .ok_or_else(|| i18n::t("vote-unknown-voter"))?;
+
+        self.ballots.retain(|b| b.voter_id != voter_id);
+        self.ballots.push(Ballot {
+            voter_id: voter.id.clone(),
+            voter_name: voter.name.clone(),
+            choice,
+        });
+
+        if self.ballots.len() < self.ballots_needed() {
+            return Ok(None);
+        }
+
+        self.decided = true;
+        Ok(Some(aggregate(pair_id, &self.voters, &self.ballots)))
+    }
+
+    /// A vote from a client that never joined; with a quorum set it would decide the
+    /// pair without waiting for anyone else's ballot, so it is refused
+    pub fn direct(&self, pair_id: &str, choice: Choice) -> Result<Decision, String> {
+        if self.quorum.is_some() {
+            return Err(i18n::t("vote-needs-voter"));
+        }
+        Ok(direct_decision(pair_id, choice))
+    }
+}
+
+fn share_of_a(ballots: &[Ballot]) -> f32 {
+    let a = ballots.iter().filter(|b| b.choice == Choice::A).count();
+    let b = ballots.iter().filter(|b| b.choice == Choice::B).count();
+    if a + b == 0 {
+        0.5
+    } else {
+        a as f32 / (a + b) as f32
+    }
+}
+
+/// Majority vote across ballots, breaking ties by voter join order
+pub fn aggregate(pair_id: &str, voters: &[Voter], ballots: &[Ballot]) -> Decision {
+    let count = |choice: Choice| ballots.iter().filter(|b| b.choice == choice).count();
+    let top = [Choice::A, Choice::B, Choice::Skip]
+        .into_iter()
+        .map(count)
+        .max()
+        .unwrap_or(0);
+
+    let winner = voters
+        .iter()
+        .filter_map(|voter| ballots.iter().find(|b| b.voter_id == voter.id))
+        .map(|ballot| ballot.choice)
+        .find(|choice| count(*choice) == top)
+        .unwrap_or(Choice::Skip);
+
+    Decision {
+        pair_id: pair_id.to_string(),
+        winner,
+        ballots: ballots.to_vec(),
+        share_a: share_of_a(ballots),
+    }
+}
+
+/// A vote cast directly (without joining a session) decides the pair on its own
+pub fn direct_decision(pair_id: &str, choice: Choice) -> Decision {
+    Decision {
+        pair_id: pair_id.to_string(),
+        winner: choice,
+        ballots: Vec::new(),
+        share_a: match choice {
+            Choice::A => 1.0,
+            Choice::B => 0.0,
+            Choice::Skip => 0.5,
+        },
+    }
+}
@@ -0,0 +1,30 @@
+use tauri::AppHandle;
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+use crate::i18n;
+
+fn dialog(
+    app: &AppHandle,
+    title: &str,
+    message: &str,
+) -> tauri_plugin_dialog::MessageDialogBuilder<tauri::Wry> {
+    app.dialog()
+        .message(message)
+        .title(title)
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            i18n::t("confirm-allow"),
+            i18n::t("confirm-cancel"),
+        ))
+}
+
+/// Ask in a native dialog, which nothing running in the webview can answer for the
+/// user; `then` gets the answer once the dialog closes
+pub fn ask(app: &AppHandle, title: &str, message: &str, then: impl FnOnce(bool) + Send + 'static) {
+    dialog(app, title, message).show(then);
+}
+
+/// Like [`ask`], waiting for the answer; blocks, so never on the main thread
+pub fn ask_blocking(app: &AppHandle, title: &str, message: &str) -> bool {
+    dialog(app, title, message).blocking_show()
+}
@@ -0,0 +1,391 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{AppHandle, Manager, State};
+
+use crate::export::{passes_min_score, sorted_results};
+use crate::jobs::{self, JobKind};
+use crate::journal::{now_secs, Journal, OperationKind};
+use crate::manifest::{self, ExportKind, ExportManifest, ManifestEntry};
+use crate::path_template::{self, PathTemplate, TemplateValues};
+use crate::settings::SettingsStore;
+use crate::{archive, audit, variations, AllowedPaths, Sample};
+use crate::{i18n, notifications};
+
+pub const MANIFEST_FILE_NAME: &str = "vs1-manifest.json";
+
+/// Sidecar written next to the copies when notes are not put into filenames
+pub const NOTES_FILE_NAME: &str = "vs1-notes.csv";
+
+/// Persist the manifest every this many files so an interrupted export can resume
+const MANIFEST_FLUSH_INTERVAL: usize = 25;
+/// Hex digits of the source path hash used to tell apart files that share a name
+const COLLISION_HASH_LEN: usize = 8;
+
+/// Where to record each copy's detected root note
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteTagging {
+    /// Append to the filename, e.g. `bass_01_F#2.wav`
+    Filename,
+    /// List in a `vs1-notes.csv` next to the copies
+    Sidecar,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CopyFailure {
+    pub source: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CopyExportReport {
+    pub copied: usize,
+    /// Files already present and verified from a previous, interrupted run
+    pub resumed: usize,
+    pub failed: Vec<CopyFailure>,
+    /// Files renamed because a better-ranked file already had their name
+    pub renamed: usize,
+    pub manifest_path: String,
+    /// (source, destination) of every file written in this run, for the journal
+    #[serde(skip)]
+    pub written: Vec<(String, String)>,
+}
+
+/// Open a sample for reading, whether it is a plain file or an archive entry
+pub fn open_source(path: &str) -> io::Result<Box<dyn Read>> {
+    match archive::split_archive_path(path) {
+        Some((archive_path, entry_name)) => {
+            let bytes = archive::read_entry(Path::new(archive_path), entry_name)
+                .map_err(io::Error::other)?;
+            Ok(Box::new(io::Cursor::new(bytes)))
+        }
+        None => Ok(Box::new(BufReader::new(File::open(path)?))),
+    }
+}
+
+/// Copy `reader` to `writer`, returning the byte count and SHA-256 of what was copied
+pub fn copy_hashed(reader: &mut dyn Read, writer: &mut dyn Write) -> io::Result<(u64, String)> {
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1 << 16];
+    let mut total = 0u64;
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        writer.write_all(&buffer[..read])?;
+        total += read as u64;
+    }
+
+    Ok((total, format!("{:x}", hasher.finalize())))
+}
+
+pub fn hash_file(path: &Path) -> io::Result<String> {
+    let mut file = BufReader::new(File::open(path)?);
+    let (_, hash) = copy_hashed(&mut file, &mut io::sink())?;
+    Ok(hash)
+}
+
+/// Size and SHA-256 of a sample, whether it is a plain file or an archive entry
+pub fn hash_source(path: &str) -> io::Result<(u64, String)> {
+    copy_hashed(&mut open_source(path)?, &mut io::sink())
+}
+
+/// Root note from the sample's cached analysis; samples never analyzed go untagged
+/// rather than holding up the copy with a full decode
+fn root_note(sample: &Sample) -> Option<(String, f32)> {
+    let analysis = sample.analysis.as_ref()?;
+    Some((analysis.root_note.clone()?, analysis.root_hz?))
+}
+
+/// `kick.wav` + `F#2` -> `kick_F#2.wav`
+fn suffixed(filename: &str, suffix: &str) -> String {
+    match filename.rsplit_once('.') {
+        Some((stem, extension)) if !extension.contains('/') => {
+            format!("{}_{}.{}", stem, suffix, extension)
+        }
+        _ => format!("{}_{}", filename, suffix),
+    }
+}
+
+/// A destination no earlier file in this export uses. Later (lower-ranked) files
+/// give way: first the pack name is appended, then a short hash of the source path,
+/// so the same export always produces the same names. Compared case-insensitively
+/// because the usual macOS and Windows volumes are.
+fn unique_destination(relative: String, sample: &Sample, taken: &mut HashSet<String>) -> String {
+    let source_hash = format!("{:x}", Sha256::digest(sample.path.as_bytes()));
+    let short_hash = &source_hash[..COLLISION_HASH_LEN];
+    let candidates = std::iter::once(relative.clone())
+        .chain(
+            sample
+                .pack
+                .as_deref()
+                .map(|pack| suffixed(&relative, &path_template::sanitize(pack))),
+        )
+        .chain(std::iter::once(suffixed(&relative, short_hash)))
+        .chain((2..).map(|n| suffixed(&relative, &format!("{}-{}", short_hash, n))));
+
+    for candidate in candidates {
+        if taken.insert(candidate.to_lowercase()) {
+            return candidate;
+        }
+    }
+    unreachable!("the numbered candidates never run out")
+}
+
+fn write_notes(path: &Path, rows: &[(String, String, f32)]) -> Result<(), String> {
+    let mut writer = csv::Writer::from_path(path).map_err(|e| e.to_string())?;
+    writer
+        .write_record(["file", "note", "hz"])
+        .map_err(|e| e.to_string())?;
+    for (file, note, hz) in rows {
+        writer
+            .write_record([file.as_str(), note.as_str(), &format!("{:.2}", hz)])
+            .map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())
+}
+
+/// Copy one sample into `dest_dir` and verify the written file against the source hash
+fn copy_one(sample: &Sample, dest_dir: &Path, relative: &str) -> Result<(u64, String), String> {
+    let target = dest_dir.join(relative);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let partial = target.with_extension("vs1-partial");
+    let (size, hash) = {
+        let mut reader = open_source(&sample.path).map_err(|e| e.to_string())?;
+        let mut out = File::create(&partial).map_err(|e| e.to_string())?;
+        let result = copy_hashed(&mut reader, &mut out).map_err(|e| e.to_string())?;
+        out.sync_all().map_err(|e| e.to_string())?;
+        result
+    };
+
+    let written_hash = hash_file(&partial).map_err(|e| e.to_string())?;
+    if written_hash != hash {
+        let _ = fs::remove_file(&partial);
+        return Err(i18n::t("copy-verification-failed"));
+    }
+
+    fs::rename(&partial, &target).map_err(|e| e.to_string())?;
+    audit::record_write(&target, Some(&sample.path));
+    Ok((size, hash))
+}
+
+/// Copy ranked samples into `dest_dir`, resuming from an existing manifest if present.
+/// With a `template` each copy goes to the rendered relative path instead of the top level.
+/// Sample paths come from the webview, so only files inside allowed folders are copied.
+pub fn copy_samples(
+    samples: &[&Sample],
+    allowed_paths: &AllowedPaths,
+    dest_dir: &Path,
+    note_tagging: Option<NoteTagging>,
+    template: Option<&PathTemplate>,
+    on_progress: impl Fn(u64),
+) -> Result<CopyExportReport, String> {
+    fs::create_dir_all(dest_dir).map_err(|e| e.to_string())?;
+
+    let manifest_path = dest_dir.join(MANIFEST_FILE_NAME);
+    let previous: HashMap<String, ManifestEntry> = manifest::load(&manifest_path)
+        .files
+        .into_iter()
+        .map(|entry| (entry.source.clone(), entry))
+        .collect();
+
+    let mut manifest = ExportManifest::new(ExportKind::Copy);
+    let mut copied = 0;
+    let mut resumed = 0;
+    let mut failed = Vec::new();
+    let mut written = Vec::new();
+    let mut notes = Vec::new();
+    let mut taken = HashSet::new();
+    let mut renamed = 0;
+
+    for (index, sample) in samples.iter().enumerate() {
+        on_progress(index as u64);
+        if !allowed_paths.is_path_allowed(Path::new(&sample.path)) {
+            failed.push(CopyFailure {
+                source: sample.path.clone(),
+                error: i18n::t("access-denied"),
+            });
+            continue;
+        }
+        let rank = index + 1;
+        let note = note_tagging.and_then(|_| root_note(sample));
+        let filename = match (&note, note_tagging) {
+            (Some((name, _)), Some(NoteTagging::Filename)) => suffixed(&sample.filename, name),
+            _ => sample.filename.clone(),
+        };
+        let relative = template
+            .map(|template| {
+                template.render(&TemplateValues {
+                    sample,
+                    rank,
+                    filename: &filename,
+                    note: note.as_ref().map(|(name, _)| name.as_str()),
+                })
+            })
+            .filter(|rendered| !rendered.is_empty())
+            .unwrap_or(filename);
+        let wanted = relative.clone();
+        let relative = unique_destination(relative, sample, &mut taken);
+        let renamed_from = (relative != wanted).then_some(wanted);
+        if renamed_from.is_some() {
+            renamed += 1;
+        }
+        if let Some((name, hz)) = &note {
+            notes.push((relative.clone(), name.clone(), *hz));
+        }
+
+        let done = previous.get(&sample.path).filter(|entry| {
+            entry.verified
+                && entry.destination == relative
+                && fs::metadata(dest_dir.join(&entry.destination))
+                    .map(|m| m.len() == entry.size)
+                    .unwrap_or(false)
+        });
+
+        if let Some(entry) = done {
+            manifest.files.push(ManifestEntry {
+                rank,
+                score: sample.score,
+                renamed_from,
+                ..entry.clone()
+            });
+            resumed += 1;
+            continue;
+        }
+
+        match copy_one(sample, dest_dir, &relative) {
+            Ok((size, sha256)) => {
+                written.push((
+                    sample.path.clone(),
+                    dest_dir.join(&relative).to_string_lossy().to_string(),
+                ));
+                manifest.files.push(ManifestEntry {
+                    source: sample.path.clone(),
+                    destination: relative,
+                    rank,
+                    score: sample.score,
+                    size,
+                    sha256,
+                    verified: true,
+                    renamed_from,
+                    timestamp: now_secs(),
+                });
+                copied += 1;
+            }
+            Err(error) => failed.push(CopyFailure {
+                source: sample.path.clone(),
+                error,
+            }),
+        }
+
+        if (copied + failed.len()) % MANIFEST_FLUSH_INTERVAL == 0 {
+            manifest::write(&manifest_path, &manifest)?;
+        }
+    }
+
+    manifest::write(&manifest_path, &manifest)?;
+    if note_tagging == Some(NoteTagging::Sidecar) {
+        write_notes(&dest_dir.join(NOTES_FILE_NAME), &notes)?;
+    }
+
+    Ok(CopyExportReport {
+        copied,
+        resumed,
+        failed,
+        renamed,
+        manifest_path: manifest_path.to_string_lossy().to_string(),
+        written,
+    })
+}
+
+/// Where `export_copy` copies to, which samples and how the copies are named
+#[derive(Debug, Deserialize, Clone)]
+pub struct CopyExportOptions {
+    pub dest_dir: String,
+    pub min_score: i32,
+    pub note_tagging: Option<NoteTagging>,
+    pub path_template: Option<String>,
+}
+
+#[tauri::command]
+pub async fn export_copy(
+    samples: Vec<Sample>,
+    options: CopyExportOptions,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+    settings: State<'_, SettingsStore>,
+) -> Result<CopyExportReport, String> {
+    settings.ensure_writable()?;
+    let CopyExportOptions {
+        dest_dir,
+        min_score,
+        note_tagging,
+        path_template,
+    } = options;
+    let dest = PathBuf::from(&dest_dir);
+    allowed_paths.check_file(&dest)?;
+
+    // The folder the user exported into is allowed from now on
+    allowed_paths.add_allowed_path(dest.clone());
+
+    // Reject a bad template before anything is copied
+    if let Some(template) = &path_template {
+        PathTemplate::parse(template)?;
+    }
+
+    let started = Instant::now();
+    let job_app = app.clone();
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        let template = path_template
+            .as_deref()
+            .map(PathTemplate::parse)
+            .transpose()?;
+        let ranked: Vec<&Sample> = sorted_results(&samples)
+            .into_iter()
+            .filter(|s| passes_min_score(s, min_score))
+            .collect();
+        let members = variations::expand(&ranked);
+        let members: Vec<&Sample> = members.iter().collect();
+        let job = jobs::start(&job_app, JobKind::Export, Some(members.len() as u64));
+        copy_samples(
+            &members,
+            &job_app.state::<AllowedPaths>(),
+            &dest,
+            note_tagging,
+            template.as_ref(),
+            |done| job.set_done(done),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    if !report.written.is_empty() {
+        app.state::<Journal>()
+            .record_batch(OperationKind::Copy, &report.written)?;
+    }
+
+    notifications::job_finished(
+        &app,
+        started,
+        &i18n::t("export-complete"),
+        &i18n::t_args(
+            "export-complete-body",
+            [
+                ("count", (report.copied + report.resumed).into()),
+                ("failed", report.failed.len().into()),
+            ],
+        ),
+    );
+    Ok(report)
+}
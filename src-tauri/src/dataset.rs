@@ -0,0 +1,213 @@
+use arrow_array::builder::{ListBuilder, StringBuilder};
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, Float32Array, Int32Array, RecordBatch, StringArray, UInt16Array,
+    UInt32Array,
+};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+use tauri::State;
+
+use crate::analysis::{Analysis, QualityFlag};
+use crate::export::{sorted_results, win_rate, ELIMINATED_SCORE};
+use crate::pins::Pin;
+use crate::{audit, AllowedPaths, Sample};
+
+fn column(array: impl Array + 'static) -> ArrayRef {
+    Arc::new(array)
+}
+
+fn strings<'a>(samples: &[&'a Sample], field: impl Fn(&'a Sample) -> Option<&'a str>) -> ArrayRef {
+    column(samples.iter().map(|&s| field(s)).collect::<StringArray>())
+}
+
+/// One analysis measurement per row, null for unanalyzed samples
+fn measured(samples: &[&Sample], field: impl Fn(&Analysis) -> f32) -> ArrayRef {
+    column(
+        samples
+            .iter()
+            .map(|s| s.analysis.as_ref().map(&field))
+            .collect::<Float32Array>(),
+    )
+}
+
+fn flags(samples: &[&Sample]) -> ArrayRef {
+    let mut builder = ListBuilder::new(StringBuilder::new());
+    for sample in samples {
+        let Some(analysis) = &sample.analysis else {
+            builder.append(false);
+            continue;
+        };
+        for flag in &analysis.flags {
+            builder.values().append_value(match flag {
+                QualityFlag::Clipping => "clipping",
+                QualityFlag::InterSampleClipping => "inter_sample_clipping",
+                QualityFlag::DcOffset => "dc_offset",
+            });
+        }
+        builder.append(true);
+    }
+    column(builder.finish())
+}
+
+/// Every sample with its standing and analysis features as typed columns, one row per
+/// sample in standings order (eliminated samples last, with a null rank)
+pub fn record_batch(samples: &[Sample]) -> Result<RecordBatch, String> {
+    let ranked = sorted_results(samples);
+    let ranks = (1..=ranked.len() as u32).map(Some);
+    let mut rows = ranked;
+    rows.extend(samples.iter().filter(|s| s.score <= ELIMINATED_SCORE));
+    let rows = rows.as_slice();
+
+    let rank: UInt32Array = ranks
+        .chain(std::iter::repeat(None))
+        .take(rows.len())
+        .collect();
+
+    RecordBatch::try_from_iter([
+        ("rank", column(rank)),
+        ("path", strings(rows, |s| Some(s.path.as_str()))),
+        ("filename", strings(rows, |s| Some(s.filename.as_str()))),
+        (
+            "score",
+            column(rows.iter().map(|s| s.score).collect::<Int32Array>()),
+        ),
+        (
+            "comparisons",
+            column(rows.iter().map(|s| s.comparisons).collect::<Int32Array>()),
+        ),
+        (
+            "win_rate",
+            column(rows.iter().map(|s| win_rate(s)).collect::<Float32Array>()),
+        ),
+        (
+            "eliminated",
+            column(BooleanArray::from(
+                rows.iter()
+                    .map(|s| s.score <= ELIMINATED_SCORE)
+                    .collect::<Vec<_>>(),
+            )),
+        ),
+        (
+            "uncertainty",
+            column(rows.iter().map(|s| s.uncertainty).collect::<Float32Array>()),
+        ),
+        (
+            "pin",
+            strings(rows, |s| match s.pin {
+                Some(Pin::AlwaysAdvance) => Some("always_advance"),
+                Some(Pin::Eliminate) => Some("eliminate"),
+                None => None,
+            }),
+        ),
+        ("pack", strings(rows, |s| s.pack.as_deref())),
+        ("instrument", strings(rows, |s| s.instrument.as_deref())),
+        (
+            "channels",
+            column(rows.iter().map(|s| s.channels).collect::<UInt16Array>()),
+        ),
+        (
+            "sample_rate",
+            column(
+                rows.iter()
+                    .map(|s| s.analysis.as_ref().map(|a| a.sample_rate))
+                    .collect::<UInt32Array>(),
+            ),
+        ),
+        ("duration_secs", measured(rows, |a| a.duration_secs)),
+        ("peak_dbfs", measured(rows, |a| a.peak_dbfs)),
+        ("true_peak_dbfs", measured(rows, |a| a.true_peak_dbfs)),
+        ("dc_offset", measured(rows, |a| a.dc_offset)),
+        (
+            "leading_silence_secs",
+            measured(rows, |a| a.leading_silence_secs),
+        ),
+        (
+            "trailing_silence_secs",
+            measured(rows, |a| a.trailing_silence_secs),
+        ),
+        ("silence_ratio", measured(rows, |a| a.silence_ratio)),
+        ("noise_floor_dbfs", measured(rows, |a| a.noise_floor_dbfs)),
+        (
+            "stereo_correlation",
+            measured(rows, |a| a.stereo_correlation),
+        ),
+        ("stereo_width", measured(rows, |a| a.stereo_width)),
+        ("mono_loss_db", measured(rows, |a| a.mono_loss_db)),
+        (
+            "spectral_centroid_hz",
+            measured(rows, |a| a.spectral_centroid_hz),
+        ),
+        (
+            "spectral_rolloff_hz",
+            measured(rows, |a| a.spectral_rolloff_hz),
+        ),
+        ("spectral_flatness", measured(rows, |a| a.spectral_flatness)),
+        (
+            "root_hz",
+            column(
+                rows.iter()
+                    .map(|s| s.analysis.as_ref().and_then(|a| a.root_hz))
+                    .collect::<Float32Array>(),
+            ),
+        ),
+        (
+            "root_note",
+            strings(rows, |s| {
+                s.analysis.as_ref().and_then(|a| a.root_note.as_deref())
+            }),
+        ),
+        (
+            "tempo_bpm",
+            column(
+                rows.iter()
+                    .map(|s| s.analysis.as_ref().and_then(|a| a.tempo_bpm))
+                    .collect::<Float32Array>(),
+            ),
+        ),
+        ("flags", flags(rows)),
+    ])
+    .map_err(|e| e.to_string())
+}
+
+/// Write the full per-sample dataset as a zstd-compressed Parquet file
+pub fn write_parquet(samples: &[Sample], dest: &Path) -> Result<(), String> {
+    let batch = record_batch(samples)?;
+    let props = WriterProperties::builder()
+        .set_compression(Compression::ZSTD(ZstdLevel::default()))
+        .build();
+
+    let file = File::create(dest).map_err(|e| e.to_string())?;
+    let mut writer =
+        ArrowWriter::try_new(file, batch.schema(), Some(props)).map_err(|e| e.to_string())?;
+    writer.write(&batch).map_err(|e| e.to_string())?;
+    writer.close().map_err(|e| e.to_string())?;
+    audit::record_write(dest, None);
+    Ok(())
+}
+
+/// Typed export for data analysis (pandas, polars, DuckDB), without the CSV round trip
+#[tauri::command]
+pub async fn export_parquet(
+    samples: Vec<Sample>,
+    file_path: String,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<(), String> {
+    let path = Path::new(&file_path).to_path_buf();
+    allowed_paths.check_file(&path)?;
+
+    // The folder the user exported into is allowed from now on
+    if let Some(parent) = path.parent() {
+        allowed_paths.add_allowed_path(parent.to_path_buf());
+    }
+
+    let count = samples.len();
+    tauri::async_runtime::spawn_blocking(move || write_parquet(&samples, &path))
+        .await
+        .map_err(|e| e.to_string())??;
+    tracing::info!(samples = count, "exported parquet dataset");
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State, Url};
+
+use crate::{confirm, i18n, AllowedPaths};
+
+/// What a `vs1://` URL asks the app to do
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum DeepLinkAction {
+    /// `vs1://open?save=/path/to/session.json`
+    Open { save: String },
+    /// `vs1://scan?dir=/path/to/samples`
+    Scan { directory: String },
+}
+
+/// Save file extensions the app is registered to open
+pub const SAVE_EXTENSIONS: &[&str] = &["vs1", "json"];
+
+/// Actions received before (or while) the frontend was ready to handle them
+#[derive(Default)]
+pub struct PendingDeepLinks(Mutex<Vec<DeepLinkAction>>);
+
+fn query_param(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.into_owned())
+}
+
+fn is_save_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .map(|ext| {
+                let ext_lower = ext.to_string_lossy().to_lowercase();
+                SAVE_EXTENSIONS.contains(&ext_lower.as_str())
+            })
+            .unwrap_or(false)
+}
+
+pub fn parse(url: &Url) -> Result<DeepLinkAction, String> {
+    // Double-clicked save files arrive as file:// URLs
+    if url.scheme() == "file" {
+        let path = url
+            .to_file_path()
+            .map_err(|_| i18n::t_args("deep-link-invalid", [("url", url.as_str().into())]))?;
+        if !is_save_file(&path) {
+            return Err(i18n::t("not-save-file"));
+        }
+        return Ok(DeepLinkAction::Open {
+            save: path.to_string_lossy().to_string(),
+        });
+    }
+
+    if url.scheme() != "vs1" {
+        return Err(i18n::t_args(
+            "deep-link-invalid",
+            [("url", url.as_str().into())],
+        ));
+    }
+
+    match url.host_str() {
+        Some("open") => {
+            let save = query_param(url, "save").ok_or_else(|| {
+                i18n::t_args("deep-link-missing-parameter", [("name", "save".into())])
+            })?;
+            // Links can come from any web page, so only save files can be opened this way
+            if !is_save_file(Path::new(&save)) {
+                return Err(i18n::t("not-save-file"));
+            }
+            Ok(DeepLinkAction::Open { save })
+        }
+        Some("scan") => {
+            let directory = query_param(url, "dir").ok_or_else(|| {
+                i18n::t_args("deep-link-missing-parameter", [("name", "dir".into())])
+            })?;
+            if !Path::new(&directory).is_dir() {
+                return Err(i18n::t("directory-not-found"));
+            }
+            Ok(DeepLinkAction::Scan { directory })
+        }
+        _ => Err(i18n::t_args(
+            "deep-link-invalid",
+            [("url", url.as_str().into())],
+        )),
+    }
+}
+
+pub fn focus_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// Queue deep links for the frontend; saves are only opened once the user agrees
+pub fn handle_urls(app: &AppHandle, urls: Vec<Url>) {
+    let actions: Vec<DeepLinkAction> = urls
+        .iter()
+        .filter_map(|url| match parse(url) {
+            Ok(action) => Some(action),
+            Err(e) => {
+                tracing::warn!(error = %e, "ignoring deep link");
+                None
+            }
+        })
+        .collect();
+
+    for action in actions {
+        let save = match &action {
+            DeepLinkAction::Open { save } => PathBuf::from(save),
+            DeepLinkAction::Scan { .. } => {
+                queue(app, action);
+                continue;
+            }
+        };
+
+        // Any web page can link here, and opening replaces the running tournament, so
+        // the user confirms outside the webview first
+        let path = save.to_string_lossy().to_string();
+        let message = i18n::t_args("confirm-open-save", [("path", path.into())]);
+        let confirm_app = app.clone();
+        focus_main_window(app);
+        confirm::ask(
+            app,
+            &i18n::t("confirm-open-save-title"),
+            &message,
+            move |confirmed| {
+                if !confirmed {
+                    tracing::info!("deep link to a save declined");
+                    return;
+                }
+                // Opening a save from the OS counts as picking it, like the file dialog
+                confirm_app.state::<AllowedPaths>().grant_file(save);
+                queue(&confirm_app, action);
+            },
+        );
+    }
+}
+
+/// Hand an action to the frontend and bring the app forward
+fn queue(app: &AppHandle, action: DeepLinkAction) {
+    app.state::<PendingDeepLinks>()
+        .0
+        .lock()
+        .unwrap()
+        .push(action);
+
+    focus_main_window(app);
+    let _ = app.emit("deep-link://received", ());
+}
+
+/// Handle save files passed on the command line (file association on Windows/Linux)
+pub fn handle_file_args(app: &AppHandle, args: &[String]) {
+    let urls = args
+        .iter()
+        .skip(1)
+        .map(Path::new)
+        .filter(|path| is_save_file(path))
+        .filter_map(|path| path.canonicalize().ok())
+        .filter_map(|path| Url::from_file_path(path).ok())
+        .collect();
+
+    handle_urls(app, urls);
+}
+
+/// Drain queued deep link actions; called on startup and on `deep-link://received`
+#[tauri::command]
+pub fn take_pending_deep_links(pending: State<PendingDeepLinks>) -> Vec<DeepLinkAction> {
+    std::mem::take(&mut *pending.0.lock().unwrap())
+}
@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tauri::State;
+
+use crate::analysis::{self, Analysis, QualityFlag};
+use crate::{i18n, AllowedPaths};
+
+/// Material at least this long with a detected tempo reads as a loop
+const LOOP_MIN_SECS: f32 = 1.5;
+/// Centroid bounds for the "dark" / "bright" wording and the category guess
+const DARK_CENTROID_HZ: f32 = 500.0;
+const BRIGHT_CENTROID_HZ: f32 = 4000.0;
+/// Flatness above which unpitched material reads as noisy (hats, cymbals, risers)
+const NOISY_FLATNESS: f32 = 0.3;
+
+/// Loudness in words, from the overall RMS level
+fn loudness(rms_dbfs: f32) -> String {
+    i18n::t(match rms_dbfs {
+        l if l >= -10.0 => "loudness-very-loud",
+        l if l >= -18.0 => "loudness-loud",
+        l if l >= -28.0 => "loudness-moderate",
+        l if l >= -40.0 => "loudness-quiet",
+        _ => "loudness-very-quiet",
+    })
+}
+
+fn brightness(centroid_hz: f32) -> String {
+    i18n::t(if centroid_hz < DARK_CENTROID_HZ {
+        "brightness-dark"
+    } else if centroid_hz < BRIGHT_CENTROID_HZ {
+        "brightness-balanced"
+    } else {
+        "brightness-bright"
+    })
+}
+
+/// Rough kind of sound from the measurements alone; filenames are already read out
+/// by the screen reader
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Loop,
+    SustainedTonal,
+    BassOrKick,
+    PitchedOneShot,
+    HiHatOrCymbal,
+    NoiseOrTexture,
+    LowPercussion,
+    PercussiveOneShot,
+}
+
+impl Category {
+    /// Stable name, the same in every language; used for folder names
+    pub fn id(self) -> &'static str {
+        match self {
+            Category::Loop => "loop",
+            Category::SustainedTonal => "sustained_tonal",
+            Category::BassOrKick => "bass_or_kick",
+            Category::PitchedOneShot => "pitched_one_shot",
+            Category::HiHatOrCymbal => "hi_hat_or_cymbal",
+            Category::NoiseOrTexture => "noise_or_texture",
+            Category::LowPercussion => "low_percussion",
+            Category::PercussiveOneShot => "percussive_one_shot",
+        }
+    }
+
+    /// Wording in the current language
+    pub fn label(self) -> String {
+        i18n::t(&format!("category-{}", self.id().replace('_', "-")))
+    }
+}
+
+pub fn category(analysis: &Analysis) -> Category {
+    let long = analysis.duration_secs >= LOOP_MIN_SECS;
+    if long && analysis.tempo_bpm.is_some() {
+        Category::Loop
+    } else if analysis.root_hz.is_some() && long {
+        Category::SustainedTonal
+    } else if analysis.root_hz.is_some() {
+        if analysis.spectral_centroid_hz < DARK_CENTROID_HZ {
+            Category::BassOrKick
+        } else {
+            Category::PitchedOneShot
+        }
+    } else if analysis.spectral_flatness > NOISY_FLATNESS {
+        if analysis.spectral_centroid_hz >= BRIGHT_CENTROID_HZ {
+            Category::HiHatOrCymbal
+        } else {
+            Category::NoiseOrTexture
+        }
+    } else if analysis.spectral_centroid_hz < DARK_CENTROID_HZ {
+        Category::LowPercussion
+    } else {
+        Category::PercussiveOneShot
+    }
+}
+
+fn duration(secs: f32) -> String {
+    if secs < 1.0 {
+        let millis = (secs * 1000.0).round() as u32;
+        i18n::t_args("duration-milliseconds", [("milliseconds", millis.into())])
+    } else if secs < 60.0 {
+        i18n::t_args(
+            "duration-seconds",
+            [("seconds", format!("{secs:.1}").into())],
+        )
+    } else {
+        i18n::t_args(
+            "duration-minutes",
+            [
+                ("minutes", (secs as u32 / 60).into()),
+                ("seconds", (secs as u32 % 60).into()),
+            ],
+        )
+    }
+}
+
+/// Short sentences meant to be read aloud, most useful facts first
+pub fn describe(analysis: &Analysis, rms_dbfs: f32) -> String {
+    let mut sentences = vec![
+        i18n::t_args(
+            "describe-category",
+            [("category", category(analysis).label().into())],
+        ),
+        i18n::t_args(
+            "describe-overview",
+            [
+                ("duration", duration(analysis.duration_secs).into()),
+                ("loudness", loudness(rms_dbfs).into()),
+                (
+                    "brightness",
+                    brightness(analysis.spectral_centroid_hz).into(),
+                ),
+            ],
+        ),
+    ];
+
+    let bpm = analysis.tempo_bpm.map(|bpm| bpm.round() as u32);
+    match (&analysis.root_note, bpm) {
+        (Some(note), Some(bpm)) => sentences.push(i18n::t_args(
+            "describe-root-tempo",
+            [("note", note.as_str().into()), ("bpm", bpm.into())],
+        )),
+        (Some(note), None) => sentences.push(i18n::t_args(
+            "describe-root",
+            [("note", note.as_str().into())],
+        )),
+        (None, Some(bpm)) => sentences.push(i18n::t_args("describe-tempo", [("bpm", bpm.into())])),
+        (None, None) => {}
+    }
+    if analysis.channels == 1 {
+        sentences.push(i18n::t("describe-mono"));
+    }
+    if analysis.flags.contains(&QualityFlag::Clipping) {
+        sentences.push(i18n::t("describe-clipping"));
+    }
+    sentences.join(" ")
+}
+
+/// Spoken-friendly summary of a sample (length, loudness, brightness and a guess at
+/// what it is) so screen-reader users get context without looking at the waveform
+#[tauri::command]
+pub async fn describe_sample(
+    path: String,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<String, String> {
+    if !allowed_paths.is_path_allowed(Path::new(&path)) {
+        return Err(i18n::t("access-denied"));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let audio = analysis::decode(&path)?;
+        let mean_square =
+            audio.samples.iter().map(|s| s * s).sum::<f32>() / audio.samples.len().max(1) as f32;
+        Ok(describe(
+            &analysis::analyze_audio(&audio),
+            analysis::to_dbfs(mean_square.sqrt()),
+        ))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,235 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use tauri::{AppHandle, Manager, State};
+use zip::write::SimpleFileOptions;
+
+use crate::export::ELIMINATED_SCORE;
+use crate::hooks::HookTarget;
+use crate::logging::Logging;
+use crate::sessions::Sessions;
+use crate::settings::SettingsStore;
+use crate::{audit, AllowedPaths, TournamentState};
+
+/// Only the newest rolled log files go into a bundle
+const MAX_LOG_FILES: usize = 3;
+
+#[derive(Debug, Serialize)]
+struct SystemInfo {
+    app_version: String,
+    tauri_version: &'static str,
+    os: &'static str,
+    os_family: &'static str,
+    arch: &'static str,
+    log_level: String,
+}
+
+/// Tournament shape without any file names or paths
+#[derive(Debug, Serialize)]
+struct TournamentSummary {
+    sample_count: usize,
+    eliminated: usize,
+    current_round: i32,
+    comparisons_this_round: usize,
+    current_comparison_index: usize,
+    advancement_threshold: f32,
+    seed: u32,
+    archive_entries: usize,
+    online_only: usize,
+    extensions: BTreeMap<String, usize>,
+    score_histogram: BTreeMap<i32, usize>,
+}
+
+fn summarize(state: &TournamentState) -> TournamentSummary {
+    let mut extensions = BTreeMap::new();
+    let mut score_histogram = BTreeMap::new();
+    for sample in &state.samples {
+        let ext = Path::new(&sample.filename)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        *extensions.entry(ext).or_insert(0) += 1;
+        *score_histogram.entry(sample.score).or_insert(0) += 1;
+    }
+
+    TournamentSummary {
+        sample_count: state.samples.len(),
+        eliminated: state
+            .samples
+            .iter()
+            .filter(|s| s.score <= ELIMINATED_SCORE)
+            .count(),
+        current_round: state.current_round,
+        comparisons_this_round: state.comparisons_this_round.len(),
+        current_comparison_index: state.current_comparison_index,
+        advancement_threshold: state.advancement_threshold,
+        seed: state.seed,
+        archive_entries: state
+            .samples
+            .iter()
+            .filter(|s| crate::archive::split_archive_path(&s.path).is_some())
+            .count(),
+        online_only: state.samples.iter().filter(|s| s.online_only).count(),
+        extensions,
+        score_histogram,
+    }
+}
+
+fn redacted_settings(settings: &SettingsStore) -> Result<String, String> {
+    // Hook URLs and commands can embed tokens, so only their kind is kept
+    let mut settings = settings.get();
+    for hook in &mut settings.hooks {
+        hook.target = match hook.target {
+            HookTarget::Http { .. } => HookTarget::Http {
+                url: "<redacted>".to_string(),
+            },
+            HookTarget::Program { .. } => HookTarget::Program {
+                program: "<redacted>".to_string(),
+                args: Vec::new(),
+            },
+            HookTarget::Shell { .. } => HookTarget::Shell {
+                command: "<redacted>".to_string(),
+            },
+        };
+    }
+    serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())
+}
+
+/// Logs may hold the remote token, hook targets from before they were kept out, and
+/// the user's home folder in paths; none of that belongs in a bundle someone shares
+fn redact_log(content: &str, home: Option<&str>) -> String {
+    content
+        .lines()
+        .map(|line| redact_line(line, home))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn redact_line(line: &str, home: Option<&str>) -> String {
+    // Hook failures used to log the URL or command line; now they log the hook's index
+    if let Some(at) = line.find(" hook=") {
+        let value = line[at + " hook=".len()..].split(' ').next().unwrap_or("");
+        if value.parse::<usize>().is_err() {
+            return format!("{} hook=<redacted>", &line[..at]);
+        }
+    }
+    let line = redact_after(&redact_after(line, "token="), "Bearer ");
+    match home {
+        Some(home) if !home.is_empty() => line.replace(home, "~"),
+        _ => line,
+    }
+}
+
+/// Replace what follows each `marker`, up to the next space, `&` or quote
+fn redact_after(line: &str, marker: &str) -> String {
+    let mut redacted = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find(marker) {
+        let value = start + marker.len();
+        redacted.push_str(&rest[..value]);
+        redacted.push_str("<redacted>");
+        let len = rest[value..]
+            .find([' ', '&', '"', '\''])
+            .unwrap_or(rest.len() - value);
+        rest = &rest[value + len..];
+    }
+    redacted.push_str(rest);
+    redacted
+}
+
+fn newest_logs(dir: &Path) -> Vec<std::path::PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut logs: Vec<_> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .collect();
+    // Rolled files are suffixed with the date, so name order is age order
+    logs.sort();
+    logs.into_iter().rev().take(MAX_LOG_FILES).collect()
+}
+
+#[tauri::command]
+pub fn generate_diagnostics_bundle(
+    dest_path: &str,
+    session_id: Option<String>,
+    app: AppHandle,
+    logging: State<Logging>,
+    settings: State<SettingsStore>,
+    sessions: State<Sessions>,
+    allowed_paths: State<AllowedPaths>,
+) -> Result<String, String> {
+    let path = Path::new(dest_path);
+    allowed_paths.check_file(path)?;
+    let summary = session_id
+        .map(|id| sessions.read(&id, summarize))
+        .transpose()?;
+
+    // The folder the user saved into is allowed from now on
+    if let Some(parent) = path.parent() {
+        allowed_paths.add_allowed_path(parent.to_path_buf());
+    }
+
+    let file = File::create(path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(BufWriter::new(file));
+    let options = SimpleFileOptions::default();
+
+    let mut add = |name: &str, content: &[u8]| -> Result<(), String> {
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(content).map_err(|e| e.to_string())
+    };
+
+    let system = SystemInfo {
+        app_version: app.package_info().version.to_string(),
+        tauri_version: tauri::VERSION,
+        os: std::env::consts::OS,
+        os_family: std::env::consts::FAMILY,
+        arch: std::env::consts::ARCH,
+        log_level: logging.level(),
+    };
+    add(
+        "system.json",
+        &serde_json::to_vec_pretty(&system).map_err(|e| e.to_string())?,
+    )?;
+    add("settings.json", redacted_settings(&settings)?.as_bytes())?;
+
+    let home = app
+        .path()
+        .home_dir()
+        .ok()
+        .map(|dir| dir.to_string_lossy().to_string());
+    let recent = logging.recent.tail(usize::MAX);
+    let errors: Vec<String> = recent
+        .iter()
+        .filter(|line| line.contains(" ERROR ") || line.contains(" WARN "))
+        .map(|line| redact_line(line, home.as_deref()))
+        .collect();
+    add("recent-errors.log", errors.join("\n").as_bytes())?;
+
+    for log in newest_logs(&logging.dir) {
+        if let (Some(name), Ok(content)) = (log.file_name(), fs::read(&log)) {
+            let content = redact_log(&String::from_utf8_lossy(&content), home.as_deref());
+            add(
+                &format!("logs/{}", name.to_string_lossy()),
+                content.as_bytes(),
+            )?;
+        }
+    }
+
+    if let Some(summary) = &summary {
+        add(
+            "tournament-summary.json",
+            &serde_json::to_vec_pretty(summary).map_err(|e| e.to_string())?,
+        )?;
+    }
+
+    zip.finish().map_err(|e| e.to_string())?;
+    audit::record_write(path, None);
+    tracing::info!(path = dest_path, "diagnostics bundle written");
+    Ok(dest_path.to_string())
+}
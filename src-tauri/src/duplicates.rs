@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::State;
+
+use crate::archive::split_archive_path;
+use crate::copy_export::hash_file;
+use crate::export::ELIMINATED_SCORE;
+use crate::sessions::Sessions;
+use crate::{i18n, TournamentState};
+
+/// Group files with identical content; every group has at least two paths
+pub fn duplicate_groups<'a>(
+    paths: impl IntoIterator<Item = &'a str>,
+    mut on_error: impl FnMut(&str, std::io::Error),
+) -> Vec<Vec<String>> {
+    let mut by_hash: HashMap<String, Vec<String>> = HashMap::new();
+    for path in paths {
+        match hash_file(Path::new(path)) {
+            Ok(hash) => by_hash.entry(hash).or_default().push(path.to_string()),
+            Err(e) => on_error(path, e),
+        }
+    }
+
+    let mut groups: Vec<Vec<String>> = by_hash
+        .into_values()
+        .filter(|paths| paths.len() > 1)
+        .collect();
+    groups.iter_mut().for_each(|paths| paths.sort());
+    groups.sort();
+    groups
+}
+
+/// Fold `drop` into `keep`: scores and comparison counts are combined and every
+/// reference to the dropped sample (votes, calibration, pairings) now names the kept one
+pub fn merge(state: &mut TournamentState, keep: &str, drop: &str) -> Result<(), String> {
+    if keep == drop {
+        return Err(i18n::t("merge-into-itself"));
+    }
+    let position = |path: &str| state.samples.iter().position(|s| s.path == path);
+    let (Some(kept), Some(dropped)) = (position(keep), position(drop)) else {
+        return Err(i18n::t("merge-not-in-tournament"));
+    };
+
+    let removed = state.samples.remove(dropped);
+    let kept = if kept > dropped { kept - 1 } else { kept };
+    let sample = &mut state.samples[kept];
+    sample.score = if sample.score <= ELIMINATED_SCORE || removed.score <= ELIMINATED_SCORE {
+        sample.score.max(removed.score)
+    } else {
+        sample.score + removed.score
+    };
+    sample.comparisons += removed.comparisons;
+    sample.pin = sample.pin.or(removed.pin);
+
+    for vote in &mut state.votes {
+        for path in [&mut vote.a, &mut vote.b] {
+            if *path == drop {
+                *path = keep.to_string();
+            }
+        }
+    }
+    if let Some(reference) = &mut state.reference {
+        if reference.path == drop {
+            reference.path = keep.to_string();
+        }
+        for check in &mut reference.checks {
+            if check.opponent == drop {
+                check.opponent = keep.to_string();
+            }
+        }
+    }
+
+    // Indices after the dropped sample shift down; pending pairings of the two
+    // duplicates against each other are pointless and go away
+    let remap = |index: usize| match index {
+        i if i == dropped => kept,
+        i if i > dropped => i - 1,
+        i => i,
+    };
+    let next = state.current_comparison_index;
+    state.comparisons_this_round = state
+        .comparisons_this_round
+        .iter()
+        .map(|&(a, b)| (remap(a), remap(b)))
+        .enumerate()
+        .filter(|&(position, (a, b))| position < next || a != b)
+        .map(|(_, pair)| pair)
+        .collect();
+    Ok(())
+}
+
+/// Identical files among the tournament's samples. Archive entries and cloud
+/// placeholders are skipped, since hashing them means extracting or downloading.
+#[tauri::command]
+pub async fn find_duplicates(
+    session_id: String,
+    sessions: State<'_, Sessions>,
+) -> Result<Vec<Vec<String>>, String> {
+    let paths: Vec<String> = sessions.read(&session_id, |state| {
+        state
+            .samples
+            .iter()
+            .filter(|s| !s.online_only && split_archive_path(&s.path).is_none())
+            .map(|s| s.path.clone())
+            .collect()
+    })?;
+    tauri::async_runtime::spawn_blocking(move || {
+        duplicate_groups(
+            paths.iter().map(String::as_str),
+            |path, e| tracing::debug!(path, error = %e, "skipping unreadable sample"),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,84 @@
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+
+use crate::i18n;
+
+/// Error handed to the frontend as `{ kind, message }`, so it can react to the kind
+/// and still show a translated message
+#[derive(Debug)]
+pub enum CommandError {
+    PathNotAllowed,
+    FileNotFound,
+    InvalidArgument(String),
+    /// Too many external programs launched in a short time
+    RateLimited,
+    /// The external program isn't installed or not on PATH
+    ProgramMissing(String),
+    /// Read-only mode is on and the operation would change files
+    ReadOnly,
+    /// A session changed since the revision the caller last saw; holds the current one
+    Conflict(u64),
+    Failed(String),
+}
+
+impl CommandError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CommandError::PathNotAllowed => "path_not_allowed",
+            CommandError::FileNotFound => "file_not_found",
+            CommandError::InvalidArgument(_) => "invalid_argument",
+            CommandError::RateLimited => "rate_limited",
+            CommandError::ProgramMissing(_) => "program_missing",
+            CommandError::ReadOnly => "read_only",
+            CommandError::Conflict(_) => "conflict",
+            CommandError::Failed(_) => "failed",
+        }
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            CommandError::PathNotAllowed => i18n::t("access-denied"),
+            CommandError::FileNotFound => i18n::t("file-not-found"),
+            CommandError::InvalidArgument(reason) => {
+                i18n::t_args("invalid-argument", [("reason", reason.as_str().into())])
+            }
+            CommandError::RateLimited => i18n::t("rate-limited"),
+            CommandError::ProgramMissing(program) => {
+                i18n::t_args("program-missing", [("program", program.as_str().into())])
+            }
+            CommandError::ReadOnly => i18n::t("read-only-mode"),
+            CommandError::Conflict(revision) => {
+                i18n::t_args("session-conflict", [("revision", (*revision).into())])
+            }
+            CommandError::Failed(message) => message.clone(),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+impl Serialize for CommandError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("CommandError", 2)?;
+        error.serialize_field("kind", self.kind())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
+    }
+}
+
+/// Lets commands returning this use `?` on helpers that still return `String` errors
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::Failed(message)
+    }
+}
+
+/// Lets commands still returning `String` errors use `?` on helpers returning this
+impl From<CommandError> for String {
+    fn from(error: CommandError) -> Self {
+        error.to_string()
+    }
+}
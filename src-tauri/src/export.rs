@@ -0,0 +1,448 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+
+use crate::analysis::{Analysis, QualityFlag};
+use crate::describe::{self, Category};
+use crate::error::CommandError;
+use crate::jobs::{self, JobKind};
+use crate::manifest::{self, ExportKind};
+use crate::pins::{self, Pin};
+use crate::{audit, i18n, AllowedPaths, Sample};
+
+/// Progress is reported every this many records
+const NDJSON_PROGRESS_EVERY: usize = 1000;
+
+/// Score assigned to samples removed via "skip both"
+pub const ELIMINATED_SCORE: i32 = -1000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultsFormat {
+    Paths,
+    Csv,
+    Markdown,
+}
+
+/// Standings order used by the leaderboard: pins, then score, then win rate
+pub fn sorted_results(samples: &[Sample]) -> Vec<&Sample> {
+    let mut sorted: Vec<&Sample> = samples
+        .iter()
+        .filter(|s| s.score > ELIMINATED_SCORE)
+        .collect();
+
+    sorted.sort_by(|a, b| {
+        pins::rank(a)
+            .cmp(&pins::rank(b))
+            .then(b.score.cmp(&a.score))
+            .then_with(|| {
+                win_rate(b)
+                    .partial_cmp(&win_rate(a))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    });
+
+    sorted
+}
+
+/// Export cut: pinned samples are always in or always out, whatever their score
+pub fn passes_min_score(sample: &Sample, min_score: i32) -> bool {
+    match sample.pin {
+        Some(Pin::AlwaysAdvance) => true,
+        Some(Pin::Eliminate) => false,
+        None => sample.score >= min_score,
+    }
+}
+
+/// Which samples an export includes; criteria left empty let everything through.
+/// Criteria needing analysis (category, duration) exclude unanalyzed samples.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ExportFilter {
+    /// Pinned samples are in or out regardless, as with every export cut
+    pub min_score: Option<i32>,
+    pub max_score: Option<i32>,
+    /// 1-based positions among the samples passing the other criteria, in export order;
+    /// `max_rank: 25` exports the top 25
+    pub min_rank: Option<usize>,
+    pub max_rank: Option<usize>,
+    /// Guessed kind of sound (`loop`, `bass_or_kick`, ...)
+    pub category: Option<Category>,
+    /// Only samples carrying every one of these tags; case is ignored
+    pub tags: Vec<String>,
+    pub pack: Option<String>,
+    /// Only samples inside this folder or its subfolders
+    pub folder: Option<String>,
+    pub min_duration_secs: Option<f32>,
+    pub max_duration_secs: Option<f32>,
+}
+
+impl ExportFilter {
+    fn matches(&self, sample: &Sample) -> bool {
+        let duration = sample.analysis.as_ref().map(|a| a.duration_secs);
+        self.min_score
+            .is_none_or(|min| passes_min_score(sample, min))
+            && self.max_score.is_none_or(|max| sample.score <= max)
+            && self.category.is_none_or(|category| {
+                sample
+                    .analysis
+                    .as_ref()
+                    .is_some_and(|a| describe::category(a) == category)
+            })
+            && self.tags.iter().all(|wanted| {
+                sample
+                    .tags
+                    .iter()
+                    .any(|tag| tag.eq_ignore_ascii_case(wanted))
+            })
+            && self
+                .pack
+                .as_deref()
+                .is_none_or(|pack| sample.pack.as_deref() == Some(pack))
+            && self
+                .folder
+                .as_deref()
+                .is_none_or(|folder| Path::new(&sample.path).starts_with(folder))
+            && self
+                .min_duration_secs
+                .is_none_or(|min| duration.is_some_and(|d| d >= min))
+            && self
+                .max_duration_secs
+                .is_none_or(|max| duration.is_some_and(|d| d <= max))
+    }
+
+    /// Samples passing the filter, in export order so the rank range means standings
+    pub fn apply<'a>(&self, samples: &'a [Sample]) -> Vec<&'a Sample> {
+        let first = self.min_rank.unwrap_or(1).max(1);
+        let last = self.max_rank.unwrap_or(usize::MAX);
+        sorted_results(samples)
+            .into_iter()
+            .filter(|s| self.matches(s))
+            .enumerate()
+            .filter(|(i, _)| (first..=last).contains(&(i + 1)))
+            .map(|(_, s)| s)
+            .collect()
+    }
+}
+
+pub fn win_rate(sample: &Sample) -> f32 {
+    if sample.comparisons > 0 {
+        sample.score as f32 / sample.comparisons as f32
+    } else {
+        0.0
+    }
+}
+
+/// One leaderboard row as handed to hooks and export scripts
+#[derive(Debug, Serialize, Clone)]
+pub struct ResultRow<'a> {
+    pub rank: usize,
+    pub path: &'a str,
+    pub filename: &'a str,
+    pub score: i32,
+    pub comparisons: i32,
+    pub win_rate: f32,
+    pub pack: Option<&'a str>,
+    pub analysis: Option<&'a Analysis>,
+}
+
+pub fn result_rows<'a>(samples: &[&'a Sample]) -> Vec<ResultRow<'a>> {
+    samples
+        .iter()
+        .enumerate()
+        .map(|(i, sample)| ResultRow {
+            rank: i + 1,
+            path: &sample.path,
+            filename: &sample.filename,
+            score: sample.score,
+            comparisons: sample.comparisons,
+            win_rate: win_rate(sample),
+            pack: sample.pack.as_deref(),
+            analysis: sample.analysis.as_ref(),
+        })
+        .collect()
+}
+
+/// Write one JSON result row per line, never holding more than one record as text.
+/// `on_progress` gets the number of rows written so far.
+pub fn write_ndjson(
+    samples: &[&Sample],
+    writer: impl Write,
+    on_progress: impl Fn(u64),
+) -> Result<(), String> {
+    let mut writer = BufWriter::new(writer);
+    for (i, row) in result_rows(samples).iter().enumerate() {
+        serde_json::to_writer(&mut writer, row).map_err(|e| e.to_string())?;
+        writer.write_all(b"\n").map_err(|e| e.to_string())?;
+        if (i + 1) % NDJSON_PROGRESS_EVERY == 0 {
+            on_progress(i as u64 + 1);
+        }
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    on_progress(samples.len() as u64);
+    Ok(())
+}
+
+/// Comma-separated quality flags, empty when unanalyzed or clean
+pub fn flags_label(sample: &Sample) -> String {
+    sample
+        .analysis
+        .iter()
+        .flat_map(|a| &a.flags)
+        .map(|flag| match flag {
+            QualityFlag::Clipping => "clipping",
+            QualityFlag::InterSampleClipping => "inter-sample clipping",
+            QualityFlag::DcOffset => "dc offset",
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Formatted analysis value, empty for unanalyzed samples
+fn descriptor(sample: &Sample, format: impl Fn(&Analysis) -> String) -> String {
+    sample.analysis.as_ref().map(format).unwrap_or_default()
+}
+
+fn escape_markdown(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+/// Header row of a Markdown table, translated from message ids
+pub fn markdown_heading(ids: &[&str]) -> String {
+    let cells: Vec<String> = ids.iter().map(|id| escape_markdown(&i18n::t(id))).collect();
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Render ranked samples in the requested text format
+pub fn format_results(samples: &[&Sample], format: ResultsFormat) -> Result<String, String> {
+    match format {
+        ResultsFormat::Paths => Ok(samples
+            .iter()
+            .map(|s| s.path.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")),
+        ResultsFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer
+                .write_record([
+                    "rank",
+                    "filename",
+                    "score",
+                    "comparisons",
+                    "path",
+                    "flags",
+                    "pack",
+                    "centroid_hz",
+                    "rolloff_hz",
+                    "flatness",
+                ])
+                .map_err(|e| e.to_string())?;
+            for (i, s) in samples.iter().enumerate() {
+                writer
+                    .write_record([
+                        (i + 1).to_string(),
+                        s.filename.clone(),
+                        s.score.to_string(),
+                        s.comparisons.to_string(),
+                        s.path.clone(),
+                        flags_label(s),
+                        s.pack.clone().unwrap_or_default(),
+                        descriptor(s, |a| format!("{:.0}", a.spectral_centroid_hz)),
+                        descriptor(s, |a| format!("{:.0}", a.spectral_rolloff_hz)),
+                        descriptor(s, |a| format!("{:.3}", a.spectral_flatness)),
+                    ])
+                    .map_err(|e| e.to_string())?;
+            }
+            let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+            String::from_utf8(bytes).map_err(|e| e.to_string())
+        }
+        ResultsFormat::Markdown => {
+            let headings = [
+                "heading-rank",
+                "heading-filename",
+                "heading-score",
+                "heading-comparisons",
+                "heading-win-rate",
+                "heading-brightness",
+                "heading-flags",
+            ];
+            let mut lines = vec![
+                markdown_heading(&headings),
+                "|-----:|----------|------:|------------:|---------:|-----------:|-------|"
+                    .to_string(),
+            ];
+            for (i, s) in samples.iter().enumerate() {
+                lines.push(format!(
+                    "| {} | {} | {} | {} | {:.1}% | {} | {} |",
+                    i + 1,
+                    escape_markdown(&s.filename),
+                    s.score,
+                    s.comparisons,
+                    win_rate(s) * 100.0,
+                    descriptor(s, |a| format!("{:.0} Hz", a.spectral_centroid_hz)),
+                    flags_label(s)
+                ));
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+/// Result of checking an export destination before writing anything
+#[derive(Debug, Serialize, Clone)]
+pub struct PreflightReport {
+    /// Existing directory the checks were run against
+    pub checked_directory: String,
+    pub writable: bool,
+    pub available_bytes: Option<u64>,
+    pub required_bytes: u64,
+    pub enough_space: bool,
+    pub problems: Vec<String>,
+}
+
+fn nearest_existing_dir(dest: &Path) -> Option<PathBuf> {
+    let mut current = if dest.is_dir() {
+        Some(dest)
+    } else {
+        dest.parent()
+    };
+
+    while let Some(dir) = current {
+        if dir.is_dir() {
+            return Some(dir.to_path_buf());
+        }
+        current = dir.parent();
+    }
+
+    None
+}
+
+/// Verify write permission and free space at `dest` (a file or directory, existing or not)
+pub fn preflight(dest: &Path, estimated_bytes: u64) -> PreflightReport {
+    let mut problems = Vec::new();
+
+    let Some(dir) = nearest_existing_dir(dest) else {
+        return PreflightReport {
+            checked_directory: String::new(),
+            writable: false,
+            available_bytes: None,
+            required_bytes: estimated_bytes,
+            enough_space: false,
+            problems: vec![i18n::t("preflight-no-parent")],
+        };
+    };
+
+    // Creating a real file is the only reliable permission check across platforms
+    let probe = dir.join(format!(".vs1-preflight-{}", std::process::id()));
+    let writable = match fs::File::create(&probe) {
+        Ok(_) => {
+            let _ = fs::remove_file(&probe);
+            true
+        }
+        Err(e) => {
+            problems.push(i18n::t_args(
+                "preflight-not-writable",
+                [("error", e.to_string().into())],
+            ));
+            false
+        }
+    };
+
+    let available_bytes = match fs2::available_space(&dir) {
+        Ok(bytes) => Some(bytes),
+        Err(e) => {
+            problems.push(i18n::t_args(
+                "preflight-free-space-unknown",
+                [("error", e.to_string().into())],
+            ));
+            None
+        }
+    };
+
+    let enough_space = available_bytes
+        .map(|available| available >= estimated_bytes)
+        .unwrap_or(false);
+    if let Some(available) = available_bytes.filter(|_| !enough_space) {
+        problems.push(i18n::t_args(
+            "preflight-not-enough-space",
+            [
+                ("needed", estimated_bytes.into()),
+                ("available", available.into()),
+            ],
+        ));
+    }
+
+    PreflightReport {
+        checked_directory: dir.to_string_lossy().to_string(),
+        writable,
+        available_bytes,
+        required_bytes: estimated_bytes,
+        enough_space,
+        problems,
+    }
+}
+
+#[tauri::command]
+pub fn preflight_export(
+    dest: &str,
+    estimated_bytes: u64,
+    allowed_paths: State<AllowedPaths>,
+) -> Result<PreflightReport, CommandError> {
+    let dest = Path::new(dest);
+    allowed_paths.check_file(dest)?;
+    Ok(preflight(dest, estimated_bytes))
+}
+
+/// Standings as JSON Lines, streamed to disk for pools too large to export as one string.
+/// Written to a `.part` file first so an interrupted export never looks complete.
+#[tauri::command]
+pub async fn export_ndjson(
+    samples: Vec<Sample>,
+    file_path: String,
+    min_score: i32,
+    write_manifest: Option<bool>,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<(), String> {
+    let dest = PathBuf::from(&file_path);
+    allowed_paths.check_file(&dest)?;
+
+    // The folder the user exported into is allowed from now on
+    if let Some(parent) = dest.parent() {
+        allowed_paths.add_allowed_path(parent.to_path_buf());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let ranked: Vec<&Sample> = sorted_results(&samples)
+            .into_iter()
+            .filter(|s| passes_min_score(s, min_score))
+            .collect();
+        let job = jobs::start(&app, JobKind::Export, Some(ranked.len() as u64));
+
+        let partial = dest.with_extension("ndjson.part");
+        let file = File::create(&partial).map_err(|e| e.to_string())?;
+        let written = write_ndjson(&ranked, file, |done| job.set_done(done))
+            .and_then(|()| fs::rename(&partial, &dest).map_err(|e| e.to_string()));
+        match written {
+            Ok(()) => audit::record_write(&dest, None),
+            Err(_) => {
+                let _ = fs::remove_file(&partial);
+            }
+        }
+        tracing::info!(
+            rows = ranked.len(),
+            ok = written.is_ok(),
+            "ndjson export finished"
+        );
+        written?;
+
+        if write_manifest.unwrap_or(false) {
+            manifest::write_beside(&dest, ExportKind::Ndjson, &ranked)?;
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
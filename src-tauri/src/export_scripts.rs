@@ -0,0 +1,121 @@
+use rhai::{Dynamic, Engine, Scope};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+use crate::export::{passes_min_score, result_rows, sorted_results};
+use crate::{audit, i18n, AllowedPaths, Sample};
+
+pub const SCRIPT_EXTENSION: &str = "rhai";
+
+/// Keeps a runaway script (e.g. an infinite loop) from freezing the export
+const MAX_OPERATIONS: u64 = 50_000_000;
+/// Keep a script from building values that eat the machine's memory; the output is one
+/// string, so it gets the most room
+const MAX_STRING_BYTES: usize = 64 << 20;
+const MAX_ARRAY_LEN: usize = 1_000_000;
+const MAX_MAP_LEN: usize = 100_000;
+
+#[derive(Debug, Serialize)]
+pub struct ExportScript {
+    pub name: String,
+    pub path: String,
+}
+
+pub fn scripts_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("export-scripts"))
+}
+
+pub fn list(dir: &Path) -> Vec<ExportScript> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut scripts: Vec<ExportScript> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some(SCRIPT_EXTENSION))
+        .filter_map(|path| {
+            Some(ExportScript {
+                name: path.file_stem()?.to_string_lossy().to_string(),
+                path: path.to_string_lossy().to_string(),
+            })
+        })
+        .collect();
+
+    scripts.sort_by(|a, b| a.name.cmp(&b.name));
+    scripts
+}
+
+/// Run a Rhai template over the standings. The script sees `results` (an array of
+/// maps with rank, path, filename, score, comparisons and win_rate) and must
+/// evaluate to the text to write.
+pub fn render(script: &str, samples: &[&Sample]) -> Result<String, String> {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_string_size(MAX_STRING_BYTES);
+    engine.set_max_array_size(MAX_ARRAY_LEN);
+    engine.set_max_map_size(MAX_MAP_LEN);
+
+    let results = rhai::serde::to_dynamic(result_rows(samples)).map_err(|e| e.to_string())?;
+    let mut scope = Scope::new();
+    scope.push("results", results);
+
+    let output: Dynamic = engine
+        .eval_with_scope(&mut scope, script)
+        .map_err(|e| i18n::t_args("export-script-failed", [("error", e.to_string().into())]))?;
+
+    if output.is_string() {
+        Ok(output.into_string().unwrap_or_default())
+    } else {
+        Err(i18n::t_args(
+            "export-script-not-string",
+            [("type", output.type_name().into())],
+        ))
+    }
+}
+
+#[tauri::command]
+pub fn list_export_scripts(app: AppHandle) -> Result<Vec<ExportScript>, String> {
+    Ok(list(&scripts_dir(&app)?))
+}
+
+#[tauri::command]
+pub fn export_with_script(
+    script_name: &str,
+    samples: Vec<Sample>,
+    file_path: &str,
+    min_score: i32,
+    app: AppHandle,
+    allowed_paths: State<AllowedPaths>,
+) -> Result<(), String> {
+    let script_path = list(&scripts_dir(&app)?)
+        .into_iter()
+        .find(|s| s.name == script_name)
+        .map(|s| s.path)
+        .ok_or_else(|| i18n::t_args("export-script-not-found", [("name", script_name.into())]))?;
+    let script = fs::read_to_string(script_path).map_err(|e| e.to_string())?;
+
+    let path = Path::new(file_path);
+    allowed_paths.check_file(path)?;
+
+    // The folder the user exported into is allowed from now on
+    if let Some(parent) = path.parent() {
+        allowed_paths.add_allowed_path(parent.to_path_buf());
+    }
+
+    let ranked: Vec<&Sample> = sorted_results(&samples)
+        .into_iter()
+        .filter(|s| passes_min_score(s, min_score))
+        .collect();
+
+    let content = render(&script, &ranked)?;
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    audit::record_write(path, None);
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+use tauri::State;
+
+use crate::audit::{self, AuditKind};
+use crate::export::{result_rows, sorted_results, ResultRow};
+use crate::sessions::Sessions;
+use crate::settings::SettingsStore;
+use crate::{i18n, TournamentState};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HookEvent {
+    RoundComplete,
+    TournamentComplete,
+}
+
+impl HookEvent {
+    fn as_str(self) -> &'static str {
+        match self {
+            HookEvent::RoundComplete => "round_complete",
+            HookEvent::TournamentComplete => "tournament_complete",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum HookTarget {
+    /// POST the payload as JSON
+    Http { url: String },
+    /// Run a program directly, without a shell, with the payload on stdin
+    Program {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// Shell command line from older settings; kept so they still load, but no longer
+    /// run, since a shell hands whoever wrote the line arbitrary commands
+    Shell { command: String },
+}
+
+impl HookTarget {
+    /// What the logs say about a hook: URLs and arguments often embed tokens
+    fn kind(&self) -> &'static str {
+        match self {
+            HookTarget::Http { .. } => "http",
+            HookTarget::Program { .. } => "program",
+            HookTarget::Shell { .. } => "shell",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Hook {
+    pub events: Vec<HookEvent>,
+    pub target: HookTarget,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize)]
+pub struct HookPayload<'a> {
+    pub event: HookEvent,
+    pub round: i32,
+    pub source_directory: &'a str,
+    pub results: Vec<ResultRow<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HookFailure {
+    pub target: String,
+    pub error: String,
+}
+
+pub fn payload(event: HookEvent, state: &TournamentState) -> HookPayload<'_> {
+    let results = result_rows(&sorted_results(&state.samples));
+
+    HookPayload {
+        event,
+        round: state.current_round,
+        source_directory: &state.source_directory,
+        results,
+    }
+}
+
+fn post(url: &str, body: &str) -> Result<(), String> {
+    ureq::post(url)
+        .timeout(HTTP_TIMEOUT)
+        .set("Content-Type", "application/json")
+        .send_string(body)
+        .map(|_| ())
+        // ureq's messages repeat the URL, token and all
+        .map_err(|e| match e {
+            ureq::Error::Status(code, _) => format!("HTTP {}", code),
+            ureq::Error::Transport(transport) => transport.kind().to_string(),
+        })
+}
+
+fn run_program(program: &str, args: &[String], event: HookEvent, body: &str) -> Result<(), String> {
+    audit::record(AuditKind::Launch, program, Some(event.as_str().to_string()));
+    let mut child = Command::new(program)
+        .args(args)
+        .env("VS1_EVENT", event.as_str())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| i18n::t_args("hook-failed", [("error", e.to_string().into())]))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // Scripts that ignore stdin close it early; that's not a failure
+        let _ = stdin.write_all(body.as_bytes());
+    }
+
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(i18n::t_args(
+            "hook-exited",
+            [
+                ("status", output.status.to_string().into()),
+                ("error", stderr.trim().into()),
+            ],
+        ))
+    }
+}
+
+/// Run every enabled hook subscribed to `event`; failures don't stop the remaining hooks
+pub fn fire(hooks: &[Hook], event: HookEvent, state: &TournamentState) -> Vec<HookFailure> {
+    let hooks: Vec<&Hook> = hooks
+        .iter()
+        .filter(|h| h.enabled && h.events.contains(&event))
+        .collect();
+    if hooks.is_empty() {
+        return Vec::new();
+    }
+
+    let body = match serde_json::to_string(&payload(event, state)) {
+        Ok(body) => body,
+        Err(e) => {
+            return vec![HookFailure {
+                target: "payload".to_string(),
+                error: e.to_string(),
+            }]
+        }
+    };
+
+    hooks
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, hook)| {
+            let (target, result) = match &hook.target {
+                HookTarget::Http { url } => (url, post(url, &body)),
+                HookTarget::Program { program, args } => {
+                    (program, run_program(program, args, event, &body))
+                }
+                HookTarget::Shell { command } => (command, Err(i18n::t("hook-shell-unsupported"))),
+            };
+            if let Err(error) = &result {
+                tracing::warn!(hook = index, kind = hook.target.kind(), %error, "hook failed");
+            }
+            result.err().map(|error| HookFailure {
+                target: target.clone(),
+                error,
+            })
+        })
+        .collect()
+}
+
+/// Called by the frontend when a round or the whole tournament finishes
+#[tauri::command]
+pub async fn fire_hooks(
+    event: HookEvent,
+    session_id: String,
+    sessions: State<'_, Sessions>,
+    settings: State<'_, SettingsStore>,
+) -> Result<Vec<HookFailure>, String> {
+    let hooks = settings.get().hooks;
+    let state = sessions.get(&session_id)?.state;
+
+    tauri::async_runtime::spawn_blocking(move || fire(&hooks, event, &state))
+        .await
+        .map_err(|e| e.to_string())
+}
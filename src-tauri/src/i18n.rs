@@ -0,0 +1,93 @@
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use std::sync::{OnceLock, RwLock};
+use unic_langid::LanguageIdentifier;
+
+/// Bundled translations; the first one is the fallback for missing messages
+const LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en/main.ftl")),
+    ("de", include_str!("../locales/de/main.ftl")),
+];
+
+static BUNDLES: OnceLock<Vec<(&'static str, FluentBundle<FluentResource>)>> = OnceLock::new();
+static LANGUAGE: RwLock<&'static str> = RwLock::new("en");
+
+fn bundles() -> &'static [(&'static str, FluentBundle<FluentResource>)] {
+    BUNDLES.get_or_init(|| {
+        LOCALES
+            .iter()
+            .map(|&(code, source)| {
+                let language: LanguageIdentifier = code.parse().expect("valid locale code");
+                let resource = FluentResource::try_new(source.to_string())
+                    .expect("bundled translations parse");
+                let mut bundle = FluentBundle::new_concurrent(vec![language]);
+                // Isolation marks end up as stray characters in logs and exported files
+                bundle.set_use_isolating(false);
+                bundle
+                    .add_resource(resource)
+                    .expect("bundled translations have unique ids");
+                (code, bundle)
+            })
+            .collect()
+    })
+}
+
+/// Codes of the bundled languages, for the settings picker
+#[tauri::command]
+pub fn list_languages() -> Vec<&'static str> {
+    LOCALES.iter().map(|&(code, _)| code).collect()
+}
+
+/// Switch backend messages to `language` ("de", "de-AT", ...). `None` follows the
+/// system locale; anything without a translation falls back to English.
+pub fn set_language(language: Option<&str>) {
+    let system = std::env::var("LC_ALL")
+        .or_else(|_| std::env::var("LC_MESSAGES"))
+        .or_else(|_| std::env::var("LANG"))
+        .ok();
+    let requested = language.or(system.as_deref()).unwrap_or_default();
+    let primary = requested
+        .split(['-', '_', '.'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let code = LOCALES
+        .iter()
+        .map(|&(code, _)| code)
+        .find(|&code| code == primary)
+        .unwrap_or(LOCALES[0].0);
+    *LANGUAGE.write().unwrap() = code;
+    tracing::debug!(language = code, "backend language set");
+}
+
+fn format(id: &str, args: Option<&FluentArgs>) -> String {
+    let current = *LANGUAGE.read().unwrap();
+    let bundles = bundles();
+    let preferred = bundles.iter().filter(|(code, _)| *code == current);
+
+    for (_, bundle) in preferred.chain(bundles.first()) {
+        let Some(pattern) = bundle.get_message(id).and_then(|m| m.value()) else {
+            continue;
+        };
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            tracing::warn!(id, ?errors, "failed to format message");
+        }
+        return text.into_owned();
+    }
+    tracing::warn!(id, "missing message");
+    id.to_string()
+}
+
+/// User-facing text for message `id` in the current language
+pub fn t(id: &str) -> String {
+    format(id, None)
+}
+
+/// Like [`t`], filling in the message's `{ $name }` placeholders
+pub fn t_args<'a>(id: &str, args: impl IntoIterator<Item = (&'a str, FluentValue<'a>)>) -> String {
+    let args: FluentArgs = args.into_iter().collect();
+    format(id, Some(&args))
+}
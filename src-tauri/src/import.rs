@@ -0,0 +1,337 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+use crate::{analysis, i18n, AllowedPaths, Sample};
+
+/// Seed points per star when importing ratings, so five stars starts five wins ahead
+const DEFAULT_POINTS_PER_STAR: i32 = 1;
+
+/// Describes which CSV columns hold the match keys and the rating
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RatingMapping {
+    /// Column containing the full file path (preferred match key)
+    pub path_column: Option<String>,
+    /// Column containing the bare filename (fallback match key)
+    pub filename_column: Option<String>,
+    /// Column containing the numeric rating
+    pub score_column: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RatingImport {
+    pub samples: Vec<Sample>,
+    pub matched: usize,
+    pub unmatched_rows: usize,
+}
+
+/// Looks samples up by path first and filename second
+struct SampleIndex {
+    by_path: HashMap<String, usize>,
+    /// Filenames are not unique across a library, so only names that occur once match
+    by_filename: HashMap<String, Option<usize>>,
+}
+
+impl SampleIndex {
+    fn new(samples: &[Sample]) -> Self {
+        let by_path = samples
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.path.clone(), i))
+            .collect();
+        let mut by_filename: HashMap<String, Option<usize>> = HashMap::new();
+        for (i, s) in samples.iter().enumerate() {
+            by_filename
+                .entry(s.filename.to_lowercase())
+                .and_modify(|slot| *slot = None)
+                .or_insert(Some(i));
+        }
+        Self {
+            by_path,
+            by_filename,
+        }
+    }
+
+    fn find(&self, path: Option<&str>, filename: Option<&str>) -> Option<usize> {
+        path.and_then(|path| self.by_path.get(path).copied())
+            .or_else(|| {
+                filename.and_then(|name| {
+                    self.by_filename
+                        .get(&name.to_lowercase())
+                        .copied()
+                        .flatten()
+                })
+            })
+    }
+}
+
+fn column_index(headers: &csv::StringRecord, name: &str) -> Result<usize, String> {
+    headers
+        .iter()
+        .position(|h| h.eq_ignore_ascii_case(name))
+        .ok_or_else(|| i18n::t_args("csv-column-missing", [("column", name.into())]))
+}
+
+/// Seed sample scores from a CSV file, matching rows by path first and filename second
+pub fn apply_ratings(
+    mut samples: Vec<Sample>,
+    csv_path: &Path,
+    mapping: &RatingMapping,
+) -> Result<RatingImport, String> {
+    if mapping.path_column.is_none() && mapping.filename_column.is_none() {
+        return Err(i18n::t("mapping-needs-column"));
+    }
+
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_path(csv_path)
+        .map_err(|e| e.to_string())?;
+
+    let headers = reader.headers().map_err(|e| e.to_string())?.clone();
+    let score_idx = column_index(&headers, &mapping.score_column)?;
+    let path_idx = match &mapping.path_column {
+        Some(name) => Some(column_index(&headers, name)?),
+        None => None,
+    };
+    let filename_idx = match &mapping.filename_column {
+        Some(name) => Some(column_index(&headers, name)?),
+        None => None,
+    };
+
+    let index = SampleIndex::new(&samples);
+    let mut matched = 0;
+    let mut unmatched_rows = 0;
+
+    for record in reader.records() {
+        let record = record.map_err(|e| e.to_string())?;
+
+        let score = match record.get(score_idx).and_then(|v| v.parse::<f64>().ok()) {
+            Some(score) => score.round() as i32,
+            None => {
+                unmatched_rows += 1;
+                continue;
+            }
+        };
+
+        let found = index.find(
+            path_idx.and_then(|idx| record.get(idx)),
+            filename_idx.and_then(|idx| record.get(idx)),
+        );
+
+        match found {
+            Some(i) => {
+                samples[i].score = score;
+                matched += 1;
+            }
+            None => unmatched_rows += 1,
+        }
+    }
+
+    Ok(RatingImport {
+        samples,
+        matched,
+        unmatched_rows,
+    })
+}
+
+#[tauri::command]
+pub fn import_ratings(
+    samples: Vec<Sample>,
+    csv_path: &str,
+    mapping: RatingMapping,
+    allowed_paths: State<AllowedPaths>,
+) -> Result<RatingImport, String> {
+    let path = Path::new(csv_path);
+    if !path.exists() {
+        return Err(i18n::t("file-not-found"));
+    }
+    allowed_paths.check_file(path)?;
+
+    apply_ratings(samples, path, &mapping)
+}
+
+/// Stars (0 to 5) from a rating however the tagger wrote it: as an ID3 popularimeter
+/// byte when it came from a POPM frame, otherwise as stars or as a percentage (Music,
+/// Vorbis `RATING`)
+pub fn stars(rating: f64, popularimeter: bool) -> Option<f64> {
+    let stars = match rating {
+        r if r < 0.0 => return None,
+        // Windows Media Player's POPM steps: 1, 64, 128, 196 and 255; 0 is unrated
+        r if popularimeter => match r {
+            r if r < 1.0 || r > 255.0 => return None,
+            r if r < 32.0 => 1.0,
+            r if r < 96.0 => 2.0,
+            r if r < 160.0 => 3.0,
+            r if r < 224.0 => 4.0,
+            _ => 5.0,
+        },
+        r if r <= 5.0 => r,
+        r if r <= 100.0 => r / 20.0,
+        _ => return None,
+    };
+    Some(stars.round())
+}
+
+/// Path of a `file://` URL as Music writes them in `Location`, percent-decoded
+fn path_from_location(location: &str) -> Option<String> {
+    let rest = location.strip_prefix("file://")?;
+    let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+
+    let bytes = rest.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    let path = String::from_utf8(decoded).ok()?;
+
+    // Windows locations read /C:/Music/...
+    match path.as_bytes() {
+        [b'/', _, b':', ..] => Some(path[1..].to_string()),
+        _ => Some(path),
+    }
+}
+
+/// Seed scores from the star ratings in a Music (or iTunes) library XML export,
+/// `points_per_star` each. Ratings Music derived from the album aren't the user's and
+/// are skipped; `unmatched_rows` counts rated tracks that aren't in the pool.
+pub fn apply_music_library(
+    mut samples: Vec<Sample>,
+    library_path: &Path,
+    points_per_star: i32,
+) -> Result<RatingImport, String> {
+    let library = plist::Value::from_file(library_path).map_err(|e| e.to_string())?;
+    let tracks = library
+        .as_dictionary()
+        .and_then(|library| library.get("Tracks"))
+        .and_then(|tracks| tracks.as_dictionary())
+        .ok_or_else(|| i18n::t("not-music-library"))?;
+
+    let index = SampleIndex::new(&samples);
+    let mut matched = 0;
+    let mut unmatched_rows = 0;
+    for track in tracks.values().filter_map(|track| track.as_dictionary()) {
+        let computed = track
+            .get("Rating Computed")
+            .and_then(|v| v.as_boolean())
+            .unwrap_or(false);
+        let Some(rating) = track
+            .get("Rating")
+            .and_then(|v| v.as_signed_integer())
+            .filter(|_| !computed)
+        else {
+            continue;
+        };
+
+        let path = track
+            .get("Location")
+            .and_then(|v| v.as_string())
+            .and_then(path_from_location);
+        let filename = path
+            .as_deref()
+            .and_then(|path| Path::new(path).file_name())
+            .and_then(|name| name.to_str());
+        match (
+            index.find(path.as_deref(), filename),
+            stars(rating as f64, false),
+        ) {
+            (Some(i), Some(stars)) => {
+                samples[i].score = (stars as i32) * points_per_star;
+                matched += 1;
+            }
+            _ => unmatched_rows += 1,
+        }
+    }
+
+    Ok(RatingImport {
+        samples,
+        matched,
+        unmatched_rows,
+    })
+}
+
+/// Seed scores from the rating each file carries in its own tags; `unmatched_rows`
+/// counts samples without one, and those outside the allowed folders, which aren't read
+pub fn apply_tag_ratings(
+    mut samples: Vec<Sample>,
+    points_per_star: i32,
+    allowed_paths: &AllowedPaths,
+) -> RatingImport {
+    let mut matched = 0;
+    let mut unmatched_rows = 0;
+    for sample in &mut samples {
+        if !allowed_paths.is_path_allowed(Path::new(&sample.path)) {
+            unmatched_rows += 1;
+            continue;
+        }
+        let rated = analysis::rating_tag(&sample.path).and_then(|tag| {
+            let rating = tag.value.trim().parse::<f64>().ok()?;
+            stars(rating, tag.popularimeter)
+        });
+        match rated {
+            Some(stars) => {
+                sample.score = (stars as i32) * points_per_star;
+                matched += 1;
+            }
+            None => unmatched_rows += 1,
+        }
+    }
+    RatingImport {
+        samples,
+        matched,
+        unmatched_rows,
+    }
+}
+
+/// Give the pool a head start from years of star ratings in Music (or iTunes): the
+/// library XML from File > Library > Export Library
+#[tauri::command]
+pub async fn import_music_library(
+    samples: Vec<Sample>,
+    library_path: String,
+    points_per_star: Option<i32>,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<RatingImport, String> {
+    let path = PathBuf::from(library_path);
+    if !path.exists() {
+        return Err(i18n::t("file-not-found"));
+    }
+    allowed_paths.check_file(&path)?;
+
+    // Libraries run to hundreds of megabytes of XML
+    let points = points_per_star.unwrap_or(DEFAULT_POINTS_PER_STAR);
+    let imported =
+        tauri::async_runtime::spawn_blocking(move || apply_music_library(samples, &path, points))
+            .await
+            .map_err(|e| e.to_string())??;
+    tracing::info!(
+        matched = imported.matched,
+        unmatched = imported.unmatched_rows,
+        "imported Music library ratings"
+    );
+    Ok(imported)
+}
+
+/// Same from the rating tags in the files themselves (ID3 POPM, Vorbis `RATING`)
+#[tauri::command]
+pub async fn import_tag_ratings(
+    samples: Vec<Sample>,
+    points_per_star: Option<i32>,
+    app: AppHandle,
+) -> Result<RatingImport, String> {
+    let points = points_per_star.unwrap_or(DEFAULT_POINTS_PER_STAR);
+    tauri::async_runtime::spawn_blocking(move || {
+        apply_tag_ratings(samples, points, &app.state::<AllowedPaths>())
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::scan::{is_audio_name, sample_from_path};
+use crate::{archive, Sample};
+
+/// Sampler instrument formats whose samples can be pulled into the pool.
+/// Logic `.exs` files are binary and not supported.
+const INSTRUMENT_EXTENSIONS: &[&str] = &["sfz", "xrni"];
+
+pub fn is_instrument(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| {
+            INSTRUMENT_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+        .unwrap_or(false)
+}
+
+/// Where an opcode value ends: before the next `key=` on the line, or at a header
+fn value_end(rest: &str) -> usize {
+    let header = rest.find('<').unwrap_or(rest.len());
+    match rest[..header].find('=') {
+        Some(eq) => rest[..eq].rfind(char::is_whitespace).unwrap_or(0),
+        None => header,
+    }
+}
+
+/// `sample=` paths of an SFZ file, with `default_path` applied. Values may
+/// contain spaces, so each runs until the next opcode.
+fn sfz_sample_paths(content: &str) -> Vec<String> {
+    let mut default_path = String::new();
+    let mut paths = Vec::new();
+
+    for line in content.lines() {
+        let mut rest = line.split("//").next().unwrap_or("");
+        while let Some(eq) = rest.find('=') {
+            let key = rest[..eq]
+                .rsplit(|c: char| c.is_whitespace() || c == '>')
+                .next()
+                .unwrap_or("");
+            let after = &rest[eq + 1..];
+            let end = value_end(after);
+            let value = after[..end].trim();
+
+            match key {
+                "default_path" => default_path = value.replace('\\', "/"),
+                // `*sine`, `*noise`, ... are built-in generators, not files
+                "sample" if !value.starts_with('*') => {
+                    paths.push(format!("{}{}", default_path, value.replace('\\', "/")))
+                }
+                _ => {}
+            }
+            rest = &after[end..];
+        }
+    }
+
+    paths
+}
+
+/// Samples referenced by an SFZ file; files outside `root` are ignored
+fn sfz_samples(root: &Path, sfz: &Path) -> Result<Vec<Sample>, String> {
+    let content = fs::read_to_string(sfz).map_err(|e| e.to_string())?;
+    let dir = sfz.parent().unwrap_or(root);
+    // Sample paths resolve to canonical form, so the root has to as well
+    let canonical_root = root.canonicalize().map_err(|e| e.to_string())?;
+
+    let mut samples = Vec::new();
+    for relative in sfz_sample_paths(&content) {
+        let resolved = match dir.join(&relative).canonicalize() {
+            Ok(path) => path,
+            Err(e) => {
+                tracing::debug!(
+                    instrument = %sfz.display(),
+                    sample = %relative,
+                    error = %e,
+                    "missing instrument sample"
+                );
+                continue;
+            }
+        };
+        let Ok(within_root) = resolved.strip_prefix(&canonical_root) else {
+            continue;
+        };
+        if !is_audio_name(&resolved.to_string_lossy()) {
+            continue;
+        }
+        // Spelled under `root` like the scanned files, so pool duplicates are recognised
+        samples.extend(sample_from_path(&root.join(within_root)));
+    }
+
+    Ok(samples)
+}
+
+fn instrument_samples(root: &Path, instrument: &Path) -> Result<Vec<Sample>, String> {
+    let mut samples = if archive::is_container(instrument) {
+        archive::list_audio_entries(instrument)?
+    } else {
+        sfz_samples(root, instrument)?
+    };
+
+    let provenance = instrument.to_string_lossy().to_string();
+    for sample in &mut samples {
+        sample.instrument = Some(provenance.clone());
+    }
+    Ok(samples)
+}
+
+/// Add the samples referenced by `instruments` to `samples`. Files already in
+/// the pool only gain their provenance.
+pub fn include_referenced(root: &Path, instruments: &[PathBuf], samples: &mut Vec<Sample>) {
+    let mut known: HashMap<String, usize> = samples
+        .iter()
+        .enumerate()
+        .map(|(i, sample)| (sample.path.clone(), i))
+        .collect();
+
+    for instrument in instruments {
+        let found = match instrument_samples(root, instrument) {
+            Ok(found) => found,
+            Err(e) => {
+                tracing::warn!(
+                    instrument = %instrument.display(),
+                    error = %e,
+                    "skipping instrument"
+                );
+                continue;
+            }
+        };
+
+        for sample in found {
+            match known.get(&sample.path) {
+                Some(&index) => {
+                    let existing = &mut samples[index];
+                    existing.instrument = existing.instrument.take().or(sample.instrument);
+                }
+                None => {
+                    known.insert(sample.path.clone(), samples.len());
+                    samples.push(sample);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::window::{ProgressBarState, ProgressBarStatus};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::power::{self, PowerGuard};
+
+/// Taskbar updates are OS calls; a 50k-file scan must not make one per file
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+/// Lower CPU shares would leave a big library analyzing for days
+const MIN_CPU_PERCENT: u8 = 10;
+/// How often a job held for battery power checks whether the charger is back
+const BATTERY_POLL: Duration = Duration::from_secs(5);
+
+/// How hard background analysis may work, so a full-library run doesn't spin up the
+/// fans during a listening session; persisted in the settings
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct Throttle {
+    /// Worker threads; `None` uses every core
+    pub max_threads: Option<usize>,
+    /// Share of each worker's time spent working (10-100); it sleeps the rest
+    pub cpu_percent: Option<u8>,
+    /// Hold analysis while the computer runs on battery
+    pub pause_on_battery: bool,
+}
+
+impl Throttle {
+    /// Worker threads for `pending` items of work
+    pub fn workers(&self, pending: usize) -> usize {
+        let cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        self.max_threads
+            .unwrap_or(cores)
+            .clamp(1, cores)
+            .min(pending.max(1))
+    }
+
+    /// How long to rest after `busy` of work to stay within the CPU share
+    fn rest(&self, busy: Duration) -> Duration {
+        match self.cpu_percent {
+            Some(percent) if percent < 100 => {
+                let percent = percent.max(MIN_CPU_PERCENT) as u32;
+                busy * (100 - percent) / percent
+            }
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JobKind {
+    Scan,
+    Analysis,
+    Export,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct JobProgress {
+    pub id: u64,
+    pub kind: JobKind,
+    pub done: u64,
+    /// `None` while the amount of work is unknown (e.g. a directory walk)
+    pub total: Option<u64>,
+    /// Held until the computer is back on mains power
+    pub paused: bool,
+}
+
+/// Long-running backend jobs, mirrored to the dock/taskbar and `jobs://progress`
+#[derive(Default)]
+pub struct JobQueue {
+    next_id: AtomicU64,
+    jobs: Mutex<BTreeMap<u64, JobProgress>>,
+    last_refresh: Mutex<Option<Instant>>,
+    throttle: Mutex<Throttle>,
+}
+
+impl JobQueue {
+    /// Applies to running jobs too, from their next item of work on
+    pub fn set_throttle(&self, throttle: Throttle) {
+        *self.throttle.lock().unwrap() = throttle;
+    }
+}
+
+/// Removes its job from the queue when dropped, including on early returns
+pub struct JobHandle {
+    app: AppHandle,
+    id: u64,
+}
+
+pub fn start(app: &AppHandle, kind: JobKind, total: Option<u64>) -> JobHandle {
+    let queue = app.state::<JobQueue>();
+    let id = queue.next_id.fetch_add(1, Ordering::Relaxed);
+    queue.jobs.lock().unwrap().insert(
+        id,
+        JobProgress {
+            id,
+            kind,
+            done: 0,
+            total,
+            paused: false,
+        },
+    );
+    refresh(app, true);
+
+    JobHandle {
+        app: app.clone(),
+        id,
+    }
+}
+
+impl JobHandle {
+    pub fn set_done(&self, done: u64) {
+        let queue = self.app.state::<JobQueue>();
+        if let Some(job) = queue.jobs.lock().unwrap().get_mut(&self.id) {
+            job.done = done;
+        }
+        refresh(&self.app, false);
+    }
+
+    fn set_paused(&self, paused: bool) {
+        let queue = self.app.state::<JobQueue>();
+        if let Some(job) = queue.jobs.lock().unwrap().get_mut(&self.id) {
+            job.paused = paused;
+        }
+        refresh(&self.app, true);
+    }
+
+    pub fn throttle(&self) -> Throttle {
+        self.app
+            .state::<JobQueue>()
+            .throttle
+            .lock()
+            .unwrap()
+            .clone()
+    }
+
+    /// Called by each worker after an item of work that took `busy`: rests to keep to
+    /// the CPU share, and holds while on battery if the settings ask for it
+    pub fn pace(&self, busy: Duration) {
+        let throttle = self.throttle();
+        std::thread::sleep(throttle.rest(busy));
+
+        if throttle.pause_on_battery && power::on_battery() {
+            tracing::info!(job = self.id, "pausing on battery power");
+            self.set_paused(true);
+            while self.throttle().pause_on_battery && power::on_battery() {
+                std::thread::sleep(BATTERY_POLL);
+            }
+            self.set_paused(false);
+        }
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        let queue = self.app.state::<JobQueue>();
+        queue.jobs.lock().unwrap().remove(&self.id);
+        refresh(&self.app, true);
+    }
+}
+
+fn progress_bar(jobs: &[JobProgress]) -> ProgressBarState {
+    if jobs.is_empty() {
+        return ProgressBarState {
+            status: Some(ProgressBarStatus::None),
+            progress: None,
+        };
+    }
+
+    let (done, total) = jobs.iter().fold((0u64, Some(0u64)), |(done, total), job| {
+        (done + job.done, total.zip(job.total).map(|(a, b)| a + b))
+    });
+
+    match total {
+        Some(total) if total > 0 => ProgressBarState {
+            status: Some(ProgressBarStatus::Normal),
+            progress: Some((done.min(total) * 100 / total).max(1)),
+        },
+        _ => ProgressBarState {
+            status: Some(ProgressBarStatus::Indeterminate),
+            progress: None,
+        },
+    }
+}
+
+fn refresh(app: &AppHandle, force: bool) {
+    let queue = app.state::<JobQueue>();
+    if force {
+        // Jobs only start or finish on forced refreshes
+        let busy = !queue.jobs.lock().unwrap().is_empty();
+        app.state::<PowerGuard>().set_jobs_active(busy);
+    }
+
+    {
+        let mut last = queue.last_refresh.lock().unwrap();
+        if !force && last.is_some_and(|t| t.elapsed() < REFRESH_INTERVAL) {
+            return;
+        }
+        *last = Some(Instant::now());
+    }
+
+    let jobs: Vec<JobProgress> = queue.jobs.lock().unwrap().values().cloned().collect();
+    let _ = app.emit("jobs://progress", &jobs);
+
+    if let Some(window) = app.get_webview_window("main") {
+        if let Err(e) = window.set_progress_bar(progress_bar(&jobs)) {
+            tracing::debug!(error = %e, "failed to update taskbar progress");
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_jobs(queue: tauri::State<JobQueue>) -> Vec<JobProgress> {
+    queue.jobs.lock().unwrap().values().cloned().collect()
+}
@@ -0,0 +1,300 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, State};
+
+use crate::audit::{self, AuditKind};
+use crate::copy_export::{hash_file, MANIFEST_FILE_NAME};
+use crate::manifest;
+use crate::settings::SettingsStore;
+use crate::{i18n, AllowedPaths};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationKind {
+    Copy,
+    Trash,
+    /// A symlink to `source` placed at `destination`
+    Link,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JournalEntry {
+    pub id: u64,
+    /// Operations performed by a single command share a batch
+    pub batch_id: u64,
+    pub kind: OperationKind,
+    pub source: String,
+    pub destination: String,
+    pub timestamp: u64,
+    pub undone: bool,
+}
+
+/// Append-only record of backend-initiated file operations (persisted in the app data dir)
+pub struct Journal {
+    file: PathBuf,
+    entries: Mutex<Vec<JournalEntry>>,
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Rename, falling back to copy + delete when crossing volumes
+pub fn move_file(from: &Path, to: &Path) -> Result<(), String> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to).map_err(|e| e.to_string())?;
+        fs::remove_file(from).map_err(|e| e.to_string())?;
+    }
+    audit::record(
+        AuditKind::Move,
+        to.to_string_lossy(),
+        Some(from.to_string_lossy().to_string()),
+    );
+    Ok(())
+}
+
+/// A copy is only deleted while it still matches the hash its export manifest recorded,
+/// so undo never throws away a file the user edited after exporting
+fn verify_copy(destination: &Path) -> Result<(), String> {
+    let path = [("path", destination.to_string_lossy().to_string().into())];
+    let recorded = destination
+        .ancestors()
+        .skip(1)
+        .find_map(|dir| {
+            manifest::load(&dir.join(MANIFEST_FILE_NAME))
+                .files
+                .into_iter()
+                .find(|entry| dir.join(&entry.destination) == destination)
+        })
+        .ok_or_else(|| i18n::t_args("undo-copy-unverified", path.clone()))?;
+    let current = hash_file(destination).map_err(|e| e.to_string())?;
+    if current != recorded.sha256 {
+        return Err(i18n::t_args("undo-copy-changed", path));
+    }
+    Ok(())
+}
+
+impl Journal {
+    pub fn load(data_dir: &Path) -> Self {
+        let file = data_dir.join("file-journal.json");
+        let entries = fs::read_to_string(&file)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            file,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    fn persist(&self, entries: &[JournalEntry]) -> Result<(), String> {
+        if let Some(parent) = self.file.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(entries).map_err(|e| e.to_string())?;
+        fs::write(&self.file, json).map_err(|e| e.to_string())
+    }
+
+    /// Record a group of completed operations as one batch; returns the batch id
+    pub fn record_batch(
+        &self,
+        kind: OperationKind,
+        operations: &[(String, String)],
+    ) -> Result<u64, String> {
+        let mut entries = self.entries.lock().unwrap();
+        let next_id = entries.last().map(|e| e.id + 1).unwrap_or(1);
+        let batch_id = entries.last().map(|e| e.batch_id + 1).unwrap_or(1);
+        let timestamp = now_secs();
+
+        for (offset, (source, destination)) in operations.iter().enumerate() {
+            entries.push(JournalEntry {
+                id: next_id + offset as u64,
+                batch_id,
+                kind,
+                source: source.clone(),
+                destination: destination.clone(),
+                timestamp,
+                undone: false,
+            });
+        }
+
+        self.persist(&entries)?;
+        Ok(batch_id)
+    }
+
+    fn undo_entry(entry: &mut JournalEntry) -> Result<(), String> {
+        if entry.undone {
+            return Err(i18n::t("undo-already-undone"));
+        }
+
+        let source = Path::new(&entry.source);
+        let destination = Path::new(&entry.destination);
+        match entry.kind {
+            OperationKind::Copy => {
+                if destination.exists() {
+                    verify_copy(destination)?;
+                    fs::remove_file(destination).map_err(|e| e.to_string())?;
+                }
+            }
+            // Only a link still pointing at the original is ours to remove
+            OperationKind::Link => {
+                if let Ok(metadata) = fs::symlink_metadata(destination) {
+                    let ours = metadata.file_type().is_symlink()
+                        && fs::read_link(destination).is_ok_and(|target| target == source);
+                    if !ours {
+                        return Err(i18n::t_args(
+                            "undo-link-changed",
+                            [("path", entry.destination.as_str().into())],
+                        ));
+                    }
+                    fs::remove_file(destination).map_err(|e| e.to_string())?;
+                }
+            }
+            OperationKind::Trash => {
+                if source.exists() {
+                    return Err(i18n::t_args(
+                        "undo-restore-exists",
+                        [("path", entry.source.as_str().into())],
+                    ));
+                }
+                move_file(destination, source)?;
+            }
+        }
+
+        entry.undone = true;
+        Ok(())
+    }
+
+    pub fn undo(&self, op_id: u64) -> Result<JournalEntry, String> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries
+            .iter_mut()
+            .find(|e| e.id == op_id)
+            .ok_or_else(|| i18n::t("undo-unknown-operation"))?;
+
+        Self::undo_entry(entry)?;
+        let undone = entry.clone();
+        self.persist(&entries)?;
+        Ok(undone)
+    }
+
+    /// Undo every remaining operation of the most recent batch, newest first
+    pub fn undo_last_batch(&self) -> Result<Vec<JournalEntry>, String> {
+        let mut entries = self.entries.lock().unwrap();
+        let Some(batch_id) = entries.iter().rev().find(|e| !e.undone).map(|e| e.batch_id) else {
+            return Ok(Vec::new());
+        };
+
+        let mut undone = Vec::new();
+        let mut result = Ok(());
+        for entry in entries
+            .iter_mut()
+            .rev()
+            .filter(|e| e.batch_id == batch_id && !e.undone)
+        {
+            if let Err(e) = Self::undo_entry(entry) {
+                result = Err(e);
+                break;
+            }
+            undone.push(entry.clone());
+        }
+
+        // Persist partial progress even if one operation failed
+        self.persist(&entries)?;
+        result.map(|_| undone)
+    }
+
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.entries.lock().unwrap().clone()
+    }
+}
+
+#[tauri::command]
+pub fn get_file_journal(journal: State<Journal>) -> Vec<JournalEntry> {
+    journal.entries()
+}
+
+#[tauri::command]
+pub fn undo_file_operation(
+    op_id: u64,
+    journal: State<Journal>,
+    settings: State<SettingsStore>,
+) -> Result<JournalEntry, String> {
+    settings.ensure_writable()?;
+    journal.undo(op_id)
+}
+
+#[tauri::command]
+pub fn undo_last_batch(
+    journal: State<Journal>,
+    settings: State<SettingsStore>,
+) -> Result<Vec<JournalEntry>, String> {
+    settings.ensure_writable()?;
+    journal.undo_last_batch()
+}
+
+/// Move samples into the app's discard folder (reversible via the journal)
+#[tauri::command]
+pub fn discard_samples(
+    paths: Vec<String>,
+    app: AppHandle,
+    journal: State<Journal>,
+    allowed_paths: State<AllowedPaths>,
+    settings: State<SettingsStore>,
+) -> Result<u64, String> {
+    settings.ensure_writable()?;
+    let trash_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join("discarded")
+        .join(now_secs().to_string());
+
+    let mut moved = Vec::new();
+    let mut result = Ok(());
+    for (index, path) in paths.iter().enumerate() {
+        let source = Path::new(path);
+        if !source.is_file() {
+            result = Err(i18n::t_args(
+                "file-not-found-path",
+                [("path", path.as_str().into())],
+            ));
+            break;
+        }
+
+        // Validate path is within allowed directories
+        if !allowed_paths.is_path_allowed(source) {
+            result = Err(i18n::t("access-denied"));
+            break;
+        }
+
+        // Prefix with the index so identical filenames from different folders don't clash
+        let file_name = source.file_name().unwrap_or_default().to_string_lossy();
+        let destination = trash_dir.join(format!("{:05}_{}", index, file_name));
+        if let Err(e) = move_file(source, &destination) {
+            result = Err(e);
+            break;
+        }
+        moved.push((path.clone(), destination.to_string_lossy().to_string()));
+    }
+
+    // Journal whatever was moved, even if a later file failed
+    let batch_id = if moved.is_empty() {
+        0
+    } else {
+        journal.record_batch(OperationKind::Trash, &moved)?
+    };
+
+    result.map(|_| batch_id)
+}
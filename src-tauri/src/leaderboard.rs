@@ -0,0 +1,160 @@
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::export::{result_rows, sorted_results, ResultRow};
+use crate::{i18n, Sample};
+
+/// Rows sent to the detached window; the full list stays in the main window
+const MAX_ROWS: usize = 500;
+
+#[derive(Debug, Serialize)]
+pub struct Standings<'a> {
+    pub round: i32,
+    /// Samples still in the running, including those beyond `rows`
+    pub total: usize,
+    pub rows: Vec<ResultRow<'a>>,
+}
+
+/// Rows whose rank, score or comparisons moved since the last update, so listeners
+/// patch their copy instead of receiving every sample after each vote
+#[derive(Debug, Serialize)]
+pub struct StandingsDelta<'a> {
+    pub round: i32,
+    pub total: usize,
+    /// New or changed rows, best first
+    pub rows: Vec<ResultRow<'a>>,
+    /// Paths that dropped off the shown rows
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RoundAdvanced {
+    pub round: i32,
+    /// Samples no longer in the pool, cut with the previous round
+    pub removed: Vec<String>,
+}
+
+/// What each shown row last displayed: rank, score and comparisons
+type Shown = HashMap<String, (usize, i32, i32)>;
+
+/// Latest standings of the open session
+#[derive(Default)]
+pub struct Leaderboard(Mutex<Option<Published>>);
+
+struct Published {
+    round: i32,
+    samples: Vec<Sample>,
+    shown: Shown,
+}
+
+fn standings(round: i32, samples: &[Sample]) -> Standings<'_> {
+    let ranked = sorted_results(samples);
+    let total = ranked.len();
+    let top: Vec<&Sample> = ranked.into_iter().take(MAX_ROWS).collect();
+    Standings {
+        round,
+        total,
+        rows: result_rows(&top),
+    }
+}
+
+fn shown(standings: &Standings) -> Shown {
+    standings
+        .rows
+        .iter()
+        .map(|row| (row.path.to_string(), (row.rank, row.score, row.comparisons)))
+        .collect()
+}
+
+/// Replace the published pool; done when a session opens and after every change but a
+/// vote
+pub fn publish(app: &AppHandle, round: i32, samples: Vec<Sample>) -> Result<(), String> {
+    let leaderboard = app.state::<Leaderboard>();
+    let mut published = leaderboard.0.lock().unwrap();
+    if let Some(previous) = published.as_ref().filter(|p| p.round < round) {
+        let remaining: HashSet<&str> = samples.iter().map(|s| s.path.as_str()).collect();
+        let advanced = RoundAdvanced {
+            round,
+            removed: previous
+                .samples
+                .iter()
+                .filter(|s| !remaining.contains(s.path.as_str()))
+                .map(|s| s.path.clone())
+                .collect(),
+        };
+        app.emit("round://advanced", &advanced)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let current = standings(round, &samples);
+    if app.get_webview_window("leaderboard").is_some() {
+        app.emit_to("leaderboard", "leaderboard://updated", &current)
+            .map_err(|e| e.to_string())?;
+    }
+    let shown = shown(&current);
+    *published = Some(Published {
+        round,
+        samples,
+        shown,
+    });
+    Ok(())
+}
+
+/// Apply the samples a vote changed to the published pool and announce them as
+/// `sample://updated` and `standings://changed`. Fails when the pool hasn't been
+/// published for `round` yet, so the caller publishes it whole instead.
+pub fn publish_changes(app: &AppHandle, round: i32, samples: &[Sample]) -> Result<(), String> {
+    let leaderboard = app.state::<Leaderboard>();
+    let mut guard = leaderboard.0.lock().unwrap();
+    let published = guard
+        .as_mut()
+        .filter(|p| p.round == round)
+        .ok_or_else(|| i18n::t("standings-not-published"))?;
+
+    let positions: HashMap<String, usize> = published
+        .samples
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.path.clone(), i))
+        .collect();
+    for sample in samples {
+        match positions.get(&sample.path) {
+            Some(&i) => published.samples[i] = sample.clone(),
+            None => published.samples.push(sample.clone()),
+        }
+    }
+    app.emit("sample://updated", samples)
+        .map_err(|e| e.to_string())?;
+
+    let current = standings(round, &published.samples);
+    let shown = shown(&current);
+    let delta = StandingsDelta {
+        round,
+        total: current.total,
+        removed: published
+            .shown
+            .keys()
+            .filter(|path| !shown.contains_key(path.as_str()))
+            .cloned()
+            .collect(),
+        rows: current
+            .rows
+            .iter()
+            .filter(|row| published.shown.get(row.path) != shown.get(row.path))
+            .cloned()
+            .collect(),
+    };
+    app.emit("standings://changed", &delta)
+        .map_err(|e| e.to_string())?;
+    published.shown = shown;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_standings(leaderboard: State<Leaderboard>) -> Option<serde_json::Value> {
+    let current = leaderboard.0.lock().unwrap();
+    let published = current.as_ref()?;
+    serde_json::to_value(standings(published.round, &published.samples)).ok()
+}
@@ -1,11 +1,92 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::sync::Mutex;
-use tauri::State;
-use walkdir::WalkDir;
-
-const AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "flac", "ogg", "aiff", "m4a"];
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_fs::FsExt;
+
+mod ableton_export;
+mod advancement;
+mod analysis;
+mod archive;
+mod audit;
+mod backup;
+mod bench;
+mod byes;
+mod cache;
+mod calibration;
+pub mod cli;
+mod clipboard;
+mod cloud;
+mod collab;
+mod confirm;
+mod copy_export;
+mod dataset;
+mod deep_link;
+mod describe;
+mod diagnostics;
+mod duplicates;
+mod error;
+mod export;
+mod export_scripts;
+mod hooks;
+mod i18n;
+mod import;
+mod instruments;
+mod jobs;
+mod journal;
+mod leaderboard;
+mod logging;
+mod loops;
+mod manifest;
+mod media;
+mod notifications;
+mod packs;
+mod path_template;
+mod pdf_report;
+mod pins;
+mod player_export;
+mod plugins;
+mod power;
+mod presets;
+mod process;
+mod queue;
+mod redemption;
+mod reel;
+mod rematches;
+mod remote;
+mod reveal;
+mod rng;
+mod round_summary;
+mod save_merge;
+mod savefile;
+mod scan;
+mod scheduler;
+mod search;
+mod session_archive;
+mod sessions;
+mod settings;
+mod share;
+mod spectrogram;
+mod staleness;
+mod statistics;
+mod summary_image;
+mod tags;
+mod thumbnails;
+mod tournaments;
+mod transcode;
+mod variations;
+mod video;
+mod voting;
+mod watch;
+mod windows;
+
+use audit::AuditKind;
+use error::CommandError;
+use export::ResultsFormat;
+use savefile::Compression;
+use scan::ScanOptions;
 
 /// Tracks allowed directories for file operations (security)
 #[derive(Default)]
@@ -14,6 +95,11 @@ pub struct AllowedPaths {
     source_directory: Mutex<Option<PathBuf>>,
     /// Additional paths that have been explicitly allowed (e.g., save locations)
     allowed_paths: Mutex<Vec<PathBuf>>,
+    /// Files the user picked in a dialog or opened from the OS (double-clicked saves)
+    granted_files: Mutex<Vec<PathBuf>>,
+    /// Canonical forms of the source directory and allowed paths, so lookups don't
+    /// resolve every root again; cleared whenever either changes
+    canonical_roots: Mutex<Option<Vec<PathBuf>>>,
 }
 
 impl AllowedPaths {
@@ -23,44 +109,109 @@ impl AllowedPaths {
 
     /// Set the source directory (called when user selects a directory to scan)
     pub fn set_source_directory(&self, path: PathBuf) {
+        audit::record(AuditKind::Grant, path.to_string_lossy(), None);
         *self.source_directory.lock().unwrap() = Some(path);
+        self.invalidate_roots();
     }
 
     /// Add an allowed path (called when user explicitly selects a file via dialog)
     pub fn add_allowed_path(&self, path: PathBuf) {
         let mut paths = self.allowed_paths.lock().unwrap();
         if !paths.contains(&path) {
+            audit::record(AuditKind::Grant, path.to_string_lossy(), None);
             paths.push(path);
+            drop(paths);
+            self.invalidate_roots();
+        }
+    }
+
+    fn invalidate_roots(&self) {
+        *self.canonical_roots.lock().unwrap() = None;
+    }
+
+    /// Canonical roots, and whether all of them resolved; a root that doesn't exist yet
+    /// may be created later, so an incomplete list isn't cached
+    fn resolve_roots(&self) -> (Vec<PathBuf>, bool) {
+        let source = self.source_directory.lock().unwrap().clone();
+        let allowed = self.allowed_paths.lock().unwrap().clone();
+        let mut complete = true;
+        let roots = source
+            .iter()
+            .chain(allowed.iter())
+            .filter_map(|root| {
+                let canonical = root.canonicalize().ok();
+                complete &= canonical.is_some();
+                canonical
+            })
+            .collect();
+        (roots, complete)
+    }
+
+    /// Record a file the user chose outside the webview, so commands may read or write it
+    pub fn grant_file(&self, path: PathBuf) {
+        let mut files = self.granted_files.lock().unwrap();
+        if !files.contains(&path) {
+            audit::record(AuditKind::Grant, path.to_string_lossy(), None);
+            files.push(path);
+        }
+    }
+
+    /// Check a file the frontend asks to read or write: it must have been picked by the
+    /// user, or lie inside an allowed directory (new files: their parent directory)
+    pub fn check_file(&self, path: &Path) -> Result<(), CommandError> {
+        if !path.is_absolute() || path.components().any(|c| c == Component::ParentDir) {
+            audit::record(AuditKind::Denial, path.to_string_lossy(), None);
+            return Err(CommandError::PathNotAllowed);
+        }
+        if self.granted_files.lock().unwrap().iter().any(|p| p == path) {
+            return Ok(());
+        }
+
+        // Existing files are resolved first, so a symlink can't lead out of allowed folders
+        let allowed = if path.exists() {
+            self.is_path_allowed(path)
+        } else {
+            path.parent()
+                .is_some_and(|parent| self.is_path_allowed(parent))
+        };
+        if !allowed {
+            tracing::warn!(path = %path.display(), "refused file outside allowed paths");
+            return Err(CommandError::PathNotAllowed);
         }
+        Ok(())
     }
 
-    /// Check if a path is within allowed directories
+    /// Check if a path is within allowed directories; refusals go to the audit log
     pub fn is_path_allowed(&self, path: &Path) -> bool {
+        let allowed = self.within_roots(path);
+        if !allowed {
+            audit::record(AuditKind::Denial, path.to_string_lossy(), None);
+        }
+        allowed
+    }
+
+    fn within_roots(&self, path: &Path) -> bool {
+        // Archive entries are allowed if the archive itself is
+        if let Some((archive_path, _)) = archive::split_archive_path(&path.to_string_lossy()) {
+            return self.within_roots(Path::new(archive_path));
+        }
+
         let canonical = match path.canonicalize() {
             Ok(p) => p,
             Err(_) => return false,
         };
 
-        // Check if within source directory
-        if let Some(ref source) = *self.source_directory.lock().unwrap() {
-            if let Ok(source_canonical) = source.canonicalize() {
-                if canonical.starts_with(&source_canonical) {
-                    return true;
-                }
-            }
+        // Within the source directory or an explicitly allowed path
+        let mut cache = self.canonical_roots.lock().unwrap();
+        if let Some(roots) = cache.as_ref() {
+            return roots.iter().any(|root| canonical.starts_with(root));
         }
-
-        // Check if in explicitly allowed paths
-        let allowed = self.allowed_paths.lock().unwrap();
-        for allowed_path in allowed.iter() {
-            if let Ok(allowed_canonical) = allowed_path.canonicalize() {
-                if canonical.starts_with(&allowed_canonical) || canonical == allowed_canonical {
-                    return true;
-                }
-            }
+        let (roots, complete) = self.resolve_roots();
+        let allowed = roots.iter().any(|root| canonical.starts_with(root));
+        if complete {
+            *cache = Some(roots);
         }
-
-        false
+        allowed
     }
 }
 
@@ -70,6 +221,40 @@ pub struct Sample {
     pub filename: String,
     pub score: i32,
     pub comparisons: i32,
+    /// Auto-enrolled by watch mode and not yet confirmed by the user
+    #[serde(default)]
+    pub provisional: bool,
+    /// Cloud placeholder whose content has not been downloaded yet
+    #[serde(default)]
+    pub online_only: bool,
+    /// Filled in by audio analysis (opt-in, it decodes every file)
+    #[serde(default)]
+    pub analysis: Option<analysis::Analysis>,
+    /// Other takes of this sound (kick_01b, ...) competing as one entrant; expanded on export
+    #[serde(default)]
+    pub variants: Vec<String>,
+    /// Pack (vendor folder) the sample came from, if it sits inside one
+    #[serde(default)]
+    pub pack: Option<String>,
+    /// Sampler instrument (.sfz, .xrni) that references this sample
+    #[serde(default)]
+    pub instrument: Option<String>,
+    /// Channel count, known once the sample is analyzed; playback reads the header
+    /// itself and downmixes more than two
+    #[serde(default)]
+    pub channels: Option<u16>,
+    /// Manual keep/reject verdict; pinned samples are no longer paired
+    #[serde(default)]
+    pub pin: Option<pins::Pin>,
+    /// Set aside for now: not paired and not cut, with its score and history kept
+    #[serde(default)]
+    pub benched: bool,
+    /// How far the score may be off, in points; grows while a session sits idle
+    #[serde(default)]
+    pub uncertainty: f32,
+    /// The user's own labels ("keeper", "for the EP"), e.g. to filter exports by
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -80,6 +265,58 @@ pub struct TournamentState {
     pub current_comparison_index: usize,
     pub advancement_threshold: f32,
     pub source_directory: String,
+    /// Individual ballots from collaborative (multi-voter) comparisons
+    #[serde(default)]
+    pub votes: Vec<collab::VoteRecord>,
+    /// Calibration opponent used to detect drifting judgments
+    #[serde(default)]
+    pub reference: Option<calibration::Reference>,
+    /// Samples cut at the end of earlier rounds, with their final scores
+    #[serde(default)]
+    pub eliminated: Vec<Sample>,
+    /// Seeds every shuffle and random pairing, making the schedule reproducible
+    #[serde(default)]
+    pub seed: u32,
+    /// Names the tournament across saves, so every window that opens it shares one
+    /// backend session; given out when it is first opened
+    #[serde(default)]
+    pub id: String,
+    /// Per-round advancement thresholds from a preset; the last one repeats and an
+    /// empty schedule uses `advancement_threshold` throughout
+    #[serde(default)]
+    pub threshold_schedule: Vec<f32>,
+    /// Advance samples scoring this many standard deviations above the mean
+    /// instead of a fixed fraction
+    #[serde(default)]
+    pub adaptive_cutoff: Option<f32>,
+    /// Every decided comparison, oldest first
+    #[serde(default)]
+    pub history: Vec<round_summary::MatchRecord>,
+    /// Summary of each finished round
+    #[serde(default)]
+    pub round_summaries: Vec<round_summary::RoundSummary>,
+    /// Set when the pool is a random subset of a capped scan, to repeat or widen it
+    #[serde(default)]
+    pub scan_sampling: Option<scan::ScanSampling>,
+    /// Export settings of the template the session started from
+    #[serde(default)]
+    pub export_defaults: Option<presets::ExportDefaults>,
+    /// How scores carry over at round boundaries
+    #[serde(default)]
+    pub score_carry: advancement::ScoreCarry,
+    /// Who sits out rounds with an odd number of samples
+    #[serde(default)]
+    pub bye_policy: byes::ByePolicy,
+    /// Every bye given, oldest first
+    #[serde(default)]
+    pub byes: Vec<byes::Bye>,
+    /// Let pairings repeat matchups that were already decided; off, a rematch only
+    /// happens once every other opponent is taken
+    #[serde(default)]
+    pub allow_rematches: bool,
+    /// Written with every save and consumed on load; `None` in a running session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduler: Option<scheduler::SchedulerSnapshot>,
 }
 
 impl TournamentState {
@@ -91,18 +328,36 @@ impl TournamentState {
             current_comparison_index: 0,
             advancement_threshold,
             source_directory,
+            votes: Vec::new(),
+            reference: None,
+            eliminated: Vec::new(),
+            seed: 0,
+            id: String::new(),
+            threshold_schedule: Vec::new(),
+            adaptive_cutoff: None,
+            history: Vec::new(),
+            round_summaries: Vec::new(),
+            scan_sampling: None,
+            export_defaults: None,
+            score_carry: advancement::ScoreCarry::Raw,
+            bye_policy: byes::ByePolicy::Fair,
+            byes: Vec::new(),
+            allow_rematches: false,
+            scheduler: None,
         }
     }
 }
 
 #[tauri::command]
-fn scan_directory(
-    directory: &str,
-    allowed_paths: State<AllowedPaths>,
-) -> Result<Vec<Sample>, String> {
-    let path = Path::new(directory);
+async fn scan_directory(
+    directory: String,
+    options: Option<ScanOptions>,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<scan::ScanOutcome, String> {
+    let path = Path::new(&directory);
     if !path.exists() {
-        return Err("Directory does not exist".to_string());
+        return Err(i18n::t("directory-not-found"));
     }
 
     // Validate and canonicalize the path
@@ -111,99 +366,196 @@ fn scan_directory(
     // Register this directory as allowed for future operations
     allowed_paths.set_source_directory(canonical_path.clone());
 
-    let mut samples = Vec::new();
-
-    // Don't follow symlinks to prevent escape attacks
-    for entry in WalkDir::new(&canonical_path)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let entry_path = entry.path();
-        if entry_path.is_file() {
-            if let Some(ext) = entry_path.extension() {
-                let ext_lower = ext.to_string_lossy().to_lowercase();
-                if AUDIO_EXTENSIONS.contains(&ext_lower.as_str()) {
-                    let filename = entry_path
-                        .file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_default();
-
-                    samples.push(Sample {
-                        path: entry_path.to_string_lossy().to_string(),
-                        filename,
-                        score: 0,
-                        comparisons: 0,
-                    });
-                }
-            }
+    let options = options.unwrap_or_default();
+    let started = std::time::Instant::now();
+    let job = jobs::start(&app, jobs::JobKind::Scan, None);
+    let root = canonical_path.clone();
+    let analysis_app = app.clone();
+    let (samples, report) = tauri::async_runtime::spawn_blocking(move || {
+        let (mut samples, mut report) =
+            scan::scan_samples_with_progress(&root, &options, |visited| job.set_done(visited));
+        drop(job);
+
+        if options.needs_analysis() {
+            let job = jobs::start(
+                &analysis_app,
+                jobs::JobKind::Analysis,
+                Some(samples.len() as u64),
+            );
+            samples = analysis::analyze_batch(samples, Some(&job)).samples;
+            let analyzed = samples.len();
+            samples.retain(|sample| options.keep(sample));
+            report.too_silent = analyzed - samples.len();
         }
-    }
 
-    Ok(samples)
+        if options.group_variations {
+            samples = variations::group(samples);
+        }
+        (samples, report)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    tracing::info!(
+        directory = %canonical_path.display(),
+        samples = samples.len(),
+        unreadable = report.unreadable,
+        permission_denied = report.permission_denied,
+        symlinks = report.symlinks,
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        "scan finished"
+    );
+    notifications::job_finished(
+        &app,
+        started,
+        &i18n::t("scan-complete"),
+        &i18n::t_args("scan-complete-body", [("count", samples.len().into())]),
+    );
+    Ok(scan::ScanOutcome { samples, report })
 }
 
-#[tauri::command]
-fn save_progress(
-    state: TournamentState,
+/// Write a save file, keeping backups of the one it replaces
+pub(crate) fn write_save(
+    state: &TournamentState,
     file_path: &str,
-    allowed_paths: State<AllowedPaths>,
-) -> Result<(), String> {
+    compression: Option<Compression>,
+    password: Option<&str>,
+    allowed_paths: &AllowedPaths,
+) -> Result<(), CommandError> {
     let path = Path::new(file_path);
+    allowed_paths.check_file(path)?;
 
-    // Register this path as allowed (user selected via dialog)
+    // The folder the user saved into is allowed from now on
     if let Some(parent) = path.parent() {
         allowed_paths.add_allowed_path(parent.to_path_buf());
     }
 
-    let json = serde_json::to_string_pretty(&state).map_err(|e| e.to_string())?;
-    fs::write(file_path, json).map_err(|e| e.to_string())?;
+    let bytes = savefile::write(state, compression.unwrap_or_default(), password)?;
+
+    // Keep the previous versions in case this overwrites the wrong tournament
+    backup::rotate(path)?;
+    fs::write(file_path, bytes).map_err(|e| e.to_string())?;
+    audit::record_write(path, None);
     Ok(())
 }
 
+/// Allow the folder a loaded session was scanned from. A save can name any folder, so
+/// one that isn't allowed already is only allowed once the user agrees in a native
+/// dialog; otherwise the session still opens, but its samples can't be played.
+pub(crate) async fn restore_source_directory(app: &AppHandle, directory: &str) {
+    let path = PathBuf::from(directory);
+    if app.state::<AllowedPaths>().within_roots(&path) {
+        app.state::<AllowedPaths>().set_source_directory(path);
+        return;
+    }
+    if !path.is_absolute() || !path.is_dir() {
+        return;
+    }
+
+    let title = i18n::t("confirm-source-folder-title");
+    let message = i18n::t_args("confirm-source-folder", [("path", directory.into())]);
+    let dialog_app = app.clone();
+    let confirmed = tauri::async_runtime::spawn_blocking(move || {
+        confirm::ask_blocking(&dialog_app, &title, &message)
+    })
+    .await
+    .unwrap_or(false);
+    if confirmed {
+        app.state::<AllowedPaths>().set_source_directory(path);
+    } else {
+        tracing::info!(path = %directory, "source folder of loaded session not allowed");
+    }
+}
+
 #[tauri::command]
-fn load_progress(
-    file_path: &str,
-    allowed_paths: State<AllowedPaths>,
-) -> Result<TournamentState, String> {
-    let path = Path::new(file_path);
+async fn load_progress(
+    file_path: String,
+    password: Option<String>,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+    settings: State<'_, settings::SettingsStore>,
+) -> Result<TournamentState, CommandError> {
+    let path = PathBuf::from(&file_path);
+    allowed_paths.check_file(&path)?;
 
     // Register parent directory as allowed
     if let Some(parent) = path.parent() {
         allowed_paths.add_allowed_path(parent.to_path_buf());
     }
 
-    let content = fs::read_to_string(file_path).map_err(|e| e.to_string())?;
-    let state: TournamentState = serde_json::from_str(&content).map_err(|e| e.to_string())?;
-
-    // Also register the source directory from the loaded state
-    allowed_paths.set_source_directory(PathBuf::from(&state.source_directory));
+    let stale_after_days = settings.get().stale_after_days;
+    let state = tauri::async_runtime::spawn_blocking(move || {
+        // Compressed and encrypted saves are detected from their header
+        let content = fs::read(&path).map_err(|e| e.to_string())?;
+        let mut state = savefile::decode(content, password.as_deref())?;
+
+        // The save's modification time is when the session was last worked on
+        if let Some(stale_after_days) = stale_after_days {
+            let idle = fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok());
+            if let Some(idle) = idle {
+                staleness::inflate(&mut state, idle.as_secs(), stale_after_days);
+            }
+        }
+        Ok::<_, CommandError>(state)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
 
+    restore_source_directory(&app, &state.source_directory).await;
     Ok(state)
 }
 
 #[tauri::command]
-fn export_results(
+async fn export_results(
     samples: Vec<Sample>,
-    file_path: &str,
-    min_score: i32,
-    allowed_paths: State<AllowedPaths>,
-) -> Result<(), String> {
-    let path = Path::new(file_path);
+    file_path: String,
+    filter: export::ExportFilter,
+    password: Option<String>,
+    write_manifest: Option<bool>,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<(), CommandError> {
+    let write_manifest = write_manifest.unwrap_or(false);
+    if write_manifest && password.is_some() {
+        return Err(i18n::t("manifest-with-password").into());
+    }
+    let path = PathBuf::from(file_path);
+    allowed_paths.check_file(&path)?;
 
-    // Register this path as allowed (user selected via dialog)
+    // The folder the user exported into is allowed from now on
     if let Some(parent) = path.parent() {
         allowed_paths.add_allowed_path(parent.to_path_buf());
     }
 
-    let good_samples: Vec<&str> = samples
-        .iter()
-        .filter(|s| s.score >= min_score)
-        .map(|s| s.path.as_str())
-        .collect();
+    // Hashing for the manifest reads every listed file
+    tauri::async_runtime::spawn_blocking(move || {
+        let good_samples = filter.apply(&samples);
+        let listed = variations::expand(&good_samples);
+
+        let content = listed
+            .iter()
+            .map(|s| s.path.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        match password {
+            Some(password) => {
+                let encrypted = savefile::encrypt(content.as_bytes(), &password)?;
+                fs::write(&path, encrypted).map_err(|e| e.to_string())?;
+            }
+            None => fs::write(&path, content).map_err(|e| e.to_string())?,
+        }
+        audit::record_write(&path, None);
 
-    let content = good_samples.join("\n");
-    fs::write(file_path, content).map_err(|e| e.to_string())?;
+        if write_manifest {
+            let listed: Vec<&Sample> = listed.iter().collect();
+            manifest::write_beside(&path, manifest::ExportKind::Playlist, &listed)?;
+        }
+        Ok::<_, String>(())
+    })
+    .await
+    .map_err(|e| e.to_string())??;
     Ok(())
 }
 
@@ -214,57 +566,100 @@ fn get_audio_file_url(
 ) -> Result<String, String> {
     let path = Path::new(file_path);
     if !path.exists() {
-        return Err("File does not exist".to_string());
+        return Err(i18n::t("file-not-found"));
     }
 
     // Validate path is within allowed directories
     if !allowed_paths.is_path_allowed(path) {
-        return Err("Access denied: path is outside allowed directories".to_string());
+        return Err(i18n::t("access-denied"));
     }
 
     // Return a file:// URL that the frontend can use
     Ok(format!("file://{}", file_path))
 }
 
+/// Resolve a sample path to a local file the webview can play, extracting archive entries
+/// and video audio tracks and converting surround, odd-rate or too-loud files into the app
+/// cache on demand
+#[tauri::command]
+async fn resolve_playback_path(
+    file_path: String,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+    settings: State<'_, settings::SettingsStore>,
+) -> Result<String, String> {
+    // Validate path is within allowed directories
+    if !allowed_paths.is_path_allowed(Path::new(&file_path)) {
+        return Err(i18n::t("access-denied"));
+    }
+
+    let cache_root = app.path().app_cache_dir().map_err(|e| e.to_string())?;
+    let ceiling_dbfs = settings.get().output_ceiling_dbfs;
+
+    let source = file_path.clone();
+    let cache = cache_root.clone();
+    let playable = tauri::async_runtime::spawn_blocking(move || {
+        transcode::prepare_playback(&source, &cache, ceiling_dbfs)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .inspect_err(
+        |e| tracing::warn!(path = %file_path, error = %e, "playback preparation failed"),
+    )?;
+
+    let Some(playable) = playable else {
+        return Ok(file_path);
+    };
+
+    // Extracted copies are served from the cache, so allow it explicitly
+    allowed_paths.add_allowed_path(cache_root);
+
+    Ok(playable.to_string_lossy().to_string())
+}
+
+/// Download an online-only (cloud placeholder) file so it can be played
+#[tauri::command]
+async fn materialize_file(
+    file_path: String,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<(), String> {
+    let path = PathBuf::from(&file_path);
+
+    // iCloud stubs are hidden siblings, so validate against the parent directory
+    let check = path.parent().unwrap_or(&path);
+    if !allowed_paths.is_path_allowed(check) {
+        return Err(i18n::t("access-denied"));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || cloud::materialize(&path))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 fn reveal_in_finder(
     file_path: String,
     allowed_paths: State<AllowedPaths>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let path = Path::new(&file_path);
     if !path.exists() {
-        return Err("File does not exist".to_string());
+        return Err(CommandError::FileNotFound);
     }
 
     // Validate path is within allowed directories
     if !allowed_paths.is_path_allowed(path) {
-        return Err("Access denied: path is outside allowed directories".to_string());
+        return Err(CommandError::PathNotAllowed);
     }
 
     #[cfg(target_os = "macos")]
-    {
-        std::process::Command::new("open")
-            .args(["-R", &file_path])
-            .spawn()
-            .map_err(|e| e.to_string())?;
-    }
+    process::spawn("open", &["-R"], &[&file_path])?;
 
     #[cfg(target_os = "windows")]
-    {
-        std::process::Command::new("explorer")
-            .args(["/select,", &file_path])
-            .spawn()
-            .map_err(|e| e.to_string())?;
-    }
+    process::spawn("explorer", &["/select,"], &[&file_path])?;
 
     #[cfg(target_os = "linux")]
-    {
-        if let Some(parent) = path.parent() {
-            std::process::Command::new("xdg-open")
-                .arg(parent)
-                .spawn()
-                .map_err(|e| e.to_string())?;
-        }
+    if let Some(parent) = path.parent() {
+        process::spawn("xdg-open", &[], &[&parent.to_string_lossy()])?;
     }
 
     Ok(())
@@ -274,83 +669,252 @@ fn reveal_in_finder(
 fn copy_file_to_clipboard(
     file_path: String,
     allowed_paths: State<AllowedPaths>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let path = std::path::PathBuf::from(&file_path);
     if !path.exists() {
-        return Err("File does not exist".to_string());
+        return Err(CommandError::FileNotFound);
     }
 
     // Validate path is within allowed directories
     if !allowed_paths.is_path_allowed(&path) {
-        return Err("Access denied: path is outside allowed directories".to_string());
+        return Err(CommandError::PathNotAllowed);
     }
 
-    #[cfg(target_os = "macos")]
-    {
-        use cocoa::appkit::NSPasteboard;
-        use cocoa::base::{id, nil};
-        use cocoa::foundation::{NSArray, NSString, NSURL};
-
-        unsafe {
-            let pasteboard: id = NSPasteboard::generalPasteboard(nil);
-            NSPasteboard::clearContents(pasteboard);
+    clipboard::set_files(&[&file_path])
+}
 
-            let file_url: id = NSURL::fileURLWithPath_(nil, NSString::alloc(nil).init_str(&file_path));
-            let array: id = NSArray::arrayWithObject(nil, file_url);
+#[tauri::command]
+fn copy_results_to_clipboard(
+    samples: Vec<Sample>,
+    format: ResultsFormat,
+    min_score: i32,
+    limit: Option<usize>,
+) -> Result<usize, String> {
+    let ranked: Vec<&Sample> = export::sorted_results(&samples)
+        .into_iter()
+        .filter(|s| export::passes_min_score(s, min_score))
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
 
-            NSPasteboard::writeObjects(pasteboard, array);
-        }
-    }
+    let text = export::format_results(&ranked, format)?;
+    clipboard::set_text(&text)?;
+    Ok(ranked.len())
+}
 
-    #[cfg(target_os = "windows")]
-    {
-        // Use PowerShell to copy file to clipboard
-        std::process::Command::new("powershell")
-            .args([
-                "-Command",
-                &format!("Set-Clipboard -Path '{}'", file_path.replace("'", "''"))
-            ])
-            .output()
-            .map_err(|e| e.to_string())?;
-    }
+/// Put every qualifying file (variations included) on the clipboard in one go;
+/// archive entries have no file of their own and are left out
+#[tauri::command]
+fn copy_results_files_to_clipboard(
+    samples: Vec<Sample>,
+    min_score: i32,
+    allowed_paths: State<AllowedPaths>,
+) -> Result<usize, String> {
+    let ranked: Vec<&Sample> = export::sorted_results(&samples)
+        .into_iter()
+        .filter(|s| export::passes_min_score(s, min_score))
+        .collect();
+    let members = variations::expand(&ranked);
 
-    #[cfg(target_os = "linux")]
-    {
-        // Use xclip to copy file URI to clipboard
-        let file_uri = format!("file://{}", file_path);
-        let mut child = std::process::Command::new("xclip")
-            .args(["-selection", "clipboard", "-t", "text/uri-list"])
-            .stdin(std::process::Stdio::piped())
-            .spawn()
-            .map_err(|e| format!("Failed to run xclip: {}. Make sure xclip is installed.", e))?;
-
-        if let Some(stdin) = child.stdin.as_mut() {
-            use std::io::Write;
-            stdin.write_all(file_uri.as_bytes()).map_err(|e| e.to_string())?;
+    let mut paths = Vec::new();
+    for sample in &members {
+        if archive::split_archive_path(&sample.path).is_some() {
+            continue;
         }
-
-        child.wait().map_err(|e| e.to_string())?;
+        if !allowed_paths.is_path_allowed(Path::new(&sample.path)) {
+            return Err(i18n::t("access-denied"));
+        }
+        paths.push(sample.path.as_str());
     }
 
-    Ok(())
+    clipboard::set_files(&paths)?;
+    Ok(paths.len())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            // Deep links on Windows/Linux arrive here via the `deep-link` feature,
+            // double-clicked save files as plain arguments
+            deep_link::handle_file_args(app, &argv);
+            deep_link::focus_main_window(app);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .manage(AllowedPaths::new())
+        .manage(deep_link::PendingDeepLinks::default())
+        .manage(watch::WatchState::default())
+        .manage(remote::RemoteServer::default())
+        .manage(share::ShareServer::default())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(
+            tauri_plugin_window_state::Builder::default()
+                .with_filename(windows::WINDOW_STATE_FILE)
+                .build(),
+        )
+        .setup(|app| {
+            let data_dir = app.path().app_data_dir()?;
+            audit::init(&data_dir);
+            app.manage(journal::Journal::load(&data_dir));
+            app.manage(tournaments::TournamentStore::load(&data_dir));
+            app.manage(presets::PresetStore::load(&data_dir));
+            let settings = settings::SettingsStore::load(&data_dir);
+            let log_level = settings.get().log_level;
+            i18n::set_language(settings.get().language.as_deref());
+            app.manage(logging::init(&data_dir, log_level.as_deref())?);
+            let queue = jobs::JobQueue::default();
+            queue.set_throttle(settings.get().analysis_throttle);
+            app.manage(queue);
+            app.manage(settings);
+            app.manage(leaderboard::Leaderboard::default());
+            app.manage(sessions::Sessions::default());
+            app.manage(power::PowerGuard::start());
+            media::init(app.handle());
+            tracing::info!(version = %app.package_info().version, "starting");
+            app.manage(plugins::PluginHost::new(&data_dir)?);
+
+            // The dialog plugin adds every picked file to the fs scope; that is how
+            // commands know a destination came from the user and not the webview
+            let handle = app.handle().clone();
+            app.fs_scope().listen(move |event| {
+                if let tauri::scope::fs::Event::PathAllowed(path) = event {
+                    handle.state::<AllowedPaths>().grant_file(path.clone());
+                }
+            });
+
+            #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+            app.deep_link().register_all()?;
+
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                deep_link::handle_urls(&handle, event.urls());
+            });
+
+            // The app may have been launched by a `vs1://` link
+            if let Some(urls) = app.deep_link().get_current()? {
+                deep_link::handle_urls(app.handle(), urls);
+            }
+
+            // ...or by opening a save file
+            let args: Vec<String> = std::env::args().collect();
+            deep_link::handle_file_args(app.handle(), &args);
+
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             scan_directory,
-            save_progress,
+            scan::estimate_scan,
+            thumbnails::get_waveform_peaks,
+            thumbnails::get_thumbnail_cache_usage,
+            thumbnails::clear_analysis_cache,
             load_progress,
+            sessions::open_session,
+            sessions::get_session,
+            sessions::change_session,
+            sessions::close_session,
+            sessions::save_session,
             export_results,
+            backup::list_backups,
+            backup::restore_backup,
+            session_archive::export_session_archive,
+            session_archive::import_session_archive,
+            audit::get_audit_log,
+            tournaments::list_tournaments,
+            tournaments::create_tournament,
+            tournaments::save_tournament,
+            tournaments::open_tournament,
+            tournaments::delete_tournament,
+            tournaments::duplicate_tournament,
+            tournaments::compare_tournaments,
+            deep_link::take_pending_deep_links,
+            remote::start_remote_server,
+            remote::stop_remote_server,
+            remote::set_remote_pair,
+            remote::get_current_pair,
+            remote::submit_local_vote,
+            remote::set_vote_quorum,
+            remote::get_collab_session,
+            settings::get_settings,
+            settings::save_settings,
+            settings::set_read_only,
+            i18n::list_languages,
+            hooks::fire_hooks,
+            logging::set_log_level,
+            logging::get_recent_logs,
+            diagnostics::generate_diagnostics_bundle,
+            jobs::get_jobs,
+            analysis::analyze_samples,
+            analysis::get_onset,
+            describe::describe_sample,
+            loops::get_loop_slices,
+            spectrogram::generate_spectrogram,
+            search::search_samples,
+            statistics::get_statistics,
+            dataset::export_parquet,
+            calibration::get_calibration,
+            duplicates::find_duplicates,
+            presets::list_presets,
+            presets::save_preset,
+            presets::delete_preset,
+            presets::apply_preset,
+            presets::save_template,
+            presets::new_from_template,
+            queue::peek_upcoming,
+            power::set_playback_active,
+            media::set_now_playing,
+            windows::reset_window_state,
+            windows::open_mini_window,
+            windows::open_leaderboard_window,
+            leaderboard::get_standings,
+            plugins::list_plugins,
+            export_scripts::list_export_scripts,
+            export_scripts::export_with_script,
             get_audio_file_url,
+            resolve_playback_path,
+            materialize_file,
             reveal_in_finder,
+            reveal::reveal_results,
             copy_file_to_clipboard,
+            copy_results_to_clipboard,
+            copy_results_files_to_clipboard,
+            export::preflight_export,
+            export::export_ndjson,
+            copy_export::export_copy,
+            player_export::export_to_player,
+            ableton_export::ableton_user_library,
+            ableton_export::export_to_ableton,
+            reel::render_reel,
+            pdf_report::export_pdf_report,
+            summary_image::render_summary_image,
+            share::share_results_link,
+            share::get_results_share,
+            share::stop_results_share,
+            save_merge::find_conflicted_saves,
+            save_merge::resolve_conflicted_saves,
+            journal::get_file_journal,
+            journal::undo_file_operation,
+            journal::undo_last_batch,
+            journal::discard_samples,
+            import::import_ratings,
+            import::import_music_library,
+            import::import_tag_ratings,
+            manifest::import_manifest,
+            watch::start_watch,
+            watch::stop_watch,
+            watch::undo_enrollment,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            // macOS delivers double-clicked save files as an event instead of arguments
+            #[cfg(target_os = "macos")]
+            if let tauri::RunEvent::Opened { urls } = event {
+                deep_link::handle_urls(app, urls);
+            }
+
+            #[cfg(not(target_os = "macos"))]
+            let _ = (app, event);
+        });
 }
@@ -0,0 +1,147 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::State;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, reload, Registry};
+
+use crate::i18n;
+use crate::settings::SettingsStore;
+
+/// Lines kept in memory for the diagnostics panel
+const RECENT_LOG_LINES: usize = 2000;
+
+/// In-memory tail of the log, fed by its own fmt layer
+#[derive(Clone, Default)]
+pub struct RecentLogs(Arc<Mutex<VecDeque<String>>>);
+
+impl RecentLogs {
+    pub fn tail(&self, limit: usize) -> Vec<String> {
+        let lines = self.0.lock().unwrap();
+        lines
+            .iter()
+            .skip(lines.len().saturating_sub(limit))
+            .cloned()
+            .collect()
+    }
+}
+
+impl io::Write for RecentLogs {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // The fmt layer writes each event with a single call
+        let text = String::from_utf8_lossy(buf);
+        let mut lines = self.0.lock().unwrap();
+        for line in text.lines().filter(|l| !l.is_empty()) {
+            if lines.len() == RECENT_LOG_LINES {
+                lines.pop_front();
+            }
+            lines.push_back(line.to_string());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for RecentLogs {
+    type Writer = RecentLogs;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+pub struct Logging {
+    pub dir: PathBuf,
+    pub recent: RecentLogs,
+    level: reload::Handle<LevelFilter, Registry>,
+    // Dropping the guard stops the background file writer
+    _guard: WorkerGuard,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogInfo {
+    pub level: String,
+    pub directory: String,
+    pub lines: Vec<String>,
+}
+
+pub fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    level
+        .parse::<LevelFilter>()
+        .map_err(|_| i18n::t_args("unknown-log-level", [("level", level.into())]))
+}
+
+/// Install the global subscriber: daily-rolling file in `<data_dir>/logs`, stderr, and the in-memory tail
+pub fn init(data_dir: &Path, level: Option<&str>) -> Result<Logging, String> {
+    let dir = data_dir.join("logs");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+
+    let level = level
+        .and_then(|l| parse_level(l).ok())
+        .unwrap_or(LevelFilter::INFO);
+    let (filter, handle) = reload::Layer::new(level);
+
+    let (file_writer, guard) =
+        tracing_appender::non_blocking(tracing_appender::rolling::daily(&dir, "vs1.log"));
+    let recent = RecentLogs::default();
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(file_writer).with_ansi(false))
+        .with(fmt::layer().with_writer(recent.clone()).with_ansi(false))
+        .with(fmt::layer().with_writer(io::stderr))
+        .try_init()
+        .map_err(|e| e.to_string())?;
+
+    Ok(Logging {
+        dir,
+        recent,
+        level: handle,
+        _guard: guard,
+    })
+}
+
+impl Logging {
+    pub fn level(&self) -> String {
+        self.level
+            .with_current(|level| level.to_string())
+            .unwrap_or_default()
+    }
+}
+
+#[tauri::command]
+pub fn set_log_level(
+    level: &str,
+    logging: State<Logging>,
+    settings: State<SettingsStore>,
+) -> Result<(), String> {
+    let filter = parse_level(level)?;
+    logging
+        .level
+        .modify(|current| *current = filter)
+        .map_err(|e| e.to_string())?;
+
+    let mut updated = settings.get();
+    updated.log_level = Some(filter.to_string());
+    settings.set(updated)?;
+
+    tracing::info!(level = %filter, "log level changed");
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_recent_logs(limit: Option<usize>, logging: State<Logging>) -> LogInfo {
+    LogInfo {
+        level: logging.level(),
+        directory: logging.dir.to_string_lossy().to_string(),
+        lines: logging.recent.tail(limit.unwrap_or(500)),
+    }
+}
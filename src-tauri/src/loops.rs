@@ -0,0 +1,88 @@
+use serde::Serialize;
+use std::path::Path;
+use tauri::State;
+
+use crate::analysis::{decode, detect_tempo, mono_mix, onset_secs};
+use crate::{i18n, AllowedPaths};
+
+const DEFAULT_BARS: u32 = 2;
+const BEATS_PER_BAR: f32 = 4.0;
+/// Slice length when neither file has a detectable tempo
+const FALLBACK_SLICE_SECS: f32 = 4.0;
+
+/// Region of a file to loop, starting at its first downbeat (the onset)
+#[derive(Debug, Serialize, Clone)]
+pub struct LoopSlice {
+    pub start_secs: f32,
+    pub end_secs: f32,
+    pub bpm: Option<f32>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LoopSlices {
+    pub a: LoopSlice,
+    pub b: LoopSlice,
+}
+
+struct Measured {
+    onset_secs: f32,
+    duration_secs: f32,
+    bpm: Option<f32>,
+}
+
+fn measure(path: &str) -> Result<Measured, String> {
+    let audio = decode(path)?;
+    let mono = mono_mix(&audio);
+    Ok(Measured {
+        onset_secs: onset_secs(&audio),
+        duration_secs: audio.frames() as f32 / audio.sample_rate as f32,
+        bpm: detect_tempo(&mono, audio.sample_rate),
+    })
+}
+
+fn slice(measured: &Measured, length_secs: f32) -> LoopSlice {
+    let start_secs = measured.onset_secs.min(measured.duration_secs);
+    LoopSlice {
+        start_secs,
+        end_secs: (start_secs + length_secs).min(measured.duration_secs),
+        bpm: measured.bpm,
+    }
+}
+
+/// The first `bars` bars of each file at its own tempo. A file without a
+/// detectable tempo borrows the other's, so both cover the same musical length.
+fn matching_slices(a: &Measured, b: &Measured, bars: u32) -> LoopSlices {
+    let length = |own: Option<f32>, other: Option<f32>| {
+        own.or(other)
+            .map(|bpm| bars as f32 * BEATS_PER_BAR * 60.0 / bpm)
+            .unwrap_or(FALLBACK_SLICE_SECS)
+    };
+
+    LoopSlices {
+        a: slice(a, length(a.bpm, b.bpm)),
+        b: slice(b, length(b.bpm, a.bpm)),
+    }
+}
+
+/// Matching loop regions for a pair of long files, for synchronized A/B looping
+#[tauri::command]
+pub async fn get_loop_slices(
+    path_a: String,
+    path_b: String,
+    bars: Option<u32>,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<LoopSlices, String> {
+    for path in [&path_a, &path_b] {
+        if !allowed_paths.is_path_allowed(Path::new(path)) {
+            return Err(i18n::t("access-denied"));
+        }
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let a = measure(&path_a)?;
+        let b = measure(&path_b)?;
+        Ok(matching_slices(&a, &b, bars.unwrap_or(DEFAULT_BARS).max(1)))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
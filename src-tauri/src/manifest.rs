@@ -0,0 +1,270 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+use crate::archive::{self, ARCHIVE_SEPARATOR};
+use crate::copy_export::hash_source;
+use crate::journal::now_secs;
+use crate::scan::{self, ScanReport, SkipReason};
+use crate::{audit, i18n, AllowedPaths, Sample};
+
+/// Appended to the export's own file name: `winners.zip.manifest.json`
+pub const MANIFEST_SUFFIX: &str = ".manifest.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportKind {
+    /// Copies into a folder; older copy manifests have no kind and are these
+    #[default]
+    Copy,
+    /// Session archive zip
+    Archive,
+    /// List of sample paths
+    Playlist,
+    /// Standings as JSON Lines
+    Ndjson,
+}
+
+/// One exported file and where it came from
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ManifestEntry {
+    pub source: String,
+    /// Relative to the export directory for copies, the entry name for archives and the
+    /// listed path for playlists and JSON Lines
+    pub destination: String,
+    /// 1-based standing at export time, 0 for eliminated samples
+    pub rank: usize,
+    pub score: i32,
+    pub size: u64,
+    /// Of the source contents; empty when the source could not be read
+    pub sha256: String,
+    /// Set once the copy was re-read and its hash matched the source
+    pub verified: bool,
+    /// The destination this file would have had if another export had not taken it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<String>,
+    /// When the file was exported, in seconds since the epoch
+    #[serde(default)]
+    pub timestamp: u64,
+}
+
+/// Provenance of an export, so downstream tools and later imports can trace every
+/// file back to its source and standing
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ExportManifest {
+    pub kind: ExportKind,
+    pub created: u64,
+    pub files: Vec<ManifestEntry>,
+}
+
+impl ExportManifest {
+    pub fn new(kind: ExportKind) -> Self {
+        Self {
+            kind,
+            created: now_secs(),
+            files: Vec::new(),
+        }
+    }
+}
+
+/// Where the manifest of a single-file export goes
+pub fn manifest_path(export: &Path) -> PathBuf {
+    let mut name = export.as_os_str().to_os_string();
+    name.push(MANIFEST_SUFFIX);
+    PathBuf::from(name)
+}
+
+/// A missing or unreadable manifest reads as empty
+pub fn load(path: &Path) -> ExportManifest {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn write(path: &Path, manifest: &ExportManifest) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    fs::write(path, json).map_err(|e| e.to_string())
+}
+
+/// Entry for a sample exported by reference rather than copied, hashing its source
+pub fn entry_for(sample: &Sample, destination: String, rank: usize) -> ManifestEntry {
+    let (size, sha256) = hash_source(&sample.path)
+        .inspect_err(|e| tracing::warn!(path = %sample.path, error = %e, "could not hash"))
+        .unwrap_or_default();
+    ManifestEntry {
+        source: sample.path.clone(),
+        destination,
+        rank,
+        score: sample.score,
+        size,
+        sha256,
+        verified: false,
+        renamed_from: None,
+        timestamp: now_secs(),
+    }
+}
+
+/// Write the manifest of a single-file export (playlist, JSON Lines) next to it, listing
+/// `samples` in rank order
+pub fn write_beside(export: &Path, kind: ExportKind, samples: &[&Sample]) -> Result<(), String> {
+    let mut manifest = ExportManifest::new(kind);
+    manifest.files = samples
+        .iter()
+        .enumerate()
+        .map(|(index, sample)| entry_for(sample, sample.path.clone(), index + 1))
+        .collect();
+
+    let path = manifest_path(export);
+    write(&path, &manifest)?;
+    audit::record_write(&path, None);
+    Ok(())
+}
+
+/// A pool rebuilt from a manifest, shaped like a scan so it can start a tournament
+#[derive(Debug, Serialize, Clone)]
+pub struct ManifestImport {
+    pub samples: Vec<Sample>,
+    /// Folder the export went to, used as the session's source directory
+    pub directory: String,
+    /// `files` counts manifest entries and `unreadable` those found nowhere
+    pub report: ScanReport,
+}
+
+/// Plain file or archive entry that is still there
+fn still_exists(path: &str) -> bool {
+    match archive::split_archive_path(path) {
+        Some((archive_path, _)) => Path::new(archive_path).is_file(),
+        None => Path::new(path).is_file(),
+    }
+}
+
+/// Where the exported copy of `entry` lives, for exports that made one. Destinations
+/// that would leave the export (absolute, `..`) are ignored.
+fn exported_copy(manifest_file: &Path, kind: ExportKind, entry: &ManifestEntry) -> Option<String> {
+    let destination = Path::new(&entry.destination);
+    if destination.as_os_str().is_empty()
+        || !destination
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return None;
+    }
+    let directory = manifest_file.parent()?;
+    match kind {
+        ExportKind::Copy => Some(
+            directory
+                .join(&entry.destination)
+                .to_string_lossy()
+                .to_string(),
+        ),
+        ExportKind::Archive => {
+            let name = manifest_file
+                .file_name()?
+                .to_str()?
+                .strip_suffix(MANIFEST_SUFFIX)?;
+            let zip = directory.join(name);
+            Some(format!(
+                "{}{}{}",
+                zip.display(),
+                ARCHIVE_SEPARATOR,
+                entry.destination
+            ))
+        }
+        ExportKind::Playlist | ExportKind::Ndjson => None,
+    }
+}
+
+/// Rebuild a sample pool from a manifest, best-ranked first. The exported copy is
+/// used where there is one, otherwise the original source; entries found in neither
+/// place are reported as unreadable. Sources are only used where `allowed` says the
+/// user already gave access to them. With `seed_scores` samples start from the scores
+/// they were exported with.
+pub fn import(
+    manifest_file: &Path,
+    seed_scores: bool,
+    allowed: impl Fn(&str) -> bool,
+) -> Result<ManifestImport, String> {
+    let content = fs::read_to_string(manifest_file).map_err(|e| e.to_string())?;
+    let mut manifest: ExportManifest = serde_json::from_str(&content)
+        .map_err(|e| i18n::t_args("not-export-manifest", [("error", e.to_string().into())]))?;
+    manifest
+        .files
+        .sort_by_key(|entry| (entry.rank == 0, entry.rank));
+
+    let mut report = ScanReport {
+        files: manifest.files.len() as u64,
+        ..Default::default()
+    };
+    let mut seen = HashSet::new();
+    let mut samples = Vec::new();
+    for entry in &manifest.files {
+        let found = exported_copy(manifest_file, manifest.kind, entry)
+            .into_iter()
+            .chain(Some(entry.source.clone()).filter(|source| allowed(source)))
+            .find(|path| still_exists(path));
+        let Some(path) = found else {
+            report.skip(Path::new(&entry.source), SkipReason::Unreadable);
+            continue;
+        };
+        if !seen.insert(path.clone()) {
+            continue;
+        }
+
+        let Some(mut sample) = scan::sample_from_path(Path::new(&path)) else {
+            report.non_audio += 1;
+            continue;
+        };
+        if seed_scores {
+            sample.score = entry.score;
+        }
+        samples.push(sample);
+    }
+
+    let directory = manifest_file
+        .parent()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(ManifestImport {
+        samples,
+        directory,
+        report,
+    })
+}
+
+/// Start a new pool from a manifest written by an earlier export
+#[tauri::command]
+pub async fn import_manifest(
+    path: String,
+    seed_scores: Option<bool>,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<ManifestImport, String> {
+    let manifest_file = PathBuf::from(&path);
+    allowed_paths.check_file(&manifest_file)?;
+    if !manifest_file.is_file() {
+        return Err(i18n::t("file-not-found"));
+    }
+
+    let seed_scores = seed_scores.unwrap_or(false);
+    let export_folder = manifest_file.parent().map(Path::to_path_buf);
+    let imported = tauri::async_runtime::spawn_blocking(move || {
+        let allowed_paths = app.state::<AllowedPaths>();
+        import(&manifest_file, seed_scores, |source| {
+            allowed_paths.within_roots(Path::new(source))
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    // The manifest was picked by the user, like a save file, so the export it sits
+    // in becomes playable; sources elsewhere keep needing their own grant
+    if let Some(folder) = export_folder {
+        allowed_paths.add_allowed_path(folder);
+    }
+
+    Ok(imported)
+}
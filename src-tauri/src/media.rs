@@ -0,0 +1,116 @@
+use serde::Serialize;
+use souvlaki::{MediaControlEvent, MediaControls, MediaMetadata, MediaPlayback, PlatformConfig};
+use std::cell::RefCell;
+use tauri::{AppHandle, Emitter};
+
+// The OS media APIs must be driven from the main (event loop) thread
+thread_local! {
+    static CONTROLS: RefCell<Option<MediaControls>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaAction {
+    /// Play/pause keys replay the side that was heard last
+    Replay,
+    /// Next/previous keys switch to the other side
+    Switch,
+    Stop,
+}
+
+fn action_for(event: MediaControlEvent) -> Option<MediaAction> {
+    match event {
+        MediaControlEvent::Play | MediaControlEvent::Pause | MediaControlEvent::Toggle => {
+            Some(MediaAction::Replay)
+        }
+        MediaControlEvent::Next | MediaControlEvent::Previous => Some(MediaAction::Switch),
+        MediaControlEvent::Stop => Some(MediaAction::Stop),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn window_handle(app: &AppHandle) -> Option<*mut std::ffi::c_void> {
+    use tauri::Manager;
+
+    let window = app.get_webview_window("main")?;
+    window
+        .hwnd()
+        .ok()
+        .map(|hwnd| hwnd.0 as *mut std::ffi::c_void)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn window_handle(_app: &AppHandle) -> Option<*mut std::ffi::c_void> {
+    None
+}
+
+/// Register with the system media session (Now Playing, SMTC or MPRIS); call from setup
+pub fn init(app: &AppHandle) {
+    let config = PlatformConfig {
+        dbus_name: "vs1",
+        display_name: "VS-1",
+        hwnd: window_handle(app),
+    };
+
+    let mut controls = match MediaControls::new(config) {
+        Ok(controls) => controls,
+        Err(e) => {
+            tracing::warn!(error = ?e, "media controls unavailable");
+            return;
+        }
+    };
+
+    let handle = app.clone();
+    let attached = controls.attach(move |event| {
+        if let Some(action) = action_for(event) {
+            let _ = handle.emit("media://action", action);
+        }
+    });
+    if let Err(e) = attached {
+        tracing::warn!(error = ?e, "failed to attach media key handler");
+        return;
+    }
+
+    CONTROLS.with(|cell| *cell.borrow_mut() = Some(controls));
+}
+
+fn with_controls(app: &AppHandle, f: impl FnOnce(&mut MediaControls) + Send + 'static) {
+    let _ = app.run_on_main_thread(move || {
+        CONTROLS.with(|cell| {
+            if let Some(controls) = cell.borrow_mut().as_mut() {
+                f(controls);
+            }
+        })
+    });
+}
+
+pub fn set_playing(app: &AppHandle, playing: bool) {
+    with_controls(app, move |controls| {
+        let playback = if playing {
+            MediaPlayback::Playing { progress: None }
+        } else {
+            MediaPlayback::Paused { progress: None }
+        };
+        let _ = controls.set_playback(playback);
+    });
+}
+
+/// Show the current pair in the system media UI; `None` clears it
+#[tauri::command]
+pub fn set_now_playing(title: Option<String>, subtitle: Option<String>, app: AppHandle) {
+    with_controls(&app, move |controls| {
+        let result = match &title {
+            Some(title) => controls.set_metadata(MediaMetadata {
+                title: Some(title),
+                artist: subtitle.as_deref(),
+                album: Some("VS-1"),
+                ..Default::default()
+            }),
+            None => controls.set_playback(MediaPlayback::Stopped),
+        };
+        if let Err(e) = result {
+            tracing::debug!(error = ?e, "failed to update now playing");
+        }
+    });
+}
@@ -0,0 +1,23 @@
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+/// Jobs quicker than this finish before anyone switches away, so they never notify
+const MIN_JOB_DURATION: Duration = Duration::from_secs(5);
+
+fn window_focused(app: &AppHandle) -> bool {
+    app.get_webview_window("main")
+        .and_then(|window| window.is_focused().ok())
+        .unwrap_or(false)
+}
+
+/// Show a native notification for a finished long-running job, unless the user is looking at the app
+pub fn job_finished(app: &AppHandle, started: Instant, title: &str, body: &str) {
+    if started.elapsed() < MIN_JOB_DURATION || window_focused(app) {
+        return;
+    }
+
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        tracing::warn!(error = %e, "failed to show notification");
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{archive, Sample};
+
+/// Marker file that makes its folder a pack root; its first line, if any, names the pack
+pub const PACK_MARKER: &str = ".vs1-pack";
+
+/// Works out which pack a sample came from. Marked folders win; otherwise the
+/// first folder below the source directory is the pack.
+pub struct PackDetector<'a> {
+    root: &'a Path,
+    /// Marker lookups per folder: `None` = unmarked, `Some(name)` = pack root
+    markers: HashMap<PathBuf, Option<String>>,
+}
+
+fn folder_name(dir: &Path) -> String {
+    dir.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+impl<'a> PackDetector<'a> {
+    pub fn new(root: &'a Path) -> Self {
+        Self {
+            root,
+            markers: HashMap::new(),
+        }
+    }
+
+    fn marked_pack(&mut self, dir: &Path) -> Option<String> {
+        self.markers
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                let content = fs::read_to_string(dir.join(PACK_MARKER)).ok()?;
+                let name = content
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .map(str::to_string);
+                Some(name.unwrap_or_else(|| folder_name(dir)))
+            })
+            .clone()
+    }
+
+    pub fn detect(&mut self, sample_path: &str) -> Option<String> {
+        // Archive entries belong to the pack of the archive file
+        let file = match archive::split_archive_path(sample_path) {
+            Some((archive_path, _)) => Path::new(archive_path),
+            None => Path::new(sample_path),
+        };
+        let relative = file.strip_prefix(self.root).ok()?;
+
+        let mut dir = file.parent();
+        while let Some(current) = dir.filter(|d| d.starts_with(self.root)) {
+            if let Some(name) = self.marked_pack(current) {
+                return Some(name);
+            }
+            dir = current.parent();
+        }
+
+        let mut components = relative.components();
+        let first = components.next()?;
+        if components.next().is_some() {
+            return Some(first.as_os_str().to_string_lossy().to_string());
+        }
+
+        // A loose file in the source directory has no pack, but a loose archive is one
+        archive::is_archive(file).then(|| {
+            file.file_stem()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// Record pack provenance for every sample found under `root`
+pub fn assign(root: &Path, samples: &mut [Sample]) {
+    let mut detector = PackDetector::new(root);
+    for sample in samples {
+        sample.pack = detector.detect(&sample.path);
+    }
+}
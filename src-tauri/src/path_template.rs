@@ -0,0 +1,171 @@
+use std::path::{Component, Path};
+
+use crate::{describe, i18n, Sample};
+
+/// Placeholders understood by export path templates
+const FIELDS: &[&str] = &[
+    "rank",
+    "score",
+    "filename",
+    "stem",
+    "ext",
+    "category",
+    "pack",
+    "instrument",
+    "note",
+];
+
+/// One parsed `{name}` or `{name:0N}`; `width` zero-pads numbers
+struct Placeholder<'a> {
+    name: &'a str,
+    width: usize,
+}
+
+enum Part<'a> {
+    Text(&'a str),
+    Field(Placeholder<'a>),
+}
+
+/// Parsed export layout such as `{category}/{rank:02}_{filename}`; `/` (or `\`)
+/// separates subfolders
+pub struct PathTemplate<'a> {
+    parts: Vec<Part<'a>>,
+}
+
+/// Values for one sample's placeholders
+pub struct TemplateValues<'a> {
+    pub sample: &'a Sample,
+    pub rank: usize,
+    /// Filename as exported (note-tagged when requested)
+    pub filename: &'a str,
+    pub note: Option<&'a str>,
+}
+
+impl<'a> PathTemplate<'a> {
+    pub fn parse(template: &'a str) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+        while let Some(open) = rest.find('{') {
+            if open > 0 {
+                parts.push(Part::Text(&rest[..open]));
+            }
+            let close = rest[open..].find('}').ok_or_else(|| {
+                i18n::t_args("template-unclosed", [("template", template.into())])
+            })?;
+            let inner = &rest[open + 1..open + close];
+            let (name, spec) = inner.split_once(':').unwrap_or((inner, ""));
+            if !FIELDS.contains(&name) {
+                return Err(i18n::t_args(
+                    "template-unknown-placeholder",
+                    [("name", name.into()), ("fields", FIELDS.join(", ").into())],
+                ));
+            }
+            let width = match spec {
+                "" => 0,
+                spec => spec
+                    .strip_prefix('0')
+                    .and_then(|digits| digits.parse().ok())
+                    .ok_or_else(|| {
+                        i18n::t_args(
+                            "template-invalid-format",
+                            [("format", spec.into()), ("name", name.into())],
+                        )
+                    })?,
+            };
+            parts.push(Part::Field(Placeholder { name, width }));
+            rest = &rest[open + close + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Text(rest));
+        }
+
+        if escapes_folder(template, &parts) {
+            return Err(i18n::t("template-outside-folder"));
+        }
+        Ok(Self { parts })
+    }
+
+    /// Relative destination path for one sample
+    pub fn render(&self, values: &TemplateValues) -> String {
+        let sample = values.sample;
+        let path = Path::new(values.filename);
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(values.filename);
+        let ext = path
+            .extension()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+
+        let mut rendered = String::new();
+        for part in &self.parts {
+            let field = match part {
+                Part::Text(text) => {
+                    rendered.push_str(text);
+                    continue;
+                }
+                Part::Field(field) => field,
+            };
+            let value = match field.name {
+                "rank" => format!("{:0width$}", values.rank, width = field.width),
+                "score" => format!("{:0width$}", sample.score, width = field.width),
+                "filename" => values.filename.to_string(),
+                "stem" => stem.to_string(),
+                "ext" => ext.to_string(),
+                "category" => sample
+                    .analysis
+                    .as_ref()
+                    .map_or("uncategorized", |a| describe::category(a).id())
+                    .to_string(),
+                "pack" => sample.pack.clone().unwrap_or_else(|| "loose".to_string()),
+                "instrument" => sample.instrument.clone().unwrap_or_default(),
+                "note" => values.note.unwrap_or_default().to_string(),
+                _ => unreachable!("placeholders are checked when parsing"),
+            };
+            rendered.push_str(&sanitize(&value));
+        }
+
+        // Empty values must not leave empty folder names behind, and a leading `\` must
+        // not root the path on Windows
+        rendered
+            .split(['/', '\\'])
+            .filter(|component| !component.is_empty())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Whether a template could lead out of the export folder on any platform: rooted
+/// (`/x`, `\x`), with a drive or UNC prefix (`C:\`, `\\server`) or going up with `..`.
+/// Placeholder values are sanitized, so only the literal text matters.
+fn escapes_folder(template: &str, parts: &[Part]) -> bool {
+    let path = Path::new(template);
+    path.is_absolute()
+        || path.has_root()
+        || path
+            .components()
+            .any(|c| matches!(c, Component::Prefix(_) | Component::ParentDir))
+        || template.starts_with(['/', '\\'])
+        || template.split(['/', '\\']).any(|c| c == "..")
+        // Drive prefixes need a colon, which no file name may contain anyway
+        || parts
+            .iter()
+            .any(|part| matches!(part, Part::Text(text) if text.contains(':')))
+}
+
+/// Keep substituted values from adding folders or escaping the export directory
+pub fn sanitize(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect();
+    if cleaned == ".." {
+        "_".to_string()
+    } else {
+        cleaned
+    }
+}
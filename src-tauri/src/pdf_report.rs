@@ -0,0 +1,351 @@
+use printpdf::{
+    BuiltinFont, Color, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference,
+    PdfLayerReference, Point, Rect, Rgb,
+};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+use tauri::State;
+
+use crate::export::{sorted_results, win_rate};
+use crate::sessions::Sessions;
+use crate::statistics;
+use crate::{audit, i18n, AllowedPaths, TournamentState};
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 18.0;
+const ROW_HEIGHT: f32 = 5.5;
+const BODY_SIZE: f32 = 9.0;
+const HEADING_SIZE: f32 = 14.0;
+const CHART_HEIGHT: f32 = 45.0;
+/// Filenames longer than this are shortened to keep the standings in their column
+const MAX_NAME_CHARS: usize = 48;
+
+const ADVANCED: (f32, f32, f32) = (0.22, 0.6, 0.35);
+const ELIMINATED: (f32, f32, f32) = (0.78, 0.28, 0.25);
+const NEUTRAL: (f32, f32, f32) = (0.3, 0.45, 0.7);
+
+/// Writes top to bottom, starting a new A4 page whenever the next block won't fit
+struct Writer {
+    doc: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    /// Distance from the bottom of the page to the next line's baseline
+    y: f32,
+}
+
+impl Writer {
+    fn new(title: &str) -> Result<Self, String> {
+        let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Report");
+        let regular = doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(|e| e.to_string())?;
+        let bold = doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(|e| e.to_string())?;
+        let layer = doc.get_page(page).get_layer(layer);
+        Ok(Self {
+            doc,
+            layer,
+            regular,
+            bold,
+            y: PAGE_HEIGHT - MARGIN,
+        })
+    }
+
+    fn ensure_room(&mut self, height: f32) {
+        if self.y - height >= MARGIN {
+            return;
+        }
+        let (page, layer) = self.doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Report");
+        self.layer = self.doc.get_page(page).get_layer(layer);
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    fn text(&self, text: &str, size: f32, x: f32, bold: bool) {
+        let font = if bold { &self.bold } else { &self.regular };
+        self.layer
+            .use_text(printable(text), size, Mm(x), Mm(self.y), font);
+    }
+
+    fn heading(&mut self, text: &str) {
+        self.ensure_room(HEADING_SIZE + ROW_HEIGHT * 3.0);
+        self.y -= 4.0;
+        self.text(text, HEADING_SIZE, MARGIN, true);
+        self.y -= ROW_HEIGHT * 1.6;
+    }
+
+    fn line(&mut self, text: &str) {
+        self.ensure_room(ROW_HEIGHT);
+        self.text(text, BODY_SIZE, MARGIN, false);
+        self.y -= ROW_HEIGHT;
+    }
+
+    /// One table row; `columns` holds each cell's left edge
+    fn row(&mut self, cells: &[String], columns: &[f32], bold: bool) {
+        self.ensure_room(ROW_HEIGHT);
+        for (cell, x) in cells.iter().zip(columns) {
+            self.text(cell, BODY_SIZE, MARGIN + x, bold);
+        }
+        self.y -= ROW_HEIGHT;
+    }
+
+    fn fill(&self, color: (f32, f32, f32), left: f32, bottom: f32, right: f32, top: f32) {
+        let (r, g, b) = color;
+        self.layer
+            .set_fill_color(Color::Rgb(Rgb::new(r, g, b, None)));
+        self.layer
+            .add_rect(Rect::new(Mm(left), Mm(bottom), Mm(right), Mm(top)));
+    }
+
+    /// Bar chart with one group of bars per label, scaled to the largest value
+    fn bar_chart(&mut self, groups: &[(String, Vec<usize>)], colors: &[(f32, f32, f32)]) {
+        self.ensure_room(CHART_HEIGHT + ROW_HEIGHT * 2.0);
+        let bottom = self.y - CHART_HEIGHT;
+        let width = PAGE_WIDTH - 2.0 * MARGIN;
+        let max = groups
+            .iter()
+            .flat_map(|(_, values)| values.iter().copied())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let slot = width / groups.len().max(1) as f32;
+        let bars = colors.len().max(1) as f32;
+        let bar_width = (slot * 0.8 / bars).min(12.0);
+
+        let left = |index: usize| MARGIN + slot * index as f32 + slot * 0.1;
+        for (index, (_, values)) in groups.iter().enumerate() {
+            for (bar, (&value, &color)) in values.iter().zip(colors).enumerate() {
+                let height = CHART_HEIGHT * value as f32 / max as f32;
+                let x = left(index) + bar_width * bar as f32;
+                self.fill(color, x, bottom, x + bar_width * 0.9, bottom + height);
+            }
+        }
+
+        // Text takes the fill colour too
+        self.layer
+            .set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+        self.y = bottom - ROW_HEIGHT;
+        for (index, (label, _)) in groups.iter().enumerate() {
+            self.text(label, BODY_SIZE - 1.0, left(index), false);
+        }
+        self.y = bottom + CHART_HEIGHT;
+
+        let axis = Line {
+            points: vec![
+                (Point::new(Mm(MARGIN), Mm(bottom)), false),
+                (Point::new(Mm(MARGIN + width), Mm(bottom)), false),
+            ],
+            is_closed: false,
+        };
+        self.layer.add_line(axis);
+        self.text(
+            &max.to_string(),
+            BODY_SIZE - 1.0,
+            MARGIN + width - 8.0,
+            false,
+        );
+        self.y = bottom - ROW_HEIGHT * 2.0;
+    }
+
+    /// Colour swatch and label for each series of a chart
+    fn legend(&mut self, series: &[(String, (f32, f32, f32))]) {
+        self.ensure_room(ROW_HEIGHT);
+        let mut x = MARGIN;
+        for (label, color) in series {
+            self.fill(*color, x, self.y, x + 3.0, self.y + 3.0);
+            x += 5.0;
+            self.layer
+                .set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+            self.text(label, BODY_SIZE, x, false);
+            x += 40.0;
+        }
+        self.y -= ROW_HEIGHT;
+    }
+
+    fn save(self, path: &Path) -> Result<(), String> {
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        self.doc
+            .save(&mut BufWriter::new(file))
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// The built-in PDF fonts only cover Latin-1; anything else shows as `?`
+fn printable(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if (c as u32) < 0x100 && !c.is_control() {
+                c
+            } else {
+                '?'
+            }
+        })
+        .collect()
+}
+
+fn shortened(name: &str) -> String {
+    if name.chars().count() <= MAX_NAME_CHARS {
+        return name.to_string();
+    }
+    let kept: String = name.chars().take(MAX_NAME_CHARS - 3).collect();
+    format!("{}...", kept)
+}
+
+fn headings(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|id| i18n::t(id)).collect()
+}
+
+fn standings(writer: &mut Writer, state: &TournamentState) {
+    writer.heading(&i18n::t("report-standings"));
+    let columns = [0.0, 12.0, 104.0, 122.0, 146.0];
+    writer.row(
+        &headings(&[
+            "heading-rank",
+            "heading-filename",
+            "heading-score",
+            "heading-comparisons",
+            "heading-win-rate",
+        ]),
+        &columns,
+        true,
+    );
+    for (index, sample) in sorted_results(&state.samples).iter().enumerate() {
+        writer.row(
+            &[
+                (index + 1).to_string(),
+                shortened(&sample.filename),
+                sample.score.to_string(),
+                sample.comparisons.to_string(),
+                format!("{:.1}%", win_rate(sample) * 100.0),
+            ],
+            &columns,
+            false,
+        );
+    }
+}
+
+fn rounds(writer: &mut Writer, state: &TournamentState) {
+    let mut comparisons: BTreeMap<i32, usize> = BTreeMap::new();
+    for record in &state.history {
+        *comparisons.entry(record.round).or_default() += 1;
+    }
+    if comparisons.is_empty() && state.round_summaries.is_empty() {
+        return;
+    }
+    let round_label = |round: i32| format!("{} {}", i18n::t("heading-round"), round);
+
+    writer.heading(&i18n::t("report-comparisons-per-round"));
+    let groups: Vec<(String, Vec<usize>)> = comparisons
+        .iter()
+        .map(|(&round, &count)| (round_label(round), vec![count]))
+        .collect();
+    writer.bar_chart(&groups, &[NEUTRAL]);
+
+    if state.round_summaries.is_empty() {
+        return;
+    }
+    writer.heading(&i18n::t("report-advanced-per-round"));
+    let groups: Vec<(String, Vec<usize>)> = state
+        .round_summaries
+        .iter()
+        .map(|summary| {
+            let counts = vec![summary.advancers.len(), summary.eliminated.len()];
+            (round_label(summary.round), counts)
+        })
+        .collect();
+    writer.bar_chart(&groups, &[ADVANCED, ELIMINATED]);
+    writer.legend(&[
+        (i18n::t("heading-advanced"), ADVANCED),
+        (i18n::t("heading-eliminated"), ELIMINATED),
+    ]);
+}
+
+fn packs(writer: &mut Writer, state: &TournamentState) {
+    let stats = statistics::statistics(&state.samples, &state.history, &state.votes);
+    if stats.packs.len() < 2 {
+        return;
+    }
+    writer.heading(&i18n::t("report-packs"));
+    let columns = [0.0, 70.0, 92.0, 120.0, 140.0];
+    writer.row(
+        &headings(&[
+            "heading-pack",
+            "heading-samples",
+            "heading-mean-score",
+            "heading-best",
+            "heading-top-ten",
+        ]),
+        &columns,
+        true,
+    );
+    for pack in &stats.packs {
+        writer.row(
+            &[
+                shortened(pack.pack.as_deref().unwrap_or("-")),
+                pack.samples.to_string(),
+                format!("{:.2}", pack.mean_score),
+                pack.best_score.to_string(),
+                pack.top_ten.to_string(),
+            ],
+            &columns,
+            false,
+        );
+    }
+}
+
+/// Standings, per-round charts and pack summaries as an A4 PDF
+pub fn write(state: &TournamentState, path: &Path) -> Result<(), String> {
+    let title = i18n::t("report-title");
+    let mut writer = Writer::new(&title)?;
+    writer.text(&title, HEADING_SIZE + 4.0, MARGIN, true);
+    writer.y -= ROW_HEIGHT * 2.0;
+    writer.line(&state.source_directory);
+    writer.line(&i18n::t_args(
+        "report-overview",
+        [
+            ("round", state.current_round.into()),
+            ("samples", state.samples.len().into()),
+            ("comparisons", state.history.len().into()),
+        ],
+    ));
+
+    standings(&mut writer, state);
+    rounds(&mut writer, state);
+    packs(&mut writer, state);
+
+    let partial = path.with_extension("pdf.part");
+    let saved = writer
+        .save(&partial)
+        .and_then(|()| fs::rename(&partial, path).map_err(|e| e.to_string()));
+    if saved.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    saved?;
+    audit::record_write(path, None);
+    Ok(())
+}
+
+/// Shootout report for archiving alongside project documentation
+#[tauri::command]
+pub async fn export_pdf_report(
+    session_id: String,
+    file_path: String,
+    sessions: State<'_, Sessions>,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<(), String> {
+    let path = std::path::PathBuf::from(&file_path);
+    allowed_paths.check_file(&path)?;
+    let state = sessions.get(&session_id)?.state;
+
+    // The folder the user exported into is allowed from now on
+    if let Some(parent) = path.parent() {
+        allowed_paths.add_allowed_path(parent.to_path_buf());
+    }
+    tauri::async_runtime::spawn_blocking(move || write(&state, &path))
+        .await
+        .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{i18n, Sample, TournamentState};
+
+/// Manual verdict that takes a sample out of further comparisons
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Pin {
+    /// Obvious keeper: survives every cut and is always exported
+    AlwaysAdvance,
+    /// Obvious reject: stays listed in the results but is never exported
+    Eliminate,
+}
+
+/// Results order: keepers first, rejects last, everything else in between
+pub fn rank(sample: &Sample) -> u8 {
+    match sample.pin {
+        Some(Pin::AlwaysAdvance) => 0,
+        None => 1,
+        Some(Pin::Eliminate) => 2,
+    }
+}
+
+/// Pin (or with `None`, unpin) `path`. Its comparisons still to come this round
+/// are dropped, since the scheduler no longer pairs pinned samples.
+pub fn pin_sample(state: &mut TournamentState, path: &str, pin: Option<Pin>) -> Result<(), String> {
+    let index = state
+        .samples
+        .iter()
+        .position(|s| s.path == path)
+        .ok_or_else(|| i18n::t("sample-not-in-tournament"))?;
+    state.samples[index].pin = pin;
+
+    if pin.is_some() {
+        unschedule(state, index);
+    }
+    Ok(())
+}
+
+/// Drop the sample at `index` from the comparisons still to come this round
+pub fn unschedule(state: &mut TournamentState, index: usize) {
+    let next = state.current_comparison_index;
+    let mut position = 0;
+    state.comparisons_this_round.retain(|&(a, b)| {
+        position += 1;
+        position <= next || (a != index && b != index)
+    });
+}
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+use crate::copy_export::{self, CopyFailure};
+use crate::export::{passes_min_score, sorted_results};
+use crate::jobs::{self, JobKind};
+use crate::journal::{Journal, OperationKind};
+use crate::settings::SettingsStore;
+use crate::{archive, audit, manifest, path_template, variations, AllowedPaths, Sample};
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PlayerExport {
+    pub folder: String,
+    pub playlist_path: String,
+    pub tracks: usize,
+    /// Archive entries left out: a player can't open them without the files copied
+    pub skipped: usize,
+    pub failed: Vec<CopyFailure>,
+}
+
+/// Where `export_to_player` writes the playlist folder and what goes in it
+#[derive(Debug, Deserialize, Clone)]
+pub struct PlayerExportOptions {
+    pub dest_dir: String,
+    pub playlist_name: String,
+    pub min_score: i32,
+    /// Copy the samples in beside the playlist instead of pointing at the originals
+    pub copy_files: bool,
+}
+
+/// `#EXTINF` line; players show the title and use the length until they scan the file
+fn extinf(sample: &Sample) -> String {
+    let seconds = sample
+        .analysis
+        .as_ref()
+        .map(|a| a.duration_secs.round() as i64)
+        .unwrap_or(-1);
+    let title = sample
+        .filename
+        .rsplit_once('.')
+        .map_or(sample.filename.as_str(), |(stem, _)| stem);
+    format!("#EXTINF:{},{}", seconds, title)
+}
+
+fn write_playlist(path: &Path, tracks: &[(&Sample, String)]) -> Result<(), String> {
+    let mut content = String::from("#EXTM3U\n");
+    for (sample, location) in tracks {
+        content.push_str(&extinf(sample));
+        content.push('\n');
+        content.push_str(location);
+        content.push('\n');
+    }
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    audit::record_write(path, None);
+    Ok(())
+}
+
+/// Write the winners as a folder named after the playlist, holding a `.m3u8` in
+/// ranked order, for players that import whatever lands in a watched folder (Music's
+/// "Automatically Add to Music", Engine DJ, rekordbox). With `copy_files` the samples
+/// are copied in beside it and listed by relative path, so the folder still works
+/// after moving it to another machine; otherwise the playlist points at the originals.
+#[tauri::command]
+pub async fn export_to_player(
+    samples: Vec<Sample>,
+    options: PlayerExportOptions,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+    settings: State<'_, SettingsStore>,
+) -> Result<PlayerExport, String> {
+    let PlayerExportOptions {
+        dest_dir,
+        playlist_name,
+        min_score,
+        copy_files,
+    } = options;
+    if copy_files {
+        settings.ensure_writable()?;
+    }
+    let dest = PathBuf::from(&dest_dir);
+    allowed_paths.check_file(&dest)?;
+
+    // The folder the user exported into is allowed from now on
+    allowed_paths.add_allowed_path(dest.clone());
+
+    let name = path_template::sanitize(playlist_name.trim());
+    let name = if name.is_empty() {
+        "vs1".to_string()
+    } else {
+        name
+    };
+
+    let job_app = app.clone();
+    let (result, written) = tauri::async_runtime::spawn_blocking(move || {
+        let ranked: Vec<&Sample> = sorted_results(&samples)
+            .into_iter()
+            .filter(|s| passes_min_score(s, min_score))
+            .collect();
+        let members = variations::expand(&ranked);
+        let members: Vec<&Sample> = members.iter().collect();
+        let folder = dest.join(&name);
+        fs::create_dir_all(&folder).map_err(|e| e.to_string())?;
+        let job = jobs::start(&job_app, JobKind::Export, Some(members.len() as u64));
+
+        let mut skipped = 0;
+        let mut failed = Vec::new();
+        let mut written = Vec::new();
+        let tracks: Vec<(&Sample, String)> = if copy_files {
+            let report = copy_export::copy_samples(
+                &members,
+                &job_app.state::<AllowedPaths>(),
+                &folder,
+                None,
+                None,
+                |done| job.set_done(done),
+            )?;
+            let copied = manifest::load(Path::new(&report.manifest_path)).files;
+            failed = report.failed;
+            written = report.written;
+            members
+                .iter()
+                .filter_map(|&sample| {
+                    let entry = copied.iter().find(|entry| entry.source == sample.path)?;
+                    Some((sample, entry.destination.replace('\\', "/")))
+                })
+                .collect()
+        } else {
+            members
+                .iter()
+                .filter(|sample| {
+                    let packed = archive::split_archive_path(&sample.path).is_some();
+                    skipped += packed as usize;
+                    !packed
+                })
+                .map(|&sample| (sample, sample.path.clone()))
+                .collect()
+        };
+
+        let playlist_path = folder.join(format!("{}.m3u8", name));
+        write_playlist(&playlist_path, &tracks)?;
+        tracing::info!(
+            tracks = tracks.len(),
+            skipped,
+            failed = failed.len(),
+            copy_files,
+            "player export finished"
+        );
+        let result = PlayerExport {
+            folder: folder.to_string_lossy().to_string(),
+            playlist_path: playlist_path.to_string_lossy().to_string(),
+            tracks: tracks.len(),
+            skipped,
+            failed,
+        };
+        Ok::<_, String>((result, written))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    if !written.is_empty() {
+        app.state::<Journal>()
+            .record_batch(OperationKind::Copy, &written)?;
+    }
+    Ok(result)
+}
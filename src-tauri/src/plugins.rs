@@ -0,0 +1,271 @@
+//! WASM plugins for experimenting with scoring and pair selection.
+//!
+//! A plugin is a `.wasm` module in the app data `plugins` folder. It must export
+//! `memory` and `alloc(len: i32) -> i32`, plus any of:
+//!
+//! - `select_pairs(ptr, len) -> i64`: input `{"samples": [{"score", "comparisons"}]}`,
+//!   output `[[a, b], ...]` (indices into `samples`)
+//! - `update_ratings(ptr, len) -> i64`: input `{"winner": {..}, "loser": {..}}`,
+//!   output `{"winner_score", "loser_score"}`
+//!
+//! Data is exchanged as UTF-8 JSON; the returned i64 packs `(ptr << 32) | len`.
+//! Plugins get no imports (no WASI), so they can't touch the file system or network,
+//! and each call runs off the main thread with a fuel budget and at most 64 MB of memory.
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+use wasmtime::{
+    Config, Engine, Instance, Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder,
+};
+
+use crate::settings::SettingsStore;
+use crate::{i18n, Sample};
+
+/// Upper bound on work per call so a buggy plugin can't hang the app
+const FUEL_PER_CALL: u64 = 1_000_000_000;
+/// Linear memory a plugin may grow to, so one can't take the host's memory with it
+const MAX_MEMORY_BYTES: usize = 64 << 20;
+const MAX_TABLE_ELEMENTS: usize = 10_000;
+
+#[derive(Debug, Serialize)]
+pub struct PluginInfo {
+    pub name: String,
+    pub path: String,
+    pub select_pairs: bool,
+    pub update_ratings: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PluginSample {
+    pub score: i32,
+    pub comparisons: i32,
+}
+
+impl From<&Sample> for PluginSample {
+    fn from(sample: &Sample) -> Self {
+        Self {
+            score: sample.score,
+            comparisons: sample.comparisons,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RatingUpdate {
+    pub winner_score: i32,
+    pub loser_score: i32,
+}
+
+pub struct PluginHost {
+    engine: Engine,
+    dir: PathBuf,
+    modules: Mutex<HashMap<PathBuf, Module>>,
+}
+
+impl PluginHost {
+    pub fn new(data_dir: &Path) -> Result<Self, String> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+
+        Ok(Self {
+            engine: Engine::new(&config).map_err(|e| e.to_string())?,
+            dir: data_dir.join("plugins"),
+            modules: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn plugin_path(&self, name: &str) -> Result<PathBuf, String> {
+        // Names come from `list_plugins`; anything path-like is rejected
+        if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+            return Err(i18n::t_args("plugin-invalid-name", [("name", name.into())]));
+        }
+        let path = self.dir.join(format!("{}.wasm", name));
+        if !path.is_file() {
+            return Err(i18n::t_args("plugin-not-found", [("name", name.into())]));
+        }
+        Ok(path)
+    }
+
+    fn module(&self, path: &Path) -> Result<Module, String> {
+        let mut modules = self.modules.lock().unwrap();
+        if let Some(module) = modules.get(path) {
+            return Ok(module.clone());
+        }
+        let module = Module::from_file(&self.engine, path).map_err(|e| e.to_string())?;
+        modules.insert(path.to_path_buf(), module.clone());
+        Ok(module)
+    }
+
+    pub fn list(&self) -> Vec<PluginInfo> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+
+        let mut plugins: Vec<PluginInfo> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("wasm"))
+            .filter_map(|path| {
+                let name = path.file_stem()?.to_string_lossy().to_string();
+                let module = self.module(&path).ok()?;
+                let exports = |export: &str| module.get_export(export).is_some();
+                Some(PluginInfo {
+                    select_pairs: exports("select_pairs"),
+                    update_ratings: exports("update_ratings"),
+                    path: path.to_string_lossy().to_string(),
+                    name,
+                })
+            })
+            .collect();
+
+        plugins.sort_by(|a, b| a.name.cmp(&b.name));
+        plugins
+    }
+
+    /// Call a JSON-in/JSON-out export; `Ok(None)` when the plugin doesn't provide it
+    pub fn call<I: Serialize, O: DeserializeOwned>(
+        &self,
+        name: &str,
+        export: &str,
+        input: &I,
+    ) -> Result<Option<O>, String> {
+        let module = self.module(&self.plugin_path(name)?)?;
+        if module.get_export(export).is_none() {
+            return Ok(None);
+        }
+
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(MAX_MEMORY_BYTES)
+            .table_elements(MAX_TABLE_ELEMENTS)
+            .instances(1)
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(FUEL_PER_CALL).map_err(|e| e.to_string())?;
+        let instance = Linker::new(&self.engine)
+            .instantiate(&mut store, &module)
+            .map_err(|e| e.to_string())?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| i18n::t_args("plugin-no-memory", [("name", name.into())]))?;
+
+        let input = serde_json::to_vec(input).map_err(|e| e.to_string())?;
+        let ptr = write_input(&mut store, &instance, &memory, &input)?;
+
+        let func = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, export)
+            .map_err(|e| e.to_string())?;
+        let packed = func
+            .call(&mut store, (ptr, input.len() as i32))
+            .map_err(|e| plugin_error("plugin-failed", name, e))?;
+
+        let (out_ptr, out_len) = ((packed >> 32) as u32 as usize, packed as u32 as usize);
+        let output = memory
+            .data(&store)
+            .get(out_ptr..out_ptr + out_len)
+            .ok_or_else(|| i18n::t_args("plugin-out-of-bounds", [("name", name.into())]))?;
+
+        serde_json::from_slice(output)
+            .map(Some)
+            .map_err(|e| plugin_error("plugin-invalid-json", name, e))
+    }
+}
+
+/// Error `id` for plugin `name`, with what went wrong inside it
+fn plugin_error(id: &str, name: &str, error: impl std::fmt::Display) -> String {
+    i18n::t_args(
+        id,
+        [("name", name.into()), ("error", error.to_string().into())],
+    )
+}
+
+fn write_input(
+    store: &mut Store<StoreLimits>,
+    instance: &Instance,
+    memory: &Memory,
+    input: &[u8],
+) -> Result<i32, String> {
+    let alloc = instance
+        .get_typed_func::<i32, i32>(&mut *store, "alloc")
+        .map_err(|e| e.to_string())?;
+    let ptr = alloc
+        .call(&mut *store, input.len() as i32)
+        .map_err(|e| e.to_string())?;
+    memory
+        .write(&mut *store, ptr as u32 as usize, input)
+        .map_err(|e| e.to_string())?;
+    Ok(ptr)
+}
+
+fn active_plugin(settings: &SettingsStore) -> Option<String> {
+    settings.get().plugin.filter(|name| !name.is_empty())
+}
+
+#[tauri::command]
+pub fn list_plugins(plugins: State<PluginHost>) -> Vec<PluginInfo> {
+    plugins.list()
+}
+
+/// Run a plugin export on a blocking worker; plugin code never runs on the main thread
+async fn call_blocking<O: DeserializeOwned + Send + 'static>(
+    app: AppHandle,
+    name: String,
+    export: &'static str,
+    input: serde_json::Value,
+) -> Result<Option<O>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        app.state::<PluginHost>().call(&name, export, &input)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Pairings for a new round from the active plugin; `None` falls back to the built-in pairing
+pub async fn select_pairs(
+    app: &AppHandle,
+    samples: &[Sample],
+) -> Result<Option<Vec<(usize, usize)>>, String> {
+    let Some(name) = active_plugin(&app.state::<SettingsStore>()) else {
+        return Ok(None);
+    };
+
+    let input = serde_json::json!({
+        "samples": samples.iter().map(PluginSample::from).collect::<Vec<_>>(),
+    });
+    let pairs: Option<Vec<(usize, usize)>> =
+        call_blocking(app.clone(), name.clone(), "select_pairs", input).await?;
+
+    if let Some(pairs) = &pairs {
+        let valid = pairs
+            .iter()
+            .all(|&(a, b)| a != b && a < samples.len() && b < samples.len());
+        if !valid {
+            return Err(i18n::t_args(
+                "plugin-invalid-pairings",
+                [("name", name.into())],
+            ));
+        }
+    }
+    Ok(pairs)
+}
+
+/// New scores for a decided comparison; `None` keeps the built-in +1 for the winner
+pub async fn update_ratings(
+    app: &AppHandle,
+    winner: &Sample,
+    loser: &Sample,
+) -> Result<Option<RatingUpdate>, String> {
+    let Some(name) = active_plugin(&app.state::<SettingsStore>()) else {
+        return Ok(None);
+    };
+
+    let input = serde_json::json!({
+        "winner": PluginSample::from(winner),
+        "loser": PluginSample::from(loser),
+    });
+    call_blocking(app.clone(), name, "update_ratings", input).await
+}
@@ -0,0 +1,101 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, State};
+
+use crate::media;
+
+/// Asking the OS for the power source costs a few syscalls; workers ask after every file
+const POWER_SOURCE_TTL: Duration = Duration::from_secs(5);
+
+static POWER_SOURCE: Mutex<Option<(Instant, bool)>> = Mutex::new(None);
+
+/// Whether the computer is running on battery (any battery discharging); desktops and
+/// machines where the state can't be read count as plugged in
+pub fn on_battery() -> bool {
+    let mut cached = POWER_SOURCE.lock().unwrap();
+    if let Some((checked, on_battery)) = *cached {
+        if checked.elapsed() < POWER_SOURCE_TTL {
+            return on_battery;
+        }
+    }
+
+    let on_battery = battery::Manager::new()
+        .and_then(|manager| manager.batteries())
+        .map(|batteries| {
+            batteries
+                .flatten()
+                .any(|battery| battery.state() == battery::State::Discharging)
+        })
+        .unwrap_or(false);
+    *cached = Some((Instant::now(), on_battery));
+    on_battery
+}
+
+/// Keeps the system awake while audio is playing or a backend job is running
+pub struct PowerGuard {
+    playback: AtomicBool,
+    jobs: AtomicBool,
+    tx: Mutex<Sender<bool>>,
+}
+
+impl PowerGuard {
+    pub fn start() -> Self {
+        let (tx, rx) = mpsc::channel::<bool>();
+
+        // SetThreadExecutionState is per-thread on Windows, so the assertion is
+        // taken and released on one long-lived thread
+        std::thread::spawn(move || {
+            let mut awake = None;
+            for active in rx {
+                if !active {
+                    awake = None;
+                    continue;
+                }
+                if awake.is_some() {
+                    continue;
+                }
+                awake = keepawake::Builder::default()
+                    .idle(true)
+                    .sleep(true)
+                    .reason("Playing audio or running a long job")
+                    .app_name("VS-1")
+                    .app_reverse_domain("com.vs1.app")
+                    .create()
+                    .inspect_err(|e| tracing::warn!(error = %e, "failed to prevent sleep"))
+                    .ok();
+            }
+        });
+
+        Self {
+            playback: AtomicBool::new(false),
+            jobs: AtomicBool::new(false),
+            tx: Mutex::new(tx),
+        }
+    }
+
+    fn update(&self) {
+        let active = self.playback.load(Ordering::Relaxed) || self.jobs.load(Ordering::Relaxed);
+        let _ = self.tx.lock().unwrap().send(active);
+    }
+
+    pub fn set_jobs_active(&self, active: bool) {
+        if self.jobs.swap(active, Ordering::Relaxed) != active {
+            self.update();
+        }
+    }
+
+    pub fn set_playback_active(&self, active: bool) {
+        if self.playback.swap(active, Ordering::Relaxed) != active {
+            self.update();
+        }
+    }
+}
+
+/// Called by the frontend player, which owns playback
+#[tauri::command]
+pub fn set_playback_active(active: bool, app: AppHandle, power: State<PowerGuard>) {
+    power.set_playback_active(active);
+    media::set_playing(&app, active);
+}
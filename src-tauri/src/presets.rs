@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, State};
+
+use crate::advancement::ScoreCarry;
+use crate::copy_export::NoteTagging;
+use crate::scan::{ScanOptions, ScanOutcome};
+use crate::settings::SettingsStore;
+use crate::{i18n, AllowedPaths};
+
+/// How the results screen starts out, so a template's exports come out the same
+/// way every time
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ExportDefaults {
+    pub min_score: i32,
+    /// Only the best this many; `None` exports everything past `min_score`
+    pub top_n: Option<usize>,
+    pub write_manifest: bool,
+    pub note_tagging: Option<NoteTagging>,
+    pub path_template: Option<String>,
+}
+
+/// Named starting configuration for one kind of tournament ("drum one-shots",
+/// "full loops"), so each workflow starts correctly set up
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Preset {
+    pub name: String,
+    /// Fraction of samples advancing after each round; the last entry repeats for
+    /// later rounds, and an empty schedule uses the default threshold
+    pub threshold_schedule: Vec<f32>,
+    /// Cut each round at this many standard deviations above the mean score instead
+    pub adaptive_cutoff: Option<f32>,
+    /// How scores carry over from one round to the next
+    pub score_carry: ScoreCarry,
+    /// WASM plugin used for pairing and rating; `None` uses the built-in ones
+    pub plugin: Option<String>,
+    /// Scan filters applied when picking the source folder
+    pub scan: ScanOptions,
+    /// Starting values for the results screen's export controls
+    pub export: ExportDefaults,
+}
+
+pub struct PresetStore {
+    file: PathBuf,
+    presets: Mutex<Vec<Preset>>,
+}
+
+impl PresetStore {
+    pub fn load(data_dir: &Path) -> Self {
+        let file = data_dir.join("presets.json");
+        let presets = fs::read_to_string(&file)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            file,
+            presets: Mutex::new(presets),
+        }
+    }
+
+    pub fn list(&self) -> Vec<Preset> {
+        self.presets.lock().unwrap().clone()
+    }
+
+    pub fn get(&self, name: &str) -> Option<Preset> {
+        self.presets
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|p| p.name == name)
+            .cloned()
+    }
+
+    fn persist(&self, presets: &[Preset]) -> Result<(), String> {
+        if let Some(parent) = self.file.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(presets).map_err(|e| e.to_string())?;
+        fs::write(&self.file, json).map_err(|e| e.to_string())
+    }
+
+    /// Add `preset`, replacing one with the same name
+    pub fn save(&self, preset: Preset) -> Result<(), String> {
+        let mut presets = self.presets.lock().unwrap();
+        match presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => presets.push(preset),
+        }
+        self.persist(&presets)
+    }
+
+    pub fn delete(&self, name: &str) -> Result<(), String> {
+        let mut presets = self.presets.lock().unwrap();
+        let before = presets.len();
+        presets.retain(|p| p.name != name);
+        if presets.len() == before {
+            return Err(i18n::t("unknown-preset"));
+        }
+        self.persist(&presets)
+    }
+}
+
+#[tauri::command]
+pub fn list_presets(presets: State<PresetStore>) -> Vec<Preset> {
+    presets.list()
+}
+
+fn validate(preset: Preset) -> Result<Preset, String> {
+    let name = preset.name.trim().to_string();
+    if name.is_empty() {
+        return Err(i18n::t("name-empty"));
+    }
+    if preset
+        .threshold_schedule
+        .iter()
+        .any(|t| !(0.0..=1.0).contains(t))
+    {
+        return Err(i18n::t("threshold-out-of-range"));
+    }
+    Ok(Preset { name, ..preset })
+}
+
+#[tauri::command]
+pub fn save_preset(preset: Preset, presets: State<PresetStore>) -> Result<(), String> {
+    presets.save(validate(preset)?)
+}
+
+/// Save the current setup as a template: the frontend's scan filters, thresholds and
+/// export defaults plus the rating plugin currently in use. Samples are never stored.
+#[tauri::command]
+pub fn save_template(
+    name: String,
+    setup: Preset,
+    presets: State<PresetStore>,
+    settings: State<SettingsStore>,
+) -> Result<Preset, String> {
+    let template = validate(Preset {
+        name,
+        plugin: settings.get().plugin,
+        ..setup
+    })?;
+    presets.save(template.clone())?;
+    Ok(template)
+}
+
+/// A template applied and its folder scanned, ready to start a tournament
+#[derive(Debug, Serialize, Clone)]
+pub struct TemplateStart {
+    pub template: Preset,
+    pub directory: String,
+    pub outcome: ScanOutcome,
+}
+
+/// Apply a template and scan `directory` with its filters in one go
+#[tauri::command]
+pub async fn new_from_template(
+    name: String,
+    directory: String,
+    app: AppHandle,
+    presets: State<'_, PresetStore>,
+    settings: State<'_, SettingsStore>,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<TemplateStart, String> {
+    let template = apply_preset(name, presets, settings)?;
+    let outcome = crate::scan_directory(
+        directory.clone(),
+        Some(template.scan.clone()),
+        app,
+        allowed_paths,
+    )
+    .await?;
+    Ok(TemplateStart {
+        template,
+        directory,
+        outcome,
+    })
+}
+
+#[tauri::command]
+pub fn delete_preset(name: String, presets: State<PresetStore>) -> Result<(), String> {
+    presets.delete(&name)
+}
+
+/// Switch to the preset's plugin and hand its thresholds and filters to the frontend
+#[tauri::command]
+pub fn apply_preset(
+    name: String,
+    presets: State<PresetStore>,
+    settings: State<SettingsStore>,
+) -> Result<Preset, String> {
+    let preset = presets
+        .get(&name)
+        .ok_or_else(|| i18n::t("unknown-preset"))?;
+
+    let mut current = settings.get();
+    if current.plugin != preset.plugin {
+        current.plugin = preset.plugin.clone();
+        settings.set(current)?;
+    }
+    Ok(preset)
+}
@@ -0,0 +1,155 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::audit::{self, AuditKind};
+use crate::error::CommandError;
+
+/// The only programs the desktop integrations (reveal, open, clipboard) may launch
+const ALLOWED_PROGRAMS: &[&str] = &[
+    "open",
+    "explorer",
+    "xdg-open",
+    "dbus-send",
+    "powershell",
+    "xclip",
+];
+/// At most this many launches per window, so a runaway frontend loop can't spawn
+/// hundreds of file manager windows
+const MAX_LAUNCHES: usize = 20;
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+
+static LAUNCHES: Mutex<VecDeque<Instant>> = Mutex::new(VecDeque::new());
+
+fn check_rate() -> Result<(), CommandError> {
+    let mut launches = LAUNCHES.lock().unwrap();
+    let now = Instant::now();
+    while launches
+        .front()
+        .is_some_and(|&at| now.duration_since(at) > RATE_WINDOW)
+    {
+        launches.pop_front();
+    }
+    if launches.len() >= MAX_LAUNCHES {
+        return Err(CommandError::RateLimited);
+    }
+    launches.push_back(now);
+    Ok(())
+}
+
+/// User data (paths, URIs) handed to a program may not pose as options or smuggle in
+/// line breaks and NULs, whether it goes on the command line or one per line on stdin
+pub fn check_values(values: &[&str]) -> Result<(), CommandError> {
+    match values
+        .iter()
+        .find(|v| v.starts_with('-') || v.contains(['\0', '\n', '\r']))
+    {
+        Some(value) => {
+            tracing::warn!(value, "rejected process argument");
+            Err(CommandError::InvalidArgument(value.to_string()))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Percent-encoded `file://` URI for an absolute path, as file managers and
+/// `text/uri-list` expect; commas are encoded too so URIs can be joined with them
+#[cfg(target_os = "linux")]
+pub fn file_uri(path: &str) -> Option<String> {
+    tauri::Url::from_file_path(path)
+        .ok()
+        .map(|url| url.as_str().replace(',', "%2C"))
+}
+
+/// Build the command after every check; `flags` are fixed by the caller
+fn command(
+    program: &'static str,
+    flags: &[&'static str],
+    values: &[&str],
+) -> Result<Command, CommandError> {
+    if !ALLOWED_PROGRAMS.contains(&program) {
+        return Err(CommandError::InvalidArgument(format!(
+            "{} may not be launched",
+            program
+        )));
+    }
+    check_values(values)?;
+    check_rate().inspect_err(|_| tracing::warn!(program, "process launch rate limited"))?;
+
+    tracing::info!(program, ?flags, ?values, "launching process");
+    audit::record(
+        AuditKind::Launch,
+        program,
+        Some([flags, values].concat().join(" ")),
+    );
+    let mut command = Command::new(program);
+    command.args(flags).args(values);
+    Ok(command)
+}
+
+fn launch_error(program: &str, error: io::Error) -> CommandError {
+    tracing::warn!(program, error = %error, "process launch failed");
+    match error.kind() {
+        io::ErrorKind::NotFound => CommandError::ProgramMissing(program.to_string()),
+        _ => CommandError::Failed(format!("{}: {}", program, error)),
+    }
+}
+
+/// Start a program without waiting for it (file managers, viewers)
+pub fn spawn(
+    program: &'static str,
+    flags: &[&'static str],
+    values: &[&str],
+) -> Result<(), CommandError> {
+    command(program, flags, values)?
+        .spawn()
+        .map_err(|e| launch_error(program, e))?;
+    Ok(())
+}
+
+/// Run a program to completion; returns whether it reported success
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub fn run(
+    program: &'static str,
+    flags: &[&'static str],
+    values: &[&str],
+) -> Result<bool, CommandError> {
+    let status = command(program, flags, values)?
+        .stdin(Stdio::null())
+        .status()
+        .map_err(|e| launch_error(program, e))?;
+    Ok(status.success())
+}
+
+/// Run a program with `input` on stdin; user data goes through stdin rather than the
+/// command line wherever the program allows it
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+pub fn pipe(
+    program: &'static str,
+    flags: &[&'static str],
+    input: &str,
+) -> Result<(), CommandError> {
+    let mut child = command(program, flags, &[])?
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| launch_error(program, e))?;
+
+    if let Some(stdin) = child.stdin.as_mut() {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| launch_error(program, e))?;
+    }
+    // Close stdin so the program sees the end of its input
+    drop(child.stdin.take());
+
+    let status = child.wait().map_err(|e| launch_error(program, e))?;
+    if !status.success() {
+        return Err(CommandError::Failed(format!(
+            "{} exited with {}",
+            program, status
+        )));
+    }
+    Ok(())
+}
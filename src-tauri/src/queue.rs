@@ -0,0 +1,76 @@
+use serde::Serialize;
+use tauri::State;
+
+use crate::sessions::Sessions;
+use crate::{i18n, Sample, TournamentState};
+
+/// Most comparisons `peek_upcoming` returns at once
+const MAX_PEEK: usize = 50;
+
+/// A scheduled comparison; `position` 0 is the one on screen
+#[derive(Debug, Serialize, Clone)]
+pub struct Upcoming {
+    pub position: usize,
+    pub a: Sample,
+    pub b: Sample,
+    /// Against the calibration reference rather than a regular match
+    pub calibration: bool,
+}
+
+/// The next `n` comparisons, starting with the one on screen
+pub fn peek(state: &TournamentState, n: usize) -> Vec<Upcoming> {
+    let reference = state.reference.as_ref().map(|r| r.path.as_str());
+    state
+        .comparisons_this_round
+        .iter()
+        .skip(state.current_comparison_index)
+        .take(n.min(MAX_PEEK))
+        .enumerate()
+        .filter_map(|(position, &(a, b))| {
+            let (a, b) = (state.samples.get(a)?, state.samples.get(b)?);
+            Some(Upcoming {
+                position,
+                calibration: [a, b].iter().any(|s| Some(s.path.as_str()) == reference),
+                a: a.clone(),
+                b: b.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Put the upcoming comparisons in a new order. `indices` lists positions as
+/// [`peek`] numbers them and must cover `0..indices.len()` exactly once; comparisons
+/// past that window keep their order after it. Decided comparisons never move.
+pub fn reorder(state: &mut TournamentState, indices: &[usize]) -> Result<(), String> {
+    let start = state
+        .current_comparison_index
+        .min(state.comparisons_this_round.len());
+    let upcoming = &mut state.comparisons_this_round[start..];
+    if indices.len() > upcoming.len() {
+        return Err(i18n::t_args(
+            "reorder-too-many",
+            [("count", upcoming.len().into())],
+        ));
+    }
+
+    let mut seen = vec![false; indices.len()];
+    for &index in indices {
+        if index >= indices.len() || std::mem::replace(&mut seen[index], true) {
+            return Err(i18n::t("reorder-invalid"));
+        }
+    }
+
+    let window: Vec<(usize, usize)> = indices.iter().map(|&i| upcoming[i]).collect();
+    upcoming[..window.len()].copy_from_slice(&window);
+    Ok(())
+}
+
+/// See (and later adjust) what's coming up this round
+#[tauri::command]
+pub fn peek_upcoming(
+    session_id: String,
+    n: usize,
+    sessions: State<Sessions>,
+) -> Result<Vec<Upcoming>, String> {
+    sessions.read(&session_id, |state| peek(state, n))
+}
@@ -0,0 +1,35 @@
+use crate::export::sorted_results;
+use crate::{i18n, rematches, TournamentState};
+
+/// Bring back the `count` best samples cut in earlier rounds for a mini-bracket of
+/// their own. They keep their scores, so a win there can lift them over the next cut.
+pub fn redeem(state: &mut TournamentState, count: usize) -> Result<usize, String> {
+    if state.current_comparison_index < state.comparisons_this_round.len() {
+        return Err(i18n::t("round-not-finished"));
+    }
+
+    // Samples removed with "skip both" were rejected outright and stay out
+    let redeemed: Vec<String> = sorted_results(&state.eliminated)
+        .into_iter()
+        .take(count)
+        .map(|s| s.path.clone())
+        .collect();
+    if redeemed.len() < 2 {
+        return Err(i18n::t("redemption-too-few"));
+    }
+
+    let first = state.samples.len();
+    for path in &redeemed {
+        if let Some(position) = state.eliminated.iter().position(|s| &s.path == path) {
+            state.samples.push(state.eliminated.remove(position));
+        }
+    }
+
+    // Neighbours in the standings face each other, unless they already have; an odd
+    // one out gets a bye
+    let order: Vec<usize> = (first..state.samples.len()).collect();
+    state.comparisons_this_round = rematches::pair_neighbours(state, &order);
+    state.current_comparison_index = 0;
+    state.current_round += 1;
+    Ok(redeemed.len())
+}
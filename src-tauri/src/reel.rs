@@ -0,0 +1,332 @@
+use serde::Serialize;
+use std::fs::{self, File};
+use std::io::{BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{AppHandle, State};
+
+use crate::copy_export::CopyFailure;
+use crate::export::{passes_min_score, sorted_results};
+use crate::jobs::{self, JobKind};
+use crate::{analysis, audit, i18n, notifications, transcode, variations, AllowedPaths, Sample};
+
+const REEL_RATE: u32 = 44_100;
+const REEL_CHANNELS: u16 = 2;
+const BYTES_PER_SAMPLE: u16 = 3;
+/// Audio a WAV's 32-bit sizes can hold, leaving the header's share of the RIFF size
+const MAX_DATA_BYTES: u64 = u32::MAX as u64 - 36;
+/// Silence between samples unless the caller asks for another gap
+const DEFAULT_GAP_SECS: f32 = 0.5;
+/// Longer samples are cut here, so one long loop doesn't swallow the reel
+const MAX_CLIP_SECS: f32 = 30.0;
+/// CD frames per second, the unit of `.cue` index times
+const CUE_FRAMES_PER_SEC: u64 = 75;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ReelReport {
+    pub tracks: usize,
+    pub duration_secs: f32,
+    pub cue_path: String,
+    /// Samples that could not be decoded and are missing from the reel
+    pub failed: Vec<CopyFailure>,
+}
+
+/// Where one sample starts in the reel
+struct Marker {
+    rank: usize,
+    name: String,
+    score: i32,
+    start_frame: u64,
+}
+
+/// 24-bit stereo WAV written as the samples are decoded, so the reel never has to fit
+/// in memory. The sizes are patched in at the end, followed by a `cue ` chunk and
+/// `LIST/adtl` labels, the chapter markers editors and players read from WAVs.
+struct ReelWriter {
+    out: BufWriter<File>,
+    frames: u64,
+}
+
+impl ReelWriter {
+    fn create(path: &Path) -> Result<Self, String> {
+        let mut out = BufWriter::new(File::create(path).map_err(|e| e.to_string())?);
+        let block_align = REEL_CHANNELS * BYTES_PER_SAMPLE;
+        let mut header = Vec::with_capacity(44);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&0u32.to_le_bytes());
+        header.extend_from_slice(b"WAVEfmt ");
+        header.extend_from_slice(&16u32.to_le_bytes());
+        header.extend_from_slice(&1u16.to_le_bytes());
+        header.extend_from_slice(&REEL_CHANNELS.to_le_bytes());
+        header.extend_from_slice(&REEL_RATE.to_le_bytes());
+        header.extend_from_slice(&(REEL_RATE * block_align as u32).to_le_bytes());
+        header.extend_from_slice(&block_align.to_le_bytes());
+        header.extend_from_slice(&(BYTES_PER_SAMPLE * 8).to_le_bytes());
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&0u32.to_le_bytes());
+        out.write_all(&header).map_err(|e| e.to_string())?;
+        Ok(Self { out, frames: 0 })
+    }
+
+    /// Append interleaved stereo samples. Refused, before anything is written, once the
+    /// reel would outgrow what a WAV can hold, instead of finding out at the end.
+    fn write(&mut self, samples: &[f32]) -> Result<(), String> {
+        let frames = self.frames + (samples.len() / REEL_CHANNELS as usize) as u64;
+        if frames * (REEL_CHANNELS * BYTES_PER_SAMPLE) as u64 > MAX_DATA_BYTES {
+            return Err(i18n::t("reel-too-long"));
+        }
+        for sample in samples {
+            let value = (sample.clamp(-1.0, 1.0) * 8_388_607.0) as i32;
+            self.out
+                .write_all(&value.to_le_bytes()[..3])
+                .map_err(|e| e.to_string())?;
+        }
+        self.frames = frames;
+        Ok(())
+    }
+
+    fn silence(&mut self, secs: f32) -> Result<(), String> {
+        let frames = (secs * REEL_RATE as f32) as usize;
+        self.write(&vec![0.0; frames * REEL_CHANNELS as usize])
+    }
+
+    /// Write the markers and patch in the sizes; returns the frames written
+    fn finish(mut self, markers: &[Marker]) -> Result<u64, String> {
+        // `write` kept the data within `MAX_DATA_BYTES`
+        let data_len = (self.frames * (REEL_CHANNELS * BYTES_PER_SAMPLE) as u64) as u32;
+        // Frames are six bytes, so the data chunk never needs a pad byte
+        let chunks = marker_chunks(markers);
+        let riff_len = u32::try_from(36 + data_len as u64 + chunks.len() as u64)
+            .map_err(|_| i18n::t("reel-too-long"))?;
+        self.out.write_all(&chunks).map_err(|e| e.to_string())?;
+
+        let mut file = self.out.into_inner().map_err(|e| e.to_string())?;
+        file.seek(SeekFrom::Start(4)).map_err(|e| e.to_string())?;
+        file.write_all(&riff_len.to_le_bytes())
+            .map_err(|e| e.to_string())?;
+        file.seek(SeekFrom::Start(40)).map_err(|e| e.to_string())?;
+        file.write_all(&data_len.to_le_bytes())
+            .map_err(|e| e.to_string())?;
+        file.sync_all().map_err(|e| e.to_string())?;
+        Ok(self.frames)
+    }
+}
+
+fn chunk(id: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut chunk = Vec::with_capacity(body.len() + 9);
+    chunk.extend_from_slice(id);
+    chunk.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    chunk.extend_from_slice(body);
+    if body.len() % 2 == 1 {
+        chunk.push(0);
+    }
+    chunk
+}
+
+/// `cue ` points at each sample's first frame, labelled `03 kick.wav` in `LIST/adtl`
+fn marker_chunks(markers: &[Marker]) -> Vec<u8> {
+    let mut points = (markers.len() as u32).to_le_bytes().to_vec();
+    let mut labels = b"adtl".to_vec();
+    for (id, marker) in (1u32..).zip(markers) {
+        let offset = marker.start_frame as u32;
+        points.extend_from_slice(&id.to_le_bytes());
+        points.extend_from_slice(&offset.to_le_bytes());
+        points.extend_from_slice(b"data");
+        points.extend_from_slice(&0u32.to_le_bytes());
+        points.extend_from_slice(&0u32.to_le_bytes());
+        points.extend_from_slice(&offset.to_le_bytes());
+
+        let mut label = id.to_le_bytes().to_vec();
+        label.extend_from_slice(format!("{:02} {}", marker.rank, marker.name).as_bytes());
+        label.push(0);
+        labels.extend(chunk(b"labl", &label));
+    }
+    let mut chunks = chunk(b"cue ", &points);
+    chunks.extend(chunk(b"LIST", &labels));
+    chunks
+}
+
+/// `mm:ss:ff` with 75 frames to the second; minutes go past 99 for long reels
+fn cue_time(frame: u64) -> String {
+    let cue_frames = frame * CUE_FRAMES_PER_SEC / REEL_RATE as u64;
+    let secs = cue_frames / CUE_FRAMES_PER_SEC;
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 60,
+        secs % 60,
+        cue_frames % CUE_FRAMES_PER_SEC
+    )
+}
+
+/// Quotes can't be escaped in cue sheets, so they become apostrophes
+fn cue_quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "'"))
+}
+
+fn write_cue(path: &Path, reel: &Path, markers: &[Marker]) -> Result<(), String> {
+    let reel_name = reel
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut content = format!(
+        "TITLE {}\nFILE {} WAVE\n",
+        cue_quoted("Audition reel"),
+        cue_quoted(&reel_name)
+    );
+    for (number, marker) in (1..).zip(markers) {
+        content.push_str(&format!(
+            "  TRACK {:02} AUDIO\n    TITLE {}\n    PERFORMER {}\n    REM RANK {}\n    \
+             INDEX 01 {}\n",
+            number,
+            cue_quoted(&marker.name),
+            cue_quoted(&format!("#{} - score {}", marker.rank, marker.score)),
+            marker.rank,
+            cue_time(marker.start_frame)
+        ));
+    }
+    fs::write(path, content).map_err(|e| e.to_string())?;
+    audit::record_write(path, None);
+    Ok(())
+}
+
+/// A sample's audio as stereo at the reel's rate, cut to `MAX_CLIP_SECS`. The cut comes
+/// first, so a long file isn't resampled only to be thrown away.
+fn clip(sample: &Sample) -> Result<Vec<f32>, String> {
+    let audio = analysis::decode(&sample.path)?;
+    let mut stereo: Vec<f32> = match audio.channels {
+        1 => audio.samples.iter().flat_map(|&x| [x, x]).collect(),
+        2 => audio.samples,
+        _ => transcode::downmix_to_stereo(&audio),
+    };
+    stereo.truncate((MAX_CLIP_SECS * audio.sample_rate as f32) as usize * REEL_CHANNELS as usize);
+    if audio.sample_rate == REEL_RATE {
+        return Ok(stereo);
+    }
+    Ok(transcode::resample(
+        &stereo,
+        REEL_CHANNELS as usize,
+        audio.sample_rate,
+        REEL_RATE,
+    ))
+}
+
+/// Write each sample and the gap after it, then the markers; samples that don't decode
+/// are left out. Returns the markers, the failures and the frames written.
+fn write_samples(
+    mut writer: ReelWriter,
+    samples: &[&Sample],
+    gap_secs: f32,
+    on_progress: impl Fn(u64),
+) -> Result<(Vec<Marker>, Vec<CopyFailure>, u64), String> {
+    let mut markers = Vec::new();
+    let mut failed = Vec::new();
+    for (index, sample) in samples.iter().enumerate() {
+        on_progress(index as u64);
+        match clip(sample) {
+            Ok(audio) => {
+                markers.push(Marker {
+                    rank: index + 1,
+                    name: sample.filename.clone(),
+                    score: sample.score,
+                    start_frame: writer.frames,
+                });
+                writer.write(&audio)?;
+                writer.silence(gap_secs)?;
+            }
+            Err(error) => {
+                tracing::warn!(path = %sample.path, %error, "sample left out of the reel");
+                failed.push(CopyFailure {
+                    source: sample.path.clone(),
+                    error,
+                });
+            }
+        }
+    }
+    let frames = writer.finish(&markers)?;
+    Ok((markers, failed, frames))
+}
+
+/// Render ranked samples back to back into one WAV at `reel_path`, and a `.cue` beside it
+pub fn render(
+    samples: &[&Sample],
+    reel_path: &Path,
+    gap_secs: f32,
+    on_progress: impl Fn(u64),
+) -> Result<ReelReport, String> {
+    let partial = reel_path.with_extension("wav.part");
+    let writer = ReelWriter::create(&partial)?;
+    // The writer is closed either way by now, so the partial file can go
+    let finished = write_samples(writer, samples, gap_secs, on_progress).and_then(|written| {
+        fs::rename(&partial, reel_path).map_err(|e| e.to_string())?;
+        Ok(written)
+    });
+    if finished.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    let (markers, failed, frames) = finished?;
+    audit::record_write(reel_path, None);
+
+    let cue_path = reel_path.with_extension("cue");
+    write_cue(&cue_path, reel_path, &markers)?;
+    Ok(ReelReport {
+        tracks: markers.len(),
+        duration_secs: frames as f32 / REEL_RATE as f32,
+        cue_path: cue_path.to_string_lossy().to_string(),
+        failed,
+    })
+}
+
+/// Render the winners, best first, into one audition reel with a cue sheet to jump
+/// between them
+#[tauri::command]
+pub async fn render_reel(
+    samples: Vec<Sample>,
+    file_path: String,
+    min_score: i32,
+    gap_secs: Option<f32>,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<ReelReport, String> {
+    let dest = PathBuf::from(&file_path);
+    allowed_paths.check_file(&dest)?;
+
+    // The folder the user exported into is allowed from now on
+    if let Some(parent) = dest.parent() {
+        allowed_paths.add_allowed_path(parent.to_path_buf());
+    }
+    let gap_secs = gap_secs.unwrap_or(DEFAULT_GAP_SECS).clamp(0.0, 10.0);
+
+    let started = Instant::now();
+    let job_app = app.clone();
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        let ranked: Vec<&Sample> = sorted_results(&samples)
+            .into_iter()
+            .filter(|s| passes_min_score(s, min_score))
+            .collect();
+        let members = variations::expand(&ranked);
+        let members: Vec<&Sample> = members.iter().collect();
+        let job = jobs::start(&job_app, JobKind::Export, Some(members.len() as u64));
+        render(&members, &dest, gap_secs, |done| job.set_done(done))
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    tracing::info!(
+        tracks = report.tracks,
+        failed = report.failed.len(),
+        "audition reel rendered"
+    );
+    notifications::job_finished(
+        &app,
+        started,
+        &i18n::t("reel-complete"),
+        &i18n::t_args(
+            "reel-complete-body",
+            [
+                ("count", report.tracks.into()),
+                ("failed", report.failed.len().into()),
+            ],
+        ),
+    );
+    Ok(report)
+}
@@ -0,0 +1,125 @@
+use std::collections::HashSet;
+
+use crate::rng::Rng;
+use crate::TournamentState;
+
+/// Random stream the pairings of each round are shuffled with
+const PAIRING_STREAM: u32 = 1;
+
+/// Every pair of samples that has met, keyed by path in either order. The match
+/// history doubles as the memory, so sessions saved before this still avoid rematches.
+pub struct Played(HashSet<(String, String)>);
+
+impl Played {
+    pub fn new(state: &TournamentState) -> Self {
+        Self(
+            state
+                .history
+                .iter()
+                .map(|m| key(&m.winner, &m.loser))
+                .collect(),
+        )
+    }
+
+    pub fn met(&self, a: &str, b: &str) -> bool {
+        self.0.contains(&key(a, b))
+    }
+}
+
+fn key(a: &str, b: &str) -> (String, String) {
+    if a <= b {
+        (a.to_string(), b.to_string())
+    } else {
+        (b.to_string(), a.to_string())
+    }
+}
+
+/// Pair `order` (sample indices, best first) with its nearest neighbour that hasn't
+/// met it yet, falling back to a rematch once every alternative is taken. With
+/// `allow_rematches` set this is plain neighbour pairing. An odd one out is left over.
+pub fn pair_neighbours(state: &TournamentState, order: &[usize]) -> Vec<(usize, usize)> {
+    let played = Played::new(state);
+    let met = |a: usize, b: usize| {
+        !state.allow_rematches && played.met(&state.samples[a].path, &state.samples[b].path)
+    };
+
+    let mut used = vec![false; order.len()];
+    let mut pairs = Vec::new();
+    for i in 0..order.len() {
+        if used[i] {
+            continue;
+        }
+        let open = || (i + 1..order.len()).filter(|&j| !used[j]);
+        let Some(j) = open()
+            .find(|&j| !met(order[i], order[j]))
+            .or_else(|| open().next())
+        else {
+            continue;
+        };
+        used[i] = true;
+        used[j] = true;
+        pairs.push((order[i], order[j]));
+    }
+    pairs
+}
+
+/// Pairings from elsewhere (a plugin) keep their shape, but a rematch swaps partners
+/// with another pair when that leaves both pairs fresh
+pub fn avoid_rematches(
+    state: &TournamentState,
+    mut pairs: Vec<(usize, usize)>,
+) -> Vec<(usize, usize)> {
+    if state.allow_rematches {
+        return pairs;
+    }
+    let played = Played::new(state);
+    let met = |a: usize, b: usize| played.met(&state.samples[a].path, &state.samples[b].path);
+
+    for i in 0..pairs.len() {
+        let (a, b) = pairs[i];
+        if !met(a, b) {
+            continue;
+        }
+        for j in (0..pairs.len()).filter(|&j| j != i) {
+            let (c, d) = pairs[j];
+            if !met(a, c) && !met(b, d) {
+                pairs[i] = (a, c);
+                pairs[j] = (b, d);
+                break;
+            }
+            if !met(a, d) && !met(b, c) {
+                pairs[i] = (a, d);
+                pairs[j] = (b, c);
+                break;
+            }
+        }
+    }
+    pairs
+}
+
+/// Indices of the samples the scheduler pairs: not pinned or benched and not the
+/// calibration reference
+pub fn pairable(state: &TournamentState) -> Vec<usize> {
+    let reference = state.reference.as_ref().map(|r| r.path.as_str());
+    (0..state.samples.len())
+        .filter(|&i| {
+            let sample = &state.samples[i];
+            sample.pin.is_none() && !sample.benched && Some(sample.path.as_str()) != reference
+        })
+        .collect()
+}
+
+/// Pairings for a fresh round: neighbours by score, played in an order shuffled
+/// from the session seed so the same pool and seed always give the same schedule
+pub fn pair_round(state: &TournamentState) -> Vec<(usize, usize)> {
+    let mut order = pairable(state);
+    order.sort_by(|&a, &b| state.samples[b].score.cmp(&state.samples[a].score));
+    let mut pairs = pair_neighbours(state, &order);
+
+    let mut rng = Rng::stream(state.seed, &[PAIRING_STREAM, state.current_round as u32]);
+    for i in (1..pairs.len()).rev() {
+        let j = rng.below(i as u64 + 1) as usize;
+        pairs.swap(i, j);
+    }
+    pairs
+}
@@ -0,0 +1,453 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::net::UdpSocket;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::collab::{direct_decision, Choice, CollabSession, Decision, SessionStatus};
+use crate::error::CommandError;
+use crate::{archive, i18n, sessions, AllowedPaths, Sample};
+
+const VOTE_PAGE: &str = include_str!("remote.html");
+
+/// Threads answering requests: enough that a slow audio download doesn't hold up
+/// votes, few enough that a flood of connections can't spawn threads without end
+const WORKERS: usize = 4;
+
+/// The comparison currently shown in the main window, pushed by the frontend
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemotePair {
+    /// Backend session of the tournament the pair is from; decided votes go there
+    pub session_id: String,
+    /// Changes with every comparison so stale votes can be rejected
+    pub pair_id: String,
+    pub round: i32,
+    pub a: Sample,
+    pub b: Sample,
+}
+
+/// What remote clients see of a pair: no filesystem paths
+#[derive(Debug, Serialize)]
+struct PublicPair<'a> {
+    pair_id: &'a str,
+    round: i32,
+    a: &'a str,
+    b: &'a str,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemoteVote {
+    pub pair_id: String,
+    pub winner: Choice,
+    /// Set by clients that joined a collaborative session
+    #[serde(default)]
+    pub voter_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JoinRequest {
+    name: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RemoteServerInfo {
+    pub url: String,
+    pub token: String,
+}
+
+enum Reply {
+    Data(Response<Cursor<Vec<u8>>>),
+    File(Response<File>),
+}
+
+/// State shared between the command handlers and the server thread
+#[derive(Default)]
+pub struct RemoteShared {
+    token: Mutex<String>,
+    pair: Mutex<Option<RemotePair>>,
+    /// When the current pair was first shown, for the decision time
+    shown: Mutex<Option<Instant>>,
+    session: Mutex<CollabSession>,
+}
+
+impl RemoteShared {
+    /// Apply a decided comparison to the session its pair is from. A decision the
+    /// session turns down opens the pair for voting again.
+    async fn decide(
+        &self,
+        app: &AppHandle,
+        session_id: &str,
+        decision: Decision,
+    ) -> Result<(), CommandError> {
+        let decision_ms = self
+            .shown
+            .lock()
+            .unwrap()
+            .map(|at| at.elapsed().as_millis() as u64);
+        sessions::decide(app, session_id, decision, decision_ms)
+            .await
+            .inspect_err(|_| self.session.lock().unwrap().reset_pair())?;
+        Ok(())
+    }
+}
+
+struct Running {
+    server: Arc<Server>,
+    info: RemoteServerInfo,
+}
+
+/// Optional embedded HTTP server for voting from another device (e.g. a phone)
+#[derive(Default)]
+pub struct RemoteServer {
+    shared: Arc<RemoteShared>,
+    running: Mutex<Option<Running>>,
+}
+
+pub fn random_token() -> Result<String, String> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|e| e.to_string())?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Best-effort LAN address; no packets are sent by connecting a UDP socket
+pub fn lan_ip() -> Option<String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    Some(socket.local_addr().ok()?.ip().to_string())
+}
+
+pub fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
+
+fn json_response<T: Serialize>(value: &T) -> Response<Cursor<Vec<u8>>> {
+    let body = serde_json::to_vec(value).unwrap_or_default();
+    Response::from_data(body).with_header(header("Content-Type", "application/json"))
+}
+
+pub fn error_response(status: u16, message: &str) -> Response<Cursor<Vec<u8>>> {
+    json_response(&serde_json::json!({ "error": message })).with_status_code(status)
+}
+
+fn query_param<'a>(url: &'a str, key: &str) -> Option<&'a str> {
+    let query = url.split_once('?')?.1;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+}
+
+pub fn is_authorized(request: &Request, token: &str) -> bool {
+    let bearer = request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv("Authorization"))
+        .and_then(|h| h.value.as_str().strip_prefix("Bearer "));
+
+    bearer
+        .or_else(|| query_param(request.url(), "token"))
+        .is_some_and(|given| tokens_match(given, token))
+}
+
+/// Compares every byte whatever the first difference, so response times don't leak how
+/// much of a guessed token was right
+fn tokens_match(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Answer requests on a fixed pool of `WORKERS` threads until `stop_serving`
+pub fn serve(server: &Arc<Server>, handler: impl Fn(Request) + Send + Sync + 'static) {
+    let handler = Arc::new(handler);
+    for _ in 0..WORKERS {
+        let server = server.clone();
+        let handler = handler.clone();
+        std::thread::spawn(move || {
+            for request in server.incoming_requests() {
+                handler(request);
+            }
+        });
+    }
+}
+
+/// Each `unblock` wakes a single waiting worker, so every worker gets one
+pub fn stop_serving(server: &Server) {
+    for _ in 0..WORKERS {
+        server.unblock();
+    }
+}
+
+fn content_type(path: &str) -> &'static str {
+    let ext = Path::new(path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match ext.as_str() {
+        "wav" => "audio/wav",
+        "mp3" => "audio/mpeg",
+        "flac" => "audio/flac",
+        "ogg" => "audio/ogg",
+        "aiff" => "audio/aiff",
+        "m4a" => "audio/mp4",
+        _ => "application/octet-stream",
+    }
+}
+
+pub fn serve_audio(app: &AppHandle, sample: &Sample, request: Request) {
+    if !app
+        .state::<AllowedPaths>()
+        .is_path_allowed(Path::new(&sample.path))
+    {
+        let _ = request.respond(error_response(403, "Access denied"));
+        return;
+    }
+
+    let mime = header("Content-Type", content_type(&sample.path));
+    let result = match archive::split_archive_path(&sample.path) {
+        Some((archive_path, entry_name)) => {
+            archive::read_entry(Path::new(archive_path), entry_name)
+                .map(|bytes| Reply::Data(Response::from_data(bytes).with_header(mime)))
+        }
+        // Files are sent with a length so the browser can seek
+        None => File::open(&sample.path)
+            .map(|file| Response::from_file(file).with_header(mime))
+            .map(Reply::File)
+            .map_err(|e| e.to_string()),
+    };
+
+    let _ = match result {
+        Ok(Reply::Data(response)) => request.respond(response),
+        Ok(Reply::File(response)) => request.respond(response),
+        Err(e) => request.respond(error_response(404, &e)),
+    };
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(request: &mut Request) -> Result<T, String> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .read_to_string(&mut body)
+        .map_err(|e| e.to_string())?;
+    serde_json::from_str(&body).map_err(|e| e.to_string())
+}
+
+/// A remote ballot, or a direct vote from a client that never joined
+fn submit_result(
+    app: &AppHandle,
+    shared: &RemoteShared,
+    pair: Option<&RemotePair>,
+    vote: RemoteVote,
+) -> Response<Cursor<Vec<u8>>> {
+    let Some(pair) = pair.filter(|p| p.pair_id == vote.pair_id) else {
+        return error_response(409, "This comparison is no longer current");
+    };
+    let outcome = match &vote.voter_id {
+        Some(voter_id) => shared
+            .session
+            .lock()
+            .unwrap()
+            .cast(&vote.pair_id, voter_id, vote.winner),
+        None => shared
+            .session
+            .lock()
+            .unwrap()
+            .direct(&vote.pair_id, vote.winner)
+            .map(Some),
+    };
+
+    match outcome {
+        // Server threads aren't async, so wait for the session here
+        Ok(Some(decision)) => {
+            match tauri::async_runtime::block_on(shared.decide(app, &pair.session_id, decision)) {
+                Ok(()) => json_response(&serde_json::json!({ "ok": true, "decided": true })),
+                Err(e) => error_response(409, &e.to_string()),
+            }
+        }
+        Ok(None) => json_response(&serde_json::json!({ "ok": true, "decided": false })),
+        Err(e) => error_response(409, &e),
+    }
+}
+
+fn handle_request(app: &AppHandle, shared: &RemoteShared, mut request: Request) {
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+
+    if path == "/" && *request.method() == Method::Get {
+        let response = Response::from_string(VOTE_PAGE)
+            .with_header(header("Content-Type", "text/html; charset=utf-8"));
+        let _ = request.respond(response);
+        return;
+    }
+
+    let token = shared.token.lock().unwrap().clone();
+    if !is_authorized(&request, &token) {
+        let _ = request.respond(error_response(401, "Invalid token"));
+        return;
+    }
+
+    let pair = shared.pair.lock().unwrap().clone();
+
+    match (request.method().clone(), path.as_str()) {
+        (Method::Get, "/api/next_pair") => {
+            let response = match &pair {
+                Some(pair) => json_response(&PublicPair {
+                    pair_id: &pair.pair_id,
+                    round: pair.round,
+                    a: &pair.a.filename,
+                    b: &pair.b.filename,
+                }),
+                None => error_response(404, "No comparison in progress"),
+            };
+            let _ = request.respond(response);
+        }
+        (Method::Get, "/api/audio/a") | (Method::Get, "/api/audio/b") => match &pair {
+            Some(pair) => {
+                let sample = if path.ends_with('a') {
+                    &pair.a
+                } else {
+                    &pair.b
+                };
+                serve_audio(app, sample, request);
+            }
+            None => {
+                let _ = request.respond(error_response(404, "No comparison in progress"));
+            }
+        },
+        (Method::Get, "/api/session") => {
+            let status = shared.session.lock().unwrap().status();
+            let _ = request.respond(json_response(&status));
+        }
+        (Method::Post, "/api/join_session") => {
+            let response = match read_json::<JoinRequest>(&mut request)
+                .and_then(|join| shared.session.lock().unwrap().join(&join.name))
+            {
+                Ok(voter) => json_response(&voter),
+                Err(e) => error_response(400, &e),
+            };
+            let _ = request.respond(response);
+        }
+        (Method::Post, "/api/submit_result") => {
+            let response = match read_json::<RemoteVote>(&mut request) {
+                Ok(vote) => submit_result(app, shared, pair.as_ref(), vote),
+                Err(e) => error_response(400, &e),
+            };
+            let _ = request.respond(response);
+        }
+        _ => {
+            let _ = request.respond(error_response(404, "Not found"));
+        }
+    }
+}
+
+#[tauri::command]
+pub fn start_remote_server(
+    lan: bool,
+    port: Option<u16>,
+    app: AppHandle,
+    remote: State<RemoteServer>,
+) -> Result<RemoteServerInfo, String> {
+    let mut running = remote.running.lock().unwrap();
+    if let Some(running) = running.as_ref() {
+        return Ok(running.info.clone());
+    }
+
+    let bind = if lan { "0.0.0.0" } else { "127.0.0.1" };
+    let server = Server::http((bind, port.unwrap_or(0)))
+        .map_err(|e| i18n::t_args("server-failed", [("error", e.to_string().into())]))?;
+    let port = server
+        .server_addr()
+        .to_ip()
+        .map(|addr| addr.port())
+        .ok_or_else(|| i18n::t("server-no-address"))?;
+
+    let token = random_token()?;
+    *remote.shared.token.lock().unwrap() = token.clone();
+
+    let host = if lan {
+        lan_ip().unwrap_or_else(|| "127.0.0.1".to_string())
+    } else {
+        "127.0.0.1".to_string()
+    };
+    let info = RemoteServerInfo {
+        url: format!("http://{}:{}/?token={}", host, port, token),
+        token,
+    };
+
+    let server = Arc::new(server);
+    let shared = remote.shared.clone();
+    serve(&server, move |request| {
+        handle_request(&app, &shared, request)
+    });
+
+    *running = Some(Running {
+        server,
+        info: info.clone(),
+    });
+    // The URL carries the token, so only where it listens goes to the log
+    tracing::info!(%host, port, lan, "remote voting server started");
+    Ok(info)
+}
+
+#[tauri::command]
+pub fn stop_remote_server(remote: State<RemoteServer>) {
+    if let Some(running) = remote.running.lock().unwrap().take() {
+        stop_serving(&running.server);
+    }
+}
+
+/// Publish the current comparison to remote clients and secondary windows (`None` when there is none)
+#[tauri::command]
+pub fn set_remote_pair(pair: Option<RemotePair>, app: AppHandle, remote: State<RemoteServer>) {
+    let mut current = remote.shared.pair.lock().unwrap();
+    let changed = current.as_ref().map(|p| &p.pair_id) != pair.as_ref().map(|p| &p.pair_id);
+    if changed {
+        remote.shared.session.lock().unwrap().reset_pair();
+        *remote.shared.shown.lock().unwrap() = pair.as_ref().map(|_| Instant::now());
+        let _ = app.emit("pair://changed", &pair);
+    }
+    *current = pair;
+}
+
+#[tauri::command]
+pub fn get_current_pair(remote: State<RemoteServer>) -> Option<RemotePair> {
+    remote.shared.pair.lock().unwrap().clone()
+}
+
+/// Vote from another window of this app (e.g. the mini window); applied like a direct remote vote
+#[tauri::command]
+pub async fn submit_local_vote(
+    pair_id: String,
+    winner: Choice,
+    app: AppHandle,
+    remote: State<'_, RemoteServer>,
+) -> Result<(), CommandError> {
+    let current = remote.shared.pair.lock().unwrap().clone();
+    let session_id = match current {
+        Some(pair) if pair.pair_id == pair_id => pair.session_id,
+        _ => return Err(i18n::t("vote-not-current").into()),
+    };
+
+    remote
+        .shared
+        .decide(&app, &session_id, direct_decision(&pair_id, winner))
+        .await
+}
+
+/// Number of ballots required per comparison; `None` waits for every joined voter
+#[tauri::command]
+pub fn set_vote_quorum(quorum: Option<usize>, remote: State<RemoteServer>) {
+    remote.shared.session.lock().unwrap().quorum = quorum;
+}
+
+#[tauri::command]
+pub fn get_collab_session(remote: State<RemoteServer>) -> SessionStatus {
+    remote.shared.session.lock().unwrap().status()
+}
@@ -0,0 +1,128 @@
+use std::path::Path;
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::export::{passes_min_score, sorted_results};
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+use crate::process;
+use crate::{archive, variations, AllowedPaths, Sample};
+
+/// Winners spread over more folders than this only open the first ones, best ranked first
+const MAX_FOLDERS: usize = 10;
+
+/// Qualifying files grouped by folder, folders in the order of their best-ranked file
+fn group_by_folder<'a>(paths: &[&'a str]) -> Vec<(&'a Path, Vec<&'a str>)> {
+    let mut folders: Vec<(&Path, Vec<&str>)> = Vec::new();
+    for &path in paths {
+        let Some(parent) = Path::new(path).parent() else {
+            continue;
+        };
+        match folders.iter_mut().find(|(folder, _)| *folder == parent) {
+            Some((_, files)) => files.push(path),
+            None => folders.push((parent, vec![path])),
+        }
+    }
+    folders
+}
+
+#[cfg(target_os = "macos")]
+fn select_files(folders: &[(&Path, Vec<&str>)]) -> Result<(), CommandError> {
+    use cocoa::base::{id, nil};
+    use cocoa::foundation::{NSArray, NSString, NSURL};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    // Finder selects every file, one window per folder
+    unsafe {
+        let urls: Vec<id> = folders
+            .iter()
+            .flat_map(|(_, files)| files)
+            .map(|path| NSURL::fileURLWithPath_(nil, NSString::alloc(nil).init_str(path)))
+            .collect();
+        let array: id = NSArray::arrayWithObjects(nil, &urls);
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let _: () = msg_send![workspace, activateFileViewerSelectingURLs: array];
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn select_files(folders: &[(&Path, Vec<&str>)]) -> Result<(), CommandError> {
+    // `explorer /select` takes a single file, so each folder opens on its best sample.
+    // The switch and the path must arrive as one argument.
+    for (_, files) in folders {
+        process::spawn("explorer", &[], &[&format!("/select,{}", files[0])])?;
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn select_files(folders: &[(&Path, Vec<&str>)]) -> Result<(), CommandError> {
+    // File managers implementing the freedesktop FileManager1 interface (Nautilus,
+    // Dolphin, Nemo, ...) select every file; `file_uri` encodes the commas dbus-send's
+    // array syntax can't carry
+    let uris: Vec<String> = folders
+        .iter()
+        .flat_map(|(_, files)| files)
+        .filter_map(|path| process::file_uri(path))
+        .collect();
+    if !uris.is_empty() {
+        let shown = process::run(
+            "dbus-send",
+            &[
+                "--session",
+                "--dest=org.freedesktop.FileManager1",
+                "--type=method_call",
+                "/org/freedesktop/FileManager1",
+                "org.freedesktop.FileManager1.ShowItems",
+            ],
+            &[&format!("array:string:{}", uris.join(",")), "string:"],
+        );
+        if matches!(shown, Ok(true)) {
+            return Ok(());
+        }
+    }
+
+    for (folder, _) in folders {
+        process::spawn("xdg-open", &[], &[&folder.to_string_lossy()])?;
+    }
+    Ok(())
+}
+
+/// Open the folders holding every qualifying file and select them where the platform
+/// allows it. Returns how many folders were opened.
+#[tauri::command]
+pub fn reveal_results(
+    samples: Vec<Sample>,
+    min_score: i32,
+    allowed_paths: State<AllowedPaths>,
+) -> Result<usize, CommandError> {
+    let ranked: Vec<&Sample> = sorted_results(&samples)
+        .into_iter()
+        .filter(|s| passes_min_score(s, min_score))
+        .collect();
+    let members = variations::expand(&ranked);
+
+    let mut paths = Vec::new();
+    for sample in &members {
+        // Archive entries have no file to select; their archive stands in for them
+        let path = archive::split_archive_path(&sample.path)
+            .map_or(sample.path.as_str(), |(archive_path, _)| archive_path);
+        if !allowed_paths.is_path_allowed(Path::new(path)) {
+            return Err(CommandError::PathNotAllowed);
+        }
+        if Path::new(path).exists() && !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    if paths.is_empty() {
+        return Err(CommandError::FileNotFound);
+    }
+
+    let mut folders = group_by_folder(&paths);
+    if folders.len() > MAX_FOLDERS {
+        tracing::info!(folders = folders.len(), "revealing only the first folders");
+        folders.truncate(MAX_FOLDERS);
+    }
+    select_files(&folders)?;
+    Ok(folders.len())
+}
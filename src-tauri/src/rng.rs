@@ -0,0 +1,52 @@
+/// Seeded random numbers for the scheduler, so a session's seed always gives the same
+/// schedule. Not suitable for anything security related.
+pub struct Rng(u32);
+
+impl Rng {
+    pub fn new(seed: u32) -> Self {
+        Self(seed)
+    }
+
+    /// Independent stream per purpose and position, e.g. the pairings of round 3, so
+    /// resuming a session mid-way doesn't change what comes next
+    pub fn stream(seed: u32, keys: &[u32]) -> Self {
+        let mixed = keys.iter().fold(seed, |mixed, &key| {
+            let mixed = (mixed ^ key).wrapping_mul(0x9e37_79b1);
+            mixed ^ (mixed >> 16)
+        });
+        Self(mixed)
+    }
+
+    /// mulberry32: tiny, fast and good enough for shuffling and sampling
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 = self.0.wrapping_add(0x6d2b_79f5);
+        let t = self.0;
+        let mut r = (t ^ (t >> 15)).wrapping_mul(t | 1);
+        r ^= r.wrapping_add((r ^ (r >> 7)).wrapping_mul(r | 61));
+        r ^ (r >> 14)
+    }
+
+    /// Uniform in `0.0..1.0`
+    pub fn next_f64(&mut self) -> f64 {
+        self.next_u32() as f64 / 4_294_967_296.0
+    }
+
+    /// Uniform in `0..n`
+    pub fn below(&mut self, n: u64) -> u64 {
+        (self.next_f64() * n as f64) as u64
+    }
+}
+
+/// A fresh seed for when the user didn't pick one
+pub fn random_seed() -> u32 {
+    let mut bytes = [0u8; 4];
+    if getrandom::getrandom(&mut bytes).is_err() {
+        // Still different per run, which is all a seed needs here
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        return nanos;
+    }
+    u32::from_le_bytes(bytes)
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use crate::statistics::{self, Upset};
+use crate::{advancement, Sample, TournamentState};
+
+/// Matches listed per category in a summary
+const SUMMARY_MATCHES: usize = 3;
+
+/// One decided comparison, with both scores as they were before it
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MatchRecord {
+    pub round: i32,
+    pub winner: String,
+    pub loser: String,
+    pub winner_score: i32,
+    pub loser_score: i32,
+    /// Time from the pair appearing to the decision; long ones were hard calls
+    #[serde(default)]
+    pub decision_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RoundSummary {
+    pub round: i32,
+    pub advancers: Vec<String>,
+    pub eliminated: Vec<String>,
+    /// Underdog wins, most surprising first
+    pub upsets: Vec<Upset>,
+    /// Decisions that took longest
+    pub closest: Vec<MatchRecord>,
+}
+
+pub fn summarize(state: &TournamentState) -> RoundSummary {
+    let (advancing, cut) = advancement::split(state);
+    let matches: Vec<&MatchRecord> = state
+        .history
+        .iter()
+        .filter(|m| m.round == state.current_round)
+        .collect();
+
+    let mut closest: Vec<&MatchRecord> = matches
+        .iter()
+        .copied()
+        .filter(|m| m.decision_ms.is_some())
+        .collect();
+    closest.sort_by_key(|m| std::cmp::Reverse(m.decision_ms));
+
+    let paths = |samples: Vec<&Sample>| samples.iter().map(|s| s.path.clone()).collect();
+    RoundSummary {
+        round: state.current_round,
+        advancers: paths(advancing),
+        eliminated: paths(cut),
+        upsets: statistics::upsets(matches)
+            .into_iter()
+            .take(SUMMARY_MATCHES)
+            .collect(),
+        closest: closest.into_iter().take(SUMMARY_MATCHES).cloned().collect(),
+    }
+}
+
+/// Summarize the round once its last comparison is decided and store the summary in
+/// the state; returns it the first time
+pub fn complete_round(state: &mut TournamentState) -> Option<RoundSummary> {
+    let finished = state.current_comparison_index >= state.comparisons_this_round.len();
+    if !finished
+        || state
+            .round_summaries
+            .iter()
+            .any(|s| s.round == state.current_round)
+    {
+        return None;
+    }
+
+    let summary = summarize(state);
+    state.round_summaries.push(summary.clone());
+    Some(summary)
+}
+
+/// Tell every window a round finished
+pub fn announce(app: &AppHandle, summary: &RoundSummary) {
+    if let Err(e) = app.emit("round://complete", summary) {
+        tracing::warn!(error = %e, "failed to emit round summary");
+    }
+}
@@ -0,0 +1,199 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::round_summary::MatchRecord;
+use crate::savefile::{self, Compression};
+use crate::{i18n, voting, AllowedPaths, TournamentState};
+
+/// Result of folding conflicted copies of a save back into one
+#[derive(Debug, Serialize, Clone)]
+pub struct ConflictMerge {
+    /// Where the merged save was written
+    pub path: String,
+    /// Copies merged, the base included
+    pub copies: usize,
+    /// Comparisons the base was missing, now in its history and scores
+    pub added: usize,
+    /// Of those, comparisons from another round than the base's, or whose samples are
+    /// no longer in its pool; they are kept in the history but don't change any score
+    pub unapplied: usize,
+    pub state: TournamentState,
+}
+
+/// Whether `name` is a sync client's conflicted copy of `original`: Dropbox's
+/// `save (Alex's conflicted copy 2026-01-02).vs1` or Syncthing's
+/// `save.sync-conflict-20260102-101500-ABCDEFG.vs1`
+fn is_conflict_of(name: &str, original: &Path) -> bool {
+    let Some(stem) = original.file_stem().map(|s| s.to_string_lossy()) else {
+        return false;
+    };
+    let extension = original
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let Some(middle) = name
+        .strip_prefix(stem.as_ref())
+        .and_then(|rest| rest.strip_suffix(extension.as_str()))
+    else {
+        return false;
+    };
+    (middle.starts_with(" (") && middle.ends_with(')') && middle.contains("conflicted copy"))
+        || middle.starts_with(".sync-conflict-")
+}
+
+/// Conflicted copies of `path` sitting next to it
+pub fn conflicted_copies(path: &Path) -> Vec<PathBuf> {
+    let Some(dir) = path.parent() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut copies: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| is_conflict_of(&entry.file_name().to_string_lossy(), path))
+        .map(|entry| entry.path())
+        .collect();
+    copies.sort();
+    copies
+}
+
+/// Records of `other` that `base` lacks. Copies share the history from before they
+/// diverged; past that, a record counts as new unless the base has it as well.
+fn missing_records<'a>(base: &[MatchRecord], other: &'a [MatchRecord]) -> Vec<&'a MatchRecord> {
+    let shared = base.iter().zip(other).take_while(|(a, b)| a == b).count();
+    let mut unmatched: Vec<&MatchRecord> = base[shared..].iter().collect();
+    other[shared..]
+        .iter()
+        .filter(|record| match unmatched.iter().position(|r| r == record) {
+            Some(found) => {
+                unmatched.swap_remove(found);
+                false
+            }
+            None => true,
+        })
+        .collect()
+}
+
+/// Add one comparison to the state, scored as a vote scores it, and take its pair off
+/// what the base still had coming this round. Only comparisons of the base's current
+/// round are scored; earlier rounds' scores were carried over or cut when they ended.
+fn apply(state: &mut TournamentState, record: &MatchRecord) -> bool {
+    if record.round != state.current_round {
+        return false;
+    }
+    let position = |path: &str| state.samples.iter().position(|s| s.path == path);
+    let (Some(winner), Some(loser)) = (position(&record.winner), position(&record.loser)) else {
+        return false;
+    };
+    for (index, points) in [(winner, 1), (loser, 0)] {
+        let sample = &mut state.samples[index];
+        sample.score += points;
+        sample.comparisons += 1;
+        voting::settle(sample);
+    }
+
+    // Moved up to the current comparison and past it, so the order of the rest holds
+    let current = state.current_comparison_index;
+    let upcoming = state
+        .comparisons_this_round
+        .get(current..)
+        .and_then(|rest| {
+            rest.iter()
+                .position(|&pair| pair == (winner, loser) || pair == (loser, winner))
+        });
+    if let Some(offset) = upcoming {
+        let pair = state.comparisons_this_round.remove(current + offset);
+        state.comparisons_this_round.insert(current, pair);
+        state.current_comparison_index += 1;
+    }
+    true
+}
+
+/// Merge copies of one tournament. The copy with the longest history is the base and
+/// keeps its round and schedule; the comparisons of that round only the others have are
+/// replayed onto it, and collaborative ballots are pooled. Returns the merged state with the
+/// number of comparisons added and how many of those had no samples left to score.
+pub fn merge(mut copies: Vec<TournamentState>) -> Option<(TournamentState, usize, usize)> {
+    let longest = (0..copies.len()).max_by_key(|&i| (copies[i].history.len(), usize::MAX - i))?;
+    let mut base = copies.swap_remove(longest);
+    let (mut added, mut unapplied) = (0, 0);
+
+    for other in &copies {
+        for record in missing_records(&base.history, &other.history) {
+            if !apply(&mut base, record) {
+                unapplied += 1;
+            }
+            base.history.push(record.clone());
+            added += 1;
+        }
+        for vote in &other.votes {
+            if !base.votes.iter().any(|v| v.pair_id == vote.pair_id) {
+                base.votes.push(vote.clone());
+            }
+        }
+    }
+    Some((base, added, unapplied))
+}
+
+/// Sync clients' conflicted copies of the save at `file_path`, to offer merging them
+#[tauri::command]
+pub fn find_conflicted_saves(
+    file_path: &str,
+    allowed_paths: State<AllowedPaths>,
+) -> Result<Vec<String>, CommandError> {
+    let path = Path::new(file_path);
+    allowed_paths.check_file(path)?;
+    Ok(conflicted_copies(path)
+        .into_iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect())
+}
+
+/// Fold conflicted copies of one save into a single save holding every comparison any
+/// of them recorded, instead of keeping one and losing the others' votes. The result
+/// replaces `output`, or the first path; its previous content goes to the backups like
+/// any save. The copies themselves are left for the user to delete.
+#[tauri::command]
+pub fn resolve_conflicted_saves(
+    paths: Vec<String>,
+    password: Option<String>,
+    compression: Option<Compression>,
+    output: Option<String>,
+    allowed_paths: State<AllowedPaths>,
+) -> Result<ConflictMerge, CommandError> {
+    let copies = paths
+        .iter()
+        .map(|path| {
+            allowed_paths.check_file(Path::new(path))?;
+            let bytes = fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+            savefile::decode(bytes, password.as_deref())
+                .map_err(|e| CommandError::from(format!("{}: {}", path, e)))
+        })
+        .collect::<Result<Vec<_>, CommandError>>()?;
+    let count = copies.len();
+    let (state, added, unapplied) = merge(copies).ok_or_else(|| i18n::t("merge-no-saves"))?;
+
+    let target = output
+        .or_else(|| paths.first().cloned())
+        .unwrap_or_default();
+    crate::write_save(
+        &state,
+        &target,
+        compression,
+        password.as_deref(),
+        &allowed_paths,
+    )?;
+    tracing::info!(copies = count, added, unapplied, "conflicted saves merged");
+
+    Ok(ConflictMerge {
+        path: target,
+        copies: count,
+        added,
+        unapplied,
+        state,
+    })
+}
@@ -0,0 +1,115 @@
+use age::secrecy::Secret;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+use crate::{i18n, scheduler, TournamentState};
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+const AGE_MAGIC: &[u8] = b"age-encryption.org/v1";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    /// Pretty-printed JSON, readable in any text editor
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+/// Serialize a tournament for disk; compressed saves use compact JSON
+pub fn encode(state: &TournamentState, compression: Compression) -> Result<Vec<u8>, String> {
+    match compression {
+        Compression::None => serde_json::to_vec_pretty(state).map_err(|e| e.to_string()),
+        Compression::Gzip => {
+            let json = serde_json::to_vec(state).map_err(|e| e.to_string())?;
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&json).map_err(|e| e.to_string())?;
+            encoder.finish().map_err(|e| e.to_string())
+        }
+        Compression::Zstd => {
+            let json = serde_json::to_vec(state).map_err(|e| e.to_string())?;
+            zstd::encode_all(json.as_slice(), 0).map_err(|e| e.to_string())
+        }
+    }
+}
+
+/// Undo any compression, detected from the file's magic bytes
+pub fn decompress(bytes: Vec<u8>) -> Result<Vec<u8>, String> {
+    if bytes.starts_with(GZIP_MAGIC) {
+        let mut json = Vec::new();
+        flate2::read::GzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut json)
+            .map_err(|e| e.to_string())?;
+        Ok(json)
+    } else if bytes.starts_with(ZSTD_MAGIC) {
+        zstd::decode_all(bytes.as_slice()).map_err(|e| e.to_string())
+    } else {
+        Ok(bytes)
+    }
+}
+
+pub fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.starts_with(AGE_MAGIC)
+}
+
+/// Passphrase-encrypt arbitrary bytes in the age format
+pub fn encrypt(bytes: &[u8], password: &str) -> Result<Vec<u8>, String> {
+    let encryptor = age::Encryptor::with_user_passphrase(Secret::new(password.to_string()));
+
+    let mut encrypted = Vec::new();
+    let mut writer = encryptor
+        .wrap_output(&mut encrypted)
+        .map_err(|e| e.to_string())?;
+    writer.write_all(bytes).map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    Ok(encrypted)
+}
+
+pub fn decrypt(bytes: &[u8], password: &str) -> Result<Vec<u8>, String> {
+    let decryptor = match age::Decryptor::new(bytes).map_err(|e| e.to_string())? {
+        age::Decryptor::Passphrase(d) => d,
+        _ => return Err(i18n::t("save-not-passphrase")),
+    };
+
+    let mut reader = decryptor
+        .decrypt(&Secret::new(password.to_string()), None)
+        .map_err(|_| "Wrong password or corrupted file".to_string())?;
+    let mut decrypted = Vec::new();
+    reader
+        .read_to_end(&mut decrypted)
+        .map_err(|e| e.to_string())?;
+    Ok(decrypted)
+}
+
+/// Serialize, compress, and (if a password is given) encrypt a tournament, with a
+/// snapshot of the scheduler so loading resumes on the same comparison
+pub fn write(
+    state: &TournamentState,
+    compression: Compression,
+    password: Option<&str>,
+) -> Result<Vec<u8>, String> {
+    let mut state = state.clone();
+    state.scheduler = Some(scheduler::snapshot(&state));
+    let bytes = encode(&state, compression)?;
+    match password {
+        Some(password) => encrypt(&bytes, password),
+        None => Ok(bytes),
+    }
+}
+
+pub fn decode(bytes: Vec<u8>, password: Option<&str>) -> Result<TournamentState, String> {
+    let bytes = if is_encrypted(&bytes) {
+        let password = password.ok_or_else(|| i18n::t("save-password-required"))?;
+        decrypt(&bytes, password)?
+    } else {
+        bytes
+    };
+
+    let json = decompress(bytes)?;
+    let mut state: TournamentState = serde_json::from_slice(&json).map_err(|e| e.to_string())?;
+    scheduler::restore(&mut state);
+    Ok(state)
+}
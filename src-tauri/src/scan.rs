@@ -0,0 +1,466 @@
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+use tauri::State;
+use walkdir::WalkDir;
+
+use crate::rng::{self, Rng};
+use crate::video::is_video;
+use crate::{archive, cloud, i18n, instruments, packs, AllowedPaths, Sample};
+
+pub const AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "flac", "ogg", "aiff", "m4a"];
+
+/// Optional knobs for `scan_directory`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ScanOptions {
+    /// List audio entries inside .zip archives as well
+    pub include_archives: bool,
+    /// Include video files (requires the `video` feature and ffmpeg)
+    pub include_video: bool,
+    /// Decode every file after scanning to measure levels and flag problems
+    pub analyze: bool,
+    /// Drop files that are silent for more than this fraction of their length (implies `analyze`)
+    pub max_silence_ratio: Option<f32>,
+    /// Pull in the samples referenced by sampler instruments (.sfz, .xrni)
+    pub include_instruments: bool,
+    /// Collapse filename variations (kick_01a, kick_01b) into one entrant
+    pub group_variations: bool,
+    /// How many folder levels to descend: 1 scans only the selected folder's own files,
+    /// 2 also its subfolders, ...; `None` scans everything
+    pub max_depth: Option<usize>,
+    /// Scan dotfiles and hidden folders (`.git`, macOS `._` resource forks), as scans
+    /// always did; iCloud placeholders are picked up either way
+    pub include_hidden: bool,
+    /// Keep a random subset of at most this many files, picked while walking so a huge
+    /// library never builds its full list; samples referenced by instruments come on top
+    pub sample_limit: Option<usize>,
+    /// Seed for the random subset; `None` picks one, reported back in the scan report
+    pub sample_seed: Option<u32>,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            include_archives: false,
+            include_video: false,
+            analyze: false,
+            max_silence_ratio: None,
+            include_instruments: false,
+            group_variations: false,
+            max_depth: None,
+            include_hidden: true,
+            sample_limit: None,
+            sample_seed: None,
+        }
+    }
+}
+
+impl ScanOptions {
+    pub fn needs_analysis(&self) -> bool {
+        self.analyze || self.max_silence_ratio.is_some()
+    }
+
+    /// Apply analysis-based filters; unanalyzed samples are kept
+    pub fn keep(&self, sample: &Sample) -> bool {
+        match (self.max_silence_ratio, &sample.analysis) {
+            (Some(max), Some(analysis)) => analysis.silence_ratio <= max,
+            _ => true,
+        }
+    }
+}
+
+/// Problem paths kept in a report; the counts cover everything
+const MAX_SKIPPED_EXAMPLES: usize = 20;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    Unreadable,
+    PermissionDenied,
+    Symlink,
+    BadArchive,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SkippedEntry {
+    pub path: String,
+    pub reason: SkipReason,
+}
+
+/// What a scan passed over and why, so missing samples aren't a mystery
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ScanReport {
+    /// Files visited
+    pub files: u64,
+    /// Entries that couldn't be read (vanished mid-scan, I/O errors, link loops)
+    pub unreadable: usize,
+    /// Entries the operating system refused to list or open
+    pub permission_denied: usize,
+    /// Files that aren't audio (or an enabled archive, instrument or video type)
+    pub non_audio: usize,
+    /// Symbolic links, which are never followed
+    pub symlinks: usize,
+    /// Archives that couldn't be opened
+    pub bad_archives: usize,
+    /// Hidden files and folders left out (folders count once, not per file inside)
+    pub hidden: usize,
+    /// Samples dropped for being mostly silence
+    pub too_silent: usize,
+    /// How the subset was picked, when the scan was capped
+    pub sampling: Option<ScanSampling>,
+    /// The first problem paths, for showing what went wrong
+    pub skipped: Vec<SkippedEntry>,
+}
+
+impl ScanReport {
+    pub fn skip(&mut self, path: &Path, reason: SkipReason) {
+        match reason {
+            SkipReason::Unreadable => self.unreadable += 1,
+            SkipReason::PermissionDenied => self.permission_denied += 1,
+            SkipReason::Symlink => self.symlinks += 1,
+            SkipReason::BadArchive => self.bad_archives += 1,
+        }
+        if self.skipped.len() < MAX_SKIPPED_EXAMPLES {
+            self.skipped.push(SkippedEntry {
+                path: path.to_string_lossy().to_string(),
+                reason,
+            });
+        }
+    }
+}
+
+/// How a capped scan picked its subset; kept in the tournament so it can be repeated
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ScanSampling {
+    pub limit: usize,
+    pub seed: u32,
+    /// Matching files before sampling
+    pub population: u64,
+}
+
+/// A file the walk turned up, made into a sample only if it survives sampling
+enum Found {
+    File(PathBuf),
+    OnlineOnly(PathBuf),
+    Entry(Sample),
+}
+
+impl Found {
+    fn into_sample(self) -> Sample {
+        match self {
+            Found::File(path) => new_sample(&path),
+            Found::OnlineOnly(path) => {
+                let mut sample = new_sample(&path);
+                sample.online_only = true;
+                sample
+            }
+            Found::Entry(sample) => sample,
+        }
+    }
+}
+
+/// Uniform random subset of a stream of unknown length (reservoir sampling); without
+/// a limit it keeps everything
+struct Reservoir<T> {
+    limit: Option<usize>,
+    seed: u32,
+    rng: Rng,
+    seen: u64,
+    /// Items with their position in the stream, to restore walk order afterwards
+    items: Vec<(u64, T)>,
+}
+
+impl<T> Reservoir<T> {
+    fn new(limit: Option<usize>, seed: Option<u32>) -> Self {
+        let seed = match limit {
+            Some(_) => seed.unwrap_or_else(rng::random_seed),
+            None => 0,
+        };
+        Self {
+            limit,
+            seed,
+            rng: Rng::new(seed),
+            seen: 0,
+            items: Vec::new(),
+        }
+    }
+
+    fn offer(&mut self, item: T) {
+        let index = self.seen;
+        self.seen += 1;
+        match self.limit {
+            Some(limit) if self.items.len() >= limit => {
+                let slot = self.rng.below(self.seen) as usize;
+                if slot < limit {
+                    self.items[slot] = (index, item);
+                }
+            }
+            _ => self.items.push((index, item)),
+        }
+    }
+
+    fn sampling(&self) -> Option<ScanSampling> {
+        self.limit.map(|limit| ScanSampling {
+            limit,
+            seed: self.seed,
+            population: self.seen,
+        })
+    }
+
+    fn into_items(mut self) -> Vec<T> {
+        self.items.sort_by_key(|(index, _)| *index);
+        self.items.into_iter().map(|(_, item)| item).collect()
+    }
+}
+
+/// Rough size of a scan, counted from file names alone
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ScanEstimate {
+    /// Audio (and video, if enabled) files that would become samples
+    pub samples: u64,
+    /// Archives whose entries would be added; they aren't opened to count them
+    pub archives: u64,
+    /// Instruments whose referenced samples would be added
+    pub instruments: u64,
+    /// Samples after the sampling cap, i.e. what the tournament would start with
+    pub would_add: u64,
+}
+
+/// Count what a scan with `options` would pick up without reading any file, so the
+/// UI can warn before a scan (and analysis) that would take ages
+pub fn estimate(root: &Path, options: &ScanOptions) -> ScanEstimate {
+    let mut estimate = ScanEstimate::default();
+    let walker = walk_dir(root, options)
+        .into_iter()
+        .filter_entry(|entry| visible(entry, options))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| !entry.path_is_symlink() && entry.file_type().is_file());
+    for entry in walker {
+        let path = entry.path();
+        if is_audio_name(&path.to_string_lossy()) {
+            estimate.samples += 1;
+        } else if let Some(target) = cloud::icloud_stub_target(path) {
+            estimate.samples += is_audio_name(&target.to_string_lossy()) as u64;
+        } else if options.include_instruments && instruments::is_instrument(path) {
+            estimate.instruments += 1;
+        } else if options.include_archives && archive::is_archive(path) {
+            estimate.archives += 1;
+        } else if options.include_video && is_video(path) {
+            estimate.samples += 1;
+        }
+    }
+
+    estimate.would_add = match options.sample_limit {
+        Some(limit) => estimate.samples.min(limit as u64),
+        None => estimate.samples,
+    };
+    estimate
+}
+
+#[tauri::command]
+pub async fn estimate_scan(
+    directory: String,
+    options: Option<ScanOptions>,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<ScanEstimate, String> {
+    let root = PathBuf::from(directory);
+    if !root.is_dir() {
+        return Err(i18n::t("directory-not-found"));
+    }
+    // Estimating is the first step of a scan, so the folder is registered the same way
+    let root = root.canonicalize().map_err(|e| e.to_string())?;
+    allowed_paths.set_source_directory(root.clone());
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || estimate(&root, &options))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Samples from `scan_directory` along with what was skipped
+#[derive(Debug, Serialize, Clone)]
+pub struct ScanOutcome {
+    pub samples: Vec<Sample>,
+    pub report: ScanReport,
+}
+
+#[cfg(windows)]
+fn has_hidden_attribute(entry: &walkdir::DirEntry) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+
+    entry
+        .metadata()
+        .is_ok_and(|m| m.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+}
+
+#[cfg(not(windows))]
+fn has_hidden_attribute(_entry: &walkdir::DirEntry) -> bool {
+    false
+}
+
+fn is_hidden(entry: &walkdir::DirEntry) -> bool {
+    let dotfile = entry.file_name().to_string_lossy().starts_with('.');
+    (dotfile || has_hidden_attribute(entry)) && cloud::icloud_stub_target(entry.path()).is_none()
+}
+
+// Don't follow symlinks to prevent escape attacks
+fn walk_dir(root: &Path, options: &ScanOptions) -> WalkDir {
+    WalkDir::new(root)
+        .follow_links(false)
+        .max_depth(options.max_depth.unwrap_or(usize::MAX))
+}
+
+/// The root itself is scanned even if it's hidden; skipped folders aren't entered
+fn visible(entry: &walkdir::DirEntry, options: &ScanOptions) -> bool {
+    options.include_hidden || entry.depth() == 0 || !is_hidden(entry)
+}
+
+pub fn is_audio_name(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .map(|ext| {
+            let ext_lower = ext.to_string_lossy().to_lowercase();
+            AUDIO_EXTENSIONS.contains(&ext_lower.as_str())
+        })
+        .unwrap_or(false)
+}
+
+/// Build a fresh sample for a file with a supported audio extension
+pub fn sample_from_path(path: &Path) -> Option<Sample> {
+    if !is_audio_name(&path.to_string_lossy()) {
+        return None;
+    }
+
+    Some(new_sample(path))
+}
+
+fn new_sample(path: &Path) -> Sample {
+    let filename = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    Sample {
+        path: path.to_string_lossy().to_string(),
+        filename,
+        score: 0,
+        comparisons: 0,
+        provisional: false,
+        online_only: cloud::is_placeholder(path),
+        analysis: None,
+        variants: Vec::new(),
+        pack: None,
+        instrument: None,
+        channels: None,
+        pin: None,
+        benched: false,
+        uncertainty: 0.0,
+        tags: Vec::new(),
+    }
+}
+
+/// Walk `root` and collect every audio file (and archive entry, if enabled)
+pub fn scan_samples(root: &Path, options: &ScanOptions) -> Vec<Sample> {
+    scan_samples_with_progress(root, options, |_| {}).0
+}
+
+/// Like [`scan_samples`], reporting the number of files visited so far and returning
+/// what was skipped along the way
+pub fn scan_samples_with_progress(
+    root: &Path,
+    options: &ScanOptions,
+    mut on_progress: impl FnMut(u64),
+) -> (Vec<Sample>, ScanReport) {
+    let mut found = Reservoir::new(options.sample_limit, options.sample_seed);
+    let mut instrument_files = Vec::new();
+    let mut report = ScanReport::default();
+    let mut hidden = 0;
+
+    let mut walk = walk_dir(root, options);
+    if options.sample_limit.is_some() {
+        // A fixed walk order is what makes the seed reproduce the same subset
+        walk = walk.sort_by_file_name();
+    }
+    let walker = walk.into_iter().filter_entry(|entry| {
+        let skip = !visible(entry, options);
+        hidden += skip as usize;
+        !skip
+    });
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(err) => {
+                tracing::debug!(error = %err, "skipping unreadable entry");
+                let denied = err
+                    .io_error()
+                    .is_some_and(|e| e.kind() == io::ErrorKind::PermissionDenied);
+                let reason = if denied {
+                    SkipReason::PermissionDenied
+                } else {
+                    SkipReason::Unreadable
+                };
+                report.skip(err.path().unwrap_or(root), reason);
+                continue;
+            }
+        };
+        let entry_path = entry.path();
+        if entry.path_is_symlink() {
+            report.skip(entry_path, SkipReason::Symlink);
+            continue;
+        }
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        report.files += 1;
+        on_progress(report.files);
+
+        if is_audio_name(&entry_path.to_string_lossy()) {
+            found.offer(Found::File(entry_path.to_path_buf()));
+        } else if let Some(target) = cloud::icloud_stub_target(entry_path) {
+            // Evicted iCloud files only exist as hidden `.Name.wav.icloud` stubs
+            if is_audio_name(&target.to_string_lossy()) {
+                found.offer(Found::OnlineOnly(target));
+            }
+        } else if options.include_instruments && instruments::is_instrument(entry_path) {
+            instrument_files.push(entry_path.to_path_buf());
+        } else if options.include_archives && archive::is_archive(entry_path) {
+            // Unreadable archives are skipped like unreadable directory entries
+            match archive::list_audio_entries(entry_path) {
+                Ok(entries) => entries
+                    .into_iter()
+                    .for_each(|sample| found.offer(Found::Entry(sample))),
+                Err(e) => {
+                    tracing::warn!(archive = %entry_path.display(), error = %e, "skipping archive");
+                    report.skip(entry_path, SkipReason::BadArchive);
+                }
+            }
+        } else if options.include_video && is_video(entry_path) {
+            found.offer(Found::File(entry_path.to_path_buf()));
+        } else {
+            report.non_audio += 1;
+        }
+    }
+
+    report.hidden = hidden;
+    report.sampling = found.sampling();
+    if let Some(sampling) = &report.sampling {
+        tracing::info!(
+            limit = sampling.limit,
+            seed = sampling.seed,
+            population = sampling.population,
+            "scan sampled"
+        );
+    }
+
+    // Only the chosen files get their headers read
+    let mut samples: Vec<Sample> = found
+        .into_items()
+        .into_iter()
+        .map(Found::into_sample)
+        .collect();
+    instruments::include_referenced(root, &instrument_files, &mut samples);
+    packs::assign(root, &mut samples);
+    (samples, report)
+}
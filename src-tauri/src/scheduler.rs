@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::TournamentState;
+
+/// Where the scheduler stood when a session was saved. Pairs are kept by path, since
+/// `comparisons_this_round` holds positions in `samples` that anything reordering the
+/// pool would silently repoint.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SchedulerSnapshot {
+    pub round: i32,
+    /// The comparison on screen when the session was saved
+    pub in_flight: Option<(String, String)>,
+    /// Comparisons still to come after it, in order
+    pub pending: Vec<(String, String)>,
+    /// Seed of the pairing and calibration streams. They are keyed by round and
+    /// comparison index, so with these restored every later draw repeats too.
+    pub seed: u32,
+}
+
+pub fn snapshot(state: &TournamentState) -> SchedulerSnapshot {
+    let by_path = |&(a, b): &(usize, usize)| {
+        let path = |i: usize| state.samples.get(i).map(|s| s.path.clone());
+        Some((path(a)?, path(b)?))
+    };
+    let mut upcoming = state
+        .comparisons_this_round
+        .iter()
+        .skip(state.current_comparison_index);
+    SchedulerSnapshot {
+        round: state.current_round,
+        in_flight: upcoming.next().and_then(by_path),
+        pending: upcoming.filter_map(by_path).collect(),
+        seed: state.seed,
+    }
+}
+
+/// Point the upcoming comparisons back at the samples the snapshot named, so the
+/// session resumes on exactly the comparison it was saved on. A snapshot from
+/// another round, or naming samples no longer in the pool, leaves the state alone.
+pub fn restore(state: &mut TournamentState) {
+    let Some(saved) = state.scheduler.take() else {
+        return;
+    };
+    if saved.round != state.current_round {
+        tracing::warn!(
+            saved = saved.round,
+            round = state.current_round,
+            "scheduler snapshot is from another round, ignored"
+        );
+        return;
+    }
+
+    let positions: HashMap<&str, usize> = state
+        .samples
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.path.as_str(), i))
+        .collect();
+    let upcoming: Option<Vec<(usize, usize)>> = saved
+        .in_flight
+        .iter()
+        .chain(&saved.pending)
+        .map(|(a, b)| Some((*positions.get(a.as_str())?, *positions.get(b.as_str())?)))
+        .collect();
+    let Some(upcoming) = upcoming else {
+        tracing::warn!("scheduler snapshot names samples missing from the pool, ignored");
+        return;
+    };
+
+    let done = state
+        .current_comparison_index
+        .min(state.comparisons_this_round.len());
+    state.comparisons_this_round.truncate(done);
+    state.comparisons_this_round.extend(upcoming);
+    state.current_comparison_index = done;
+    state.seed = saved.seed;
+}
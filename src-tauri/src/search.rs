@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use crate::analysis::Analysis;
+use crate::export::{passes_min_score, sorted_results};
+use crate::Sample;
+
+/// What to order search results by; descriptor keys sort low to high (e.g. darkest first)
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    /// Standings order: score, then win rate
+    Score,
+    /// Spectral centroid
+    Brightness,
+    /// Spectral rolloff
+    Rolloff,
+    /// Spectral flatness (tonal to noisy)
+    Flatness,
+    Duration,
+}
+
+impl SortKey {
+    fn descriptor(self, analysis: &Analysis) -> f32 {
+        match self {
+            SortKey::Score => 0.0,
+            SortKey::Brightness => analysis.spectral_centroid_hz,
+            SortKey::Rolloff => analysis.spectral_rolloff_hz,
+            SortKey::Flatness => analysis.spectral_flatness,
+            SortKey::Duration => analysis.duration_secs,
+        }
+    }
+}
+
+/// Filter and ordering applied on top of the standings,
+/// e.g. "darkest 20 kicks above score 5"
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SampleQuery {
+    /// Case-insensitive filename substring
+    pub text: Option<String>,
+    pub min_score: Option<i32>,
+    pub max_score: Option<i32>,
+    pub sort: Option<SortKey>,
+    /// Reverse the sort (brightest first, longest first, ...)
+    pub descending: bool,
+    pub limit: Option<usize>,
+}
+
+impl SampleQuery {
+    fn matches(&self, sample: &Sample) -> bool {
+        let text = self.text.as_deref().map(str::to_lowercase);
+        text.is_none_or(|text| sample.filename.to_lowercase().contains(&text))
+            && self
+                .min_score
+                .is_none_or(|min| passes_min_score(sample, min))
+            && self.max_score.is_none_or(|max| sample.score <= max)
+    }
+}
+
+/// Ranked samples matching `query`. Samples without analysis sort after
+/// analyzed ones when ordering by a descriptor.
+pub fn search<'a>(samples: &'a [Sample], query: &SampleQuery) -> Vec<&'a Sample> {
+    let mut found: Vec<&Sample> = sorted_results(samples)
+        .into_iter()
+        .filter(|sample| query.matches(sample))
+        .collect();
+
+    match query.sort.unwrap_or(SortKey::Score) {
+        SortKey::Score => {
+            if query.descending {
+                found.reverse();
+            }
+        }
+        key => found.sort_by(|a, b| match (&a.analysis, &b.analysis) {
+            (Some(a), Some(b)) => {
+                let order = key
+                    .descriptor(a)
+                    .partial_cmp(&key.descriptor(b))
+                    .unwrap_or(Ordering::Equal);
+                if query.descending {
+                    order.reverse()
+                } else {
+                    order
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }),
+    }
+
+    found.truncate(query.limit.unwrap_or(usize::MAX));
+    found
+}
+
+#[tauri::command]
+pub fn search_samples(samples: Vec<Sample>, query: SampleQuery) -> Vec<Sample> {
+    search(&samples, &query).into_iter().cloned().collect()
+}
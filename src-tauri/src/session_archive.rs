@@ -0,0 +1,250 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{AppHandle, Manager, State};
+use zip::write::SimpleFileOptions;
+
+use crate::copy_export::{copy_hashed, open_source};
+use crate::jobs::{self, JobKind};
+use crate::journal::now_secs;
+use crate::manifest::{self, ExportKind, ExportManifest, ManifestEntry};
+use crate::settings::SettingsStore;
+use crate::{audit, export, i18n, notifications};
+use crate::{AllowedPaths, TournamentState};
+
+const STATE_ENTRY: &str = "session.json";
+const SAMPLES_DIR: &str = "samples";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SessionArchiveReport {
+    pub archive_path: String,
+    pub samples_included: usize,
+    pub failed: Vec<String>,
+    /// Written next to the archive when asked for
+    pub manifest_path: Option<String>,
+}
+
+/// Trim a tournament down to its winners, starting a fresh round with them
+fn winners_only(state: &TournamentState, min_score: i32) -> TournamentState {
+    let mut trimmed = state.clone();
+    trimmed
+        .samples
+        .retain(|s| export::passes_min_score(s, min_score));
+    trimmed.comparisons_this_round.clear();
+    trimmed.current_comparison_index = 0;
+    trimmed
+}
+
+/// Write the save state and copies of its sample files into a single zip, and
+/// optionally a manifest of what went in beside it. Samples outside the allowed
+/// folders are left out, since their paths come from the webview.
+pub fn write_archive(
+    state: &TournamentState,
+    allowed_paths: &AllowedPaths,
+    dest: &Path,
+    with_manifest: bool,
+    on_progress: impl Fn(u64),
+) -> Result<SessionArchiveReport, String> {
+    let file = File::create(dest).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipWriter::new(BufWriter::new(file));
+
+    // Audio is already compressed; storing avoids burning CPU for nothing
+    let stored = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .large_file(true);
+
+    let ranks: HashMap<&str, usize> = export::sorted_results(&state.samples)
+        .into_iter()
+        .enumerate()
+        .map(|(index, sample)| (sample.path.as_str(), index + 1))
+        .collect();
+    let mut manifest = ExportManifest::new(ExportKind::Archive);
+
+    let mut archived = state.clone();
+    let mut failed = Vec::new();
+    let mut included = 0;
+
+    for (index, sample) in archived.samples.iter_mut().enumerate() {
+        on_progress(index as u64);
+        let entry_name = format!("{}/{:05}_{}", SAMPLES_DIR, index, sample.filename);
+
+        if !allowed_paths.is_path_allowed(Path::new(&sample.path)) {
+            failed.push(format!("{}: {}", sample.path, i18n::t("access-denied")));
+            continue;
+        }
+        let mut reader = match open_source(&sample.path) {
+            Ok(reader) => reader,
+            Err(e) => {
+                failed.push(format!("{}: {}", sample.path, e));
+                continue;
+            }
+        };
+
+        zip.start_file(entry_name.as_str(), stored)
+            .map_err(|e| e.to_string())?;
+        let (size, sha256) = copy_hashed(&mut reader, &mut zip).map_err(|e| e.to_string())?;
+        manifest.files.push(ManifestEntry {
+            source: sample.path.clone(),
+            destination: entry_name.clone(),
+            rank: ranks.get(sample.path.as_str()).copied().unwrap_or(0),
+            score: sample.score,
+            size,
+            sha256,
+            verified: false,
+            renamed_from: None,
+            timestamp: now_secs(),
+        });
+
+        sample.path = entry_name;
+        included += 1;
+    }
+
+    archived.source_directory = String::new();
+    let json = serde_json::to_vec_pretty(&archived).map_err(|e| e.to_string())?;
+    zip.start_file(STATE_ENTRY, SimpleFileOptions::default())
+        .map_err(|e| e.to_string())?;
+    zip.write_all(&json).map_err(|e| e.to_string())?;
+
+    zip.finish().map_err(|e| e.to_string())?;
+    audit::record_write(dest, None);
+
+    let manifest_path = if with_manifest {
+        manifest
+            .files
+            .sort_by_key(|entry| (entry.rank == 0, entry.rank));
+        let path = manifest::manifest_path(dest);
+        manifest::write(&path, &manifest)?;
+        audit::record_write(&path, None);
+        Some(path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
+    Ok(SessionArchiveReport {
+        archive_path: dest.to_string_lossy().to_string(),
+        samples_included: included,
+        failed,
+        manifest_path,
+    })
+}
+
+/// Unpack a session archive into `dest_dir` and point the state at the extracted files
+pub fn read_archive(archive_path: &Path, dest_dir: &Path) -> Result<TournamentState, String> {
+    let file = File::open(archive_path).map_err(|e| e.to_string())?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+
+    let mut state: TournamentState = {
+        let mut entry = zip
+            .by_name(STATE_ENTRY)
+            .map_err(|_| "Not a VS-1 session archive".to_string())?;
+        let mut json = Vec::new();
+        entry.read_to_end(&mut json).map_err(|e| e.to_string())?;
+        serde_json::from_slice(&json).map_err(|e| e.to_string())?
+    };
+
+    fs::create_dir_all(dest_dir.join(SAMPLES_DIR)).map_err(|e| e.to_string())?;
+
+    for sample in state.samples.iter_mut() {
+        // Samples that failed to archive still carry their original absolute path
+        if !sample.path.starts_with(SAMPLES_DIR) {
+            continue;
+        }
+
+        let mut entry = zip.by_name(&sample.path).map_err(|e| e.to_string())?;
+        let relative: PathBuf = entry.enclosed_name().ok_or_else(|| {
+            i18n::t_args(
+                "archive-unsafe-entry",
+                [("name", sample.path.as_str().into())],
+            )
+        })?;
+        let target = dest_dir.join(relative);
+
+        let mut out = File::create(&target).map_err(|e| e.to_string())?;
+        io::copy(&mut entry, &mut out).map_err(|e| e.to_string())?;
+        audit::record_write(&target, None);
+
+        sample.path = target.to_string_lossy().to_string();
+    }
+
+    state.source_directory = dest_dir.to_string_lossy().to_string();
+    Ok(state)
+}
+
+#[tauri::command]
+pub async fn export_session_archive(
+    state: TournamentState,
+    dest: String,
+    winners_min_score: Option<i32>,
+    write_manifest: Option<bool>,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<SessionArchiveReport, String> {
+    let dest = PathBuf::from(dest);
+    allowed_paths.check_file(&dest)?;
+
+    // The folder the user exported into is allowed from now on
+    if let Some(parent) = dest.parent() {
+        allowed_paths.add_allowed_path(parent.to_path_buf());
+    }
+
+    let state = match winners_min_score {
+        Some(min_score) => winners_only(&state, min_score),
+        None => state,
+    };
+
+    let started = Instant::now();
+    let job = jobs::start(&app, JobKind::Export, Some(state.samples.len() as u64));
+    let job_app = app.clone();
+    let report = tauri::async_runtime::spawn_blocking(move || {
+        write_archive(
+            &state,
+            &job_app.state::<AllowedPaths>(),
+            &dest,
+            write_manifest.unwrap_or(false),
+            |done| job.set_done(done),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    notifications::job_finished(
+        &app,
+        started,
+        &i18n::t("archive-complete"),
+        &i18n::t_args(
+            "archive-complete-body",
+            [("count", report.samples_included.into())],
+        ),
+    );
+    Ok(report)
+}
+
+#[tauri::command]
+pub async fn import_session_archive(
+    archive_path: String,
+    dest_dir: String,
+    allowed_paths: State<'_, AllowedPaths>,
+    settings: State<'_, SettingsStore>,
+) -> Result<TournamentState, String> {
+    // Unpacking copies the archived samples onto disk
+    settings.ensure_writable()?;
+    let archive = PathBuf::from(archive_path);
+    allowed_paths.check_file(&archive)?;
+    let dest = PathBuf::from(&dest_dir);
+    allowed_paths.check_file(&dest)?;
+    fs::create_dir_all(&dest).map_err(|e| e.to_string())?;
+
+    let extract_to = dest.clone();
+    let state = tauri::async_runtime::spawn_blocking(move || read_archive(&archive, &extract_to))
+        .await
+        .map_err(|e| e.to_string())??;
+
+    // The extracted samples become the new source directory
+    let canonical = dest.canonicalize().map_err(|e| e.to_string())?;
+    allowed_paths.set_source_directory(canonical);
+
+    Ok(state)
+}
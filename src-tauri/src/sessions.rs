@@ -0,0 +1,540 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::calibration::{self, Reference};
+use crate::collab::{Ballot, Choice, Decision, VoteRecord};
+use crate::error::CommandError;
+use crate::pins::Pin;
+use crate::remote::random_token;
+use crate::round_summary::{self, MatchRecord};
+use crate::savefile::Compression;
+use crate::{
+    advancement, bench, byes, duplicates, i18n, leaderboard, pins, plugins, queue, redemption,
+    rematches, tags, voting, AllowedPaths, Sample, TournamentState,
+};
+
+/// The authoritative copy of every open tournament, by session ID. Windows opening the
+/// same tournament share its session. Every change goes through [`change_session`] with
+/// the revision the caller last saw, and saves read the state from here instead of
+/// having the frontend send it along.
+#[derive(Default)]
+pub struct Sessions {
+    next_id: AtomicU64,
+    states: RwLock<HashMap<String, Session>>,
+}
+
+struct Session {
+    /// Bumped by every change, so a writer working from an older copy is turned away
+    /// instead of overwriting what another window or a remote voter did meanwhile
+    revision: u64,
+    /// Opens not closed yet; the session goes with the last close
+    windows: usize,
+    state: TournamentState,
+}
+
+/// A session's state along with the revision it is at
+#[derive(Debug, Serialize, Clone)]
+pub struct SessionSnapshot {
+    pub id: String,
+    pub revision: u64,
+    pub state: TournamentState,
+}
+
+/// Sent as `session://changed` after every accepted change
+#[derive(Debug, Serialize, Clone)]
+pub struct SessionChanged {
+    pub id: String,
+    pub revision: u64,
+    /// What a vote changed; `None` when windows have to fetch the state with
+    /// `get_session`
+    pub patch: Option<StatePatch>,
+}
+
+/// A change to an open session. Votes name the comparison they were cast on, so one
+/// arriving after the pair moved on is refused.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SessionChange {
+    /// Pair the first round of a new tournament
+    StartRound,
+    Vote {
+        pair_id: String,
+        winner: usize,
+        #[serde(default)]
+        decision_ms: Option<u64>,
+        /// Individual ballots when a collaborative session decided it
+        #[serde(default)]
+        ballots: Vec<Ballot>,
+    },
+    EliminateBoth {
+        pair_id: String,
+        #[serde(default)]
+        ballots: Vec<Ballot>,
+    },
+    Pin {
+        path: String,
+        pin: Option<Pin>,
+    },
+    Bench {
+        path: String,
+        benched: bool,
+    },
+    Tag {
+        path: String,
+        tags: Vec<String>,
+    },
+    /// New order of the first `indices.len()` upcoming comparisons
+    Reorder {
+        indices: Vec<usize>,
+    },
+    Reference {
+        path: Option<String>,
+        #[serde(default)]
+        interval: Option<usize>,
+    },
+    MergeDuplicates {
+        keep: String,
+        drop: Vec<String>,
+    },
+    /// Cut the round, carry scores over and pair the next one
+    AdvanceRound,
+    Redemption {
+        count: usize,
+    },
+}
+
+/// What a vote changed, so it reaches the windows without the whole state
+#[derive(Debug, Serialize, Clone)]
+pub struct StatePatch {
+    /// Replaced samples, by index
+    pub samples: Vec<(usize, Sample)>,
+    pub current_comparison_index: usize,
+    /// Only when comparisons still to come were dropped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comparisons_this_round: Option<Vec<(usize, usize)>>,
+    /// Appended to `history` and `votes`
+    pub history: Vec<MatchRecord>,
+    pub votes: Vec<VoteRecord>,
+    pub reference: Option<Reference>,
+}
+
+/// An accepted change: votes come back as a patch, everything else as the whole state
+#[derive(Debug, Serialize, Clone)]
+pub struct SessionUpdate {
+    pub revision: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patch: Option<StatePatch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<TournamentState>,
+}
+
+impl Sessions {
+    /// Open `state`, or join the session already open for the same tournament and get
+    /// its current state. Every open needs its own close.
+    pub fn open(&self, mut state: TournamentState) -> Result<SessionSnapshot, String> {
+        if state.id.is_empty() {
+            state.id = random_token()?;
+        }
+
+        let mut states = self.states.write().unwrap();
+        if let Some((id, session)) = states.iter_mut().find(|(_, s)| s.state.id == state.id) {
+            session.windows += 1;
+            return Ok(SessionSnapshot {
+                id: id.clone(),
+                revision: session.revision,
+                state: session.state.clone(),
+            });
+        }
+
+        let id = format!("s{}", self.next_id.fetch_add(1, Ordering::Relaxed) + 1);
+        let session = Session {
+            revision: 0,
+            windows: 1,
+            state: state.clone(),
+        };
+        states.insert(id.clone(), session);
+        Ok(SessionSnapshot {
+            id,
+            revision: 0,
+            state,
+        })
+    }
+
+    pub fn get(&self, id: &str) -> Result<SessionSnapshot, String> {
+        let states = self.states.read().unwrap();
+        let session = states.get(id).ok_or_else(|| unknown(id))?;
+        Ok(SessionSnapshot {
+            id: id.to_string(),
+            revision: session.revision,
+            state: session.state.clone(),
+        })
+    }
+
+    /// Run `f` on the session's state without copying it
+    pub fn read<T>(&self, id: &str, f: impl FnOnce(&TournamentState) -> T) -> Result<T, String> {
+        let states = self.states.read().unwrap();
+        states
+            .get(id)
+            .map(|s| f(&s.state))
+            .ok_or_else(|| unknown(id))
+    }
+
+    /// Run `f` on the session's state along with the revision it is at
+    pub fn read_current<T>(
+        &self,
+        id: &str,
+        f: impl FnOnce(&TournamentState) -> T,
+    ) -> Result<(u64, T), String> {
+        let states = self.states.read().unwrap();
+        states
+            .get(id)
+            .map(|s| (s.revision, f(&s.state)))
+            .ok_or_else(|| unknown(id))
+    }
+
+    /// Copy of the session's state, if it is still at `expected`
+    pub fn state_at(&self, id: &str, expected: u64) -> Result<TournamentState, CommandError> {
+        let states = self.states.read().unwrap();
+        let session = states.get(id).ok_or_else(|| unknown(id))?;
+        if session.revision != expected {
+            return Err(CommandError::Conflict(session.revision));
+        }
+        Ok(session.state.clone())
+    }
+
+    /// Change the session if it is still at `expected`; returns the new revision. `f`
+    /// must leave the state alone when it fails.
+    pub fn update<T>(
+        &self,
+        id: &str,
+        expected: u64,
+        f: impl FnOnce(&mut TournamentState) -> Result<T, String>,
+    ) -> Result<(u64, T), CommandError> {
+        let mut states = self.states.write().unwrap();
+        let session = states.get_mut(id).ok_or_else(|| unknown(id))?;
+        if session.revision != expected {
+            return Err(CommandError::Conflict(session.revision));
+        }
+        let value = f(&mut session.state)?;
+        session.revision += 1;
+        Ok((session.revision, value))
+    }
+
+    /// Returns whether that was the session's last window
+    pub fn close(&self, id: &str) -> bool {
+        let mut states = self.states.write().unwrap();
+        let Some(session) = states.get_mut(id) else {
+            return false;
+        };
+        session.windows -= 1;
+        if session.windows > 0 {
+            return false;
+        }
+        states.remove(id);
+        true
+    }
+}
+
+fn unknown(id: &str) -> String {
+    i18n::t_args("unknown-session", [("id", id.into())])
+}
+
+/// Apply `change` to the session `id`, made on the copy at `revision`, and tell every
+/// window about it
+pub async fn apply(
+    app: &AppHandle,
+    id: &str,
+    revision: u64,
+    change: SessionChange,
+) -> Result<SessionUpdate, CommandError> {
+    let sessions = app.state::<Sessions>();
+    let update = match change {
+        SessionChange::Vote {
+            pair_id,
+            winner,
+            decision_ms,
+            ballots,
+        } => {
+            // Plugins rate from the scores before the vote; calibration matches aren't rated
+            let contenders = sessions.read(id, |state| {
+                let (winner, loser) = voting::contenders(state, &pair_id, winner)?;
+                let rated = !calibration::is_calibration_pairing(state);
+                Ok::<_, String>(
+                    rated.then(|| (state.samples[winner].clone(), state.samples[loser].clone())),
+                )
+            })??;
+            let ratings = match contenders {
+                Some((winner, loser)) => plugins::update_ratings(app, &winner, &loser)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!(error = %e, "plugin rating failed");
+                        None
+                    }),
+                None => None,
+            };
+            vote(app, id, revision, |state| {
+                voting::record(state, &pair_id, winner, decision_ms, ratings, ballots)
+            })?
+        }
+        SessionChange::EliminateBoth { pair_id, ballots } => vote(app, id, revision, |state| {
+            voting::eliminate_both(state, &pair_id, ballots)
+        })?,
+        change => {
+            let mut state = sessions.state_at(id, revision)?;
+            change_state(app, &mut state, change).await?;
+            // Pins and merges can drop the round's last comparisons too
+            let summary = round_summary::complete_round(&mut state);
+            let (revision, ()) = sessions.update(id, revision, |current| {
+                *current = state.clone();
+                Ok(())
+            })?;
+            if let Some(summary) = summary {
+                round_summary::announce(app, &summary);
+            }
+            SessionUpdate {
+                revision,
+                patch: None,
+                state: Some(state),
+            }
+        }
+    };
+
+    let changed = SessionChanged {
+        id: id.to_string(),
+        revision: update.revision,
+        patch: update.patch.clone(),
+    };
+    if let Err(e) = app.emit("session://changed", changed) {
+        tracing::warn!(error = %e, "failed to emit session change");
+    }
+    publish_standings(app, id, update.patch.as_ref());
+    Ok(update)
+}
+
+/// Keep the detached leaderboard current: the samples a vote changed go out as deltas,
+/// and after anything else (or when the deltas don't fit) the whole pool
+fn publish_standings(app: &AppHandle, id: &str, patch: Option<&StatePatch>) {
+    let sessions = app.state::<Sessions>();
+    if let (Some(patch), Ok(round)) = (patch, sessions.read(id, |s| s.current_round)) {
+        let changed: Vec<Sample> = patch.samples.iter().map(|(_, s)| s.clone()).collect();
+        if leaderboard::publish_changes(app, round, &changed).is_ok() {
+            return;
+        }
+    }
+
+    let published = sessions
+        .read(id, |s| (s.current_round, s.samples.clone()))
+        .and_then(|(round, samples)| leaderboard::publish(app, round, samples));
+    if let Err(e) = published {
+        tracing::warn!(error = %e, "failed to publish standings");
+    }
+}
+
+/// Apply a decision reached outside the session's windows (a remote voter, the voters
+/// of a collaborative session or the mini window) to the comparison it was cast on
+pub async fn decide(
+    app: &AppHandle,
+    id: &str,
+    decision: Decision,
+    decision_ms: Option<u64>,
+) -> Result<SessionUpdate, CommandError> {
+    let (revision, pair) = app
+        .state::<Sessions>()
+        .read_current(id, |state| voting::current_pair(state, &decision.pair_id))?;
+    let (a, b) = pair?;
+    let Decision {
+        pair_id,
+        winner,
+        ballots,
+        ..
+    } = decision;
+    let change = match winner {
+        Choice::Skip => SessionChange::EliminateBoth { pair_id, ballots },
+        choice => SessionChange::Vote {
+            pair_id,
+            winner: if choice == Choice::A { a } else { b },
+            decision_ms,
+            ballots,
+        },
+    };
+    apply(app, id, revision, change).await
+}
+
+/// Apply a vote in place and describe it as a patch. The vote that finishes a round
+/// adds its summary, and then the whole state goes out.
+fn vote(
+    app: &AppHandle,
+    id: &str,
+    revision: u64,
+    record: impl FnOnce(&mut TournamentState) -> Result<Vec<usize>, String>,
+) -> Result<SessionUpdate, CommandError> {
+    let (revision, (patch, finished)) = app.state::<Sessions>().update(id, revision, |state| {
+        let (history, votes) = (state.history.len(), state.votes.len());
+        let scheduled = state.comparisons_this_round.len();
+        let changed = record(state)?;
+
+        if let Some(summary) = round_summary::complete_round(state) {
+            return Ok((None, Some((state.clone(), summary))));
+        }
+        let patch = StatePatch {
+            samples: changed
+                .into_iter()
+                .map(|index| (index, state.samples[index].clone()))
+                .collect(),
+            current_comparison_index: state.current_comparison_index,
+            comparisons_this_round: (state.comparisons_this_round.len() != scheduled)
+                .then(|| state.comparisons_this_round.clone()),
+            history: state.history[history..].to_vec(),
+            votes: state.votes[votes..].to_vec(),
+            reference: state.reference.clone(),
+        };
+        Ok((Some(patch), None))
+    })?;
+    let state = finished.map(|(state, summary)| {
+        round_summary::announce(app, &summary);
+        state
+    });
+    Ok(SessionUpdate {
+        revision,
+        patch,
+        state,
+    })
+}
+
+/// Every change but votes, made on a copy of the state
+async fn change_state(
+    app: &AppHandle,
+    state: &mut TournamentState,
+    change: SessionChange,
+) -> Result<(), String> {
+    match change {
+        SessionChange::StartRound => {
+            state.comparisons_this_round = rematches::pair_round(state);
+            state.current_comparison_index = 0;
+            finish_pairing(app, state).await;
+        }
+        SessionChange::AdvanceRound => {
+            advancement::advance(state);
+            finish_pairing(app, state).await;
+            tracing::info!(
+                round = state.current_round,
+                samples = state.samples.len(),
+                "advanced to next round"
+            );
+        }
+        SessionChange::Redemption { count } => {
+            let redeemed = redemption::redeem(state, count)?;
+            tracing::info!(
+                redeemed,
+                round = state.current_round,
+                "redemption round started"
+            );
+        }
+        SessionChange::Pin { path, pin } => pins::pin_sample(state, &path, pin)?,
+        SessionChange::Bench { path, benched } => bench::set_benched(state, &path, benched)?,
+        SessionChange::Tag { path, tags } => tags::tag_sample(state, &path, &tags)?,
+        SessionChange::Reorder { indices } => {
+            queue::reorder(state, &indices)?;
+            tracing::debug!(moved = indices.len(), "reordered upcoming comparisons");
+        }
+        SessionChange::Reference { path, interval } => {
+            calibration::set_reference(state, path, interval, &app.state::<AllowedPaths>())?
+        }
+        SessionChange::MergeDuplicates { keep, drop } => {
+            for path in &drop {
+                duplicates::merge(state, &keep, path)?;
+            }
+        }
+        SessionChange::Vote { .. } | SessionChange::EliminateBoth { .. } => {
+            unreachable!("votes are applied in place")
+        }
+    }
+    Ok(())
+}
+
+/// Let the active plugin (if any) replace the built-in pairings of a fresh round, then
+/// schedule the calibration matches and settle who sits out
+async fn finish_pairing(app: &AppHandle, state: &mut TournamentState) {
+    match plugins::select_pairs(app, &state.samples).await {
+        Ok(Some(pairs)) => {
+            // Plugins see every sample; benched ones still sit out
+            let playing = pairs
+                .into_iter()
+                .filter(|&(a, b)| !state.samples[a].benched && !state.samples[b].benched)
+                .collect();
+            state.comparisons_this_round = rematches::avoid_rematches(state, playing);
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!(error = %e, "plugin pairing failed"),
+    }
+    calibration::schedule(state);
+    byes::assign(state);
+}
+
+/// Hand a new or loaded tournament to the backend. A tournament another window has open
+/// already joins its session and comes back as that session has it.
+#[tauri::command]
+pub fn open_session(
+    state: TournamentState,
+    app: AppHandle,
+    sessions: State<Sessions>,
+) -> Result<SessionSnapshot, String> {
+    let snapshot = sessions.open(state)?;
+    tracing::debug!(id = %snapshot.id, revision = snapshot.revision, "session opened");
+    publish_standings(&app, &snapshot.id, None);
+    Ok(snapshot)
+}
+
+#[tauri::command]
+pub fn get_session(id: String, sessions: State<Sessions>) -> Result<SessionSnapshot, String> {
+    sessions.get(&id)
+}
+
+/// Change the session, made on the copy at `revision`. A session changed since then is
+/// left alone with a `conflict` error carrying its current revision.
+#[tauri::command]
+pub async fn change_session(
+    id: String,
+    revision: u64,
+    change: SessionChange,
+    app: AppHandle,
+) -> Result<SessionUpdate, CommandError> {
+    apply(&app, &id, revision, change)
+        .await
+        .inspect_err(|e| tracing::warn!(%id, error = %e, "session change refused"))
+}
+
+#[tauri::command]
+pub fn close_session(id: String, sessions: State<Sessions>) {
+    if sessions.close(&id) {
+        tracing::debug!(%id, "session closed");
+    }
+}
+
+/// Save an open session, without the state crossing the bridge. The state is copied
+/// out first, so other windows aren't held up while the file is written.
+#[tauri::command]
+pub async fn save_session(
+    id: String,
+    file_path: String,
+    compression: Option<Compression>,
+    password: Option<String>,
+    app: AppHandle,
+    sessions: State<'_, Sessions>,
+) -> Result<(), CommandError> {
+    let state = sessions.read(&id, TournamentState::clone)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::write_save(
+            &state,
+            &file_path,
+            compression,
+            password.as_deref(),
+            &app.state::<AllowedPaths>(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::State;
+
+use crate::error::CommandError;
+use crate::hooks::Hook;
+use crate::i18n;
+use crate::jobs::{JobQueue, Throttle};
+
+/// User preferences persisted in the app data dir; every field must have a default
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Settings {
+    /// Only changed by editing the file: hooks run programs, so the webview can't set them
+    pub hooks: Vec<Hook>,
+    /// Name of the WASM plugin (in the plugins folder) used for scoring and pairing
+    pub plugin: Option<String>,
+    /// `error`, `warn`, `info`, `debug` or `trace`
+    pub log_level: Option<String>,
+    /// Where the replay shortcut restarts the current comparison
+    pub replay_from: ReplayFrom,
+    /// Fade when switching between A and B, in ms (5-30); `None` switches instantly
+    pub crossfade_ms: Option<u32>,
+    /// Ear protection: playback is limited to this level in dBFS (e.g. -6.0);
+    /// `None` plays files as they are
+    pub output_ceiling_dbfs: Option<f32>,
+    /// Audition only this many seconds of each sample; `None` plays files in full
+    pub preview_secs: Option<f32>,
+    /// Where the preview starts, in seconds from the beginning of the file
+    pub preview_start_secs: f32,
+    /// Sessions resumed after this many idle days trust their old results less;
+    /// `None` keeps them as they were
+    pub stale_after_days: Option<u32>,
+    /// Language of backend messages, notifications and reports ("en", "de");
+    /// `None` follows the system
+    pub language: Option<String>,
+    /// For libraries on shared drives: refuse every copy, move and trash so the app
+    /// never changes files; saving sessions and reports still works
+    pub read_only: bool,
+    /// Limits for background analysis (threads, CPU share, pausing on battery)
+    pub analysis_throttle: Throttle,
+    /// Size of the waveform/spectrogram cache before the least recently used go;
+    /// `None` uses 256 MB
+    pub thumbnail_cache_mb: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayFrom {
+    #[default]
+    Start,
+    /// First non-silent moment, skipping leading silence
+    Onset,
+    /// Where playback of the pair was last stopped or switched
+    LastPosition,
+}
+
+pub struct SettingsStore {
+    file: PathBuf,
+    settings: Mutex<Settings>,
+}
+
+impl SettingsStore {
+    pub fn load(data_dir: &Path) -> Self {
+        let file = data_dir.join("settings.json");
+        let settings = fs::read_to_string(&file)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            file,
+            settings: Mutex::new(settings),
+        }
+    }
+
+    pub fn get(&self) -> Settings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    pub fn set(&self, settings: Settings) -> Result<(), String> {
+        if let Some(parent) = self.file.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+        fs::write(&self.file, json).map_err(|e| e.to_string())?;
+
+        *self.settings.lock().unwrap() = settings;
+        Ok(())
+    }
+
+    /// Refuse file operations that change the library while read-only mode is on
+    pub fn ensure_writable(&self) -> Result<(), CommandError> {
+        if self.settings.lock().unwrap().read_only {
+            return Err(CommandError::ReadOnly);
+        }
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub fn get_settings(settings: State<SettingsStore>) -> Settings {
+    settings.get()
+}
+
+#[tauri::command]
+pub fn save_settings(
+    mut new_settings: Settings,
+    settings: State<SettingsStore>,
+    queue: State<JobQueue>,
+) -> Result<(), String> {
+    new_settings.hooks = settings.get().hooks;
+    i18n::set_language(new_settings.language.as_deref());
+    queue.set_throttle(new_settings.analysis_throttle.clone());
+    settings.set(new_settings)
+}
+
+#[tauri::command]
+pub fn set_read_only(enabled: bool, settings: State<SettingsStore>) -> Result<(), String> {
+    tracing::info!(enabled, "read-only mode");
+    let mut current = settings.get();
+    current.read_only = enabled;
+    settings.set(current)
+}
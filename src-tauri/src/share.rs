@@ -0,0 +1,261 @@
+use qrcode::render::svg;
+use qrcode::QrCode;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tiny_http::{Method, Request, Response, Server};
+
+use crate::export::{sorted_results, win_rate};
+use crate::journal::now_secs;
+use crate::remote::{self, error_response, header};
+use crate::{i18n, Sample};
+
+/// How long a link works unless the caller picks another time
+const DEFAULT_TTL_MINUTES: u32 = 30;
+const MAX_TTL_MINUTES: u32 = 24 * 60;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ShareInfo {
+    pub url: String,
+    /// Unix time after which the link stops working and the server shuts down
+    pub expires_at: u64,
+    /// The URL as an SVG QR code, for scanning from a phone
+    pub qr_svg: String,
+}
+
+/// What the share serves: a snapshot of the standings taken when it started
+struct Shared {
+    token: String,
+    expires_at: u64,
+    page: String,
+    /// Ranked samples, when audio is shared; `/audio/<rank>` streams them
+    audio: Option<Vec<Sample>>,
+}
+
+struct Running {
+    server: Arc<Server>,
+    info: ShareInfo,
+    token: String,
+}
+
+/// Temporary read-only server for browsing the results from another device
+#[derive(Default)]
+pub struct ShareServer {
+    running: Mutex<Option<Running>>,
+}
+
+impl ShareServer {
+    fn stop(&self) {
+        if let Some(running) = self.running.lock().unwrap().take() {
+            remote::stop_serving(&running.server);
+            tracing::info!("results share stopped");
+        }
+    }
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Standalone HTML report of the standings, with a player per row when audio is shared
+fn report_page(ranked: &[&Sample], token: &str, with_audio: bool) -> String {
+    let mut headings = vec![
+        i18n::t("heading-rank"),
+        i18n::t("heading-filename"),
+        i18n::t("heading-score"),
+        i18n::t("heading-win-rate"),
+        i18n::t("heading-pack"),
+    ];
+    if with_audio {
+        headings.push(String::new());
+    }
+    let head: String = headings
+        .iter()
+        .map(|h| format!("<th>{}</th>", escape_html(h)))
+        .collect();
+
+    let rows: String = ranked
+        .iter()
+        .enumerate()
+        .map(|(index, sample)| {
+            let rank = index + 1;
+            let player = if with_audio {
+                let src = format!("/audio/{}?token={}", rank, token);
+                format!(
+                    "<td><audio controls preload=\"none\" src=\"{}\"></audio></td>",
+                    src
+                )
+            } else {
+                String::new()
+            };
+            format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.1}%</td><td>{}</td>{}</tr>\n",
+                rank,
+                escape_html(&sample.filename),
+                sample.score,
+                win_rate(sample) * 100.0,
+                escape_html(sample.pack.as_deref().unwrap_or("")),
+                player
+            )
+        })
+        .collect();
+
+    let title = escape_html(&i18n::t("summary-title"));
+    format!(
+        concat!(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">",
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">",
+            "<title>{title}</title><style>",
+            "body{{font-family:system-ui,sans-serif;margin:1rem;background:#18181e;color:#eee}}",
+            "table{{border-collapse:collapse;width:100%}}",
+            "th,td{{padding:.4rem;text-align:left;border-bottom:1px solid #333}}",
+            "audio{{height:2rem;max-width:14rem}}",
+            "</style></head><body><h1>{title}</h1>",
+            "<table><thead><tr>{head}</tr></thead><tbody>\n{rows}</tbody></table>",
+            "</body></html>\n"
+        ),
+        title = title,
+        head = head,
+        rows = rows
+    )
+}
+
+fn handle_request(app: &AppHandle, shared: &Shared, request: Request) {
+    if !remote::is_authorized(&request, &shared.token) {
+        let _ = request.respond(error_response(401, "Invalid token"));
+        return;
+    }
+    if now_secs() >= shared.expires_at {
+        let _ = request.respond(error_response(410, "This link has expired"));
+        return;
+    }
+
+    let path = request.url().split('?').next().unwrap_or("").to_string();
+    if *request.method() != Method::Get {
+        let _ = request.respond(error_response(405, "Read only"));
+        return;
+    }
+    if path == "/" {
+        let response = Response::from_string(shared.page.clone())
+            .with_header(header("Content-Type", "text/html; charset=utf-8"));
+        let _ = request.respond(response);
+        return;
+    }
+
+    let sample = path
+        .strip_prefix("/audio/")
+        .and_then(|rank| rank.parse::<usize>().ok())
+        .and_then(|rank| shared.audio.as_ref()?.get(rank.checked_sub(1)?));
+    match sample {
+        Some(sample) => remote::serve_audio(app, sample, request),
+        None => {
+            let _ = request.respond(error_response(404, "Not found"));
+        }
+    }
+}
+
+fn qr_svg(url: &str) -> Result<String, String> {
+    let code = QrCode::new(url.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(240, 240)
+        .quiet_zone(true)
+        .build())
+}
+
+/// Serve the standings as a web page on a temporary URL with an expiring token, so
+/// someone in the room can browse the winners on their phone. `lan` listens on the
+/// local network instead of this machine only; `include_audio` adds a player per row.
+/// Starting a new share ends the previous one.
+#[tauri::command]
+pub fn share_results_link(
+    samples: Vec<Sample>,
+    include_audio: bool,
+    lan: bool,
+    ttl_minutes: Option<u32>,
+    app: AppHandle,
+    share: State<ShareServer>,
+) -> Result<ShareInfo, String> {
+    share.stop();
+
+    let bind = if lan { "0.0.0.0" } else { "127.0.0.1" };
+    let server = Server::http((bind, 0))
+        .map_err(|e| i18n::t_args("server-failed", [("error", e.to_string().into())]))?;
+    let port = server
+        .server_addr()
+        .to_ip()
+        .map(|addr| addr.port())
+        .ok_or_else(|| i18n::t("server-no-address"))?;
+
+    let token = remote::random_token()?;
+    let ttl = ttl_minutes
+        .unwrap_or(DEFAULT_TTL_MINUTES)
+        .clamp(1, MAX_TTL_MINUTES);
+    let expires_at = now_secs() + ttl as u64 * 60;
+    let host = if lan {
+        remote::lan_ip().unwrap_or_else(|| "127.0.0.1".to_string())
+    } else {
+        "127.0.0.1".to_string()
+    };
+    let url = format!("http://{}:{}/?token={}", host, port, token);
+    let info = ShareInfo {
+        qr_svg: qr_svg(&url)?,
+        url,
+        expires_at,
+    };
+
+    let ranked = sorted_results(&samples);
+    let shared = Arc::new(Shared {
+        page: report_page(&ranked, &token, include_audio),
+        audio: include_audio.then(|| ranked.iter().copied().cloned().collect()),
+        token: token.clone(),
+        expires_at,
+    });
+
+    let server = Arc::new(server);
+    let thread_app = app.clone();
+    remote::serve(&server, move |request| {
+        handle_request(&thread_app, &shared, request)
+    });
+
+    // Shut the server down once the link expires, unless a newer share replaced it
+    let expiring = token.clone();
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_secs(ttl as u64 * 60));
+        let share = app.state::<ShareServer>();
+        let current = share
+            .running
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|r| r.token.clone());
+        if current.as_deref() == Some(expiring.as_str()) {
+            share.stop();
+        }
+    });
+
+    *share.running.lock().unwrap() = Some(Running {
+        server,
+        info: info.clone(),
+        token,
+    });
+    tracing::info!(lan, include_audio, ttl, "results share started");
+    Ok(info)
+}
+
+/// The running share, if any
+#[tauri::command]
+pub fn get_results_share(share: State<ShareServer>) -> Option<ShareInfo> {
+    let running = share.running.lock().unwrap();
+    running.as_ref().map(|r| r.info.clone())
+}
+
+#[tauri::command]
+pub fn stop_results_share(share: State<ShareServer>) {
+    share.stop();
+}
@@ -0,0 +1,158 @@
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+use crate::analysis::{decode, DecodedAudio};
+use crate::settings::SettingsStore;
+use crate::{i18n, thumbnails, AllowedPaths};
+
+const MIN_FREQUENCY: f32 = 20.0;
+/// Magnitudes are shown over this range below full scale
+const DYNAMIC_RANGE_DB: f32 = 100.0;
+const MAX_DIMENSION: u32 = 4096;
+
+fn mono(audio: &DecodedAudio) -> Vec<f32> {
+    audio
+        .samples
+        .chunks(audio.channels)
+        .map(|frame| frame.iter().sum::<f32>() / audio.channels as f32)
+        .collect()
+}
+
+/// Dark blue → magenta → orange → pale yellow, for `t` in 0..=1
+fn colormap(t: f32) -> [u8; 3] {
+    const STOPS: [[f32; 3]; 5] = [
+        [0.0, 0.0, 0.02],
+        [0.25, 0.05, 0.45],
+        [0.75, 0.15, 0.45],
+        [0.98, 0.55, 0.1],
+        [1.0, 1.0, 0.75],
+    ];
+    let scaled = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f32;
+    let index = (scaled as usize).min(STOPS.len() - 2);
+    let fraction = scaled - index as f32;
+    let (a, b) = (STOPS[index], STOPS[index + 1]);
+    [0, 1, 2].map(|c| ((a[c] + (b[c] - a[c]) * fraction) * 255.0) as u8)
+}
+
+/// Log-frequency spectrogram as RGB pixels, low frequencies at the bottom
+pub fn render(audio: &DecodedAudio, width: u32, height: u32) -> Vec<u8> {
+    let signal = mono(audio);
+    let fft_size = (height as usize * 2).next_power_of_two().clamp(512, 8192);
+    let fft = FftPlanner::<f32>::new().plan_fft_forward(fft_size);
+    let window: Vec<f32> = (0..fft_size)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / fft_size as f32).cos())
+        .collect();
+
+    let nyquist = audio.sample_rate as f32 / 2.0;
+    let bins = fft_size / 2;
+    // Row -> FFT bin on a log frequency axis
+    let row_bins: Vec<usize> = (0..height)
+        .map(|row| {
+            let position = 1.0 - row as f32 / (height - 1).max(1) as f32;
+            let frequency = MIN_FREQUENCY * (nyquist / MIN_FREQUENCY).powf(position);
+            ((frequency / nyquist * bins as f32) as usize).min(bins - 1)
+        })
+        .collect();
+
+    let mut pixels = vec![0u8; (width * height * 3) as usize];
+    let mut buffer = vec![Complex::new(0.0f32, 0.0); fft_size];
+    let reference = fft_size as f32 / 4.0;
+
+    for x in 0..width as usize {
+        let center = x * signal.len() / width as usize;
+        let start = center as isize - fft_size as isize / 2;
+        for (i, value) in buffer.iter_mut().enumerate() {
+            let index = start + i as isize;
+            let sample = if index >= 0 && (index as usize) < signal.len() {
+                signal[index as usize]
+            } else {
+                0.0
+            };
+            *value = Complex::new(sample * window[i], 0.0);
+        }
+        fft.process(&mut buffer);
+
+        for (row, &bin) in row_bins.iter().enumerate() {
+            let db = 20.0 * (buffer[bin].norm() / reference).max(1e-9).log10();
+            let color = colormap(1.0 + db / DYNAMIC_RANGE_DB);
+            let offset = (row * width as usize + x) * 3;
+            pixels[offset..offset + 3].copy_from_slice(&color);
+        }
+    }
+
+    pixels
+}
+
+pub fn write_png(path: &Path, pixels: &[u8], width: u32, height: u32) -> Result<(), String> {
+    let partial = path.with_extension("partial");
+    {
+        let file = File::create(&partial).map_err(|e| e.to_string())?;
+        let mut encoder = png::Encoder::new(BufWriter::new(file), width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer.write_image_data(pixels).map_err(|e| e.to_string())?;
+    }
+    fs::rename(&partial, path).map_err(|e| e.to_string())
+}
+
+/// Cached PNG for `source`, rendered on first request
+pub fn spectrogram_file(
+    source: &str,
+    width: u32,
+    height: u32,
+    cache_dir: &Path,
+    limit_bytes: u64,
+) -> Result<PathBuf, String> {
+    let params = format!("spectrogram:{}x{}", width, height);
+    let path = cache_dir.join(thumbnails::file_name(source, &params, "png"));
+    if path.exists() {
+        thumbnails::touch(&path);
+        return Ok(path);
+    }
+
+    let audio = decode(source)?;
+    fs::create_dir_all(cache_dir).map_err(|e| e.to_string())?;
+    write_png(&path, &render(&audio, width, height), width, height)?;
+    thumbnails::evict(cache_dir, limit_bytes);
+    Ok(path)
+}
+
+/// Spectrogram PNG path for display (load it with `convertFileSrc`)
+#[tauri::command]
+pub async fn generate_spectrogram(
+    path: String,
+    width: u32,
+    height: u32,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+    settings: State<'_, SettingsStore>,
+) -> Result<String, String> {
+    // Validate path is within allowed directories
+    if !allowed_paths.is_path_allowed(Path::new(&path)) {
+        return Err(i18n::t("access-denied"));
+    }
+
+    let (width, height) = (
+        width.clamp(16, MAX_DIMENSION),
+        height.clamp(16, MAX_DIMENSION),
+    );
+    let cache_root = app.path().app_cache_dir().map_err(|e| e.to_string())?;
+    let cache_dir = thumbnails::thumbnail_dir(&app)?;
+    let limit = thumbnails::limit_bytes(&settings.get());
+
+    let image = tauri::async_runtime::spawn_blocking(move || {
+        spectrogram_file(&path, width, height, &cache_dir, limit)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+
+    // Images are served from the cache, so allow it explicitly
+    allowed_paths.add_allowed_path(cache_root);
+
+    Ok(image.to_string_lossy().to_string())
+}
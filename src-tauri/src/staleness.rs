@@ -0,0 +1,29 @@
+use crate::TournamentState;
+
+const SECS_PER_DAY: f32 = 86_400.0;
+/// Growth of a sample's uncertainty per idle day, in score points (Glicko's `c`)
+const UNCERTAINTY_PER_DAY: f32 = 0.25;
+/// Cap so even a years-old session keeps some trust in its standings
+const MAX_UNCERTAINTY: f32 = 4.0;
+
+/// On resuming a session idle for more than `stale_after_days`, widen every sample's
+/// uncertainty the way Glicko decays rating deviation: the variance grows linearly with
+/// time. Samples near the cut then get re-verified instead of being eliminated on old
+/// judgments. Returns whether the session was stale.
+pub fn inflate(state: &mut TournamentState, idle_secs: u64, stale_after_days: u32) -> bool {
+    let idle_days = idle_secs as f32 / SECS_PER_DAY;
+    if idle_days <= stale_after_days as f32 {
+        return false;
+    }
+
+    for sample in &mut state.samples {
+        let variance = sample.uncertainty.powi(2) + UNCERTAINTY_PER_DAY.powi(2) * idle_days;
+        sample.uncertainty = variance.sqrt().min(MAX_UNCERTAINTY);
+    }
+    tracing::info!(
+        idle_days = idle_days as u32,
+        samples = state.samples.len(),
+        "resumed stale session, widened rating uncertainty"
+    );
+    true
+}
@@ -0,0 +1,511 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::analysis::Analysis;
+use crate::collab::{Choice, VoteRecord};
+use crate::export::{markdown_heading, sorted_results, ResultsFormat, ELIMINATED_SCORE};
+use crate::round_summary::MatchRecord;
+use crate::Sample;
+
+/// Rank cut-off for counting a pack's samples among the overall leaders
+const TOP_RANKS: usize = 10;
+/// Upsets listed in the statistics
+const TOP_UPSETS: usize = 10;
+/// Favourites listed per voter
+const TOP_FAVOURITES: usize = 3;
+/// Feature differences below these don't count towards a voter's bias
+const MIN_PEAK_DIFF_DB: f32 = 1.0;
+const MIN_CENTROID_DIFF_HZ: f32 = 100.0;
+const MIN_DURATION_DIFF_SECS: f32 = 0.05;
+/// Score gap at which the favourite is expected to win ten times out of eleven
+/// (the 400 points of Elo, scaled to one point per win)
+const EXPECTATION_SCALE: f32 = 4.0;
+
+/// A match the lower-rated sample won
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Upset {
+    #[serde(flatten)]
+    pub record: MatchRecord,
+    /// Chance the winner had going in; the lower, the bigger the surprise
+    pub expected: f32,
+}
+
+/// Elo-style probability of `score` beating `opponent`
+pub fn expected_win(score: i32, opponent: i32) -> f32 {
+    1.0 / (1.0 + 10f32.powf((opponent - score) as f32 / EXPECTATION_SCALE))
+}
+
+/// Matches whose winner was the underdog going in, most surprising first
+pub fn upsets<'a>(history: impl IntoIterator<Item = &'a MatchRecord>) -> Vec<Upset> {
+    let mut upsets: Vec<Upset> = history
+        .into_iter()
+        .filter(|m| m.loser_score > ELIMINATED_SCORE)
+        .map(|m| Upset {
+            record: m.clone(),
+            expected: expected_win(m.winner_score, m.loser_score),
+        })
+        .filter(|u| u.expected < 0.5)
+        .collect();
+    upsets.sort_by(|a, b| a.expected.total_cmp(&b.expected));
+    upsets
+}
+
+/// Aggregate standing of one pack (`pack` is `None` for loose files)
+#[derive(Debug, Serialize, Clone)]
+pub struct PackStats {
+    pub pack: Option<String>,
+    pub samples: usize,
+    pub eliminated: usize,
+    pub mean_score: f32,
+    pub best_score: i32,
+    pub win_rate: f32,
+    /// Samples of this pack among the overall top ten
+    pub top_ten: usize,
+}
+
+/// How one collaborative voter judged, next to the majority
+#[derive(Debug, Serialize, Clone)]
+pub struct VoterStats {
+    pub voter_id: String,
+    pub name: String,
+    /// Ballots for A or B
+    pub ballots: usize,
+    pub skips: usize,
+    /// Share of their A/B ballots that went the way the other voters' majority did,
+    /// among comparisons where the others had one
+    pub agreement: f32,
+    /// Share of picks that went to the sample with the higher peak level, among pairs
+    /// that differ audibly; 0.5 is no preference. `None` without analyzed pairs.
+    pub prefers_louder: Option<f32>,
+    /// The same for the brighter sample (higher spectral centroid)
+    pub prefers_brighter: Option<f32>,
+    /// The same for the longer sample
+    pub prefers_longer: Option<f32>,
+    /// Correlation between the voter's own wins-minus-losses per sample and the other
+    /// voters' majority's over the same comparisons: 1 ranks alike, 0 unrelated, < 0
+    /// opposed
+    pub ranking_correlation: Option<f32>,
+    /// Samples the voter picked most often over the ones they met, best first
+    pub favourites: Vec<String>,
+}
+
+/// How often two voters picked the same sample when both voted A or B
+#[derive(Debug, Serialize, Clone)]
+pub struct VoterAgreement {
+    /// Voter IDs; two voters may go by the same name
+    pub a: String,
+    pub b: String,
+    pub a_name: String,
+    pub b_name: String,
+    pub shared: usize,
+    pub rate: f32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Statistics {
+    pub samples: usize,
+    pub eliminated: usize,
+    /// Sum of comparisons across samples (each comparison counts twice)
+    pub appearances: i32,
+    /// Best mean score first
+    pub packs: Vec<PackStats>,
+    /// Biggest upsets of the whole session; often worth a second listen
+    pub upsets: Vec<Upset>,
+    /// Per-voter statistics; empty unless at least two voters cast ballots
+    pub voters: Vec<VoterStats>,
+    /// Agreement between every two voters who judged the same comparisons
+    pub voter_agreement: Vec<VoterAgreement>,
+}
+
+#[derive(Default)]
+struct PackTotals {
+    samples: usize,
+    eliminated: usize,
+    score: i64,
+    comparisons: i64,
+    best_score: Option<i32>,
+    top_ten: usize,
+}
+
+/// Path of the sample a ballot or decision picked, if it picked one
+fn picked(record: &VoteRecord, choice: Choice) -> Option<(&str, &str)> {
+    match choice {
+        Choice::A => Some((record.a.as_str(), record.b.as_str())),
+        Choice::B => Some((record.b.as_str(), record.a.as_str())),
+        Choice::Skip => None,
+    }
+}
+
+/// Tallies how often the picked sample was the one with more of a feature
+#[derive(Default)]
+struct Preference {
+    higher: usize,
+    total: usize,
+}
+
+impl Preference {
+    fn add(&mut self, winner: Option<&Analysis>, loser: Option<&Analysis>, feature: Feature) {
+        let (Some(winner), Some(loser)) = (winner, loser) else {
+            return;
+        };
+        let diff = feature.value(winner) - feature.value(loser);
+        if diff.abs() < feature.min_diff() {
+            return;
+        }
+        self.total += 1;
+        if diff > 0.0 {
+            self.higher += 1;
+        }
+    }
+
+    fn share(&self) -> Option<f32> {
+        (self.total > 0).then(|| self.higher as f32 / self.total as f32)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Feature {
+    Peak,
+    Centroid,
+    Duration,
+}
+
+impl Feature {
+    fn value(self, analysis: &Analysis) -> f32 {
+        match self {
+            Feature::Peak => analysis.peak_dbfs,
+            Feature::Centroid => analysis.spectral_centroid_hz,
+            Feature::Duration => analysis.duration_secs,
+        }
+    }
+
+    fn min_diff(self) -> f32 {
+        match self {
+            Feature::Peak => MIN_PEAK_DIFF_DB,
+            Feature::Centroid => MIN_CENTROID_DIFF_HZ,
+            Feature::Duration => MIN_DURATION_DIFF_SECS,
+        }
+    }
+}
+
+/// Pearson correlation; `None` when either side doesn't vary
+fn correlation(pairs: &[(f32, f32)]) -> Option<f32> {
+    let n = pairs.len() as f32;
+    if pairs.len() < 2 {
+        return None;
+    }
+    let (mean_x, mean_y) = pairs
+        .iter()
+        .fold((0.0, 0.0), |(x, y), (a, b)| (x + a / n, y + b / n));
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in pairs {
+        cov += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x) * (x - mean_x);
+        var_y += (y - mean_y) * (y - mean_y);
+    }
+    (var_x > 0.0 && var_y > 0.0).then(|| cov / (var_x * var_y).sqrt())
+}
+
+/// What the other voters picked on `record`, leaving `voter_id` out so their own ballot
+/// doesn't make the majority they are measured against; `None` when the others tied or
+/// nobody else picked A or B
+fn others_majority(record: &VoteRecord, voter_id: &str) -> Option<Choice> {
+    let count = |choice: Choice| {
+        record
+            .ballots
+            .iter()
+            .filter(|b| b.voter_id != voter_id && b.choice == choice)
+            .count()
+    };
+    match count(Choice::A).cmp(&count(Choice::B)) {
+        Ordering::Greater => Some(Choice::A),
+        Ordering::Less => Some(Choice::B),
+        Ordering::Equal => None,
+    }
+}
+
+fn voter_stats(
+    voter_id: &str,
+    name: &str,
+    votes: &[VoteRecord],
+    analyses: &HashMap<&str, &Analysis>,
+) -> VoterStats {
+    let (mut ballots, mut skips, mut compared, mut agreed) = (0, 0, 0, 0);
+    let mut louder = Preference::default();
+    let mut brighter = Preference::default();
+    let mut longer = Preference::default();
+    // Wins minus losses per sample over all their picks, and as they and the others'
+    // majority saw it where the others had one
+    let mut own: HashMap<&str, i32> = HashMap::new();
+    let mut against: HashMap<&str, (i32, i32)> = HashMap::new();
+
+    for record in votes {
+        let Some(ballot) = record.ballots.iter().find(|b| b.voter_id == voter_id) else {
+            continue;
+        };
+        let Some((winner, loser)) = picked(record, ballot.choice) else {
+            skips += 1;
+            continue;
+        };
+        ballots += 1;
+
+        let (won, lost) = (analyses.get(winner).copied(), analyses.get(loser).copied());
+        louder.add(won, lost, Feature::Peak);
+        brighter.add(won, lost, Feature::Centroid);
+        longer.add(won, lost, Feature::Duration);
+        *own.entry(winner).or_default() += 1;
+        *own.entry(loser).or_default() -= 1;
+
+        let Some(majority) = others_majority(record, voter_id) else {
+            continue;
+        };
+        compared += 1;
+        agreed += (ballot.choice == majority) as usize;
+        against.entry(winner).or_default().0 += 1;
+        against.entry(loser).or_default().0 -= 1;
+        if let Some((majority_winner, majority_loser)) = picked(record, majority) {
+            against.entry(majority_winner).or_default().1 += 1;
+            against.entry(majority_loser).or_default().1 -= 1;
+        }
+    }
+
+    let nets: Vec<(f32, f32)> = against
+        .values()
+        .map(|&(mine, majority)| (mine as f32, majority as f32))
+        .collect();
+    let mut favourites: Vec<(&str, i32)> = own
+        .iter()
+        .filter(|(_, mine)| **mine > 0)
+        .map(|(&path, &mine)| (path, mine))
+        .collect();
+    favourites.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    VoterStats {
+        voter_id: voter_id.to_string(),
+        name: name.to_string(),
+        ballots,
+        skips,
+        agreement: if compared > 0 {
+            agreed as f32 / compared as f32
+        } else {
+            0.0
+        },
+        prefers_louder: louder.share(),
+        prefers_brighter: brighter.share(),
+        prefers_longer: longer.share(),
+        ranking_correlation: correlation(&nets),
+        favourites: favourites
+            .into_iter()
+            .take(TOP_FAVOURITES)
+            .map(|(path, _)| path.to_string())
+            .collect(),
+    }
+}
+
+/// Per-voter agreement, bias and ranking against the majority, and how each two
+/// voters agree. Needs at least two voters; with one there is no majority to compare.
+pub fn voter_statistics(
+    samples: &[Sample],
+    votes: &[VoteRecord],
+) -> (Vec<VoterStats>, Vec<VoterAgreement>) {
+    // Voters in the order they first voted
+    let mut voters: Vec<(&str, &str)> = Vec::new();
+    for ballot in votes.iter().flat_map(|record| &record.ballots) {
+        if !voters.iter().any(|(id, _)| *id == ballot.voter_id) {
+            voters.push((ballot.voter_id.as_str(), ballot.voter_name.as_str()));
+        }
+    }
+    if voters.len() < 2 {
+        return (Vec::new(), Vec::new());
+    }
+
+    let analyses: HashMap<&str, &Analysis> = samples
+        .iter()
+        .filter_map(|s| Some((s.path.as_str(), s.analysis.as_ref()?)))
+        .collect();
+    let stats = voters
+        .iter()
+        .map(|(id, name)| voter_stats(id, name, votes, &analyses))
+        .collect();
+
+    let mut agreement = Vec::new();
+    for (i, (first, first_name)) in voters.iter().enumerate() {
+        for (second, second_name) in &voters[i + 1..] {
+            let (mut shared, mut same) = (0, 0);
+            for record in votes {
+                let choice = |voter: &str| {
+                    record
+                        .ballots
+                        .iter()
+                        .find(|b| b.voter_id == voter && b.choice != Choice::Skip)
+                        .map(|b| b.choice)
+                };
+                if let (Some(a), Some(b)) = (choice(first), choice(second)) {
+                    shared += 1;
+                    same += (a == b) as usize;
+                }
+            }
+            if shared > 0 {
+                agreement.push(VoterAgreement {
+                    a: first.to_string(),
+                    b: second.to_string(),
+                    a_name: first_name.to_string(),
+                    b_name: second_name.to_string(),
+                    shared,
+                    rate: same as f32 / shared as f32,
+                });
+            }
+        }
+    }
+    (stats, agreement)
+}
+
+pub fn statistics(samples: &[Sample], history: &[MatchRecord], votes: &[VoteRecord]) -> Statistics {
+    let mut totals: HashMap<Option<&str>, PackTotals> = HashMap::new();
+
+    for sample in samples.iter().filter(|s| s.score <= ELIMINATED_SCORE) {
+        totals.entry(sample.pack.as_deref()).or_default().eliminated += 1;
+    }
+
+    for (rank, sample) in sorted_results(samples).into_iter().enumerate() {
+        let pack = totals.entry(sample.pack.as_deref()).or_default();
+        pack.samples += 1;
+        pack.score += sample.score as i64;
+        pack.comparisons += sample.comparisons as i64;
+        pack.best_score = Some(
+            pack.best_score
+                .map_or(sample.score, |b| b.max(sample.score)),
+        );
+        if rank < TOP_RANKS {
+            pack.top_ten += 1;
+        }
+    }
+
+    let mut packs: Vec<PackStats> = totals
+        .into_iter()
+        .map(|(pack, totals)| PackStats {
+            pack: pack.map(str::to_string),
+            samples: totals.samples,
+            eliminated: totals.eliminated,
+            mean_score: if totals.samples > 0 {
+                totals.score as f32 / totals.samples as f32
+            } else {
+                0.0
+            },
+            best_score: totals.best_score.unwrap_or(0),
+            win_rate: if totals.comparisons > 0 {
+                totals.score as f32 / totals.comparisons as f32
+            } else {
+                0.0
+            },
+            top_ten: totals.top_ten,
+        })
+        .collect();
+
+    let (voters, voter_agreement) = voter_statistics(samples, votes);
+
+    packs.sort_by(|a, b| {
+        b.mean_score
+            .partial_cmp(&a.mean_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.pack.cmp(&b.pack))
+    });
+
+    Statistics {
+        samples: samples.len(),
+        eliminated: samples
+            .iter()
+            .filter(|s| s.score <= ELIMINATED_SCORE)
+            .count(),
+        appearances: samples.iter().map(|s| s.comparisons).sum(),
+        packs,
+        upsets: upsets(history).into_iter().take(TOP_UPSETS).collect(),
+        voters,
+        voter_agreement,
+    }
+}
+
+fn pack_label(pack: &PackStats) -> &str {
+    pack.pack.as_deref().unwrap_or("(loose files)")
+}
+
+/// Render the per-pack table; the paths format lists pack names only
+pub fn format_pack_stats(stats: &Statistics, format: ResultsFormat) -> Result<String, String> {
+    match format {
+        ResultsFormat::Paths => Ok(stats
+            .packs
+            .iter()
+            .map(pack_label)
+            .collect::<Vec<_>>()
+            .join("\n")),
+        ResultsFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer
+                .write_record([
+                    "pack",
+                    "samples",
+                    "eliminated",
+                    "mean_score",
+                    "best_score",
+                    "win_rate",
+                    "top_ten",
+                ])
+                .map_err(|e| e.to_string())?;
+            for pack in &stats.packs {
+                writer
+                    .write_record([
+                        pack_label(pack).to_string(),
+                        pack.samples.to_string(),
+                        pack.eliminated.to_string(),
+                        format!("{:.2}", pack.mean_score),
+                        pack.best_score.to_string(),
+                        format!("{:.3}", pack.win_rate),
+                        pack.top_ten.to_string(),
+                    ])
+                    .map_err(|e| e.to_string())?;
+            }
+            let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+            String::from_utf8(bytes).map_err(|e| e.to_string())
+        }
+        ResultsFormat::Markdown => {
+            let headings = [
+                "heading-pack",
+                "heading-samples",
+                "heading-mean-score",
+                "heading-best",
+                "heading-win-rate",
+                "heading-top-ten",
+            ];
+            let mut lines = vec![
+                markdown_heading(&headings),
+                "|------|--------:|-----------:|-----:|---------:|-------:|".to_string(),
+            ];
+            for pack in &stats.packs {
+                lines.push(format!(
+                    "| {} | {} | {:.2} | {} | {:.1}% | {} |",
+                    pack_label(pack).replace('|', "\\|"),
+                    pack.samples,
+                    pack.mean_score,
+                    pack.best_score,
+                    pack.win_rate * 100.0,
+                    pack.top_ten
+                ));
+            }
+            Ok(lines.join("\n"))
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_statistics(
+    samples: Vec<Sample>,
+    history: Option<Vec<MatchRecord>>,
+    votes: Option<Vec<VoteRecord>>,
+) -> Statistics {
+    statistics(
+        &samples,
+        &history.unwrap_or_default(),
+        &votes.unwrap_or_default(),
+    )
+}
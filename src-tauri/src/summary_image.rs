@@ -0,0 +1,264 @@
+use std::path::PathBuf;
+use tauri::State;
+
+use crate::export::sorted_results;
+use crate::journal::now_secs;
+use crate::{audit, i18n, spectrogram, AllowedPaths, Sample};
+
+/// The usual link-preview size, so forums and chat show the card uncropped
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 630;
+const PADDING: u32 = 60;
+/// Rows that fit under the title
+const MAX_ROWS: usize = 10;
+
+const BACKGROUND: [u8; 3] = [24, 24, 30];
+const ACCENT: [u8; 3] = [236, 112, 64];
+const TEXT: [u8; 3] = [240, 240, 240];
+const MUTED: [u8; 3] = [150, 150, 160];
+
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 7;
+
+/// Classic 5x7 font for printable ASCII, one byte per column with the top row in
+/// bit 0. Keeps the card free of font files and text shaping.
+const FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x5F, 0x00, 0x00],
+    [0x00, 0x07, 0x00, 0x07, 0x00],
+    [0x14, 0x7F, 0x14, 0x7F, 0x14],
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12],
+    [0x23, 0x13, 0x08, 0x64, 0x62],
+    [0x36, 0x49, 0x55, 0x22, 0x50],
+    [0x00, 0x05, 0x03, 0x00, 0x00],
+    [0x00, 0x1C, 0x22, 0x41, 0x00],
+    [0x00, 0x41, 0x22, 0x1C, 0x00],
+    [0x14, 0x08, 0x3E, 0x08, 0x14],
+    [0x08, 0x08, 0x3E, 0x08, 0x08],
+    [0x00, 0x50, 0x30, 0x00, 0x00],
+    [0x08, 0x08, 0x08, 0x08, 0x08],
+    [0x00, 0x60, 0x60, 0x00, 0x00],
+    [0x20, 0x10, 0x08, 0x04, 0x02],
+    [0x3E, 0x51, 0x49, 0x45, 0x3E],
+    [0x00, 0x42, 0x7F, 0x40, 0x00],
+    [0x42, 0x61, 0x51, 0x49, 0x46],
+    [0x21, 0x41, 0x45, 0x4B, 0x31],
+    [0x18, 0x14, 0x12, 0x7F, 0x10],
+    [0x27, 0x45, 0x45, 0x45, 0x39],
+    [0x3C, 0x4A, 0x49, 0x49, 0x30],
+    [0x01, 0x71, 0x09, 0x05, 0x03],
+    [0x36, 0x49, 0x49, 0x49, 0x36],
+    [0x06, 0x49, 0x49, 0x29, 0x1E],
+    [0x00, 0x36, 0x36, 0x00, 0x00],
+    [0x00, 0x56, 0x36, 0x00, 0x00],
+    [0x08, 0x14, 0x22, 0x41, 0x00],
+    [0x14, 0x14, 0x14, 0x14, 0x14],
+    [0x00, 0x41, 0x22, 0x14, 0x08],
+    [0x02, 0x01, 0x51, 0x09, 0x06],
+    [0x32, 0x49, 0x79, 0x41, 0x3E],
+    [0x7E, 0x11, 0x11, 0x11, 0x7E],
+    [0x7F, 0x49, 0x49, 0x49, 0x36],
+    [0x3E, 0x41, 0x41, 0x41, 0x22],
+    [0x7F, 0x41, 0x41, 0x22, 0x1C],
+    [0x7F, 0x49, 0x49, 0x49, 0x41],
+    [0x7F, 0x09, 0x09, 0x09, 0x01],
+    [0x3E, 0x41, 0x49, 0x49, 0x7A],
+    [0x7F, 0x08, 0x08, 0x08, 0x7F],
+    [0x00, 0x41, 0x7F, 0x41, 0x00],
+    [0x20, 0x40, 0x41, 0x3F, 0x01],
+    [0x7F, 0x08, 0x14, 0x22, 0x41],
+    [0x7F, 0x40, 0x40, 0x40, 0x40],
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F],
+    [0x7F, 0x04, 0x08, 0x10, 0x7F],
+    [0x3E, 0x41, 0x41, 0x41, 0x3E],
+    [0x7F, 0x09, 0x09, 0x09, 0x06],
+    [0x3E, 0x41, 0x51, 0x21, 0x5E],
+    [0x7F, 0x09, 0x19, 0x29, 0x46],
+    [0x46, 0x49, 0x49, 0x49, 0x31],
+    [0x01, 0x01, 0x7F, 0x01, 0x01],
+    [0x3F, 0x40, 0x40, 0x40, 0x3F],
+    [0x1F, 0x20, 0x40, 0x20, 0x1F],
+    [0x3F, 0x40, 0x38, 0x40, 0x3F],
+    [0x63, 0x14, 0x08, 0x14, 0x63],
+    [0x07, 0x08, 0x70, 0x08, 0x07],
+    [0x61, 0x51, 0x49, 0x45, 0x43],
+    [0x00, 0x7F, 0x41, 0x41, 0x00],
+    [0x02, 0x04, 0x08, 0x10, 0x20],
+    [0x00, 0x41, 0x41, 0x7F, 0x00],
+    [0x04, 0x02, 0x01, 0x02, 0x04],
+    [0x40, 0x40, 0x40, 0x40, 0x40],
+    [0x00, 0x01, 0x02, 0x04, 0x00],
+    [0x20, 0x54, 0x54, 0x54, 0x78],
+    [0x7F, 0x48, 0x44, 0x44, 0x38],
+    [0x38, 0x44, 0x44, 0x44, 0x20],
+    [0x38, 0x44, 0x44, 0x48, 0x7F],
+    [0x38, 0x54, 0x54, 0x54, 0x18],
+    [0x08, 0x7E, 0x09, 0x01, 0x02],
+    [0x0C, 0x52, 0x52, 0x52, 0x3E],
+    [0x7F, 0x08, 0x04, 0x04, 0x78],
+    [0x00, 0x44, 0x7D, 0x40, 0x00],
+    [0x20, 0x40, 0x44, 0x3D, 0x00],
+    [0x7F, 0x10, 0x28, 0x44, 0x00],
+    [0x00, 0x41, 0x7F, 0x40, 0x00],
+    [0x7C, 0x04, 0x18, 0x04, 0x78],
+    [0x7C, 0x08, 0x04, 0x04, 0x78],
+    [0x38, 0x44, 0x44, 0x44, 0x38],
+    [0x7C, 0x14, 0x14, 0x14, 0x08],
+    [0x08, 0x14, 0x14, 0x18, 0x7C],
+    [0x7C, 0x08, 0x04, 0x04, 0x08],
+    [0x48, 0x54, 0x54, 0x54, 0x20],
+    [0x04, 0x3F, 0x44, 0x40, 0x20],
+    [0x3C, 0x40, 0x40, 0x20, 0x7C],
+    [0x1C, 0x20, 0x40, 0x20, 0x1C],
+    [0x3C, 0x40, 0x30, 0x40, 0x3C],
+    [0x44, 0x28, 0x10, 0x28, 0x44],
+    [0x0C, 0x50, 0x50, 0x50, 0x3C],
+    [0x44, 0x64, 0x54, 0x4C, 0x44],
+    [0x00, 0x08, 0x36, 0x41, 0x00],
+    [0x00, 0x00, 0x7F, 0x00, 0x00],
+    [0x00, 0x41, 0x36, 0x08, 0x00],
+    [0x02, 0x01, 0x02, 0x04, 0x02],
+];
+
+/// RGB canvas the card is drawn on
+struct Canvas {
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new() -> Self {
+        let pixels = BACKGROUND.repeat((WIDTH * HEIGHT) as usize);
+        Self { pixels }
+    }
+
+    fn fill(&mut self, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+        for row in y..(y + height).min(HEIGHT) {
+            for column in x..(x + width).min(WIDTH) {
+                let at = ((row * WIDTH + column) * 3) as usize;
+                self.pixels[at..at + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// Draw `text` with its top-left corner at (x, y), each font pixel `scale` wide
+    fn text(&mut self, text: &str, x: u32, y: u32, scale: u32, color: [u8; 3]) {
+        for (index, c) in text.chars().enumerate() {
+            let glyph = glyph(c);
+            let left = x + index as u32 * advance(scale);
+            for (column, bits) in (0..).zip(glyph) {
+                for row in 0..GLYPH_HEIGHT {
+                    if bits & (1 << row) != 0 {
+                        let (px, py) = (left + column * scale, y + row * scale);
+                        self.fill(px, py, scale, scale, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Characters outside printable ASCII draw as `?`
+fn glyph(c: char) -> [u8; 5] {
+    let index = (c as u32).wrapping_sub(0x20) as usize;
+    FONT.get(index)
+        .copied()
+        .unwrap_or(FONT['?' as usize - 0x20])
+}
+
+/// Horizontal distance from one character to the next, with a font pixel between them
+fn advance(scale: u32) -> u32 {
+    (GLYPH_WIDTH + 1) * scale
+}
+
+/// Cut `text` to `max` characters, marking the cut
+fn fitted(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max.saturating_sub(3)).collect();
+    format!("{}...", kept)
+}
+
+/// `2026-03-14` for a Unix timestamp, in UTC
+fn date(secs: u64) -> String {
+    // Days to civil date, after Howard Hinnant's algorithm
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Card with the title, the date and the best `top_n` samples with their scores
+pub fn render(samples: &[Sample], top_n: usize, title: &str, secs: u64) -> Vec<u8> {
+    let ranked = sorted_results(samples);
+    let shown = &ranked[..top_n.clamp(1, MAX_ROWS).min(ranked.len())];
+    let mut canvas = Canvas::new();
+    canvas.fill(0, 0, 12, HEIGHT, ACCENT);
+
+    let title_scale = 6;
+    let max_title = ((WIDTH - 2 * PADDING) / advance(title_scale)) as usize;
+    canvas.text(&fitted(title, max_title), PADDING, 48, title_scale, TEXT);
+    let subtitle = i18n::t_args(
+        "summary-subtitle",
+        [("date", date(secs).into()), ("count", ranked.len().into())],
+    );
+    canvas.text(&subtitle, PADDING, 110, 3, MUTED);
+
+    let scale = 4;
+    let row_height = (HEIGHT - 170 - PADDING / 2) / MAX_ROWS as u32;
+    for (index, sample) in shown.iter().enumerate() {
+        let y = 170 + index as u32 * row_height;
+        let rank = format!("{:>2}", index + 1);
+        canvas.text(&rank, PADDING, y, scale, ACCENT);
+
+        let score = sample.score.to_string();
+        let score_x = WIDTH - PADDING - score.len() as u32 * advance(scale);
+        canvas.text(&score, score_x, y, scale, MUTED);
+
+        let name_x = PADDING + 4 * advance(scale);
+        let max_name = (score_x.saturating_sub(name_x) / advance(scale)).saturating_sub(2) as usize;
+        canvas.text(&fitted(&sample.filename, max_name), name_x, y, scale, TEXT);
+    }
+    canvas.pixels
+}
+
+/// Render the top of the standings as a PNG card for posting; returns its path
+#[tauri::command]
+pub async fn render_summary_image(
+    samples: Vec<Sample>,
+    top_n: usize,
+    file_path: String,
+    title: Option<String>,
+    allowed_paths: State<'_, AllowedPaths>,
+) -> Result<String, String> {
+    let path = PathBuf::from(&file_path);
+    allowed_paths.check_file(&path)?;
+
+    // The folder the user exported into is allowed from now on
+    if let Some(parent) = path.parent() {
+        allowed_paths.add_allowed_path(parent.to_path_buf());
+    }
+    let title = title
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| i18n::t("summary-title"));
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let pixels = render(&samples, top_n, &title, now_secs());
+        spectrogram::write_png(&path, &pixels, WIDTH, HEIGHT)?;
+        audit::record_write(&path, None);
+        Ok(file_path)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
@@ -0,0 +1,21 @@
+use crate::{i18n, TournamentState};
+
+/// Replace the tags of `path`. Blank tags and repeats (ignoring case) are dropped.
+/// Tags don't affect scheduling; exports can be filtered by them.
+pub fn tag_sample(state: &mut TournamentState, path: &str, tags: &[String]) -> Result<(), String> {
+    let sample = state
+        .samples
+        .iter_mut()
+        .find(|s| s.path == path)
+        .ok_or_else(|| i18n::t("sample-not-in-tournament"))?;
+
+    let mut kept: Vec<String> = Vec::new();
+    for tag in tags.iter().map(|tag| tag.trim()) {
+        if !tag.is_empty() && !kept.iter().any(|k| k.eq_ignore_ascii_case(tag)) {
+            kept.push(tag.to_string());
+        }
+    }
+    tracing::info!(path = %path, tags = kept.len(), "tags changed");
+    sample.tags = kept;
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use serde::Serialize;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tauri::{AppHandle, Manager, State};
+
+use crate::analysis::{self, decode, DecodedAudio};
+use crate::settings::{Settings, SettingsStore};
+use crate::{archive, cache, i18n, AllowedPaths};
+
+/// Used when the settings don't set a size limit
+const DEFAULT_LIMIT_MB: u64 = 256;
+/// Eviction trims to this share of the limit, so it doesn't run again on the next thumbnail
+const EVICT_TO_PERCENT: u64 = 90;
+const MAX_BUCKETS: usize = 4096;
+/// Long files are streamed and averaged this many frames at a time before bucketing
+const STREAM_BLOCK_FRAMES: usize = 1024;
+/// Where spectrograms were cached before thumbnails shared one directory
+const LEGACY_SPECTROGRAM_DIR: &str = "spectrograms";
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CacheUsage {
+    pub bytes: u64,
+    pub files: usize,
+    pub limit_bytes: u64,
+}
+
+/// Waveform peaks and spectrograms, named by [`file_name`]
+pub fn thumbnail_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let cache_root = app.path().app_cache_dir().map_err(|e| e.to_string())?;
+    Ok(cache_root.join("thumbnails"))
+}
+
+pub fn limit_bytes(settings: &Settings) -> u64 {
+    settings.thumbnail_cache_mb.unwrap_or(DEFAULT_LIMIT_MB) * 1024 * 1024
+}
+
+/// Content-addressed name for a thumbnail of `source` rendered with `params`: a hash of
+/// the path, its modification time and the parameters. Archive entries are keyed by
+/// their archive.
+pub fn file_name(source: &str, params: &str, extension: &str) -> String {
+    let (key_path, extra) = match archive::split_archive_path(source) {
+        Some((archive_path, entry)) => (archive_path, format!("{}:{}", entry, params)),
+        None => (source, params.to_string()),
+    };
+    format!(
+        "{}.{}",
+        cache::cache_key(Path::new(key_path), &extra),
+        extension
+    )
+}
+
+/// Mark a cached thumbnail as just used; the modification time is its LRU timestamp
+pub fn touch(path: &Path) {
+    let touched = File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+    if let Err(e) = touched {
+        tracing::debug!(path = %path.display(), error = %e, "could not touch thumbnail");
+    }
+}
+
+/// Every cached file with its size and last use
+fn entries(dir: &Path) -> Vec<(PathBuf, u64, SystemTime)> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    read_dir
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            let used = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            Some((entry.path(), metadata.len(), used))
+        })
+        .collect()
+}
+
+pub fn usage(dir: &Path, limit_bytes: u64) -> CacheUsage {
+    let entries = entries(dir);
+    CacheUsage {
+        bytes: entries.iter().map(|(_, size, _)| size).sum(),
+        files: entries.len(),
+        limit_bytes,
+    }
+}
+
+/// Delete the least recently used thumbnails until the cache fits `limit_bytes`;
+/// returns the bytes freed
+pub fn evict(dir: &Path, limit_bytes: u64) -> u64 {
+    let mut entries = entries(dir);
+    let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+    if total <= limit_bytes {
+        return 0;
+    }
+
+    let target = limit_bytes / 100 * EVICT_TO_PERCENT;
+    entries.sort_by_key(|(_, _, used)| *used);
+    let mut freed = 0;
+    for (path, size, _) in entries {
+        if total <= target {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total -= size;
+            freed += size;
+        }
+    }
+    tracing::info!(freed, remaining = total, "evicted thumbnails");
+    freed
+}
+
+/// Mean absolute level of `buckets` equal slices of the first channel, scaled so the
+/// loudest slice is 1
+pub fn peaks(audio: &DecodedAudio, buckets: usize) -> Vec<f32> {
+    let first_channel: Vec<f32> = audio
+        .samples
+        .iter()
+        .step_by(audio.channels)
+        .copied()
+        .collect();
+    let block = (first_channel.len() / buckets).max(1);
+    let levels: Vec<f32> = first_channel
+        .chunks(block)
+        .take(buckets)
+        .map(|chunk| chunk.iter().map(|s| s.abs()).sum::<f32>() / chunk.len() as f32)
+        .collect();
+    normalize(levels)
+}
+
+/// `peaks` for files too long to decode whole: the first channel is averaged in small
+/// blocks while decoding, and the blocks are merged into buckets at the end
+fn streamed_peaks(source: &str, buckets: usize) -> Result<Vec<f32>, String> {
+    let mut blocks: Vec<(f32, usize)> = Vec::new();
+    analysis::decode_chunks(source, |chunk| {
+        for sample in chunk.channel(0) {
+            match blocks.last_mut() {
+                Some((sum, count)) if *count < STREAM_BLOCK_FRAMES => {
+                    *sum += sample.abs();
+                    *count += 1;
+                }
+                _ => blocks.push((sample.abs(), 1)),
+            }
+        }
+        Ok(())
+    })?;
+
+    let buckets = buckets.min(blocks.len());
+    let levels = (0..buckets)
+        .map(|bucket| {
+            let range = bucket * blocks.len() / buckets..(bucket + 1) * blocks.len() / buckets;
+            let (sum, count) = blocks[range]
+                .iter()
+                .fold((0.0, 0), |(sum, count), (s, c)| (sum + s, count + c));
+            sum / count.max(1) as f32
+        })
+        .collect();
+    Ok(normalize(levels))
+}
+
+/// Scale so the loudest level is 1
+fn normalize(levels: Vec<f32>) -> Vec<f32> {
+    let max = levels.iter().copied().fold(0.0f32, f32::max);
+    levels
+        .into_iter()
+        .map(|level| if max > 0.0 { level / max } else { 0.0 })
+        .collect()
+}
+
+/// Cached peaks for `source`, computed on first request
+pub fn peaks_file(
+    source: &str,
+    buckets: usize,
+    dir: &Path,
+    limit_bytes: u64,
+) -> Result<Vec<f32>, String> {
+    let path = dir.join(file_name(source, &format!("peaks:{}", buckets), "json"));
+    if let Some(peaks) = fs::read(&path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+    {
+        touch(&path);
+        return Ok(peaks);
+    }
+
+    let peaks = if analysis::needs_streaming(source) {
+        streamed_peaks(source, buckets)?
+    } else {
+        peaks(&decode(source)?, buckets)
+    };
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_vec(&peaks).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())?;
+    evict(dir, limit_bytes);
+    Ok(peaks)
+}
+
+/// Normalized waveform levels for drawing, decoded in the backend and cached on disk
+#[tauri::command]
+pub async fn get_waveform_peaks(
+    path: String,
+    buckets: usize,
+    app: AppHandle,
+    allowed_paths: State<'_, AllowedPaths>,
+    settings: State<'_, SettingsStore>,
+) -> Result<Vec<f32>, String> {
+    if !allowed_paths.is_path_allowed(Path::new(&path)) {
+        return Err(i18n::t("access-denied"));
+    }
+
+    let buckets = buckets.clamp(1, MAX_BUCKETS);
+    let dir = thumbnail_dir(&app)?;
+    let limit = limit_bytes(&settings.get());
+    tauri::async_runtime::spawn_blocking(move || peaks_file(&path, buckets, &dir, limit))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub fn get_thumbnail_cache_usage(
+    app: AppHandle,
+    settings: State<SettingsStore>,
+) -> Result<CacheUsage, String> {
+    Ok(usage(&thumbnail_dir(&app)?, limit_bytes(&settings.get())))
+}
+
+/// Delete every cached waveform and spectrogram; returns the bytes freed
+#[tauri::command]
+pub fn clear_analysis_cache(app: AppHandle) -> Result<u64, String> {
+    let dir = thumbnail_dir(&app)?;
+    let freed = usage(&dir, 0).bytes;
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| e.to_string())?;
+    }
+
+    let legacy = dir.with_file_name(LEGACY_SPECTROGRAM_DIR);
+    if legacy.exists() {
+        let _ = fs::remove_dir_all(&legacy);
+    }
+    tracing::info!(freed, "cleared thumbnail cache");
+    Ok(freed)
+}
@@ -0,0 +1,291 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, State};
+
+use crate::export::sorted_results;
+use crate::journal::now_secs;
+use crate::savefile::{self, Compression};
+use crate::settings::SettingsStore;
+use crate::staleness;
+use crate::{i18n, TournamentState};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TournamentMeta {
+    pub id: String,
+    pub name: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub sample_count: usize,
+    pub current_round: i32,
+    pub source_directory: String,
+}
+
+/// Named tournaments stored in the app data dir, so users don't juggle save files
+pub struct TournamentStore {
+    dir: PathBuf,
+    index: Mutex<Vec<TournamentMeta>>,
+}
+
+fn new_id() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("{:x}", nanos)
+}
+
+impl TournamentStore {
+    pub fn load(data_dir: &Path) -> Self {
+        let dir = data_dir.join("tournaments");
+        let index = fs::read_to_string(dir.join("index.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            dir,
+            index: Mutex::new(index),
+        }
+    }
+
+    fn state_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.vs1", id))
+    }
+
+    fn persist_index(&self, index: &[TournamentMeta]) -> Result<(), String> {
+        fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+        let json = serde_json::to_string_pretty(index).map_err(|e| e.to_string())?;
+        fs::write(self.dir.join("index.json"), json).map_err(|e| e.to_string())
+    }
+
+    fn write_state(&self, id: &str, state: &TournamentState) -> Result<(), String> {
+        fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+        let bytes = savefile::write(state, Compression::Zstd, None)?;
+        fs::write(self.state_path(id), bytes).map_err(|e| e.to_string())
+    }
+
+    pub fn list(&self) -> Vec<TournamentMeta> {
+        let mut list = self.index.lock().unwrap().clone();
+        list.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        list
+    }
+
+    pub fn get(&self, id: &str) -> Option<TournamentMeta> {
+        self.index
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|m| m.id == id)
+            .cloned()
+    }
+
+    pub fn read_state(&self, id: &str) -> Result<TournamentState, String> {
+        if self.get(id).is_none() {
+            return Err(i18n::t("unknown-tournament"));
+        }
+        let bytes = fs::read(self.state_path(id)).map_err(|e| e.to_string())?;
+        savefile::decode(bytes, None)
+    }
+
+    pub fn create(&self, name: &str, state: &TournamentState) -> Result<TournamentMeta, String> {
+        let now = now_secs();
+        let meta = TournamentMeta {
+            id: new_id(),
+            name: name.to_string(),
+            created_at: now,
+            updated_at: now,
+            sample_count: state.samples.len(),
+            current_round: state.current_round,
+            source_directory: state.source_directory.clone(),
+        };
+
+        self.write_state(&meta.id, state)?;
+        let mut index = self.index.lock().unwrap();
+        index.push(meta.clone());
+        self.persist_index(&index)?;
+        Ok(meta)
+    }
+
+    pub fn save(&self, id: &str, state: &TournamentState) -> Result<TournamentMeta, String> {
+        let mut index = self.index.lock().unwrap();
+        let meta = index
+            .iter_mut()
+            .find(|m| m.id == id)
+            .ok_or_else(|| i18n::t("unknown-tournament"))?;
+
+        self.write_state(id, state)?;
+        meta.updated_at = now_secs();
+        meta.sample_count = state.samples.len();
+        meta.current_round = state.current_round;
+        meta.source_directory = state.source_directory.clone();
+
+        let updated = meta.clone();
+        self.persist_index(&index)?;
+        Ok(updated)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        let mut index = self.index.lock().unwrap();
+        let before = index.len();
+        index.retain(|m| m.id != id);
+        if index.len() == before {
+            return Err(i18n::t("unknown-tournament"));
+        }
+
+        let _ = fs::remove_file(self.state_path(id));
+        self.persist_index(&index)
+    }
+}
+
+#[tauri::command]
+pub fn list_tournaments(store: State<TournamentStore>) -> Vec<TournamentMeta> {
+    store.list()
+}
+
+#[tauri::command]
+pub fn create_tournament(
+    name: String,
+    state: TournamentState,
+    store: State<TournamentStore>,
+) -> Result<TournamentMeta, String> {
+    store.create(&name, &state)
+}
+
+#[tauri::command]
+pub fn save_tournament(
+    id: String,
+    state: TournamentState,
+    store: State<TournamentStore>,
+) -> Result<TournamentMeta, String> {
+    store.save(&id, &state)
+}
+
+#[tauri::command]
+pub async fn open_tournament(
+    id: String,
+    app: AppHandle,
+    store: State<'_, TournamentStore>,
+    settings: State<'_, SettingsStore>,
+) -> Result<TournamentState, String> {
+    let mut state = store.read_state(&id)?;
+
+    if let (Some(meta), Some(stale_after_days)) = (store.get(&id), settings.get().stale_after_days)
+    {
+        let idle = now_secs().saturating_sub(meta.updated_at);
+        staleness::inflate(&mut state, idle, stale_after_days);
+    }
+
+    crate::restore_source_directory(&app, &state.source_directory).await;
+    Ok(state)
+}
+
+#[tauri::command]
+pub fn delete_tournament(id: String, store: State<TournamentStore>) -> Result<(), String> {
+    store.delete(&id)
+}
+
+#[tauri::command]
+pub fn duplicate_tournament(
+    id: String,
+    name: String,
+    store: State<TournamentStore>,
+) -> Result<TournamentMeta, String> {
+    let state = store.read_state(&id)?;
+    store.create(&name, &state)
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Placement {
+    pub tournament_id: String,
+    pub rank: usize,
+    /// Number of ranked samples in that tournament, to put the rank in context
+    pub field_size: usize,
+    pub score: i32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CrossTournamentEntry {
+    pub path: String,
+    pub filename: String,
+    pub placements: Vec<Placement>,
+    /// Mean of rank / field_size across tournaments (lower is better)
+    pub mean_percentile: f32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ComparisonReport {
+    pub tournaments: Vec<TournamentMeta>,
+    pub samples: Vec<CrossTournamentEntry>,
+}
+
+/// Find samples ranked in more than one tournament and list their placements
+pub fn compare(tournaments: &[(TournamentMeta, TournamentState)]) -> ComparisonReport {
+    let mut by_path: HashMap<String, CrossTournamentEntry> = HashMap::new();
+
+    for (meta, state) in tournaments {
+        let ranked = sorted_results(&state.samples);
+        let field_size = ranked.len();
+        for (index, sample) in ranked.into_iter().enumerate() {
+            let entry =
+                by_path
+                    .entry(sample.path.clone())
+                    .or_insert_with(|| CrossTournamentEntry {
+                        path: sample.path.clone(),
+                        filename: sample.filename.clone(),
+                        placements: Vec::new(),
+                        mean_percentile: 0.0,
+                    });
+            entry.placements.push(Placement {
+                tournament_id: meta.id.clone(),
+                rank: index + 1,
+                field_size,
+                score: sample.score,
+            });
+        }
+    }
+
+    let mut samples: Vec<CrossTournamentEntry> = by_path
+        .into_values()
+        .filter(|entry| entry.placements.len() > 1)
+        .map(|mut entry| {
+            let total: f32 = entry
+                .placements
+                .iter()
+                .map(|p| p.rank as f32 / p.field_size.max(1) as f32)
+                .sum();
+            entry.mean_percentile = total / entry.placements.len() as f32;
+            entry
+        })
+        .collect();
+
+    samples.sort_by(|a, b| {
+        b.placements.len().cmp(&a.placements.len()).then_with(|| {
+            a.mean_percentile
+                .partial_cmp(&b.mean_percentile)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    });
+
+    ComparisonReport {
+        tournaments: tournaments.iter().map(|(meta, _)| meta.clone()).collect(),
+        samples,
+    }
+}
+
+#[tauri::command]
+pub fn compare_tournaments(
+    ids: Vec<String>,
+    store: State<TournamentStore>,
+) -> Result<ComparisonReport, String> {
+    let mut tournaments = Vec::new();
+    for id in &ids {
+        let meta = store.get(id).ok_or_else(|| i18n::t("unknown-tournament"))?;
+        tournaments.push((meta, store.read_state(id)?));
+    }
+
+    Ok(compare(&tournaments))
+}
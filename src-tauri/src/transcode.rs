@@ -0,0 +1,341 @@
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::analysis::{self, DecodedAudio};
+use crate::cache::cache_key;
+use crate::{archive, video};
+
+/// -3 dB, the usual weight for centre and surround channels in a stereo fold-down
+const FOLD_GAIN: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// Left/right gains per input channel, assuming the standard WAV/SMPTE order
+/// (L R C LFE Ls Rs, 7.1 adds Lb Rb before Ls Rs). The LFE is dropped.
+fn stereo_gains(channels: usize) -> Vec<(f32, f32)> {
+    let (l, r, c, lfe) = ((1.0, 0.0), (0.0, 1.0), (FOLD_GAIN, FOLD_GAIN), (0.0, 0.0));
+    let (ls, rs) = ((FOLD_GAIN, 0.0), (0.0, FOLD_GAIN));
+    match channels {
+        3 => vec![l, r, c],
+        4 => vec![l, r, ls, rs],
+        5 => vec![l, r, c, ls, rs],
+        6 => vec![l, r, c, lfe, ls, rs],
+        8 => vec![l, r, c, lfe, ls, rs, ls, rs],
+        // Unknown layouts: alternate channels between the sides
+        _ => (0..channels)
+            .map(|i| {
+                if i % 2 == 0 {
+                    (FOLD_GAIN, 0.0)
+                } else {
+                    (0.0, FOLD_GAIN)
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Fold interleaved multichannel audio down to stereo, scaled back if it would clip
+pub fn downmix_to_stereo(audio: &DecodedAudio) -> Vec<f32> {
+    let gains = stereo_gains(audio.channels);
+    let mut stereo: Vec<f32> = audio
+        .samples
+        .chunks(audio.channels)
+        .flat_map(|frame| {
+            let (left, right) = frame
+                .iter()
+                .zip(&gains)
+                .fold((0.0, 0.0), |(l, r), (x, (gl, gr))| (l + x * gl, r + x * gr));
+            [left, right]
+        })
+        .collect();
+
+    let peak = stereo.iter().fold(0.0f32, |p, x| p.max(x.abs()));
+    if peak > 1.0 {
+        stereo.iter_mut().for_each(|x| *x /= peak);
+    }
+    stereo
+}
+
+/// Write interleaved samples as a 24-bit PCM WAV, which every webview can play
+pub fn write_wav(
+    path: &Path,
+    sample_rate: u32,
+    channels: u16,
+    samples: &[f32],
+) -> Result<(), String> {
+    const BYTES_PER_SAMPLE: u16 = 3;
+    let block_align = channels * BYTES_PER_SAMPLE;
+    let data_len = samples.len() as u32 * BYTES_PER_SAMPLE as u32;
+
+    let mut out = BufWriter::new(File::create(path).map_err(|e| e.to_string())?);
+    let mut header = Vec::with_capacity(44);
+    header.extend_from_slice(b"RIFF");
+    header.extend_from_slice(&(36 + data_len).to_le_bytes());
+    header.extend_from_slice(b"WAVEfmt ");
+    header.extend_from_slice(&16u32.to_le_bytes());
+    header.extend_from_slice(&1u16.to_le_bytes());
+    header.extend_from_slice(&channels.to_le_bytes());
+    header.extend_from_slice(&sample_rate.to_le_bytes());
+    header.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+    header.extend_from_slice(&block_align.to_le_bytes());
+    header.extend_from_slice(&(BYTES_PER_SAMPLE * 8).to_le_bytes());
+    header.extend_from_slice(b"data");
+    header.extend_from_slice(&data_len.to_le_bytes());
+    out.write_all(&header).map_err(|e| e.to_string())?;
+
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * 8_388_607.0) as i32;
+        out.write_all(&value.to_le_bytes()[..3])
+            .map_err(|e| e.to_string())?;
+    }
+    out.flush().map_err(|e| e.to_string())
+}
+
+/// Rates every webview plays natively; anything else is converted
+const NATIVE_RATES: &[u32] = &[8000, 11025, 16000, 22050, 24000, 32000, 44100, 48000];
+/// How far the limiter looks ahead, and how fast it recovers after a peak
+const LIMITER_LOOKAHEAD_SECS: f32 = 0.005;
+const LIMITER_RELEASE_SECS: f32 = 0.08;
+/// Half-width of the resampling filter in input samples at unity ratio
+const SINC_TAPS: usize = 16;
+/// Fractional offsets the filter is tabulated at; weights in between are interpolated
+const SINC_PHASES: usize = 256;
+
+/// 44.1k family rates go to 44100, everything else to 48000
+fn playback_rate(sample_rate: u32) -> u32 {
+    if sample_rate % 11025 == 0 {
+        44100
+    } else {
+        48000
+    }
+}
+
+fn sinc(x: f32) -> f32 {
+    if x.abs() < 1e-6 {
+        1.0
+    } else {
+        let px = std::f32::consts::PI * x;
+        px.sin() / px
+    }
+}
+
+/// Hann-windowed sinc weights tabulated at `SINC_PHASES + 1` fractional offsets, so
+/// resampling does no trigonometry per sample
+struct SincTable {
+    taps: usize,
+    /// Row `p` holds the weights of taps `1 - half_width..=half_width` at offset
+    /// `p / SINC_PHASES` past the centre sample
+    weights: Vec<f32>,
+}
+
+impl SincTable {
+    fn new(cutoff: f32, half_width: isize) -> Self {
+        let taps = 2 * half_width as usize;
+        let mut weights = Vec::with_capacity((SINC_PHASES + 1) * taps);
+        for phase in 0..=SINC_PHASES {
+            let fraction = phase as f32 / SINC_PHASES as f32;
+            for tap in (1 - half_width)..=half_width {
+                let distance = tap as f32 - fraction;
+                let window =
+                    0.5 + 0.5 * (std::f32::consts::PI * distance / half_width as f32).cos();
+                weights.push(cutoff * sinc(distance * cutoff) * window);
+            }
+        }
+        Self { taps, weights }
+    }
+
+    fn row(&self, phase: usize) -> &[f32] {
+        &self.weights[phase * self.taps..(phase + 1) * self.taps]
+    }
+}
+
+/// Band-limited (windowed-sinc) conversion of interleaved audio to `to_rate`
+pub fn resample(samples: &[f32], channels: usize, from_rate: u32, to_rate: u32) -> Vec<f32> {
+    let frames = samples.len() / channels;
+    let step = from_rate as f64 / to_rate as f64;
+    // Lower the cutoff when downsampling so nothing above the new Nyquist folds back
+    let cutoff = (to_rate as f32 / from_rate as f32).min(1.0);
+    let half_width = (SINC_TAPS as f32 / cutoff).ceil() as isize;
+    let out_frames = (frames as f64 / step).floor() as usize;
+    let table = SincTable::new(cutoff, half_width);
+
+    let mut out = Vec::with_capacity(out_frames * channels);
+    let mut acc = vec![0.0f32; channels];
+    for n in 0..out_frames {
+        let position = n as f64 * step;
+        let center = position.floor() as isize;
+        let scaled = (position - center as f64) * SINC_PHASES as f64;
+        let phase = (scaled as usize).min(SINC_PHASES - 1);
+        let between = (scaled - phase as f64) as f32;
+        let first = center + 1 - half_width;
+
+        acc.fill(0.0);
+        let mut weight_sum = 0.0f32;
+        let rows = table.row(phase).iter().zip(table.row(phase + 1));
+        for (offset, (&lower, &upper)) in rows.enumerate() {
+            let k = first + offset as isize;
+            if k < 0 || k as usize >= frames {
+                continue;
+            }
+            let weight = lower + (upper - lower) * between;
+            weight_sum += weight;
+
+            let frame = &samples[k as usize * channels..(k as usize + 1) * channels];
+            acc.iter_mut()
+                .zip(frame)
+                .for_each(|(a, x)| *a += x * weight);
+        }
+
+        // Normalise so DC gain stays at unity near the edges of the file
+        let norm = if weight_sum.abs() > 1e-6 {
+            1.0 / weight_sum
+        } else {
+            0.0
+        };
+        out.extend(acc.iter().map(|a| a * norm));
+    }
+    out
+}
+
+/// Whether the file needs converting before the webview can play it reliably:
+/// more than two channels, or a rate outside [`NATIVE_RATES`] (96k, 192k, ...)
+pub fn needs_conversion(path: &Path) -> bool {
+    let Ok(info) = analysis::stream_info(&path.to_string_lossy()) else {
+        return false;
+    };
+    info.channels.is_some_and(|channels| channels > 2)
+        || info
+            .sample_rate
+            .is_some_and(|rate| !NATIVE_RATES.contains(&rate))
+}
+
+/// Brickwall limiting of interleaved audio to `ceiling` (linear). Gain reduction
+/// starts ahead of each peak and recovers slowly, so there's no pumping click.
+pub fn limit(samples: &mut [f32], channels: usize, sample_rate: u32, ceiling: f32) {
+    let frames = samples.len() / channels;
+    let lookahead = ((LIMITER_LOOKAHEAD_SECS * sample_rate as f32) as usize).max(1);
+    let attack = (-4.0 / lookahead as f32).exp();
+    let release = (-1.0 / (LIMITER_RELEASE_SECS * sample_rate as f32)).exp();
+
+    // Gain each frame needs on its own
+    let needed: Vec<f32> = samples
+        .chunks(channels)
+        .map(|frame| {
+            let peak = frame.iter().fold(0.0f32, |p, x| p.max(x.abs()));
+            if peak > ceiling {
+                ceiling / peak
+            } else {
+                1.0
+            }
+        })
+        .collect();
+
+    // Sliding minimum of `needed` over the upcoming lookahead window
+    let mut window: VecDeque<usize> = VecDeque::new();
+    let mut gain = 1.0f32;
+    for frame in 0..frames {
+        let incoming = frame + lookahead - 1;
+        if incoming < frames {
+            while window
+                .back()
+                .is_some_and(|&i| needed[i] >= needed[incoming])
+            {
+                window.pop_back();
+            }
+            window.push_back(incoming);
+        }
+        while window.front().is_some_and(|&i| i < frame) {
+            window.pop_front();
+        }
+
+        let target = window.front().map_or(1.0, |&i| needed[i]);
+        let coefficient = if target < gain { attack } else { release };
+        gain = target + (gain - target) * coefficient;
+
+        for sample in &mut samples[frame * channels..(frame + 1) * channels] {
+            // The smoothed gain can lag a sudden peak; never let anything through
+            *sample = (*sample * gain).clamp(-ceiling, ceiling);
+        }
+    }
+}
+
+/// Playable version of `source` in `cache_dir`, created on first use: stereo, at a
+/// native rate and under `ceiling_dbfs`. `None` when the file can be played as is.
+pub fn playback_copy(
+    source: &Path,
+    cache_dir: &Path,
+    ceiling_dbfs: Option<f32>,
+) -> Result<Option<PathBuf>, String> {
+    let convert = needs_conversion(source);
+    if !convert && ceiling_dbfs.is_none() {
+        return Ok(None);
+    }
+
+    let key = cache_key(source, &format!("playback:{:?}", ceiling_dbfs));
+    let target = cache_dir.join(format!("{}.wav", key));
+    // Marks files already checked against the ceiling that needed nothing
+    let unchanged = cache_dir.join(format!("{}.unchanged", key));
+    if target.exists() {
+        return Ok(Some(target));
+    }
+    if unchanged.exists() {
+        return Ok(None);
+    }
+
+    let audio = analysis::decode(&source.to_string_lossy())?;
+    let ceiling = ceiling_dbfs.map(|db| 10f32.powf(db / 20.0));
+    let too_loud = ceiling.is_some_and(|c| audio.samples.iter().any(|x| x.abs() > c));
+
+    fs::create_dir_all(cache_dir).map_err(|e| e.to_string())?;
+    if !convert && !too_loud {
+        fs::write(&unchanged, b"").map_err(|e| e.to_string())?;
+        return Ok(None);
+    }
+
+    let (mut samples, channels) = if audio.channels > 2 {
+        (downmix_to_stereo(&audio), 2)
+    } else {
+        (audio.samples, audio.channels)
+    };
+
+    let mut sample_rate = audio.sample_rate;
+    if !NATIVE_RATES.contains(&sample_rate) {
+        let to_rate = playback_rate(sample_rate);
+        samples = resample(&samples, channels, sample_rate, to_rate);
+        sample_rate = to_rate;
+    }
+
+    // Last, so resampling overshoot is caught too
+    if let Some(ceiling) = ceiling {
+        limit(&mut samples, channels, sample_rate, ceiling);
+    }
+
+    let partial = target.with_extension("partial");
+    write_wav(&partial, sample_rate, channels as u16, &samples)?;
+    fs::rename(&partial, &target).map_err(|e| e.to_string())?;
+    Ok(Some(target))
+}
+
+/// Local file the webview can play for a sample path: archive entries and video
+/// audio are extracted, then converted if needed. `None` means the path plays as is.
+pub fn prepare_playback(
+    file_path: &str,
+    cache_root: &Path,
+    ceiling_dbfs: Option<f32>,
+) -> Result<Option<PathBuf>, String> {
+    let path = Path::new(file_path);
+    let extracted = if let Some((archive, entry)) = archive::split_archive_path(file_path) {
+        Some(archive::extract_entry(
+            Path::new(archive),
+            entry,
+            &cache_root.join("archives"),
+        )?)
+    } else if video::is_video(path) {
+        Some(video::extract_audio(path, &cache_root.join("video"))?)
+    } else {
+        None
+    };
+
+    let source = extracted.as_deref().unwrap_or(path);
+    Ok(playback_copy(source, &cache_root.join("playback"), ceiling_dbfs)?.or(extracted))
+}
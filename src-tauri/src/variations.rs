@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::Sample;
+
+/// Suffix words marking an alternate take of the same sound, optionally numbered
+const VARIATION_MARKERS: [&str; 4] = ["alt", "var", "take", "v"];
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '_' | '-' | ' ' | '.')
+}
+
+/// Remove one variation suffix (`(2)`, trailing letter after a number, `_alt`, `_v2`, ...)
+fn strip_variation_suffix(stem: &str) -> &str {
+    if let Some((head, number)) = stem
+        .strip_suffix(')')
+        .and_then(|inner| inner.rsplit_once('('))
+    {
+        if !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()) {
+            return head.trim_end();
+        }
+    }
+
+    // kick_01a / kick_01b
+    let bytes = stem.as_bytes();
+    if bytes.len() >= 2
+        && bytes[bytes.len() - 1].is_ascii_lowercase()
+        && bytes[bytes.len() - 2].is_ascii_digit()
+    {
+        return &stem[..stem.len() - 1];
+    }
+
+    let without_number = stem.trim_end_matches(|c: char| c.is_ascii_digit());
+    for marker in VARIATION_MARKERS {
+        if let Some(head) = without_number.strip_suffix(marker) {
+            if head.ends_with(is_separator) {
+                return head.trim_end_matches(is_separator);
+            }
+        }
+    }
+
+    stem
+}
+
+/// Name shared by every variation of a sound, e.g. `kick_01` for `Kick_01b.wav`
+pub fn variation_key(filename: &str) -> String {
+    let stem = filename
+        .rsplit_once('.')
+        .map_or(filename, |(stem, _)| stem)
+        .to_lowercase();
+
+    let mut key = stem.as_str();
+    loop {
+        let stripped = strip_variation_suffix(key);
+        if stripped.len() == key.len() || stripped.is_empty() {
+            break;
+        }
+        key = stripped;
+    }
+
+    key.trim_end_matches(is_separator).to_string()
+}
+
+/// Collapse variation families within each folder into one entrant. The
+/// alphabetically first file is auditioned; the others are kept in `variants`.
+pub fn group(samples: Vec<Sample>) -> Vec<Sample> {
+    let mut order = Vec::new();
+    let mut families: HashMap<(String, String), Vec<Sample>> = HashMap::new();
+
+    for sample in samples {
+        let folder = Path::new(&sample.path)
+            .parent()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let key = (folder, variation_key(&sample.filename));
+        families
+            .entry(key.clone())
+            .or_insert_with(|| {
+                order.push(key);
+                Vec::new()
+            })
+            .push(sample);
+    }
+
+    order
+        .into_iter()
+        .filter_map(|key| families.remove(&key))
+        .map(|mut members| {
+            members.sort_by(|a, b| a.filename.cmp(&b.filename));
+            let mut representative = members.remove(0);
+            for member in members {
+                representative.variants.push(member.path);
+                representative.variants.extend(member.variants);
+            }
+            representative
+        })
+        .collect()
+}
+
+/// List every member of each group, each carrying its group's score
+pub fn expand(samples: &[&Sample]) -> Vec<Sample> {
+    samples
+        .iter()
+        .flat_map(|sample| {
+            let members = sample.variants.iter().map(|path| Sample {
+                path: path.clone(),
+                filename: Path::new(path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                analysis: None,
+                variants: Vec::new(),
+                ..(*sample).clone()
+            });
+            std::iter::once((*sample).clone()).chain(members)
+        })
+        .collect()
+}
@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::audit::{self, AuditKind};
+use crate::cache::cache_key;
+use crate::i18n;
+
+pub const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mov", "webm"];
+
+/// Video files are only picked up when built with the `video` feature
+pub fn is_video(path: &Path) -> bool {
+    cfg!(feature = "video")
+        && path
+            .extension()
+            .map(|ext| {
+                let ext_lower = ext.to_string_lossy().to_lowercase();
+                VIDEO_EXTENSIONS.contains(&ext_lower.as_str())
+            })
+            .unwrap_or(false)
+}
+
+/// Demux the first audio track of a video file into a cached WAV using ffmpeg
+pub fn extract_audio(video_path: &Path, cache_dir: &Path) -> Result<PathBuf, String> {
+    let stem = video_path
+        .file_stem()
+        .ok_or_else(|| i18n::t("invalid-video-path"))?
+        .to_string_lossy()
+        .to_string();
+    let target_dir = cache_dir.join(cache_key(video_path, "audio"));
+    let target = target_dir.join(format!("{}.wav", stem));
+    if target.exists() {
+        return Ok(target);
+    }
+
+    fs::create_dir_all(&target_dir).map_err(|e| e.to_string())?;
+
+    // ffmpeg picks the container from the extension, so keep `.wav` last
+    let partial = target_dir.join(format!("{}.partial.wav", stem));
+    audit::record(
+        AuditKind::Launch,
+        "ffmpeg",
+        Some(video_path.to_string_lossy().to_string()),
+    );
+    let output = std::process::Command::new("ffmpeg")
+        .args(["-nostdin", "-y", "-v", "error", "-i"])
+        .arg(video_path)
+        .args(["-vn", "-map", "0:a:0", "-c:a", "pcm_s16le"])
+        .arg(&partial)
+        .output()
+        .map_err(|e| {
+            i18n::t_args(
+                "program-failed",
+                [
+                    ("program", "ffmpeg".into()),
+                    ("error", e.to_string().into()),
+                ],
+            )
+        })?;
+
+    if !output.status.success() {
+        let _ = fs::remove_file(&partial);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(i18n::t_args(
+            "video-extract-failed",
+            [("error", stderr.trim().into())],
+        ));
+    }
+
+    fs::rename(&partial, &target).map_err(|e| e.to_string())?;
+    Ok(target)
+}
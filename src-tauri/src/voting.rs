@@ -0,0 +1,152 @@
+use crate::calibration::{self, CalibrationCheck};
+use crate::collab::{Ballot, Choice, VoteRecord};
+use crate::export::ELIMINATED_SCORE;
+use crate::plugins::RatingUpdate;
+use crate::round_summary::MatchRecord;
+use crate::{i18n, Sample, TournamentState};
+
+/// Each comparison re-confirms a sample, halving whatever staleness it carried
+pub const UNCERTAINTY_DECAY: f32 = 0.5;
+
+/// Names the comparison on screen, so a vote cast on an earlier one can be refused
+pub fn pair_id(state: &TournamentState) -> String {
+    format!("{}-{}", state.current_round, state.current_comparison_index)
+}
+
+/// The comparison `pair_id` names, if it is still the current one
+pub fn current_pair(state: &TournamentState, pair_id: &str) -> Result<(usize, usize), String> {
+    state
+        .comparisons_this_round
+        .get(state.current_comparison_index)
+        .copied()
+        .filter(|_| pair_id == self::pair_id(state))
+        .ok_or_else(|| i18n::t("vote-not-current"))
+}
+
+/// Winner and loser of the comparison `pair_id` when `winner` takes it
+pub fn contenders(
+    state: &TournamentState,
+    pair_id: &str,
+    winner: usize,
+) -> Result<(usize, usize), String> {
+    match current_pair(state, pair_id)? {
+        (a, b) if winner == a => Ok((a, b)),
+        (a, b) if winner == b => Ok((b, a)),
+        _ => Err(i18n::t("vote-not-in-pair")),
+    }
+}
+
+/// Keep the individual ballots of a collaborative comparison alongside its outcome
+fn keep_ballots(
+    state: &mut TournamentState,
+    (a, b): (usize, usize),
+    winner: Choice,
+    ballots: Vec<Ballot>,
+) {
+    if ballots.is_empty() {
+        return;
+    }
+    state.votes.push(VoteRecord {
+        pair_id: pair_id(state),
+        round: state.current_round,
+        a: state.samples[a].path.clone(),
+        b: state.samples[b].path.clone(),
+        winner,
+        ballots,
+    });
+}
+
+/// A comparison re-confirmed `sample`
+pub fn settle(sample: &mut Sample) {
+    sample.uncertainty *= UNCERTAINTY_DECAY;
+}
+
+/// Record that `winner` took the comparison `pair_id`: +1 for it, or the scores a rating
+/// plugin came up with. Calibration matches are logged on the reference instead and
+/// change no scores. Returns the indices of the samples that changed.
+pub fn record(
+    state: &mut TournamentState,
+    pair_id: &str,
+    winner: usize,
+    decision_ms: Option<u64>,
+    ratings: Option<RatingUpdate>,
+    ballots: Vec<Ballot>,
+) -> Result<Vec<usize>, String> {
+    let pair = current_pair(state, pair_id)?;
+    let (winner, loser) = contenders(state, pair_id, winner)?;
+    let choice = if winner == pair.0 {
+        Choice::A
+    } else {
+        Choice::B
+    };
+    keep_ballots(state, pair, choice, ballots);
+    let is_calibration = calibration::is_calibration_pairing(state);
+    state.current_comparison_index += 1;
+
+    if is_calibration {
+        let round = state.current_round;
+        let reference_won = Some(winner) == calibration::reference_index(state);
+        let opponent = &state.samples[if reference_won { loser } else { winner }];
+        let check = CalibrationCheck {
+            round,
+            opponent: opponent.path.clone(),
+            opponent_score: opponent.score,
+            reference_won,
+        };
+        if let Some(reference) = &mut state.reference {
+            reference.checks.push(check);
+        }
+        return Ok(Vec::new());
+    }
+
+    state.history.push(MatchRecord {
+        round: state.current_round,
+        winner: state.samples[winner].path.clone(),
+        loser: state.samples[loser].path.clone(),
+        winner_score: state.samples[winner].score,
+        loser_score: state.samples[loser].score,
+        decision_ms,
+    });
+
+    let (winner_score, loser_score) = match ratings {
+        Some(ratings) => (ratings.winner_score, ratings.loser_score),
+        None => (state.samples[winner].score + 1, state.samples[loser].score),
+    };
+    for (index, score) in [(winner, winner_score), (loser, loser_score)] {
+        let sample = &mut state.samples[index];
+        sample.score = score;
+        sample.comparisons += 1;
+        settle(sample);
+    }
+    Ok(vec![winner, loser])
+}
+
+/// Take both samples of the comparison `pair_id` out, dropping their comparisons still
+/// to come this round. Skipping a calibration match just moves on; the reference stays
+/// in. Returns the indices of the samples that changed.
+pub fn eliminate_both(
+    state: &mut TournamentState,
+    pair_id: &str,
+    ballots: Vec<Ballot>,
+) -> Result<Vec<usize>, String> {
+    let (a, b) = current_pair(state, pair_id)?;
+    keep_ballots(state, (a, b), Choice::Skip, ballots);
+    let is_calibration = calibration::is_calibration_pairing(state);
+    let current = state.current_comparison_index;
+    state.current_comparison_index += 1;
+    if is_calibration {
+        return Ok(Vec::new());
+    }
+
+    for index in [a, b] {
+        let sample = &mut state.samples[index];
+        sample.score = ELIMINATED_SCORE;
+        sample.comparisons += 1;
+    }
+    let mut position = 0;
+    state.comparisons_this_round.retain(|&(x, y)| {
+        position += 1;
+        position <= current + 1 || (x != a && x != b && y != a && y != b)
+    });
+    Ok(vec![a, b])
+}
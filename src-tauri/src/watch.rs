@@ -0,0 +1,122 @@
+use notify::event::{CreateKind, ModifyKind, RenameMode};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::packs::PackDetector;
+use crate::scan::sample_from_path;
+use crate::{i18n, AllowedPaths};
+
+/// Filesystem watcher that auto-enrolls newly created audio files
+#[derive(Default)]
+pub struct WatchState {
+    watcher: Mutex<Option<RecommendedWatcher>>,
+    /// Paths enrolled by the watcher that have not been undone yet
+    enrolled: Mutex<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct Unenrolled {
+    path: String,
+}
+
+fn is_new_file(kind: &EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::Create(CreateKind::File)
+            | EventKind::Create(CreateKind::Any)
+            | EventKind::Modify(ModifyKind::Name(RenameMode::To))
+            | EventKind::Modify(ModifyKind::Name(RenameMode::Both))
+    )
+}
+
+fn handle_event(app: &AppHandle, root: &Path, event: notify::Event) {
+    if !is_new_file(&event.kind) {
+        return;
+    }
+
+    let state = app.state::<WatchState>();
+    // A rename reports both the old and new path; only the last one exists
+    for path in event.paths.iter().filter(|p| p.is_file()) {
+        let Some(mut sample) = sample_from_path(path) else {
+            continue;
+        };
+        sample.provisional = true;
+        sample.pack = PackDetector::new(root).detect(&sample.path);
+
+        {
+            let mut enrolled = state.enrolled.lock().unwrap();
+            if enrolled.contains(&sample.path) {
+                continue;
+            }
+            enrolled.push(sample.path.clone());
+        }
+
+        let _ = app.emit("watch://enrolled", &sample);
+    }
+}
+
+#[tauri::command]
+pub fn start_watch(
+    directory: &str,
+    app: AppHandle,
+    watch_state: State<WatchState>,
+    allowed_paths: State<AllowedPaths>,
+) -> Result<(), String> {
+    let path = Path::new(directory);
+    if !path.is_dir() {
+        return Err(i18n::t("directory-not-found"));
+    }
+
+    // Only the scanned source directory may be watched
+    if !allowed_paths.is_path_allowed(path) {
+        return Err(i18n::t("access-denied"));
+    }
+
+    let handle = app.clone();
+    let root = path.to_path_buf();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res {
+            handle_event(&handle, &root, event);
+        }
+    })
+    .map_err(|e| e.to_string())?;
+
+    watcher
+        .watch(path, RecursiveMode::Recursive)
+        .map_err(|e| e.to_string())?;
+
+    // Replacing the previous watcher drops it, which stops it
+    *watch_state.watcher.lock().unwrap() = Some(watcher);
+    watch_state.enrolled.lock().unwrap().clear();
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_watch(watch_state: State<WatchState>) {
+    *watch_state.watcher.lock().unwrap() = None;
+}
+
+/// Withdraw an auto-enrolled sample; returns false if it was not enrolled by the watcher
+#[tauri::command]
+pub fn undo_enrollment(
+    path: String,
+    app: AppHandle,
+    watch_state: State<WatchState>,
+) -> Result<bool, String> {
+    let removed = {
+        let mut enrolled = watch_state.enrolled.lock().unwrap();
+        let before = enrolled.len();
+        enrolled.retain(|p| p != &path);
+        enrolled.len() != before
+    };
+
+    if removed {
+        app.emit("watch://unenrolled", Unenrolled { path })
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(removed)
+}
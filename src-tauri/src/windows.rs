@@ -0,0 +1,83 @@
+use tauri::{AppHandle, LogicalSize, Manager, WebviewUrl, WebviewWindowBuilder};
+
+/// Stored in the app config dir by tauri-plugin-window-state
+pub const WINDOW_STATE_FILE: &str = "window-state.json";
+
+// Keep in sync with the main window in tauri.conf.json
+const DEFAULT_MAIN_SIZE: LogicalSize<f64> = LogicalSize {
+    width: 1000.0,
+    height: 700.0,
+};
+
+/// Forget saved window geometry and put the main window back to its default size, centred
+#[tauri::command]
+pub fn reset_window_state(app: AppHandle) -> Result<(), String> {
+    let state_file = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| e.to_string())?
+        .join(WINDOW_STATE_FILE);
+    if state_file.exists() {
+        std::fs::remove_file(&state_file).map_err(|e| e.to_string())?;
+    }
+
+    if let Some(window) = app.get_webview_window("main") {
+        window.set_fullscreen(false).map_err(|e| e.to_string())?;
+        window.unmaximize().map_err(|e| e.to_string())?;
+        window
+            .set_size(DEFAULT_MAIN_SIZE)
+            .map_err(|e| e.to_string())?;
+        window.center().map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Focus the window with `label` if it is already open; returns whether it was
+fn focus_existing(app: &AppHandle, label: &str) -> Result<bool, String> {
+    match app.get_webview_window(label) {
+        Some(window) => {
+            window.unminimize().map_err(|e| e.to_string())?;
+            window.set_focus().map_err(|e| e.to_string())?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Compact always-on-top A/B/skip window that follows the main window's comparison
+#[tauri::command]
+pub async fn open_mini_window(app: AppHandle) -> Result<(), String> {
+    if focus_existing(&app, "mini")? {
+        return Ok(());
+    }
+
+    WebviewWindowBuilder::new(&app, "mini", WebviewUrl::App("index.html?view=mini".into()))
+        .title("VS-1 Mini")
+        .inner_size(340.0, 150.0)
+        .resizable(false)
+        .always_on_top(true)
+        .build()
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Standings on their own window, e.g. on a second monitor
+#[tauri::command]
+pub async fn open_leaderboard_window(app: AppHandle) -> Result<(), String> {
+    if focus_existing(&app, "leaderboard")? {
+        return Ok(());
+    }
+
+    WebviewWindowBuilder::new(
+        &app,
+        "leaderboard",
+        WebviewUrl::App("index.html?view=leaderboard".into()),
+    )
+    .title("VS-1 Leaderboard")
+    .inner_size(420.0, 640.0)
+    .min_inner_size(320.0, 300.0)
+    .build()
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -1,61 +1,269 @@
 import { useState, useCallback, useRef, useEffect } from 'react';
 import { save } from '@tauri-apps/plugin-dialog';
+import { listen } from '@tauri-apps/api/event';
+import { invoke } from '@tauri-apps/api/core';
 import { DirectoryPicker } from './components/DirectoryPicker';
 import { BattleView } from './components/BattleView';
 import { Results } from './components/Results';
-import { saveProgress, setLastSessionPath } from './lib/storage';
 import {
+  changeSession,
+  closeSession,
+  getSession,
+  loadProgress,
+  openSession,
+  saveSession,
+  setLastSessionPath,
+  takePendingDeepLinks,
+} from './lib/storage';
+import {
+  applyUpdate,
   createTournament,
-  recordComparison,
-  advanceToNextRound,
-  eliminateBoth,
+  getCurrentPairing,
+  isRoundComplete,
+  isTournamentComplete,
+  pairId,
 } from './lib/tournament';
-import type { Sample, TournamentState, AppView } from './types';
+import type {
+  Sample,
+  TournamentState,
+  AppView,
+  CommandError,
+  Pin,
+  SessionChange,
+  SessionChanged,
+  SessionSnapshot,
+  SessionUpdate,
+  TournamentOptions,
+} from './types';
 import './App.css';
 
 function App() {
   const [view, setView] = useState<AppView>('home');
   const [tournament, setTournament] = useState<TournamentState | null>(null);
+  const [deepLinkDirectory, setDeepLinkDirectory] = useState<string | null>(null);
   const tournamentRef = useRef<TournamentState | null>(null);
 
   useEffect(() => {
     tournamentRef.current = tournament;
   }, [tournament]);
 
+  // The backend holds the authoritative copy; this window shows it as of the revision
+  // it last saw and sends every change there
+  const sessionIdRef = useRef<string | null>(null);
+  const [sessionId, setSessionId] = useState<string | null>(null);
+  const revisionRef = useRef(0);
+  const changesRef = useRef<Promise<unknown>>(Promise.resolve());
+  // Someone else changed the session first, so this window's change wasn't made
+  const [sessionConflict, setSessionConflict] = useState(false);
+
+  // Windows opening the same tournament share its session, so the snapshot may be
+  // further along than the state this window opened
+  const adoptSession = useCallback((snapshot: SessionSnapshot | null) => {
+    const previous = sessionIdRef.current;
+    if (previous) closeSession(previous).catch(() => {});
+    sessionIdRef.current = snapshot?.id ?? null;
+    setSessionId(snapshot?.id ?? null);
+    revisionRef.current = snapshot?.revision ?? 0;
+    setTournament(snapshot?.state ?? null);
+  }, []);
+
+  const reloadSession = useCallback(async () => {
+    const id = sessionIdRef.current;
+    if (!id) return;
+    const snapshot = await getSession(id);
+    revisionRef.current = snapshot.revision;
+    setTournament(snapshot.state);
+  }, []);
+
+  // Take an update unless `session://changed` brought it first. A patch only applies
+  // on the revision it was made on; missing one means fetching the whole state.
+  const accept = useCallback((update: SessionUpdate) => {
+    if (update.revision <= revisionRef.current) return;
+    if (!update.state && update.revision !== revisionRef.current + 1) {
+      reloadSession().catch(err => console.error('Failed to reload session:', err));
+      return;
+    }
+    revisionRef.current = update.revision;
+    setTournament(prev => prev && applyUpdate(prev, update));
+  }, [reloadSession]);
+
+  // Follow changes made from other windows, remote voters and the mini window
+  useEffect(() => {
+    const unlisten = listen<SessionChanged>('session://changed', event => {
+      const { id, revision, patch } = event.payload;
+      if (id !== sessionIdRef.current) return;
+      if (patch) {
+        accept({ revision, patch });
+      } else if (revision > revisionRef.current) {
+        reloadSession().catch(err => console.error('Failed to reload session:', err));
+      }
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [accept, reloadSession]);
+
+  // Changes are queued, so each is made on the revision the one before left
+  const change = useCallback((sessionChange: SessionChange) => {
+    const run = async () => {
+      const id = sessionIdRef.current;
+      if (!id) return;
+      try {
+        accept(await changeSession(id, revisionRef.current, sessionChange));
+      } catch (err) {
+        if ((err as CommandError)?.kind !== 'conflict') throw err;
+        setSessionConflict(true);
+        await reloadSession();
+      }
+    };
+    const queued = changesRef.current.then(run);
+    changesRef.current = queued.catch(() => {});
+    return queued;
+  }, [accept, reloadSession]);
+
   const handleStartTournament = useCallback(
-    (samples: Sample[], directory: string, threshold: number) => {
-      const newTournament = createTournament(samples, directory, threshold);
-      setTournament(newTournament);
+    async (
+      samples: Sample[],
+      directory: string,
+      threshold: number,
+      options?: TournamentOptions
+    ) => {
+      const state = createTournament(samples, directory, threshold, options);
+      adoptSession(await openSession(state));
+      await change({ kind: 'start_round' });
       setView('battle');
     },
-    []
+    [adoptSession, change]
   );
 
-  const handleLoadSession = useCallback((state: TournamentState) => {
-    setTournament(state);
+  const handleLoadSession = useCallback(async (state: TournamentState) => {
+    adoptSession(await openSession(state));
     setView('battle');
-  }, []);
+  }, [adoptSession]);
+
+  useEffect(() => {
+    const handlePending = async () => {
+      const actions = await takePendingDeepLinks();
+      for (const link of actions) {
+        try {
+          if (link.action === 'open') {
+            const state = await loadProgress(link.save);
+            setLastSessionPath(link.save);
+            await handleLoadSession(state);
+          } else {
+            adoptSession(null);
+            setDeepLinkDirectory(link.directory);
+            setView('home');
+          }
+        } catch (err) {
+          console.error('Error handling deep link:', err);
+        }
+      }
+    };
+
+    handlePending();
+    const unlisten = listen('deep-link://received', handlePending);
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [handleLoadSession, adoptSession]);
+
+  // When the current pair first appeared, so summaries can tell hard calls apart
+  const pairShownRef = useRef<{ key: string; at: number } | null>(null);
+  useEffect(() => {
+    if (!tournament) return;
+    const key = `${tournament.current_round}:${tournament.current_comparison_index}`;
+    if (pairShownRef.current?.key !== key) {
+      pairShownRef.current = { key, at: performance.now() };
+    }
+  }, [tournament]);
 
   const handleSelectWinner = useCallback((winnerIndex: number) => {
-    setTournament(prev => {
-      if (!prev) return prev;
-      return recordComparison(prev, winnerIndex);
-    });
-  }, []);
+    const current = tournamentRef.current;
+    if (!current) return;
+    const shown = pairShownRef.current;
+    const decisionMs = shown ? Math.round(performance.now() - shown.at) : undefined;
+    change({ kind: 'vote', pair_id: pairId(current), winner: winnerIndex, decision_ms: decisionMs })
+      .catch(err => console.error('Failed to record vote:', err));
+  }, [change]);
 
   const handleEliminateBoth = useCallback(() => {
-    setTournament(prev => {
-      if (!prev) return prev;
-      return eliminateBoth(prev);
-    });
-  }, []);
+    const current = tournamentRef.current;
+    if (!current) return;
+    change({ kind: 'eliminate_both', pair_id: pairId(current) })
+      .catch(err => console.error('Failed to eliminate both:', err));
+  }, [change]);
+
+  const handleSetReference = useCallback((path: string | null) => {
+    change({ kind: 'reference', path })
+      .catch(err => console.error('Failed to set reference sample:', err));
+  }, [change]);
+
+  const handlePinSample = useCallback((path: string, pin: Pin | null) => {
+    change({ kind: 'pin', path, pin }).catch(err => console.error('Failed to pin sample:', err));
+  }, [change]);
+
+  const handleBenchSample = useCallback((path: string, benched: boolean) => {
+    change({ kind: 'bench', path, benched })
+      .catch(err => console.error('Failed to bench sample:', err));
+  }, [change]);
+
+  const handleReorderUpcoming = useCallback((indices: number[]) => {
+    change({ kind: 'reorder', indices })
+      .catch(err => console.error('Failed to reorder comparisons:', err));
+  }, [change]);
+
+  // Publish the current comparison for the remote voting server
+  useEffect(() => {
+    const pairing = tournament && view === 'battle' ? getCurrentPairing(tournament) : null;
+    const sessionId = sessionIdRef.current;
+    const pair = pairing && tournament && sessionId
+      ? {
+          session_id: sessionId,
+          pair_id: pairId(tournament),
+          round: tournament.current_round,
+          a: pairing[0],
+          b: pairing[1],
+        }
+      : null;
+    invoke('set_remote_pair', { pair }).catch(() => {});
+  }, [tournament, view]);
+
+  // Fire user-configured hooks once per finished round / tournament
+  const lastHookRef = useRef<string | null>(null);
+  useEffect(() => {
+    const sessionId = sessionIdRef.current;
+    if (!tournament || !sessionId) return;
+    const finished = isTournamentComplete(tournament);
+    if (!finished && !isRoundComplete(tournament)) return;
+
+    const event = finished ? 'tournament_complete' : 'round_complete';
+    const key = `${tournament.source_directory}:${tournament.current_round}:${event}`;
+    if (lastHookRef.current === key) return;
+    lastHookRef.current = key;
+
+    invoke<{ target: string; error: string }[]>('fire_hooks', { event, sessionId })
+      .then(failures => failures.forEach(f => console.error(`Hook ${f.target} failed: ${f.error}`)))
+      .catch(() => {});
+  }, [tournament]);
 
   const handleNextRound = useCallback(() => {
-    setTournament(prev => {
-      if (!prev) return prev;
-      return advanceToNextRound(prev);
-    });
-  }, []);
+    change({ kind: 'advance_round' })
+      .catch(err => console.error('Failed to advance to the next round:', err));
+  }, [change]);
+
+  const handleRedemptionRound = useCallback(async (count: number) => {
+    try {
+      await change({ kind: 'redemption', count });
+      setView('battle');
+    } catch (err) {
+      console.error('Failed to start redemption round:', err);
+    }
+  }, [change]);
+
+  const handleMergeDuplicates = useCallback(async (keep: string, drop: string[]) => {
+    await change({ kind: 'merge_duplicates', keep, drop });
+  }, [change]);
 
   const handleViewResults = useCallback(() => {
     setView('results');
@@ -66,56 +274,81 @@ function App() {
   }, []);
 
   const handleReset = useCallback(() => {
-    setTournament(null);
+    adoptSession(null);
     setView('home');
+  }, [adoptSession]);
+
+  const saveTo = useCallback(async (filePath: string) => {
+    await changesRef.current;
+    const id = sessionIdRef.current;
+    if (!id) return;
+    await saveSession(id, filePath);
+    setLastSessionPath(filePath);
   }, []);
 
   const handleSave = useCallback(async () => {
-    const currentTournament = tournamentRef.current;
-    if (!currentTournament) return;
+    if (!tournamentRef.current) return;
 
     try {
       const filePath = await save({
-        filters: [{ name: 'JSON', extensions: ['json'] }],
-        defaultPath: 'vs1_session.json',
+        filters: [{ name: 'VS-1 Session', extensions: ['vs1', 'json'] }],
+        defaultPath: 'vs1_session.vs1',
         title: 'Save Progress',
       });
 
-      if (filePath) {
-        await saveProgress(currentTournament, filePath);
-        setLastSessionPath(filePath);
-      }
+      if (filePath) await saveTo(filePath);
     } catch (err) {
       console.error('Error saving:', err);
     }
-  }, []);
+  }, [saveTo]);
 
   return (
     <div className="app">
+      {sessionConflict && (
+        <div className="session-conflict">
+          This session was changed in another window first, so your last change wasn't
+          made. It now shows the other window's changes.
+          <button className="secondary-button" onClick={() => setSessionConflict(false)}>
+            Dismiss
+          </button>
+        </div>
+      )}
       {view === 'home' && (
         <DirectoryPicker
+          initialDirectory={deepLinkDirectory}
           onStartTournament={handleStartTournament}
           onLoadSession={handleLoadSession}
         />
       )}
 
-      {view === 'battle' && tournament && (
+      {view === 'battle' && tournament && sessionId && (
         <BattleView
           tournament={tournament}
+          sessionId={sessionId}
           onSelectWinner={handleSelectWinner}
           onEliminateBoth={handleEliminateBoth}
+          onSetReference={handleSetReference}
+          onPinSample={handlePinSample}
+          onBenchSample={handleBenchSample}
+          onReorderUpcoming={handleReorderUpcoming}
           onNextRound={handleNextRound}
+          onRedemptionRound={handleRedemptionRound}
           onViewResults={handleViewResults}
           onSave={handleSave}
           onReset={handleReset}
         />
       )}
 
-      {view === 'results' && tournament && (
+      {view === 'results' && tournament && sessionId && (
         <Results
           tournament={tournament}
+          sessionId={sessionId}
           onBack={handleBackToBattle}
           onReset={handleReset}
+          onPinSample={handlePinSample}
+          onBenchSample={handleBenchSample}
+          onSaveSession={saveTo}
+          onMergeDuplicates={handleMergeDuplicates}
         />
       )}
     </div>
@@ -1,10 +1,13 @@
 import { useImperativeHandle, forwardRef } from 'react';
-import { useBufferedAudio } from '../hooks/useBufferedAudio';
+import { useBufferedAudio, type LoopRegion } from '../hooks/useBufferedAudio';
 import { Waveform } from './Waveform';
+import { Spectrogram } from './Spectrogram';
 
 export interface AudioPlayerHandle {
-  play: () => void;
+  play: (from?: number) => void;
   stop: () => void;
+  position: () => number;
+  setLoop: (region: LoopRegion | null) => void;
   isPlaying: boolean;
 }
 
@@ -16,27 +19,31 @@ interface AudioPlayerProps {
   isSelected?: boolean;
   playKey?: string;
   selectKey?: string;
+  showSpectrogram?: boolean;
 }
 
 export const AudioPlayer = forwardRef<AudioPlayerHandle, AudioPlayerProps>(
   function AudioPlayer(
-    { filePath, label, onSelect, onPlay, isSelected, playKey, selectKey },
+    { filePath, label, onSelect, onPlay, isSelected, playKey, selectKey, showSpectrogram },
     ref
   ) {
-    const { isPlaying, isLoaded, duration, currentTime, play, stop } = useBufferedAudio(filePath);
+    const { isPlaying, isLoaded, duration, currentTime, play, stop, getPosition, setLoop } =
+      useBufferedAudio(filePath);
 
     const filename = filePath?.split('/').pop() || 'No file';
 
     useImperativeHandle(ref, () => ({
-      play: () => {
+      play: (from?: number) => {
         if (isLoaded) {
-          play();
+          play(from);
           onPlay?.();
         }
       },
       stop,
+      position: getPosition,
+      setLoop,
       isPlaying,
-    }), [isLoaded, play, stop, isPlaying, onPlay]);
+    }), [isLoaded, play, stop, getPosition, setLoop, isPlaying, onPlay]);
 
     const handlePlay = () => {
       if (isLoaded) {
@@ -94,6 +101,8 @@ export const AudioPlayer = forwardRef<AudioPlayerHandle, AudioPlayerProps>(
           isPlaying={isPlaying}
         />
 
+        {showSpectrogram && <Spectrogram filePath={filePath} />}
+
         {onSelect && (
           <button className="select-button" onClick={onSelect}>
             Select {label} {selectKey && `[${selectKey}]`}
@@ -1,7 +1,32 @@
 import { useEffect, useCallback, useRef, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { AudioPlayer, AudioPlayerHandle } from './AudioPlayer';
-import { preloadMultiple } from '../hooks/useBufferedAudio';
-import type { TournamentState } from '../types';
+import {
+  preloadMultiple,
+  setCrossfade,
+  setMonoPreview,
+  setPreviewRegion,
+} from '../hooks/useBufferedAudio';
+import {
+  describeSample,
+  getCalibration,
+  getLoopSlices,
+  getOnset,
+  getSettings,
+  peekUpcoming,
+  saveSettings,
+} from '../lib/storage';
+import type {
+  CalibrationReport,
+  LoopSlices,
+  MatchRecord,
+  Pin,
+  ReplayFrom,
+  Sample,
+  TournamentState,
+  Upcoming,
+} from '../types';
 import {
   getCurrentPairing,
   getCurrentPairingIndices,
@@ -9,13 +34,47 @@ import {
   isRoundComplete,
   isTournamentComplete,
   getUpcomingPairings,
+  isCalibrationPairing,
 } from '../lib/tournament';
 
+const REPLAY_MODES: ReplayFrom[] = ['start', 'onset', 'last_position'];
+const REPLAY_LABELS: Record<ReplayFrom, string> = {
+  start: 'start',
+  onset: 'onset',
+  last_position: 'last position',
+};
+
+// Comparisons listed under "Up next", after the one on screen
+const UP_NEXT_COUNT = 5;
+
+// Eliminated samples brought back by default for a redemption round
+const DEFAULT_REDEMPTION_COUNT = 4;
+
+const onsetCache = new Map<string, number>();
+
+async function onsetOf(sample: Sample): Promise<number> {
+  if (sample.analysis) return sample.analysis.leading_silence_secs;
+
+  const cached = onsetCache.get(sample.path);
+  if (cached !== undefined) return cached;
+
+  const onset = await getOnset(sample.path).catch(() => 0);
+  onsetCache.set(sample.path, onset);
+  return onset;
+}
+
 interface BattleViewProps {
   tournament: TournamentState;
+  // The backend session `tournament` is a copy of
+  sessionId: string;
   onSelectWinner: (index: number) => void;
   onEliminateBoth: () => void;
+  onSetReference?: (path: string | null) => void;
+  onPinSample?: (path: string, pin: Pin | null) => void;
+  onBenchSample?: (path: string, benched: boolean) => void;
+  onReorderUpcoming?: (indices: number[]) => void;
   onNextRound: () => void;
+  onRedemptionRound?: (count: number) => void;
   onViewResults: () => void;
   onSave: () => void;
   onReset: () => void;
@@ -23,9 +82,15 @@ interface BattleViewProps {
 
 export function BattleView({
   tournament,
+  sessionId,
   onSelectWinner,
   onEliminateBoth,
+  onSetReference,
+  onPinSample,
+  onBenchSample,
+  onReorderUpcoming,
   onNextRound,
+  onRedemptionRound,
   onViewResults,
   onSave,
   onReset,
@@ -33,18 +98,100 @@ export function BattleView({
   const playerARef = useRef<AudioPlayerHandle>(null);
   const playerBRef = useRef<AudioPlayerHandle>(null);
   const [lastPlayed, setLastPlayed] = useState<'A' | 'B'>('A');
+  const [showSpectrogram, setShowSpectrogram] = useState(false);
+  const [mono, setMono] = useState(false);
+  const [replayFrom, setReplayFrom] = useState<ReplayFrom>('start');
+  // Where playback of the current pair was last interrupted
+  const pairPositionRef = useRef(0);
+  const [loopSlices, setLoopSlices] = useState(false);
+  const slicesRef = useRef<LoopSlices | null>(null);
+
+  useEffect(() => {
+    getSettings()
+      .then(settings => {
+        setReplayFrom(settings.replay_from ?? 'start');
+        setCrossfade(settings.crossfade_ms ?? null);
+        setPreviewRegion(settings.preview_start_secs ?? 0, settings.preview_secs ?? null);
+      })
+      .catch(() => {});
+  }, []);
+
+  const cycleReplayFrom = useCallback(() => {
+    const next = REPLAY_MODES[(REPLAY_MODES.indexOf(replayFrom) + 1) % REPLAY_MODES.length];
+    setReplayFrom(next);
+    getSettings()
+      .then(settings => saveSettings({ ...settings, replay_from: next }))
+      .catch(err => console.error('Failed to save replay mode:', err));
+  }, [replayFrom]);
+
+  useEffect(() => {
+    setMonoPreview(mono);
+  }, [mono]);
+
+  // Re-check for drift whenever another calibration match is decided
+  const [calibration, setCalibration] = useState<CalibrationReport | null>(null);
+  const calibrationChecks = tournament.reference?.checks.length ?? -1;
+  const tournamentRef = useRef(tournament);
+  tournamentRef.current = tournament;
+  useEffect(() => {
+    getCalibration(sessionId)
+      .then(setCalibration)
+      .catch(() => setCalibration(null));
+  }, [sessionId, calibrationChecks]);
+
+  useEffect(() => () => setMonoPreview(false), []);
+
+  const [upNext, setUpNext] = useState<Upcoming[]>([]);
+  useEffect(() => {
+    peekUpcoming(sessionId, UP_NEXT_COUNT + 1)
+      .then(upcoming => setUpNext(upcoming.slice(1)))
+      .catch(() => setUpNext([]));
+  }, [sessionId, tournament.current_comparison_index, tournament.comparisons_this_round]);
+
+  // Move one upcoming comparison behind everything else left this round
+  const playLater = useCallback((position: number) => {
+    const current = tournamentRef.current;
+    const remaining = current.comparisons_this_round.length - current.current_comparison_index;
+    const order = Array.from({ length: remaining }, (_, i) => i).filter(i => i !== position);
+    onReorderUpcoming?.([...order, position]);
+  }, [onReorderUpcoming]);
 
   const pairing = getCurrentPairing(tournament);
+  const pairingRef = useRef(pairing);
+  pairingRef.current = pairing;
   const indices = getCurrentPairingIndices(tournament);
   const indicesRef = useRef(indices);
   indicesRef.current = indices;
 
   const progress = getProgress(tournament);
+  const [redemptionCount, setRedemptionCount] = useState(DEFAULT_REDEMPTION_COUNT);
+  const redeemable = (tournament.eliminated ?? []).filter(s => s.score > -1000).length;
+
+  // Offered once a round is over, while there are cut samples to bring back
+  const redemptionControls = redeemable >= 2 && onRedemptionRound && (
+    <div className="redemption-controls">
+      <input
+        type="number"
+        min={2}
+        max={redeemable}
+        value={redemptionCount}
+        onChange={e => setRedemptionCount(Number(e.target.value))}
+      />
+      <button
+        className="secondary-button"
+        onClick={() => onRedemptionRound(Math.min(Math.max(redemptionCount, 2), redeemable))}
+      >
+        Redemption Round
+      </button>
+    </div>
+  );
   const roundComplete = isRoundComplete(tournament);
+  const calibrationMatch = isCalibrationPairing(tournament);
   const tournamentComplete = isTournamentComplete(tournament);
 
   useEffect(() => {
     setLastPlayed('A');
+    pairPositionRef.current = 0;
   }, [tournament.current_comparison_index]);
 
   useEffect(() => {
@@ -58,17 +205,84 @@ export function BattleView({
     }
   }, [tournament.current_comparison_index, tournament.current_round, roundComplete, tournamentComplete]);
 
+  // Loop matching bar-aligned slices of both files while loop mode is on
+  useEffect(() => {
+    slicesRef.current = null;
+    playerARef.current?.setLoop(null);
+    playerBRef.current?.setLoop(null);
+    const current = pairingRef.current;
+    if (!loopSlices || !current) return;
+
+    let cancelled = false;
+    getLoopSlices(current[0].path, current[1].path)
+      .then(slices => {
+        if (cancelled) return;
+        slicesRef.current = slices;
+        playerARef.current?.setLoop({ start: slices.a.start_secs, end: slices.a.end_secs });
+        playerBRef.current?.setLoop({ start: slices.b.start_secs, end: slices.b.end_secs });
+      })
+      .catch(err => console.error('Loop slice detection failed:', err));
+    return () => {
+      cancelled = true;
+    };
+  }, [loopSlices, pairing?.[0].path, pairing?.[1].path]);
+
+  // Spoken descriptions of both samples for screen readers
+  const [descriptions, setDescriptions] = useState<string | null>(null);
+  useEffect(() => {
+    setDescriptions(null);
+    const current = pairingRef.current;
+    if (!current || roundComplete || tournamentComplete) return;
+
+    let cancelled = false;
+    Promise.all(current.map(s => describeSample(s.path)))
+      .then(([a, b]) => {
+        if (!cancelled) setDescriptions(`Sample A: ${a} Sample B: ${b}`);
+      })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+    };
+  }, [pairing?.[0].path, pairing?.[1].path, roundComplete, tournamentComplete]);
+
+  // Start position that keeps the two loops in step when switching sides
+  const syncedStart = useCallback((to: 'A' | 'B'): number | undefined => {
+    const slices = slicesRef.current;
+    if (!slices) return undefined;
+
+    const [fromPlayer, fromSlice, toSlice] =
+      to === 'A' ? [playerBRef, slices.b, slices.a] : [playerARef, slices.a, slices.b];
+    const position = fromPlayer.current?.position() ?? 0;
+    const fromLength = fromSlice.end_secs - fromSlice.start_secs;
+    if (position < fromSlice.start_secs || fromLength <= 0) return toSlice.start_secs;
+
+    const phase = ((position - fromSlice.start_secs) % fromLength) / fromLength;
+    return toSlice.start_secs + phase * (toSlice.end_secs - toSlice.start_secs);
+  }, []);
+
+  const rememberPosition = useCallback(() => {
+    const position = Math.max(
+      playerARef.current?.position() ?? 0,
+      playerBRef.current?.position() ?? 0
+    );
+    if (position > 0) pairPositionRef.current = position;
+  }, []);
+
   const playA = useCallback(() => {
+    rememberPosition();
+    const start = syncedStart('A');
     playerBRef.current?.stop();
-    playerARef.current?.play();
+    playerARef.current?.play(start);
     setLastPlayed('A');
-  }, []);
+  }, [rememberPosition, syncedStart]);
 
   const playB = useCallback(() => {
+    rememberPosition();
+    const start = syncedStart('B');
     playerARef.current?.stop();
-    playerBRef.current?.play();
+    playerBRef.current?.play(start);
     setLastPlayed('B');
-  }, []);
+  }, [rememberPosition, syncedStart]);
 
   const selectWinner = useCallback((which: 'A' | 'B') => {
     const currentIndices = indicesRef.current;
@@ -78,6 +292,72 @@ export function BattleView({
     onSelectWinner(which === 'A' ? currentIndices[0] : currentIndices[1]);
   }, [onSelectWinner]);
 
+  // Show the current pair in the system media UI (Now Playing / MPRIS)
+  useEffect(() => {
+    const title = pairing ? `${pairing[0].filename} vs ${pairing[1].filename}` : null;
+    invoke('set_now_playing', {
+      title,
+      subtitle: `Round ${tournament.current_round}`,
+    }).catch(() => {});
+  }, [pairing?.[0].path, pairing?.[1].path, tournament.current_round]);
+
+  useEffect(() => {
+    return () => {
+      invoke('set_now_playing', { title: null, subtitle: null }).catch(() => {});
+    };
+  }, []);
+
+  const lastPlayedRef = useRef(lastPlayed);
+  lastPlayedRef.current = lastPlayed;
+
+  const replayFromRef = useRef(replayFrom);
+  replayFromRef.current = replayFrom;
+
+  // Restart the last played candidate from the configured point, so both
+  // candidates of a pair are always auditioned from the same spot
+  const replayPair = useCallback(async (from: ReplayFrom) => {
+    const current = pairingRef.current;
+    if (!current) return;
+    rememberPosition();
+
+    const side = lastPlayedRef.current;
+    const sample = side === 'A' ? current[0] : current[1];
+    const slices = slicesRef.current;
+    // Undefined starts at the configured preview region
+    let start: number | undefined;
+    if (slices && from !== 'last_position') {
+      start = side === 'A' ? slices.a.start_secs : slices.b.start_secs;
+    } else if (from === 'onset') {
+      start = await onsetOf(sample);
+    } else if (from === 'last_position') {
+      start = pairPositionRef.current;
+    }
+
+    (side === 'A' ? playerBRef : playerARef).current?.stop();
+    (side === 'A' ? playerARef : playerBRef).current?.play(start);
+  }, [rememberPosition]);
+
+  useEffect(() => {
+    const unlisten = listen<'replay' | 'switch' | 'stop'>('media://action', event => {
+      if (!indicesRef.current) return;
+      const side = lastPlayedRef.current;
+      if (event.payload === 'stop') {
+        rememberPosition();
+        playerARef.current?.stop();
+        playerBRef.current?.stop();
+      } else if (event.payload === 'replay') {
+        replayPair(replayFromRef.current);
+      } else if (side === 'A') {
+        playB();
+      } else {
+        playA();
+      }
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [playA, playB, replayPair, rememberPosition]);
+
   const handlePlayA = useCallback(() => {
     playerBRef.current?.stop();
     setLastPlayed('A');
@@ -114,6 +394,7 @@ export function BattleView({
       }
       else if (e.key === ' ') {
         e.preventDefault();
+        rememberPosition();
         playerARef.current?.stop();
         playerBRef.current?.stop();
       }
@@ -125,6 +406,43 @@ export function BattleView({
         e.preventDefault();
         onViewResults();
       }
+      else if (e.key === 'R') {
+        e.preventDefault();
+        cycleReplayFrom();
+      }
+      else if (e.key === 'r') {
+        e.preventDefault();
+        replayPair(replayFrom);
+      }
+      else if (e.key === 'b' && !calibrationMatch && pairing) {
+        e.preventDefault();
+        onSetReference?.(lastPlayed === 'A' ? pairing[0].path : pairing[1].path);
+      }
+      else if ((e.key === 'p' || e.key === 'P') && !calibrationMatch && pairing) {
+        e.preventDefault();
+        const sample = lastPlayed === 'A' ? pairing[0] : pairing[1];
+        onPinSample?.(sample.path, e.key === 'p' ? 'always_advance' : 'eliminate');
+      }
+      else if ((e.key === 'n' || e.key === 'N') && !calibrationMatch && pairing) {
+        e.preventDefault();
+        onBenchSample?.((lastPlayed === 'A' ? pairing[0] : pairing[1]).path, true);
+      }
+      else if (e.key === 'B') {
+        e.preventDefault();
+        onSetReference?.(null);
+      }
+      else if (e.key === 'k' || e.key === 'K') {
+        e.preventDefault();
+        setLoopSlices(enabled => !enabled);
+      }
+      else if (e.key === 'm' || e.key === 'M') {
+        e.preventDefault();
+        setMono(m => !m);
+      }
+      else if (e.key === 'g' || e.key === 'G') {
+        e.preventDefault();
+        setShowSpectrogram(show => !show);
+      }
       else if (e.key === 'x' || e.key === 'X') {
         e.preventDefault();
         playerARef.current?.stop();
@@ -132,7 +450,26 @@ export function BattleView({
         onEliminateBoth();
       }
     },
-    [roundComplete, indices, playA, playB, lastPlayed, selectWinner, onSave, onViewResults, onEliminateBoth]
+    [
+      roundComplete,
+      indices,
+      playA,
+      playB,
+      lastPlayed,
+      selectWinner,
+      onSave,
+      onViewResults,
+      onEliminateBoth,
+      rememberPosition,
+      replayPair,
+      replayFrom,
+      cycleReplayFrom,
+      calibrationMatch,
+      pairing,
+      onSetReference,
+      onPinSample,
+      onBenchSample,
+    ]
   );
 
   useEffect(() => {
@@ -149,23 +486,51 @@ export function BattleView({
           <button className="primary-button" onClick={onViewResults}>
             View Results
           </button>
+          {redemptionControls}
         </div>
       </div>
     );
   }
 
   if (roundComplete) {
-    const nextRoundSamples = Math.ceil(
-      tournament.samples.length * tournament.advancement_threshold
-    );
+    const summary = tournament.round_summaries?.find(s => s.round === tournament.current_round);
+    const filenames = new Map(tournament.samples.map(s => [s.path, s.filename]));
+    const describe = (m: MatchRecord) =>
+      `${filenames.get(m.winner) ?? m.winner} beat ${filenames.get(m.loser) ?? m.loser}`;
 
     return (
       <div className="battle-view">
         <div className="round-complete">
           <h2>Round {tournament.current_round} Complete!</h2>
           <p>
-            {tournament.samples.length} samples will be reduced to ~{nextRoundSamples} in the next round.
+            {summary
+              ? `${summary.advancers.length} samples advance, ${summary.eliminated.length} are eliminated.`
+              : 'Summarizing round…'}
           </p>
+          {summary && summary.upsets.length > 0 && (
+            <div className="round-summary">
+              <h3>Biggest upsets</h3>
+              <ul>
+                {summary.upsets.map((m, i) => (
+                  <li key={i}>
+                    {describe(m)} ({Math.round(m.expected * 100)}% expected)
+                  </li>
+                ))}
+              </ul>
+            </div>
+          )}
+          {summary && summary.closest.length > 0 && (
+            <div className="round-summary">
+              <h3>Closest calls</h3>
+              <ul>
+                {summary.closest.map((m, i) => (
+                  <li key={i}>
+                    {describe(m)} ({((m.decision_ms ?? 0) / 1000).toFixed(1)}s)
+                  </li>
+                ))}
+              </ul>
+            </div>
+          )}
           <div className="round-actions">
             <button className="primary-button" onClick={onNextRound}>
               Start Round {tournament.current_round + 1}
@@ -177,6 +542,7 @@ export function BattleView({
               Save Progress
             </button>
           </div>
+          {redemptionControls}
         </div>
       </div>
     );
@@ -197,7 +563,11 @@ export function BattleView({
             Comparison {progress.currentComparison} of {progress.totalComparisonsThisRound}
           </span>
           <span>{progress.samplesRemaining} samples remaining</span>
+          {calibrationMatch && <span className="calibration-tag">Calibration match</span>}
         </div>
+        <p className="sr-only" aria-live="polite">
+          {descriptions}
+        </p>
         <div className="progress-bar-container">
           <div
             className="progress-bar-fill"
@@ -206,6 +576,15 @@ export function BattleView({
         </div>
       </div>
 
+      {calibration?.drifting && (
+        <div className="calibration-warning">
+          Your judgments may be drifting: the reference won{' '}
+          {Math.round((calibration.baseline_win_rate ?? 0) * 100)}% of its calibration matches
+          earlier but {Math.round((calibration.recent_win_rate ?? 0) * 100)}% recently. Consider
+          a break.
+        </div>
+      )}
+
       <div className="battle-arena">
         <AudioPlayer
           ref={playerARef}
@@ -215,6 +594,7 @@ export function BattleView({
           onPlay={handlePlayA}
           onSelect={handleSelectA}
           isSelected={lastPlayed === 'A'}
+          showSpectrogram={showSpectrogram}
         />
 
         <div className="vs-divider">VS</div>
@@ -227,14 +607,36 @@ export function BattleView({
           onPlay={handlePlayB}
           onSelect={handleSelectB}
           isSelected={lastPlayed === 'B'}
+          showSpectrogram={showSpectrogram}
         />
       </div>
 
       <div className="battle-hint">
-        Q/W = Play | Enter = Select | X = Skip Both | Space = Stop | L = Leaderboard
+        Q/W = Play | R = Replay from {REPLAY_LABELS[replayFrom]} (Shift+R to change) | Enter = Select | X = Skip Both | Space = Stop | L = Leaderboard | G = Spectrogram | M = Mono | K = Loop Slices | P = Keep (Shift+P = Reject) | N = Bench | B = Use as Reference
+        {mono && <span className="mono-indicator"> MONO</span>}
+        {loopSlices && <span className="mono-indicator"> LOOP</span>}
         {lastPlayed && <span className="last-played"> (Ready to select {lastPlayed})</span>}
       </div>
 
+      {upNext.length > 0 && (
+        <div className="up-next">
+          <h3>Up next</h3>
+          <ol>
+            {upNext.map(u => (
+              <li key={`${u.position}:${u.a.path}:${u.b.path}`}>
+                {u.a.filename} vs {u.b.filename}
+                {u.calibration && <span className="calibration-tag">Calibration</span>}
+                {onReorderUpcoming && (
+                  <button className="secondary-button" onClick={() => playLater(u.position)}>
+                    Later
+                  </button>
+                )}
+              </li>
+            ))}
+          </ol>
+        </div>
+      )}
+
       <div className="battle-actions">
         <button className="danger-button" onClick={handleSkipBoth}>
           Skip Both (X)
@@ -245,6 +647,18 @@ export function BattleView({
         <button className="secondary-button" onClick={onSave}>
           Save
         </button>
+        <button
+          className="secondary-button"
+          onClick={() => invoke('open_mini_window').catch(() => {})}
+        >
+          Mini Window
+        </button>
+        <button
+          className="secondary-button"
+          onClick={() => invoke('open_leaderboard_window').catch(() => {})}
+        >
+          Detach Leaderboard
+        </button>
         <button className="danger-button" onClick={onReset}>
           Start Over
         </button>
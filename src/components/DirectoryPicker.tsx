@@ -1,36 +1,294 @@
-import { useState } from 'react';
-import { open } from '@tauri-apps/plugin-dialog';
-import { scanDirectory, loadProgress, getLastSessionPath } from '../lib/storage';
-import type { Sample, TournamentState } from '../types';
+import { useEffect, useState } from 'react';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import {
+  scanDirectory,
+  estimateScan,
+  loadProgress,
+  findConflictedSaves,
+  resolveConflictedSaves,
+  getLastSessionPath,
+  generateDiagnosticsBundle,
+  listPresets,
+  deletePreset,
+  applyPreset,
+  saveTemplate,
+  newFromTemplate,
+  getLastExportDefaults,
+  listLanguages,
+  getSettings,
+  saveSettings,
+  setReadOnly,
+  errorMessage,
+  getAuditLog,
+  importManifest,
+  importMusicLibrary,
+  importTagRatings,
+  getThumbnailCacheUsage,
+  clearAnalysisCache,
+} from '../lib/storage';
+import type {
+  AnalysisThrottle,
+  AuditEntry,
+  ByePolicy,
+  CacheUsage,
+  CommandError,
+  Preset,
+  RatingImport,
+  Sample,
+  ScanOptions,
+  ScanReport,
+  ScoreCarry,
+  TournamentOptions,
+  TournamentState,
+} from '../types';
+
+// Scans expected to add at least this many samples ask for confirmation first
+const LARGE_SCAN_WARNING = 5000;
+
+// Recent file activity shown on the start screen
+const AUDIT_LOG_LIMIT = 200;
+
+function formatMegabytes(bytes: number): string {
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+// Only the categories that actually skipped something, e.g. "2 unreadable, 1 symlink"
+function ScanReportSummary({ report }: { report: ScanReport }) {
+  const parts = [
+    [report.permission_denied, 'permission denied'],
+    [report.unreadable, 'unreadable'],
+    [report.bad_archives, 'broken archives'],
+    [report.symlinks, 'symlinks'],
+    [report.too_silent, 'mostly silent'],
+    [report.hidden, 'hidden'],
+    [report.non_audio, 'not audio'],
+  ]
+    .filter(([count]) => (count as number) > 0)
+    .map(([count, label]) => `${count} ${label}`);
+  if (parts.length === 0) return null;
+
+  return (
+    <details className="scan-report">
+      <summary>Skipped: {parts.join(', ')}</summary>
+      <ul>
+        {report.skipped.map(entry => (
+          <li key={entry.path}>
+            {entry.path} ({entry.reason.replace('_', ' ')})
+          </li>
+        ))}
+      </ul>
+    </details>
+  );
+}
 
 interface DirectoryPickerProps {
-  onStartTournament: (samples: Sample[], directory: string, threshold: number) => void;
+  initialDirectory?: string | null;
+  onStartTournament: (
+    samples: Sample[],
+    directory: string,
+    threshold: number,
+    options?: TournamentOptions
+  ) => void;
   onLoadSession: (state: TournamentState) => void;
 }
 
-export function DirectoryPicker({ onStartTournament, onLoadSession }: DirectoryPickerProps) {
+export function DirectoryPicker({
+  initialDirectory,
+  onStartTournament,
+  onLoadSession,
+}: DirectoryPickerProps) {
   const [isScanning, setIsScanning] = useState(false);
-  const [scanResult, setScanResult] = useState<{ samples: Sample[]; directory: string } | null>(null);
+  const [scanResult, setScanResult] = useState<{
+    samples: Sample[];
+    directory: string;
+    report: ScanReport;
+    seededScores?: boolean;
+  } | null>(null);
   const [error, setError] = useState<string | null>(null);
+  const [seedMessage, setSeedMessage] = useState<string | null>(null);
   const [threshold, setThreshold] = useState(50);
+  // Empty picks a random seed; the one used is stored in the session either way
+  const [seed, setSeed] = useState('');
+  // Standard deviations above the mean score needed to advance; `null` uses the threshold
+  const [adaptiveCutoff, setAdaptiveCutoff] = useState<number | null>(null);
+  const [scoreCarry, setScoreCarry] = useState<ScoreCarry>('raw');
+  const [byePolicy, setByePolicy] = useState<ByePolicy>('fair');
+  const [allowRematches, setAllowRematches] = useState(false);
+  const [analyze, setAnalyze] = useState(false);
+  const [skipSilent, setSkipSilent] = useState(false);
+  const [groupVariations, setGroupVariations] = useState(false);
+  const [includeInstruments, setIncludeInstruments] = useState(false);
+  const [maxDepth, setMaxDepth] = useState<number | null>(null);
+  const [includeHidden, setIncludeHidden] = useState(true);
+  const [sampleLimit, setSampleLimit] = useState('');
+  const [presets, setPresets] = useState<Preset[]>([]);
+  const [activePreset, setActivePreset] = useState<Preset | null>(null);
+  const [languages, setLanguages] = useState<string[]>([]);
+  // Language of backend messages and reports; empty follows the system
+  const [language, setLanguage] = useState('');
+  const [readOnly, setReadOnlyState] = useState(false);
+  const [throttle, setThrottle] = useState<AnalysisThrottle>({});
+  const [cacheUsage, setCacheUsage] = useState<CacheUsage | null>(null);
+  const [auditLog, setAuditLog] = useState<AuditEntry[] | null>(null);
+  const [auditLogEnded, setAuditLogEnded] = useState(false);
 
-  const lastSessionPath = getLastSessionPath();
+  useEffect(() => {
+    listPresets().then(setPresets).catch(() => setPresets([]));
+    listLanguages().then(setLanguages).catch(() => setLanguages([]));
+    getSettings()
+      .then(settings => {
+        setLanguage(settings.language ?? '');
+        setReadOnlyState(settings.read_only ?? false);
+        setThrottle(settings.analysis_throttle ?? {});
+      })
+      .catch(() => {});
+    getThumbnailCacheUsage().then(setCacheUsage).catch(() => setCacheUsage(null));
+  }, []);
 
-  const handleSelectDirectory = async () => {
+  const handleLanguageChange = async (next: string) => {
+    setLanguage(next);
+    try {
+      const settings = await getSettings();
+      await saveSettings({ ...settings, language: next || null });
+    } catch (err) {
+      setError(`Failed to change language: ${err}`);
+    }
+  };
+
+  const handleApplyPreset = async (name: string) => {
+    if (!name) {
+      setActivePreset(null);
+      return;
+    }
+    try {
+      showPreset(await applyPreset(name));
+    } catch (err) {
+      setError(`Error applying preset: ${err}`);
+    }
+  };
+
+  const showPreset = (preset: Preset) => {
+    setActivePreset(preset);
+    if (preset.threshold_schedule.length > 0) {
+      setThreshold(Math.round(preset.threshold_schedule[0] * 100));
+    }
+    setAdaptiveCutoff(preset.adaptive_cutoff ?? null);
+    setScoreCarry(preset.score_carry ?? 'raw');
+    setAnalyze(!!preset.scan.analyze);
+    setSkipSilent(preset.scan.max_silence_ratio != null);
+    setGroupVariations(!!preset.scan.group_variations);
+    setIncludeInstruments(!!preset.scan.include_instruments);
+    setMaxDepth(preset.scan.max_depth ?? null);
+    setIncludeHidden(preset.scan.include_hidden ?? true);
+  };
+
+  // Template plus folder is all it takes: scan with its filters and start right away
+  const handleNewFromTemplate = async () => {
+    if (!activePreset) return;
     try {
       const selected = await open({
         directory: true,
         multiple: false,
-        title: 'Select Audio Samples Directory',
+        title: `New ${activePreset.name} Tournament`,
       });
 
       if (!selected) return;
 
       setIsScanning(true);
       setError(null);
+      const { template, directory, outcome } = await newFromTemplate(
+        activePreset.name,
+        selected as string
+      );
+      showPreset(template);
+      if (outcome.samples.length < 2) {
+        setScanResult({ samples: outcome.samples, directory, report: outcome.report });
+        return;
+      }
+      onStartTournament(outcome.samples, directory, template.threshold_schedule[0] ?? 0.5, {
+        thresholdSchedule: template.threshold_schedule,
+        adaptiveCutoff: template.adaptive_cutoff,
+        scoreCarry: template.score_carry ?? 'raw',
+        scanSampling: outcome.report.sampling ?? null,
+        exportDefaults: template.export ?? null,
+      });
+    } catch (err) {
+      setError(`Error starting from template: ${errorMessage(err)}`);
+    } finally {
+      setIsScanning(false);
+    }
+  };
 
-      const samples = await scanDirectory(selected as string);
-      setScanResult({ samples, directory: selected as string });
+  const scanOptions: ScanOptions = {
+    analyze,
+    max_silence_ratio: skipSilent ? 0.9 : null,
+    group_variations: groupVariations,
+    include_instruments: includeInstruments,
+    max_depth: maxDepth,
+    include_hidden: includeHidden,
+    sample_limit: sampleLimit.trim() === '' ? null : Math.max(2, Number(sampleLimit)),
+  };
+
+  // Saves the current choices as a template: filters, thresholds, the rating plugin in
+  // use and the export settings last used on the results screen; a loaded template
+  // keeps its later rounds
+  const handleSavePreset = async () => {
+    const name = window.prompt('Template name', activePreset?.name ?? '');
+    if (!name?.trim()) return;
+    const setup: Preset = {
+      name: name.trim(),
+      threshold_schedule: [threshold / 100, ...(activePreset?.threshold_schedule.slice(1) ?? [])],
+      adaptive_cutoff: adaptiveCutoff,
+      score_carry: scoreCarry,
+      plugin: null,
+      scan: scanOptions,
+      export: getLastExportDefaults(),
+    };
+    try {
+      const template = await saveTemplate(setup.name, setup);
+      setPresets(await listPresets());
+      setActivePreset(template);
+    } catch (err) {
+      setError(`Error saving template: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleDeletePreset = async () => {
+    if (!activePreset) return;
+    try {
+      await deletePreset(activePreset.name);
+      setPresets(await listPresets());
+      setActivePreset(null);
+    } catch (err) {
+      setError(`Error deleting preset: ${err}`);
+    }
+  };
+
+  const lastSessionPath = getLastSessionPath();
+
+  const scan = async (directory: string) => {
+    try {
+      setIsScanning(true);
+      setError(null);
+
+      // Big libraries take a while to scan and even longer to analyze; ask first
+      const estimate = await estimateScan(directory, scanOptions);
+      if (estimate.would_add >= LARGE_SCAN_WARNING) {
+        const extras = [
+          estimate.archives > 0 ? `${estimate.archives} archives` : null,
+          estimate.instruments > 0 ? `${estimate.instruments} instruments` : null,
+        ].filter(Boolean);
+        const message =
+          `This will add ${estimate.would_add.toLocaleString()} samples` +
+          (extras.length > 0 ? ` plus the contents of ${extras.join(' and ')}` : '') +
+          (analyze || skipSilent ? ', each of them analyzed' : '') +
+          '. Continue?';
+        if (!window.confirm(message)) return;
+      }
+
+      const { samples, report } = await scanDirectory(directory, scanOptions);
+      setScanResult({ samples, directory, report });
+      setSeedMessage(null);
     } catch (err) {
       setError(`Error scanning directory: ${err}`);
     } finally {
@@ -38,20 +296,175 @@ export function DirectoryPicker({ onStartTournament, onLoadSession }: DirectoryP
     }
   };
 
+  useEffect(() => {
+    if (initialDirectory) {
+      scan(initialDirectory);
+    }
+  }, [initialDirectory]);
+
+  const handleSelectDirectory = async () => {
+    try {
+      const selected = await open({
+        directory: true,
+        multiple: false,
+        title: 'Select Audio Samples Directory',
+      });
+
+      if (!selected) return;
+
+      await scan(selected as string);
+    } catch (err) {
+      setError(`Error scanning directory: ${err}`);
+    }
+  };
+
+  // Years of star ratings give the tournament a head start: one point per star
+  const handleSeedFromRatings = async (source: 'library' | 'tags') => {
+    if (!scanResult) return;
+    try {
+      let imported: RatingImport;
+      if (source === 'library') {
+        const selected = await open({
+          filters: [{ name: 'Music Library', extensions: ['xml'] }],
+          multiple: false,
+          title: 'Select Music Library XML (File > Library > Export Library)',
+        });
+        if (!selected) return;
+        imported = await importMusicLibrary(scanResult.samples, selected as string);
+      } else {
+        setIsScanning(true);
+        imported = await importTagRatings(scanResult.samples);
+      }
+      setError(null);
+      setScanResult({ ...scanResult, samples: imported.samples, seededScores: true });
+      setSeedMessage(`Seeded ${imported.matched} samples from their star ratings`);
+    } catch (err) {
+      setError(`Error importing ratings: ${errorMessage(err)}`);
+    } finally {
+      setIsScanning(false);
+    }
+  };
+
+  // Continue curating from an earlier export: its files become the new pool
+  const handleImportManifest = async () => {
+    try {
+      const selected = await open({
+        filters: [{ name: 'Export Manifest', extensions: ['json'] }],
+        multiple: false,
+        title: 'Import Export Manifest',
+      });
+
+      if (!selected) return;
+
+      const seedScores = window.confirm('Start from the scores the samples were exported with?');
+      setError(null);
+      const imported = await importManifest(selected as string, seedScores);
+      setScanResult({ ...imported, seededScores: seedScores });
+      setSeedMessage(null);
+    } catch (err) {
+      setError(`Error importing manifest: ${errorMessage(err)}`);
+    }
+  };
+
+  // A save in a synced folder edited on two machines leaves conflicted copies beside it;
+  // offer to fold their comparisons in instead of silently loading one side
+  const loadWithConflicts = async (filePath: string): Promise<TournamentState> => {
+    const state = await loadProgress(filePath);
+    const copies = await findConflictedSaves(filePath).catch(() => []);
+    if (copies.length === 0) return state;
+
+    const merge = window.confirm(
+      `Found ${copies.length} conflicted ${copies.length === 1 ? 'copy' : 'copies'} of this ` +
+        'save from a sync client. Merge their comparisons into it?'
+    );
+    if (!merge) return state;
+    return (await resolveConflictedSaves([filePath, ...copies])).state;
+  };
+
   const handleLoadSession = async () => {
     try {
       const selected = await open({
-        filters: [{ name: 'JSON', extensions: ['json'] }],
+        filters: [{ name: 'VS-1 Session', extensions: ['vs1', 'json'] }],
         multiple: false,
         title: 'Load Session',
       });
 
       if (!selected) return;
 
-      const state = await loadProgress(selected as string);
+      const state = await loadWithConflicts(selected as string);
       onLoadSession(state);
     } catch (err) {
-      setError(`Error loading session: ${err}`);
+      setError(`Error loading session: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleSaveDiagnostics = async () => {
+    try {
+      const filePath = await save({
+        filters: [{ name: 'Zip', extensions: ['zip'] }],
+        defaultPath: 'vs1-diagnostics.zip',
+        title: 'Save Diagnostics',
+      });
+
+      if (!filePath) return;
+
+      await generateDiagnosticsBundle(filePath);
+    } catch (err) {
+      setError(`Error saving diagnostics: ${err}`);
+    }
+  };
+
+  const handleClearCache = async () => {
+    try {
+      await clearAnalysisCache();
+      setCacheUsage(await getThumbnailCacheUsage());
+    } catch (err) {
+      setError(`Failed to clear the waveform cache: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleThrottleChange = async (change: Partial<AnalysisThrottle>) => {
+    const next = { ...throttle, ...change };
+    try {
+      const settings = await getSettings();
+      await saveSettings({ ...settings, analysis_throttle: next });
+      setThrottle(next);
+    } catch (err) {
+      setError(`Failed to change analysis limits: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleReadOnlyChange = async (enabled: boolean) => {
+    try {
+      await setReadOnly(enabled);
+      setReadOnlyState(enabled);
+    } catch (err) {
+      setError(`Failed to change read-only mode: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleToggleAuditLog = async () => {
+    if (auditLog) {
+      setAuditLog(null);
+      return;
+    }
+    try {
+      const entries = await getAuditLog(AUDIT_LOG_LIMIT);
+      setAuditLog(entries);
+      setAuditLogEnded(entries.length < AUDIT_LOG_LIMIT);
+    } catch (err) {
+      setError(`Error reading file activity: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleOlderAuditLog = async () => {
+    if (!auditLog) return;
+    try {
+      const older = await getAuditLog(AUDIT_LOG_LIMIT, auditLog.length);
+      setAuditLog([...auditLog, ...older]);
+      setAuditLogEnded(older.length < AUDIT_LOG_LIMIT);
+    } catch (err) {
+      setError(`Error reading file activity: ${errorMessage(err)}`);
     }
   };
 
@@ -59,16 +472,45 @@ export function DirectoryPicker({ onStartTournament, onLoadSession }: DirectoryP
     if (!lastSessionPath) return;
 
     try {
-      const state = await loadProgress(lastSessionPath);
+      let state: TournamentState;
+      try {
+        state = await loadWithConflicts(lastSessionPath);
+      } catch (err) {
+        if ((err as CommandError)?.kind !== 'path_not_allowed') throw err;
+        // Saves are only opened once picked again after a restart; preselect the last one
+        const selected = await open({
+          filters: [{ name: 'VS-1 Session', extensions: ['vs1', 'json'] }],
+          defaultPath: lastSessionPath,
+          multiple: false,
+          title: 'Load Session',
+        });
+        if (!selected) return;
+        state = await loadWithConflicts(selected as string);
+      }
       onLoadSession(state);
     } catch (err) {
-      setError(`Error loading last session: ${err}`);
+      setError(`Error loading last session: ${errorMessage(err)}`);
     }
   };
 
   const handleStartTournament = () => {
     if (!scanResult) return;
-    onStartTournament(scanResult.samples, scanResult.directory, threshold / 100);
+    const parsedSeed = seed.trim() === '' ? undefined : Number(seed) >>> 0;
+    // The slider overrides the first round of a preset's schedule
+    const schedule = activePreset?.threshold_schedule.length
+      ? [threshold / 100, ...activePreset.threshold_schedule.slice(1)]
+      : [];
+    onStartTournament(scanResult.samples, scanResult.directory, threshold / 100, {
+      seed: parsedSeed,
+      thresholdSchedule: schedule,
+      adaptiveCutoff,
+      scoreCarry,
+      byePolicy,
+      allowRematches,
+      scanSampling: scanResult.report.sampling ?? null,
+      keepScores: scanResult.seededScores ?? false,
+      exportDefaults: activePreset?.export ?? null,
+    });
   };
 
   return (
@@ -91,17 +533,253 @@ export function DirectoryPicker({ onStartTournament, onLoadSession }: DirectoryP
           Load Saved Session
         </button>
 
+        <button className="secondary-button" onClick={handleImportManifest} disabled={isScanning}>
+          Import Export Manifest
+        </button>
+
         {lastSessionPath && (
           <button className="secondary-button" onClick={handleLoadLastSession}>
             Resume Last Session
           </button>
         )}
+
+        <div className="preset-controls">
+          <select
+            value={activePreset?.name ?? ''}
+            onChange={(e) => handleApplyPreset(e.target.value)}
+          >
+            <option value="">No preset</option>
+            {presets.map(preset => (
+              <option key={preset.name} value={preset.name}>
+                {preset.name}
+              </option>
+            ))}
+          </select>
+          <button className="secondary-button" onClick={handleSavePreset}>
+            Save as Template
+          </button>
+          {activePreset && (
+            <button
+              className="secondary-button"
+              onClick={handleNewFromTemplate}
+              disabled={isScanning}
+            >
+              New from Template
+            </button>
+          )}
+          {activePreset && (
+            <button className="secondary-button" onClick={handleDeletePreset}>
+              Delete
+            </button>
+          )}
+        </div>
+
+        <label className="scan-option">
+          <input
+            type="checkbox"
+            checked={analyze}
+            onChange={(e) => setAnalyze(e.target.checked)}
+          />
+          Analyze audio while scanning (flags clipping and DC offset; slower)
+        </label>
+
+        <div className="scan-option">
+          Analysis uses
+          <select
+            value={throttle.max_threads ?? ''}
+            onChange={(e) =>
+              handleThrottleChange({
+                max_threads: e.target.value === '' ? null : Number(e.target.value),
+              })
+            }
+          >
+            <option value="">all cores</option>
+            <option value="1">1 core</option>
+            <option value="2">2 cores</option>
+            <option value="4">4 cores</option>
+          </select>
+          at
+          <select
+            value={throttle.cpu_percent ?? ''}
+            onChange={(e) =>
+              handleThrottleChange({
+                cpu_percent: e.target.value === '' ? null : Number(e.target.value),
+              })
+            }
+          >
+            <option value="">full speed</option>
+            <option value="50">50%</option>
+            <option value="25">25%</option>
+          </select>
+          <label>
+            <input
+              type="checkbox"
+              checked={!!throttle.pause_on_battery}
+              onChange={(e) => handleThrottleChange({ pause_on_battery: e.target.checked })}
+            />
+            pause on battery
+          </label>
+        </div>
+
+        <label className="scan-option">
+          <input
+            type="checkbox"
+            checked={skipSilent}
+            onChange={(e) => setSkipSilent(e.target.checked)}
+          />
+          Skip files that are more than 90% silence
+        </label>
+
+        <label className="scan-option">
+          <input
+            type="checkbox"
+            checked={groupVariations}
+            onChange={(e) => setGroupVariations(e.target.checked)}
+          />
+          Treat variations (kick_01a, kick_01b) as one entrant
+        </label>
+
+        <label className="scan-option">
+          <input
+            type="checkbox"
+            checked={includeInstruments}
+            onChange={(e) => setIncludeInstruments(e.target.checked)}
+          />
+          Include samples used by instruments (.sfz, .xrni)
+        </label>
+
+        <label className="scan-option">
+          <input
+            type="checkbox"
+            checked={includeHidden}
+            onChange={(e) => setIncludeHidden(e.target.checked)}
+          />
+          Include hidden files and folders
+        </label>
+
+        <label className="scan-option">
+          Scan
+          <select
+            value={maxDepth ?? ''}
+            onChange={(e) => setMaxDepth(e.target.value === '' ? null : Number(e.target.value))}
+          >
+            <option value="">all subfolders</option>
+            <option value="1">only the selected folder</option>
+            <option value="2">one level of subfolders</option>
+            <option value="3">two levels of subfolders</option>
+          </select>
+        </label>
+
+        <label className="scan-option">
+          At most
+          <input
+            type="number"
+            min="2"
+            placeholder="all"
+            value={sampleLimit}
+            onChange={(e) => setSampleLimit(e.target.value)}
+          />
+          files, picked at random (for huge libraries)
+        </label>
+
+        <label className="scan-option">
+          <input
+            type="checkbox"
+            checked={readOnly}
+            onChange={(e) => handleReadOnlyChange(e.target.checked)}
+          />
+          Read-only library (never copy, move or discard files, e.g. on a shared drive)
+        </label>
+
+        {languages.length > 1 && (
+          <label className="scan-option">
+            Messages and reports in
+            <select value={language} onChange={(e) => handleLanguageChange(e.target.value)}>
+              <option value="">System language</option>
+              {languages.map(code => (
+                <option key={code} value={code}>
+                  {code}
+                </option>
+              ))}
+            </select>
+          </label>
+        )}
+
+        <button className="secondary-button" onClick={handleSaveDiagnostics}>
+          Save Diagnostics
+        </button>
+
+        {cacheUsage && (
+          <button
+            className="secondary-button"
+            onClick={handleClearCache}
+            disabled={cacheUsage.files === 0}
+            title={`Limit ${formatMegabytes(cacheUsage.limit_bytes)}`}
+          >
+            Clear Waveform Cache ({formatMegabytes(cacheUsage.bytes)})
+          </button>
+        )}
+
+        <button className="secondary-button" onClick={handleToggleAuditLog}>
+          {auditLog ? 'Hide File Activity' : 'Show File Activity'}
+        </button>
       </div>
 
+      {auditLog && (
+        <ul className="audit-log">
+          {auditLog.length === 0 && <li>Nothing recorded yet</li>}
+          {auditLog.map((entry, i) => (
+            <li key={i} className={`audit-${entry.kind}`}>
+              <span className="audit-time">
+                {new Date(entry.timestamp * 1000).toLocaleString()}
+              </span>
+              <span className="audit-kind">{entry.kind}</span>
+              <span className="audit-subject">{entry.subject}</span>
+              {entry.detail && <span className="audit-detail">{entry.detail}</span>}
+            </li>
+          ))}
+          {!auditLogEnded && (
+            <li>
+              <button className="secondary-button" onClick={handleOlderAuditLog}>
+                Show Older
+              </button>
+            </li>
+          )}
+        </ul>
+      )}
+
       {scanResult && (
         <div className="scan-result">
           <h3>Found {scanResult.samples.length} audio samples</h3>
           <p className="directory-path">{scanResult.directory}</p>
+          {scanResult.report.sampling && (
+            <p className="directory-path">
+              Random{' '}
+              {Math.min(scanResult.report.sampling.limit, scanResult.report.sampling.population)}{' '}
+              of{' '}
+              {scanResult.report.sampling.population} files (seed{' '}
+              {scanResult.report.sampling.seed})
+            </p>
+          )}
+          <ScanReportSummary report={scanResult.report} />
+
+          <div className="scan-option">
+            <button
+              className="secondary-button"
+              onClick={() => handleSeedFromRatings('library')}
+              disabled={isScanning}
+            >
+              Seed from Music Library
+            </button>
+            <button
+              className="secondary-button"
+              onClick={() => handleSeedFromRatings('tags')}
+              disabled={isScanning}
+            >
+              Seed from File Ratings
+            </button>
+            {seedMessage && <small>{seedMessage}</small>}
+          </div>
 
           <div className="threshold-setting">
             <label>
@@ -119,6 +797,82 @@ export function DirectoryPicker({ onStartTournament, onLoadSession }: DirectoryP
             />
           </div>
 
+          <div className="threshold-setting">
+            <label>
+              Cutoff
+              <br />
+              <small>Adaptive cutoffs follow each round's score spread instead</small>
+            </label>
+            <select
+              value={adaptiveCutoff ?? ''}
+              onChange={(e) =>
+                setAdaptiveCutoff(e.target.value === '' ? null : Number(e.target.value))
+              }
+            >
+              <option value="">Fixed threshold</option>
+              <option value="0">Above average score</option>
+              <option value="1">One standard deviation above average</option>
+            </select>
+          </div>
+
+          <div className="threshold-setting">
+            <label>
+              Scores between rounds
+              <br />
+              <small>Carry less so an early blowout doesn't decide the final standings</small>
+            </label>
+            <select
+              value={scoreCarry}
+              onChange={(e) => setScoreCarry(e.target.value as ScoreCarry)}
+            >
+              <option value="raw">Keep all wins</option>
+              <option value="percentage">Carry the round's win rate (up to 2 wins)</option>
+              <option value="reset">Start each round level</option>
+            </select>
+          </div>
+
+          <div className="threshold-setting">
+            <label>
+              Byes
+              <br />
+              <small>Who sits out when a round has an odd number of samples</small>
+            </label>
+            <select
+              value={byePolicy}
+              onChange={(e) => setByePolicy(e.target.value as ByePolicy)}
+            >
+              <option value="fair">Fewest comparisons, never twice in a row</option>
+              <option value="lowest">Lowest-ranked sample</option>
+            </select>
+          </div>
+
+          <div className="threshold-setting">
+            <label>
+              <input
+                type="checkbox"
+                checked={allowRematches}
+                onChange={(e) => setAllowRematches(e.target.checked)}
+              />
+              Allow rematches
+              <br />
+              <small>Otherwise samples only meet again once every other opponent is taken</small>
+            </label>
+          </div>
+
+          <div className="threshold-setting">
+            <label>
+              Seed
+              <br />
+              <small>Same samples and seed give the same schedule (leave empty for random)</small>
+            </label>
+            <input
+              type="number"
+              min="0"
+              value={seed}
+              onChange={(e) => setSeed(e.target.value)}
+            />
+          </div>
+
           <button
             className="primary-button start-button"
             onClick={handleStartTournament}
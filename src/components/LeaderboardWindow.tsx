@@ -0,0 +1,81 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+
+interface StandingsRow {
+  rank: number;
+  path: string;
+  filename: string;
+  score: number;
+  comparisons: number;
+}
+
+interface Standings {
+  round: number;
+  total: number;
+  rows: StandingsRow[];
+}
+
+// Rows that moved since the last update, sent after each vote
+interface StandingsDelta extends Standings {
+  removed: string[];
+}
+
+function applyDelta(standings: Standings | null, delta: StandingsDelta): Standings | null {
+  if (!standings || standings.round !== delta.round) return standings;
+  const changed = new Set([...delta.removed, ...delta.rows.map(row => row.path)]);
+  const rows = [...standings.rows.filter(row => !changed.has(row.path)), ...delta.rows]
+    .sort((a, b) => a.rank - b.rank);
+  return { round: delta.round, total: delta.total, rows };
+}
+
+// Read-only standings pushed by the main window through the backend
+export function LeaderboardWindow() {
+  const [standings, setStandings] = useState<Standings | null>(null);
+
+  useEffect(() => {
+    invoke<Standings | null>('get_standings').then(setStandings).catch(() => {});
+    const unlisten = listen<Standings>('leaderboard://updated', event => {
+      setStandings(event.payload);
+    });
+    const unlistenDelta = listen<StandingsDelta>('standings://changed', event => {
+      setStandings(current => applyDelta(current, event.payload));
+    });
+    return () => {
+      unlisten.then(fn => fn());
+      unlistenDelta.then(fn => fn());
+    };
+  }, []);
+
+  if (!standings) {
+    return <div className="leaderboard-window">No tournament in progress</div>;
+  }
+
+  return (
+    <div className="leaderboard-window">
+      <h3>
+        Round {standings.round} · {standings.total} samples
+      </h3>
+      <table>
+        <thead>
+          <tr>
+            <th>Rank</th>
+            <th>Filename</th>
+            <th>Score</th>
+            <th>Comparisons</th>
+          </tr>
+        </thead>
+        <tbody>
+          {standings.rows.map(row => (
+            <tr key={row.path}>
+              <td>{row.rank}</td>
+              <td title={row.path}>{row.filename}</td>
+              <td>{row.score}</td>
+              <td>{row.comparisons}</td>
+            </tr>
+          ))}
+        </tbody>
+      </table>
+    </div>
+  );
+}
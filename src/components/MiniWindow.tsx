@@ -0,0 +1,67 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import type { Sample, VoteChoice } from '../types';
+
+interface CurrentPair {
+  pair_id: string;
+  round: number;
+  a: Sample;
+  b: Sample;
+}
+
+// Compact always-on-top controls; votes go straight to the tournament's backend session
+export function MiniWindow() {
+  const [pair, setPair] = useState<CurrentPair | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<CurrentPair | null>('get_current_pair').then(setPair).catch(() => {});
+    const unlisten = listen<CurrentPair | null>('pair://changed', event => {
+      setPair(event.payload);
+      setError(null);
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  const vote = useCallback(
+    (winner: VoteChoice) => {
+      if (!pair) return;
+      invoke('submit_local_vote', { pairId: pair.pair_id, winner }).catch(err =>
+        setError(String(err))
+      );
+    },
+    [pair]
+  );
+
+  useEffect(() => {
+    const handleKeyDown = (e: KeyboardEvent) => {
+      if (e.key === 'a' || e.key === 'A') vote('a');
+      else if (e.key === 'b' || e.key === 'B') vote('b');
+      else if (e.key === 'x' || e.key === 'X') vote('skip');
+    };
+    window.addEventListener('keydown', handleKeyDown);
+    return () => window.removeEventListener('keydown', handleKeyDown);
+  }, [vote]);
+
+  if (!pair) {
+    return <div className="mini-window">No comparison in progress</div>;
+  }
+
+  return (
+    <div className="mini-window">
+      <div className="mini-names">
+        <span title={pair.a.path}>A: {pair.a.filename}</span>
+        <span title={pair.b.path}>B: {pair.b.filename}</span>
+      </div>
+      <div className="mini-actions">
+        <button className="primary-button" onClick={() => vote('a')}>A</button>
+        <button className="primary-button" onClick={() => vote('b')}>B</button>
+        <button className="danger-button" onClick={() => vote('skip')}>Skip</button>
+      </div>
+      {error && <div className="error-message">{error}</div>}
+    </div>
+  );
+}
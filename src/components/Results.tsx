@@ -1,22 +1,116 @@
-import { useState, useCallback } from 'react';
-import { save } from '@tauri-apps/plugin-dialog';
+import { useState, useCallback, useEffect } from 'react';
+import { open, save } from '@tauri-apps/plugin-dialog';
 import { invoke } from '@tauri-apps/api/core';
-import { exportResults, saveProgress } from '../lib/storage';
+import {
+  abletonUserLibrary,
+  errorMessage,
+  exportResults,
+  exportParquet,
+  exportPdfReport,
+  exportNdjson,
+  exportToAbleton,
+  exportToPlayer,
+  findDuplicates,
+  generateDiagnosticsBundle,
+  getStatistics,
+  renderReel,
+  renderSummaryImage,
+  searchSamples,
+  setLastExportDefaults,
+  shareResultsLink,
+  stopResultsShare,
+} from '../lib/storage';
 import { getSortedResults } from '../lib/tournament';
 import { Toast } from './Toast';
 import { ScrollIndicator } from './ScrollIndicator';
-import type { TournamentState } from '../types';
+import type { Pin, Sample, ShareInfo, SortKey, Statistics, TournamentState } from '../types';
+
+// Descriptor orderings offered in the search bar; descriptors sort low to high
+const SORT_OPTIONS: { label: string; sort: SortKey | null; descending: boolean }[] = [
+  { label: 'Standings', sort: null, descending: false },
+  { label: 'Darkest first', sort: 'brightness', descending: false },
+  { label: 'Brightest first', sort: 'brightness', descending: true },
+  { label: 'Most tonal first', sort: 'flatness', descending: false },
+  { label: 'Noisiest first', sort: 'flatness', descending: true },
+  { label: 'Shortest first', sort: 'duration', descending: false },
+];
 
 interface ResultsProps {
   tournament: TournamentState;
+  // The backend session `tournament` is a copy of
+  sessionId: string;
   onBack: () => void;
   onReset: () => void;
+  onPinSample?: (path: string, pin: Pin | null) => void;
+  onBenchSample?: (path: string, benched: boolean) => void;
+  onSaveSession: (filePath: string) => Promise<void>;
+  onMergeDuplicates?: (keep: string, drop: string[]) => Promise<void>;
+}
+
+// Share of a voter's picks going to the sample with more of a feature; 50% is no preference
+function preferenceLabel(share: number | null): string {
+  return share === null ? '-' : `${Math.round(share * 100)}%`;
 }
 
-export function Results({ tournament, onBack, onReset }: ResultsProps) {
+const PIN_LABELS: Record<Pin, string> = {
+  always_advance: 'kept',
+  eliminate: 'rejected',
+};
+
+export function Results({
+  tournament,
+  sessionId,
+  onBack,
+  onReset,
+  onPinSample,
+  onBenchSample,
+  onSaveSession,
+  onMergeDuplicates,
+}: ResultsProps) {
   const [exporting, setExporting] = useState(false);
   const [toastMessage, setToastMessage] = useState<string | null>(null);
-  const [minScoreFilter, setMinScoreFilter] = useState(0);
+  // A session started from a template opens with its export settings
+  const defaults = tournament.export_defaults;
+  const [minScoreFilter, setMinScoreFilter] = useState(defaults?.min_score ?? 0);
+  // List export keeps only the best N matches; 0 exports all of them
+  const [topN, setTopN] = useState(defaults?.top_n ?? 0);
+  const [writeManifest, setWriteManifest] = useState(defaults?.write_manifest ?? false);
+  // Player export copies the files into the playlist folder instead of linking the originals
+  const [copyToPlayer, setCopyToPlayer] = useState(false);
+  // Ableton export links the originals instead of copying them
+  const [linkToAbleton, setLinkToAbleton] = useState(false);
+  const [exportScripts, setExportScripts] = useState<{ name: string }[]>([]);
+  const [searchText, setSearchText] = useState('');
+  const [sortIndex, setSortIndex] = useState(0);
+  const [searchResults, setSearchResults] = useState<Sample[] | null>(null);
+  const [statistics, setStatistics] = useState<Statistics | null>(null);
+  const [duplicates, setDuplicates] = useState<string[][] | null>(null);
+  const [share, setShare] = useState<ShareInfo | null>(null);
+
+  // Remembered so the next saved template picks them up
+  useEffect(() => {
+    setLastExportDefaults({
+      ...defaults,
+      min_score: minScoreFilter,
+      top_n: topN > 0 ? topN : null,
+      write_manifest: writeManifest,
+    });
+  }, [minScoreFilter, topN, writeManifest]);
+
+  useEffect(() => {
+    invoke<{ name: string }[]>('list_export_scripts')
+      .then(setExportScripts)
+      .catch(() => setExportScripts([]));
+  }, []);
+
+  useEffect(() => {
+    getStatistics(tournament.samples, tournament.history, tournament.votes)
+      .then(setStatistics)
+      .catch(() => setStatistics(null));
+  }, [tournament.samples, tournament.history, tournament.votes]);
+
+  // Only worth showing when the source actually spans several packs
+  const packStats = statistics && statistics.packs.length > 1 ? statistics.packs : [];
 
   const showToast = useCallback((message: string) => {
     setToastMessage(message);
@@ -29,7 +123,25 @@ export function Results({ tournament, onBack, onReset }: ResultsProps) {
   const sortedSamples = getSortedResults(tournament);
   const maxScore = sortedSamples.length > 0 ? sortedSamples[0].score : 0;
 
-  const filteredSamples = sortedSamples.filter(s => s.score >= minScoreFilter);
+  useEffect(() => {
+    const { sort, descending } = SORT_OPTIONS[sortIndex];
+    if (!searchText.trim() && !sort) {
+      setSearchResults(null);
+      return;
+    }
+
+    searchSamples(tournament.samples, { text: searchText.trim() || null, sort, descending })
+      .then(setSearchResults)
+      .catch(err => console.error('Search failed:', err));
+  }, [tournament.samples, searchText, sortIndex]);
+
+  // Exports follow whatever the search bar currently shows
+  const visibleSamples = searchResults ?? sortedSamples;
+  // Pins override the score cut, as in the backend exports
+  const passesMinScore = (s: Sample) =>
+    s.pin ? s.pin === 'always_advance' : s.score >= minScoreFilter;
+  const filteredSamples = visibleSamples.filter(passesMinScore);
+  const listSamples = topN > 0 ? filteredSamples.slice(0, topN) : filteredSamples;
 
   const handleExportGood = async () => {
     try {
@@ -42,7 +154,31 @@ export function Results({ tournament, onBack, onReset }: ResultsProps) {
       if (!filePath) return;
 
       setExporting(true);
-      await exportResults(sortedSamples, filePath, minScoreFilter);
+      await exportResults(visibleSamples, filePath, {
+        min_score: minScoreFilter,
+        max_rank: topN > 0 ? topN : null,
+      }, writeManifest);
+      showToast(`Exported ${listSamples.length} samples`);
+    } catch (err) {
+      showToast(`Error exporting: ${errorMessage(err)}`);
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  // Streamed line by line on the backend, for pools too big for one text export
+  const handleExportNdjson = async () => {
+    try {
+      const filePath = await save({
+        filters: [{ name: 'JSON Lines', extensions: ['ndjson', 'jsonl'] }],
+        defaultPath: 'results.ndjson',
+        title: 'Export JSON Lines',
+      });
+
+      if (!filePath) return;
+
+      setExporting(true);
+      await exportNdjson(visibleSamples, filePath, minScoreFilter, writeManifest);
       showToast(`Exported ${filteredSamples.length} samples`);
     } catch (err) {
       showToast(`Error exporting: ${err}`);
@@ -51,20 +187,260 @@ export function Results({ tournament, onBack, onReset }: ResultsProps) {
     }
   };
 
+  // Exported playlists and collections are named after the scanned folder
+  const collectionName = `${
+    tournament.source_directory.split('/').filter(Boolean).pop() ?? 'vs-1'
+  } winners`;
+
+  // Lands in Live's browser under User Library > Samples, best first
+  const handleExportToAbleton = async () => {
+    try {
+      let libraryDir = await abletonUserLibrary();
+      if (!libraryDir) {
+        const picked = await open({
+          directory: true,
+          title: 'Choose Your Ableton User Library',
+        });
+        if (!picked || Array.isArray(picked)) return;
+        libraryDir = picked;
+      }
+
+      setExporting(true);
+      const result = await exportToAbleton(
+        visibleSamples,
+        libraryDir,
+        collectionName,
+        minScoreFilter,
+        linkToAbleton ? 'symlink' : 'copy'
+      );
+      showToast(
+        `Placed ${result.placed} samples in ${result.folder}` +
+          (result.failed.length > 0 ? ` (${result.failed.length} failed)` : '')
+      );
+    } catch (err) {
+      showToast(`Error exporting: ${errorMessage(err)}`);
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  // For Music's "Automatically Add to Music" folder, Engine DJ and other watched folders
+  const handleExportToPlayer = async () => {
+    try {
+      const destDir = await open({
+        directory: true,
+        title: 'Choose the Folder Your Player Watches',
+      });
+
+      if (!destDir || Array.isArray(destDir)) return;
+
+      setExporting(true);
+      const result = await exportToPlayer(
+        visibleSamples,
+        destDir,
+        collectionName,
+        minScoreFilter,
+        copyToPlayer
+      );
+      const notes = [
+        result.skipped > 0 ? `${result.skipped} archive entries left out` : null,
+        result.failed.length > 0 ? `${result.failed.length} failed` : null,
+      ].filter(Boolean);
+      showToast(
+        `Exported ${result.tracks} tracks to ${result.folder}` +
+          (notes.length > 0 ? ` (${notes.join(', ')})` : '')
+      );
+    } catch (err) {
+      showToast(`Error exporting: ${errorMessage(err)}`);
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  // Archived with the project documentation, so it covers the whole pool rather than the search
+  const handleExportPdf = async () => {
+    try {
+      const filePath = await save({
+        filters: [{ name: 'PDF', extensions: ['pdf'] }],
+        defaultPath: 'shootout_report.pdf',
+        title: 'Export PDF Report',
+      });
+
+      if (!filePath) return;
+
+      setExporting(true);
+      await exportPdfReport(sessionId, filePath);
+      showToast('Report exported');
+    } catch (err) {
+      showToast(`Error exporting: ${errorMessage(err)}`);
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  // Card of the top ten to post in a community thread instead of a screenshot
+  const handleShareImage = async () => {
+    try {
+      const filePath = await save({
+        filters: [{ name: 'PNG', extensions: ['png'] }],
+        defaultPath: 'shootout_results.png',
+        title: 'Save Summary Image',
+      });
+
+      if (!filePath) return;
+
+      setExporting(true);
+      await renderSummaryImage(tournament.samples, 10, filePath);
+      showToast('Summary image saved');
+    } catch (err) {
+      showToast(`Error rendering image: ${errorMessage(err)}`);
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  // Someone in the room browses and plays the winners on their phone from the QR code
+  const handleShareLink = async () => {
+    try {
+      setShare(await shareResultsLink(visibleSamples, true, true));
+    } catch (err) {
+      showToast(`Sharing failed: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleStopSharing = async () => {
+    await stopResultsShare().catch(() => {});
+    setShare(null);
+  };
+
+  // One WAV to skim the winners in any player, chapters jumping between them
+  const handleRenderReel = async () => {
+    try {
+      const filePath = await save({
+        filters: [{ name: 'WAV', extensions: ['wav'] }],
+        defaultPath: 'audition_reel.wav',
+        title: 'Render Audition Reel',
+      });
+
+      if (!filePath) return;
+
+      setExporting(true);
+      const report = await renderReel(visibleSamples, filePath, minScoreFilter);
+      const minutes = Math.floor(report.duration_secs / 60);
+      const seconds = Math.round(report.duration_secs % 60).toString().padStart(2, '0');
+      showToast(
+        `Rendered ${report.tracks} samples (${minutes}:${seconds}) with ${report.cue_path}` +
+          (report.failed.length > 0 ? `, ${report.failed.length} failed` : '')
+      );
+    } catch (err) {
+      showToast(`Error rendering reel: ${errorMessage(err)}`);
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  // Full dataset for analysis in Python etc., eliminated samples included
+  const handleExportDataset = async () => {
+    try {
+      const filePath = await save({
+        filters: [{ name: 'Parquet', extensions: ['parquet'] }],
+        defaultPath: 'tournament.parquet',
+        title: 'Export Dataset',
+      });
+
+      if (!filePath) return;
+
+      setExporting(true);
+      await exportParquet(tournament.samples, filePath);
+      showToast(`Exported ${tournament.samples.length} samples`);
+    } catch (err) {
+      showToast(`Error exporting: ${err}`);
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  const handleScriptExport = async (scriptName: string) => {
+    try {
+      const filePath = await save({
+        defaultPath: `${scriptName}.txt`,
+        title: `Export with ${scriptName}`,
+      });
+
+      if (!filePath) return;
+
+      setExporting(true);
+      await invoke('export_with_script', {
+        scriptName,
+        samples: visibleSamples,
+        filePath,
+        minScore: minScoreFilter,
+      });
+      showToast(`Exported ${filteredSamples.length} samples with ${scriptName}`);
+    } catch (err) {
+      showToast(`Error exporting: ${err}`);
+    } finally {
+      setExporting(false);
+    }
+  };
+
+  const handleSaveDiagnostics = async () => {
+    try {
+      const filePath = await save({
+        filters: [{ name: 'Zip', extensions: ['zip'] }],
+        defaultPath: 'vs1-diagnostics.zip',
+        title: 'Save Diagnostics',
+      });
+
+      if (!filePath) return;
+
+      await generateDiagnosticsBundle(filePath, sessionId);
+      showToast('Diagnostics saved');
+    } catch (err) {
+      showToast(`Error saving diagnostics: ${err}`);
+    }
+  };
+
   const handleSaveSession = async () => {
     try {
       const filePath = await save({
-        filters: [{ name: 'JSON', extensions: ['json'] }],
-        defaultPath: 'vs1_session.json',
+        filters: [{ name: 'VS-1 Session', extensions: ['vs1', 'json'] }],
+        defaultPath: 'vs1_session.vs1',
         title: 'Save Session',
       });
 
       if (!filePath) return;
 
-      await saveProgress(tournament, filePath);
+      await onSaveSession(filePath);
       showToast('Session saved');
     } catch (err) {
-      showToast(`Error saving: ${err}`);
+      showToast(`Error saving: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleFindDuplicates = async () => {
+    try {
+      const groups = await findDuplicates(sessionId);
+      setDuplicates(groups);
+      if (groups.length === 0) showToast('No duplicates found');
+    } catch (err) {
+      showToast(`Error finding duplicates: ${err}`);
+    }
+  };
+
+  // The best-ranked copy absorbs the others' scores and history
+  const handleMergeGroup = async (group: string[]) => {
+    const rank = (path: string) => {
+      const index = sortedSamples.findIndex(s => s.path === path);
+      return index < 0 ? Infinity : index;
+    };
+    const [keep, ...drop] = [...group].sort((a, b) => rank(a) - rank(b));
+    try {
+      await onMergeDuplicates?.(keep, drop);
+      setDuplicates(prev => prev && prev.filter(g => g !== group));
+      showToast(`Merged ${drop.length + 1} copies`);
+    } catch (err) {
+      showToast(`Error merging: ${err}`);
     }
   };
 
@@ -82,7 +458,31 @@ export function Results({ tournament, onBack, onReset }: ResultsProps) {
       showToast('Copied to clipboard');
     } catch (err) {
       console.error('Copy failed:', err);
-      showToast(`Copy failed: ${err}`);
+      showToast(`Copy failed: ${errorMessage(err)}`);
+    }
+  };
+
+  const handleRevealResults = async () => {
+    try {
+      await invoke<number>('reveal_results', {
+        samples: visibleSamples,
+        minScore: minScoreFilter,
+      });
+    } catch (err) {
+      showToast(`Reveal failed: ${errorMessage(err)}`);
+    }
+  };
+
+  // Every file passing the score cut, ready to paste into a folder or DAW
+  const handleCopyFiles = async () => {
+    try {
+      const count = await invoke<number>('copy_results_files_to_clipboard', {
+        samples: visibleSamples,
+        minScore: minScoreFilter,
+      });
+      showToast(`Copied ${count} files to clipboard`);
+    } catch (err) {
+      showToast(`Copy failed: ${errorMessage(err)}`);
     }
   };
 
@@ -107,19 +507,254 @@ export function Results({ tournament, onBack, onReset }: ResultsProps) {
             onChange={(e) => setMinScoreFilter(Number(e.target.value))}
           />
         </label>
+        <label>
+          Top
+          <input
+            type="number"
+            min="0"
+            value={topN}
+            onChange={(e) => setTopN(Math.max(0, Math.floor(Number(e.target.value))))}
+          />
+          {topN === 0 && <small>all</small>}
+        </label>
+        <label title="Writes <file>.manifest.json with source, rank, score and hash of every sample">
+          <input
+            type="checkbox"
+            checked={writeManifest}
+            onChange={(e) => setWriteManifest(e.target.checked)}
+          />
+          Write manifest
+        </label>
+        <label title="Copies the files into the player folder; otherwise the playlist links the originals">
+          <input
+            type="checkbox"
+            checked={copyToPlayer}
+            onChange={(e) => setCopyToPlayer(e.target.checked)}
+          />
+          Copy files for player
+        </label>
+        <label title="Links the originals into the User Library instead of copying them">
+          <input
+            type="checkbox"
+            checked={linkToAbleton}
+            onChange={(e) => setLinkToAbleton(e.target.checked)}
+          />
+          Link into Ableton
+        </label>
         <span>{filteredSamples.length} samples match filter</span>
+        <div className="search-controls">
+          <input
+            type="search"
+            placeholder="Filter by filename (e.g. kick)"
+            value={searchText}
+            onChange={(e) => setSearchText(e.target.value)}
+          />
+          <select value={sortIndex} onChange={(e) => setSortIndex(Number(e.target.value))}>
+            {SORT_OPTIONS.map((option, index) => (
+              <option key={option.label} value={index}>
+                {option.label}
+              </option>
+            ))}
+          </select>
+        </div>
       </div>
 
+      {packStats.length > 0 && (
+        <div className="pack-stats">
+          <h3>Packs</h3>
+          <table>
+            <thead>
+              <tr>
+                <th>Pack</th>
+                <th>Samples</th>
+                <th>Mean Score</th>
+                <th>Best</th>
+                <th>Top 10</th>
+              </tr>
+            </thead>
+            <tbody>
+              {packStats.map(pack => (
+                <tr key={pack.pack ?? ''}>
+                  <td>{pack.pack ?? 'Loose files'}</td>
+                  <td>{pack.samples}</td>
+                  <td>{pack.mean_score.toFixed(2)}</td>
+                  <td>{pack.best_score}</td>
+                  <td>{pack.top_ten}</td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+
+      {statistics && statistics.upsets.length > 0 && (
+        <div className="pack-stats">
+          <h3>Biggest Upsets</h3>
+          <table>
+            <thead>
+              <tr>
+                <th>Winner</th>
+                <th>Beat</th>
+                <th>Round</th>
+                <th>Expected</th>
+              </tr>
+            </thead>
+            <tbody>
+              {statistics.upsets.map((upset, i) => (
+                <tr key={i}>
+                  <td>{upset.winner.split('/').pop()}</td>
+                  <td>{upset.loser.split('/').pop()}</td>
+                  <td>{upset.round}</td>
+                  <td>{Math.round(upset.expected * 100)}%</td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+
+      {statistics?.voters && statistics.voters.length > 0 && (
+        <div className="pack-stats">
+          <h3>Voters</h3>
+          <table>
+            <thead>
+              <tr>
+                <th>Voter</th>
+                <th>Ballots</th>
+                <th>With Majority</th>
+                <th>Louder</th>
+                <th>Brighter</th>
+                <th>Longer</th>
+                <th>Ranking vs Majority</th>
+                <th>Favourites</th>
+              </tr>
+            </thead>
+            <tbody>
+              {statistics.voters.map(voter => (
+                <tr key={voter.voter_id}>
+                  <td>{voter.name}</td>
+                  <td>{voter.ballots}</td>
+                  <td>{Math.round(voter.agreement * 100)}%</td>
+                  <td>{preferenceLabel(voter.prefers_louder)}</td>
+                  <td>{preferenceLabel(voter.prefers_brighter)}</td>
+                  <td>{preferenceLabel(voter.prefers_longer)}</td>
+                  <td>{voter.ranking_correlation?.toFixed(2) ?? '-'}</td>
+                  <td>{voter.favourites.map(path => path.split('/').pop()).join(', ')}</td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+          {statistics.voter_agreement && statistics.voter_agreement.length > 0 && (
+            <table>
+              <tbody>
+                {statistics.voter_agreement.map(pair => (
+                  <tr key={`${pair.a}/${pair.b}`}>
+                    <td>{pair.a_name} &amp; {pair.b_name}</td>
+                    <td>
+                      agree {Math.round(pair.rate * 100)}% of {pair.shared} comparisons
+                    </td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          )}
+        </div>
+      )}
+
+      {duplicates && duplicates.length > 0 && (
+        <div className="pack-stats">
+          <h3>Duplicates</h3>
+          <table>
+            <tbody>
+              {duplicates.map(group => (
+                <tr key={group[0]}>
+                  <td>{group.map(path => path.split('/').pop()).join(', ')}</td>
+                  <td>
+                    <button className="secondary-button" onClick={() => handleMergeGroup(group)}>
+                      Merge
+                    </button>
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+
+      {share && (
+        <div className="share-panel">
+          <img
+            src={`data:image/svg+xml;utf8,${encodeURIComponent(share.qr_svg)}`}
+            alt="QR code for the results link"
+          />
+          <div>
+            <code>{share.url}</code>
+            <small>Expires at {new Date(share.expires_at * 1000).toLocaleTimeString()}</small>
+            <button className="secondary-button" onClick={handleStopSharing}>
+              Stop Sharing
+            </button>
+          </div>
+        </div>
+      )}
+
       <div className="results-actions">
         <button className="primary-button" onClick={onBack}>
           Continue Tournament
         </button>
         <button className="secondary-button" onClick={handleExportGood} disabled={exporting}>
-          Export List ({filteredSamples.length})
+          Export List ({listSamples.length})
+        </button>
+        <button className="secondary-button" onClick={handleRevealResults}>
+          Reveal Files
+        </button>
+        <button className="secondary-button" onClick={handleCopyFiles}>
+          Copy Files ({filteredSamples.length})
+        </button>
+        <button className="secondary-button" onClick={handleExportNdjson} disabled={exporting}>
+          Export JSON Lines
+        </button>
+        <button className="secondary-button" onClick={handleExportToPlayer} disabled={exporting}>
+          Export to Player
+        </button>
+        <button className="secondary-button" onClick={handleExportToAbleton} disabled={exporting}>
+          Export to Ableton
         </button>
+        <button className="secondary-button" onClick={handleExportPdf} disabled={exporting}>
+          Export PDF Report
+        </button>
+        <button className="secondary-button" onClick={handleShareLink}>
+          Share Link
+        </button>
+        <button className="secondary-button" onClick={handleShareImage} disabled={exporting}>
+          Summary Image
+        </button>
+        <button className="secondary-button" onClick={handleRenderReel} disabled={exporting}>
+          Render Reel
+        </button>
+        <button className="secondary-button" onClick={handleExportDataset} disabled={exporting}>
+          Export Dataset
+        </button>
+        {exportScripts.map(script => (
+          <button
+            key={script.name}
+            className="secondary-button"
+            onClick={() => handleScriptExport(script.name)}
+            disabled={exporting}
+          >
+            Export: {script.name}
+          </button>
+        ))}
         <button className="secondary-button" onClick={handleSaveSession}>
           Save Session
         </button>
+        {onMergeDuplicates && (
+          <button className="secondary-button" onClick={handleFindDuplicates}>
+            Find Duplicates
+          </button>
+        )}
+        <button className="secondary-button" onClick={handleSaveDiagnostics}>
+          Save Diagnostics
+        </button>
         <button className="danger-button" onClick={onReset}>
           Start Over
         </button>
@@ -138,7 +773,7 @@ export function Results({ tournament, onBack, onReset }: ResultsProps) {
               </tr>
             </thead>
             <tbody>
-              {sortedSamples.map((sample, index) => {
+              {visibleSamples.map((sample, index) => {
                 const winRate = sample.comparisons > 0
                   ? ((sample.score / sample.comparisons) * 100).toFixed(1)
                   : '0.0';
@@ -146,14 +781,58 @@ export function Results({ tournament, onBack, onReset }: ResultsProps) {
                 return (
                   <tr
                     key={sample.path}
-                    className={`clickable-row ${sample.score >= minScoreFilter ? 'included' : 'excluded'}`}
+                    className={`clickable-row ${passesMinScore(sample) ? 'included' : 'excluded'}`}
                     onClick={() => handleCopyToClipboard(sample.path)}
                     onDoubleClick={() => handleRevealInFinder(sample.path)}
-                    title="Click to copy, double-click to reveal in Finder"
+                    title={
+                      sample.instrument
+                        ? `From ${sample.instrument}. Click to copy, double-click to reveal in Finder`
+                        : 'Click to copy, double-click to reveal in Finder'
+                    }
                   >
                     <td>{index + 1}</td>
                     <td className="filename-cell">
                       {sample.filename}
+                      {(sample.channels ?? 0) > 2 && (
+                        <span className="variant-count" title="Downmixed to stereo for playback">
+                          {sample.channels}ch
+                        </span>
+                      )}
+                      {sample.pin && (
+                        <button
+                          className="pin-badge"
+                          title="Pinned manually. Click to unpin"
+                          onClick={e => {
+                            e.stopPropagation();
+                            onPinSample?.(sample.path, null);
+                          }}
+                        >
+                          {PIN_LABELS[sample.pin]}
+                        </button>
+                      )}
+                      {sample.benched && (
+                        <button
+                          className="pin-badge"
+                          title="Benched: not paired or cut. Click to bring it back"
+                          onClick={e => {
+                            e.stopPropagation();
+                            onBenchSample?.(sample.path, false);
+                          }}
+                        >
+                          benched
+                        </button>
+                      )}
+                      {!!sample.variants?.length && (
+                        <span className="variant-count">+{sample.variants.length} variations</span>
+                      )}
+                      {sample.analysis?.root_note && (
+                        <span className="root-note">{sample.analysis.root_note}</span>
+                      )}
+                      {sample.analysis?.flags.map(flag => (
+                        <span key={flag} className="quality-flag">
+                          {flag.replace(/_/g, ' ')}
+                        </span>
+                      ))}
                     </td>
                     <td>{sample.score}</td>
                     <td>{sample.comparisons}</td>
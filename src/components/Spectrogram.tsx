@@ -0,0 +1,37 @@
+import { useEffect, useState } from 'react';
+import { convertFileSrc, invoke } from '@tauri-apps/api/core';
+
+interface SpectrogramProps {
+  filePath: string | null;
+  width?: number;
+  height?: number;
+}
+
+// Rendered and cached by the backend; we only display the PNG
+export function Spectrogram({ filePath, width = 400, height = 160 }: SpectrogramProps) {
+  const [src, setSrc] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    if (!filePath) return;
+    let cancelled = false;
+    setSrc(null);
+    setError(null);
+
+    invoke<string>('generate_spectrogram', { path: filePath, width, height })
+      .then(image => {
+        if (!cancelled) setSrc(convertFileSrc(image));
+      })
+      .catch(err => {
+        if (!cancelled) setError(String(err));
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [filePath, width, height]);
+
+  if (error) return <div className="spectrogram spectrogram-error">{error}</div>;
+  if (!src) return <div className="spectrogram spectrogram-loading">...</div>;
+  return <img className="spectrogram" src={src} alt="Spectrogram" />;
+}
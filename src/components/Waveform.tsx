@@ -1,5 +1,5 @@
 import { useRef, useEffect, useState } from 'react';
-import { convertFileSrc } from '@tauri-apps/api/core';
+import { getWaveformPeaks } from '../lib/storage';
 
 interface WaveformProps {
   filePath: string | null;
@@ -12,42 +12,18 @@ const BAR_GAP = 1;
 
 const waveformCache = new Map<string, number[]>();
 
-async function decodeAudioData(filePath: string): Promise<number[]> {
+const BUCKETS = 100;
+
+async function loadPeaks(filePath: string): Promise<number[]> {
   const cached = waveformCache.get(filePath);
   if (cached) return cached;
 
   try {
-    const src = convertFileSrc(filePath);
-    const response = await fetch(src);
-    const arrayBuffer = await response.arrayBuffer();
-
-    const audioContext = new AudioContext();
-    const audioBuffer = await audioContext.decodeAudioData(arrayBuffer);
-
-    const rawData = audioBuffer.getChannelData(0);
-
-    const samples = 100;
-    const blockSize = Math.floor(rawData.length / samples);
-    const peaks: number[] = [];
-
-    for (let i = 0; i < samples; i++) {
-      const start = i * blockSize;
-      let sum = 0;
-      for (let j = 0; j < blockSize; j++) {
-        sum += Math.abs(rawData[start + j] || 0);
-      }
-      peaks.push(sum / blockSize);
-    }
-
-    const max = Math.max(...peaks);
-    const normalized = peaks.map(p => (max > 0 ? p / max : 0));
-
-    waveformCache.set(filePath, normalized);
-    await audioContext.close();
-
-    return normalized;
+    const peaks = await getWaveformPeaks(filePath, BUCKETS);
+    waveformCache.set(filePath, peaks);
+    return peaks;
   } catch (err) {
-    console.error('Failed to decode audio:', err);
+    console.error('Failed to load waveform:', err);
     return [];
   }
 }
@@ -64,7 +40,7 @@ export function Waveform({ filePath, progress, isPlaying }: WaveformProps) {
     }
 
     setIsLoading(true);
-    decodeAudioData(filePath)
+    loadPeaks(filePath)
       .then(setPeaks)
       .finally(() => setIsLoading(false));
   }, [filePath]);
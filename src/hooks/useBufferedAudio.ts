@@ -1,5 +1,5 @@
 import { useRef, useState, useCallback, useEffect } from 'react';
-import { convertFileSrc } from '@tauri-apps/api/core';
+import { convertFileSrc, invoke } from '@tauri-apps/api/core';
 
 interface AudioCache {
   audio: HTMLAudioElement;
@@ -39,7 +39,6 @@ export function preloadAudio(filePath: string): Promise<HTMLAudioElement> {
 
   const promise = new Promise<HTMLAudioElement>((resolve, reject) => {
     const audio = new Audio();
-    const src = convertFileSrc(filePath);
 
     audioCache.set(filePath, { audio, loaded: false });
     cacheOrder.push(filePath);
@@ -54,20 +53,32 @@ export function preloadAudio(filePath: string): Promise<HTMLAudioElement> {
       resolve(audio);
     };
 
-    const handleError = () => {
+    const fail = (message: string) => {
       loadingPromises.delete(filePath);
       audioCache.delete(filePath);
       const idx = cacheOrder.indexOf(filePath);
       if (idx > -1) cacheOrder.splice(idx, 1);
-      reject(new Error(audio.error?.message || 'Failed to load'));
+      reject(new Error(message));
+    };
+
+    const handleError = () => {
+      fail(audio.error?.message || 'Failed to load');
     };
 
     audio.addEventListener('canplaythrough', handleLoaded, { once: true });
     audio.addEventListener('error', handleError, { once: true });
 
     audio.preload = 'auto';
-    audio.src = src;
-    audio.load();
+    // Needed for Web Audio (mono preview) to read samples from the asset protocol
+    audio.crossOrigin = 'anonymous';
+
+    // Archive entries are extracted by the backend before they can be played
+    invoke<string>('resolve_playback_path', { filePath })
+      .then(playablePath => {
+        audio.src = convertFileSrc(playablePath);
+        audio.load();
+      })
+      .catch(err => fail(String(err)));
   });
 
   loadingPromises.set(filePath, promise);
@@ -80,6 +91,126 @@ export function preloadMultiple(filePaths: string[]) {
   });
 }
 
+// Web Audio output chain for mono fold-down preview and A/B crossfades. Elements
+// are routed through it the first time they play with either feature enabled.
+let monoPreview = false;
+let crossfadeMs = 0;
+let audioContext: AudioContext | null = null;
+const outputNodes = new WeakMap<HTMLAudioElement, GainNode>();
+const pendingPauses = new WeakMap<HTMLAudioElement, number>();
+
+const MIN_CROSSFADE_MS = 5;
+const MAX_CROSSFADE_MS = 30;
+
+function applyMono(node: GainNode) {
+  node.channelCount = monoPreview ? 1 : 2;
+  node.channelCountMode = monoPreview ? 'explicit' : 'max';
+  node.channelInterpretation = 'speakers';
+}
+
+function routeForPlayback(audio: HTMLAudioElement): GainNode | null {
+  if (!monoPreview && crossfadeMs === 0 && !outputNodes.has(audio)) return null;
+
+  audioContext ??= new AudioContext();
+  audioContext.resume().catch(() => {});
+
+  let node = outputNodes.get(audio);
+  if (!node) {
+    // A media element can only be connected once, so the node is kept for its lifetime
+    node = audioContext.createGain();
+    audioContext.createMediaElementSource(audio).connect(node);
+    node.connect(audioContext.destination);
+    outputNodes.set(audio, node);
+  }
+  applyMono(node);
+  return node;
+}
+
+export function setMonoPreview(enabled: boolean) {
+  monoPreview = enabled;
+  audioCache.forEach(({ audio }) => {
+    const node = outputNodes.get(audio);
+    if (node) applyMono(node);
+    else if (enabled && !audio.paused) routeForPlayback(audio);
+  });
+}
+
+/** Fade length used when starting and stopping playback; `null` or 0 disables it */
+export function setCrossfade(ms: number | null) {
+  crossfadeMs = ms ? Math.min(Math.max(ms, MIN_CROSSFADE_MS), MAX_CROSSFADE_MS) : 0;
+}
+
+function fadeIn(audio: HTMLAudioElement) {
+  const pending = pendingPauses.get(audio);
+  if (pending !== undefined) {
+    clearTimeout(pending);
+    pendingPauses.delete(audio);
+  }
+
+  const node = routeForPlayback(audio);
+  if (!node || !audioContext) return;
+
+  const now = audioContext.currentTime;
+  node.gain.cancelScheduledValues(now);
+  if (crossfadeMs > 0) {
+    node.gain.setValueAtTime(0, now);
+    node.gain.linearRampToValueAtTime(1, now + crossfadeMs / 1000);
+  } else {
+    node.gain.setValueAtTime(1, now);
+  }
+}
+
+// Ramp down before pausing so switching sides doesn't click
+function fadeOutAndPause(audio: HTMLAudioElement, onPaused?: () => void) {
+  const node = outputNodes.get(audio);
+  if (!node || !audioContext || crossfadeMs === 0 || audio.paused) {
+    audio.pause();
+    onPaused?.();
+    return;
+  }
+
+  const now = audioContext.currentTime;
+  node.gain.cancelScheduledValues(now);
+  node.gain.setValueAtTime(node.gain.value, now);
+  node.gain.linearRampToValueAtTime(0, now + crossfadeMs / 1000);
+
+  const timer = window.setTimeout(() => {
+    pendingPauses.delete(audio);
+    audio.pause();
+    onPaused?.();
+  }, crossfadeMs);
+  pendingPauses.set(audio, timer);
+}
+
+// Players currently producing sound; the backend keeps the system awake while any are
+let activePlayers = 0;
+
+function trackPlayback(delta: number) {
+  const wasActive = activePlayers > 0;
+  activePlayers += delta;
+  if (wasActive !== activePlayers > 0) {
+    invoke('set_playback_active', { active: activePlayers > 0 }).catch(() => {});
+  }
+}
+
+// Default audition window: playback starts at `start` and stops after `length` seconds
+let previewStart = 0;
+let previewLength: number | null = null;
+
+/** Audition only `lengthSecs` from `startSecs` onwards; `null` length plays files in full */
+export function setPreviewRegion(startSecs: number, lengthSecs: number | null) {
+  previewStart = Math.max(startSecs, 0);
+  previewLength = lengthSecs && lengthSecs > 0 ? lengthSecs : null;
+}
+
+export interface LoopRegion {
+  start: number;
+  end: number;
+}
+
+// Stay this far from the end so the element never fires `ended` while looping
+const LOOP_END_MARGIN_SECS = 0.05;
+
 export function useBufferedAudio(filePath: string | null) {
   const audioRef = useRef<HTMLAudioElement | null>(null);
   const [isPlaying, setIsPlaying] = useState(false);
@@ -89,6 +220,37 @@ export function useBufferedAudio(filePath: string | null) {
   const [error, setError] = useState<string | null>(null);
   const timeUpdateRef = useRef<(() => void) | null>(null);
   const endedRef = useRef<(() => void) | null>(null);
+  const loopRef = useRef<LoopRegion | null>(null);
+  // End of the preview window for the current play, if one applies
+  const stopAtRef = useRef<number | null>(null);
+
+  useEffect(() => {
+    if (!isPlaying) return;
+    trackPlayback(1);
+    return () => trackPlayback(-1);
+  }, [isPlaying]);
+
+  // `timeupdate` fires only a few times per second, too coarse for a tight loop
+  useEffect(() => {
+    if (!isPlaying) return;
+    let frame = 0;
+    const tick = () => {
+      const audio = audioRef.current;
+      const region = loopRef.current;
+      if (audio && region) {
+        const end = Math.min(region.end, audio.duration - LOOP_END_MARGIN_SECS);
+        if (audio.currentTime >= end) audio.currentTime = region.start;
+      } else if (audio && stopAtRef.current !== null && audio.currentTime >= stopAtRef.current) {
+        stopAtRef.current = null;
+        fadeOutAndPause(audio);
+        setIsPlaying(false);
+        return;
+      }
+      frame = requestAnimationFrame(tick);
+    };
+    frame = requestAnimationFrame(tick);
+    return () => cancelAnimationFrame(frame);
+  }, [isPlaying]);
 
   useEffect(() => {
     if (!filePath) {
@@ -162,10 +324,20 @@ export function useBufferedAudio(filePath: string | null) {
     };
   }, [isLoaded]);
 
-  const play = useCallback(() => {
+  // Without `from`, playback starts at the preview region (the beginning by default)
+  const play = useCallback((from?: number) => {
     if (audioRef.current && isLoaded) {
-      audioRef.current.pause();
-      audioRef.current.currentTime = 0;
+      const audio = audioRef.current;
+      const requested = from ?? previewStart;
+      const start = Math.min(
+        requested,
+        Number.isFinite(audio.duration) ? audio.duration : requested
+      );
+      stopAtRef.current = previewLength !== null ? start + previewLength : null;
+      audio.pause();
+      audio.currentTime = start;
+      setCurrentTime(start);
+      fadeIn(audio);
       audioRef.current.play().catch(err => {
         console.error('Play error:', err);
       });
@@ -175,7 +347,7 @@ export function useBufferedAudio(filePath: string | null) {
 
   const pause = useCallback(() => {
     if (audioRef.current) {
-      audioRef.current.pause();
+      fadeOutAndPause(audioRef.current);
       setIsPlaying(false);
     }
   }, []);
@@ -190,13 +362,22 @@ export function useBufferedAudio(filePath: string | null) {
 
   const stop = useCallback(() => {
     if (audioRef.current) {
-      audioRef.current.pause();
-      audioRef.current.currentTime = 0;
+      const audio = audioRef.current;
+      fadeOutAndPause(audio, () => {
+        audio.currentTime = 0;
+      });
       setIsPlaying(false);
       setCurrentTime(0);
     }
   }, []);
 
+  const setLoop = useCallback((region: LoopRegion | null) => {
+    loopRef.current = region;
+  }, []);
+
+  // Read straight from the element; `currentTime` state lags behind by a timeupdate
+  const getPosition = useCallback(() => audioRef.current?.currentTime ?? 0, []);
+
   const seek = useCallback((time: number) => {
     if (audioRef.current) {
       audioRef.current.currentTime = time;
@@ -215,5 +396,7 @@ export function useBufferedAudio(filePath: string | null) {
     toggle,
     stop,
     seek,
+    getPosition,
+    setLoop,
   };
 }
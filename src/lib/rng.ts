@@ -0,0 +1,7 @@
+// Seeds for the backend's scheduler, which draws every shuffle, pairing and
+// calibration match from them, so the same pool and seed always produce the same
+// schedule (for comparing rating systems and reproducing bug reports)
+
+export function randomSeed(): number {
+  return crypto.getRandomValues(new Uint32Array(1))[0];
+}
@@ -1,34 +1,366 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { Sample, TournamentState } from '../types';
+import type {
+  AbletonExport,
+  AuditEntry,
+  CacheUsage,
+  CalibrationReport,
+  CommandError,
+  ConflictMerge,
+  DeepLinkAction,
+  ExportDefaults,
+  ExportFilter,
+  LoopSlices,
+  ManifestImport,
+  MatchRecord,
+  PlaceMode,
+  PlayerExport,
+  Preset,
+  RatingImport,
+  ReelReport,
+  Sample,
+  SampleQuery,
+  ScanEstimate,
+  ScanOptions,
+  ScanOutcome,
+  SessionChange,
+  SessionSnapshot,
+  SessionUpdate,
+  Settings,
+  ShareInfo,
+  Statistics,
+  TemplateStart,
+  TournamentState,
+  Upcoming,
+  VoteRecord,
+} from '../types';
 
-export async function scanDirectory(directory: string): Promise<Sample[]> {
-  return invoke<Sample[]>('scan_directory', { directory });
+// Commands fail with plain strings or structured `CommandError`s
+export function errorMessage(err: unknown): string {
+  if (typeof err === 'object' && err !== null && 'message' in err) {
+    return String((err as CommandError).message);
+  }
+  return String(err);
 }
 
-export async function saveProgress(
-  state: TournamentState,
-  filePath: string
-): Promise<void> {
-  return invoke('save_progress', { state, filePath });
+export async function scanDirectory(
+  directory: string,
+  options?: ScanOptions
+): Promise<ScanOutcome> {
+  return invoke<ScanOutcome>('scan_directory', { directory, options });
+}
+
+export async function estimateScan(
+  directory: string,
+  options?: ScanOptions
+): Promise<ScanEstimate> {
+  return invoke<ScanEstimate>('estimate_scan', { directory, options });
+}
+
+export async function searchSamples(samples: Sample[], query: SampleQuery): Promise<Sample[]> {
+  return invoke<Sample[]>('search_samples', { samples, query });
+}
+
+export async function getStatistics(
+  samples: Sample[],
+  history: MatchRecord[] = [],
+  votes: VoteRecord[] = []
+): Promise<Statistics> {
+  return invoke<Statistics>('get_statistics', { samples, history, votes });
+}
+
+export async function getCalibration(sessionId: string): Promise<CalibrationReport | null> {
+  return invoke<CalibrationReport | null>('get_calibration', { sessionId });
+}
+
+export async function findDuplicates(sessionId: string): Promise<string[][]> {
+  return invoke<string[][]>('find_duplicates', { sessionId });
+}
+
+export async function listPresets(): Promise<Preset[]> {
+  return invoke<Preset[]>('list_presets');
+}
+
+export async function savePreset(preset: Preset): Promise<void> {
+  return invoke('save_preset', { preset });
+}
+
+export async function deletePreset(name: string): Promise<void> {
+  return invoke('delete_preset', { name });
+}
+
+export async function applyPreset(name: string): Promise<Preset> {
+  return invoke<Preset>('apply_preset', { name });
+}
+
+// Like savePreset, but the rating plugin is taken from the current settings
+export async function saveTemplate(name: string, setup: Preset): Promise<Preset> {
+  return invoke<Preset>('save_template', { name, setup });
+}
+
+export async function newFromTemplate(name: string, directory: string): Promise<TemplateStart> {
+  return invoke<TemplateStart>('new_from_template', { name, directory });
+}
+
+export async function peekUpcoming(sessionId: string, n: number): Promise<Upcoming[]> {
+  return invoke<Upcoming[]>('peek_upcoming', { sessionId, n });
+}
+
+export async function listLanguages(): Promise<string[]> {
+  return invoke<string[]>('list_languages');
+}
+
+export async function getSettings(): Promise<Settings> {
+  return invoke<Settings>('get_settings');
+}
+
+export async function saveSettings(newSettings: Settings): Promise<void> {
+  return invoke('save_settings', { newSettings });
+}
+
+// Shared drives: the backend refuses every copy, move and discard while this is on
+export async function setReadOnly(enabled: boolean): Promise<void> {
+  return invoke('set_read_only', { enabled });
+}
+
+export async function getOnset(filePath: string): Promise<number> {
+  return invoke<number>('get_onset', { filePath });
+}
+
+export async function describeSample(path: string): Promise<string> {
+  return invoke<string>('describe_sample', { path });
+}
+
+export async function getLoopSlices(
+  pathA: string,
+  pathB: string,
+  bars?: number
+): Promise<LoopSlices> {
+  return invoke<LoopSlices>('get_loop_slices', { pathA, pathB, bars });
+}
+
+// The backend keeps the authoritative copy of the open tournament under a session ID;
+// a tournament another window has open comes back as that session has it
+export async function openSession(state: TournamentState): Promise<SessionSnapshot> {
+  return invoke<SessionSnapshot>('open_session', { state });
+}
+
+export async function getSession(id: string): Promise<SessionSnapshot> {
+  return invoke<SessionSnapshot>('get_session', { id });
+}
+
+// `revision` is the one the change was made on; fails with a `conflict` error if the
+// session changed elsewhere meanwhile
+export async function changeSession(
+  id: string,
+  revision: number,
+  change: SessionChange
+): Promise<SessionUpdate> {
+  return invoke<SessionUpdate>('change_session', { id, revision, change });
+}
+
+export async function closeSession(id: string): Promise<void> {
+  return invoke('close_session', { id });
+}
+
+// Saves the session's backend copy; nothing but the ID crosses the bridge
+export async function saveSession(id: string, filePath: string): Promise<void> {
+  return invoke('save_session', { id, filePath });
 }
 
 export async function loadProgress(filePath: string): Promise<TournamentState> {
   return invoke<TournamentState>('load_progress', { filePath });
 }
 
+// Dropbox and Syncthing conflicted copies sitting next to a save
+export async function findConflictedSaves(filePath: string): Promise<string[]> {
+  return invoke<string[]>('find_conflicted_saves', { filePath });
+}
+
+// Merges every copy's comparisons into the first path (the old content goes to the backups)
+export async function resolveConflictedSaves(paths: string[]): Promise<ConflictMerge> {
+  return invoke<ConflictMerge>('resolve_conflicted_saves', { paths });
+}
+
 export async function exportResults(
   samples: Sample[],
   filePath: string,
-  minScore: number
+  filter: ExportFilter,
+  writeManifest = false
+): Promise<void> {
+  return invoke('export_results', { samples, filePath, filter, writeManifest });
+}
+
+// Winners as a playlist folder a player library watches, optionally with the files copied in
+export async function exportToPlayer(
+  samples: Sample[],
+  destDir: string,
+  playlistName: string,
+  minScore: number,
+  copyFiles: boolean
+): Promise<PlayerExport> {
+  return invoke<PlayerExport>('export_to_player', {
+    samples,
+    options: {
+      dest_dir: destDir,
+      playlist_name: playlistName,
+      min_score: minScore,
+      copy_files: copyFiles,
+    },
+  });
+}
+
+// Null when Live's User Library isn't in its default place
+export async function abletonUserLibrary(): Promise<string | null> {
+  return invoke<string | null>('ableton_user_library');
+}
+
+// Winners into the User Library's Samples folder, prefixed by rank and rated in XMP sidecars
+export async function exportToAbleton(
+  samples: Sample[],
+  libraryDir: string | null,
+  collectionName: string,
+  minScore: number,
+  mode: PlaceMode
+): Promise<AbletonExport> {
+  return invoke<AbletonExport>('export_to_ableton', {
+    samples,
+    options: {
+      library_dir: libraryDir,
+      collection_name: collectionName,
+      min_score: minScore,
+      mode,
+    },
+  });
+}
+
+// Standings, per-round charts and pack summaries as an A4 PDF
+export async function exportPdfReport(sessionId: string, filePath: string): Promise<void> {
+  return invoke('export_pdf_report', { sessionId, filePath });
+}
+
+// PNG card of the top `topN` (at most 10) for posting; resolves to the written path
+export async function renderSummaryImage(
+  samples: Sample[],
+  topN: number,
+  filePath: string,
+  title?: string
+): Promise<string> {
+  return invoke<string>('render_summary_image', { samples, topN, filePath, title });
+}
+
+// Serves the standings (and optionally audio) on a temporary URL; starting again replaces it
+export async function shareResultsLink(
+  samples: Sample[],
+  includeAudio: boolean,
+  lan: boolean,
+  ttlMinutes?: number
+): Promise<ShareInfo> {
+  return invoke<ShareInfo>('share_results_link', { samples, includeAudio, lan, ttlMinutes });
+}
+
+export async function stopResultsShare(): Promise<void> {
+  return invoke('stop_results_share');
+}
+
+// The reel gets cue-point chapters and a .cue sheet marking each sample's start and rank
+export async function renderReel(
+  samples: Sample[],
+  filePath: string,
+  minScore: number,
+  gapSecs?: number
+): Promise<ReelReport> {
+  return invoke<ReelReport>('render_reel', { samples, filePath, minScore, gapSecs });
+}
+
+export async function exportNdjson(
+  samples: Sample[],
+  filePath: string,
+  minScore: number,
+  writeManifest = false
 ): Promise<void> {
-  return invoke('export_results', { samples, filePath, minScore });
+  return invoke('export_ndjson', { samples, filePath, minScore, writeManifest });
+}
+
+// Rebuild a pool from an export manifest, optionally keeping the exported scores
+export async function importManifest(
+  path: string,
+  seedScores = false
+): Promise<ManifestImport> {
+  return invoke<ManifestImport>('import_manifest', { path, seedScores });
+}
+
+// Star ratings from a Music/iTunes library XML export become seed scores
+export async function importMusicLibrary(
+  samples: Sample[],
+  libraryPath: string,
+  pointsPerStar?: number
+): Promise<RatingImport> {
+  return invoke<RatingImport>('import_music_library', { samples, libraryPath, pointsPerStar });
+}
+
+// Same from the rating tags in the files themselves
+export async function importTagRatings(
+  samples: Sample[],
+  pointsPerStar?: number
+): Promise<RatingImport> {
+  return invoke<RatingImport>('import_tag_ratings', { samples, pointsPerStar });
+}
+
+export async function exportParquet(samples: Sample[], filePath: string): Promise<void> {
+  return invoke('export_parquet', { samples, filePath });
 }
 
 export async function getAudioFileUrl(filePath: string): Promise<string> {
   return invoke<string>('get_audio_file_url', { filePath });
 }
 
+export async function getRecentLogs(
+  limit?: number
+): Promise<{ level: string; directory: string; lines: string[] }> {
+  return invoke('get_recent_logs', { limit });
+}
+
+export async function setLogLevel(level: string): Promise<void> {
+  return invoke('set_log_level', { level });
+}
+
+// Most recent entries first
+// Newest first; `offset` skips that many entries to page back through older ones
+export async function getAuditLog(limit?: number, offset?: number): Promise<AuditEntry[]> {
+  return invoke<AuditEntry[]>('get_audit_log', { limit: limit ?? null, offset: offset ?? null });
+}
+
+// Normalized levels for drawing, cached on disk by the backend
+export async function getWaveformPeaks(path: string, buckets: number): Promise<number[]> {
+  return invoke<number[]>('get_waveform_peaks', { path, buckets });
+}
+
+export async function getThumbnailCacheUsage(): Promise<CacheUsage> {
+  return invoke<CacheUsage>('get_thumbnail_cache_usage');
+}
+
+// Returns the bytes freed
+export async function clearAnalysisCache(): Promise<number> {
+  return invoke<number>('clear_analysis_cache');
+}
+
+export async function generateDiagnosticsBundle(
+  destPath: string,
+  sessionId?: string | null
+): Promise<string> {
+  return invoke<string>('generate_diagnostics_bundle', { destPath, sessionId: sessionId ?? null });
+}
+
+export async function resetWindowState(): Promise<void> {
+  return invoke('reset_window_state');
+}
+
+export async function takePendingDeepLinks(): Promise<DeepLinkAction[]> {
+  return invoke<DeepLinkAction[]>('take_pending_deep_links');
+}
+
 const LAST_SESSION_KEY = 'vs1_last_session_path';
+const LAST_EXPORT_DEFAULTS_KEY = 'vs1_last_export_defaults';
 
 export function getLastSessionPath(): string | null {
   return localStorage.getItem(LAST_SESSION_KEY);
@@ -37,3 +369,16 @@ export function getLastSessionPath(): string | null {
 export function setLastSessionPath(path: string): void {
   localStorage.setItem(LAST_SESSION_KEY, path);
 }
+
+// Export controls as last left on the results screen; saved into new templates
+export function getLastExportDefaults(): ExportDefaults {
+  try {
+    return JSON.parse(localStorage.getItem(LAST_EXPORT_DEFAULTS_KEY) ?? '{}');
+  } catch {
+    return {};
+  }
+}
+
+export function setLastExportDefaults(defaults: ExportDefaults): void {
+  localStorage.setItem(LAST_EXPORT_DEFAULTS_KEY, JSON.stringify(defaults));
+}
@@ -1,104 +1,71 @@
-import type { Sample, TournamentState } from '../types';
+import type { Sample, SessionUpdate, TournamentOptions, TournamentState } from '../types';
+import { randomSeed } from './rng';
 
+// Left unpaired; the backend pairs the first round once the session is open
 export function createTournament(
   samples: Sample[],
   sourceDirectory: string,
-  advancementThreshold: number = 0.5
+  advancementThreshold: number = 0.5,
+  options: TournamentOptions = {}
 ): TournamentState {
-  const state: TournamentState = {
-    samples: samples.map(s => ({ ...s, score: 0, comparisons: 0 })),
+  return {
+    samples: samples.map(s => ({
+      ...s,
+      score: options.keepScores ? s.score : 0,
+      comparisons: 0,
+    })),
     current_round: 1,
     comparisons_this_round: [],
     current_comparison_index: 0,
     advancement_threshold: advancementThreshold,
     source_directory: sourceDirectory,
+    seed: options.seed ?? randomSeed(),
+    threshold_schedule: options.thresholdSchedule ?? [],
+    adaptive_cutoff: options.adaptiveCutoff ?? null,
+    scan_sampling: options.scanSampling ?? null,
+    export_defaults: options.exportDefaults ?? null,
+    score_carry: options.scoreCarry ?? 'raw',
+    bye_policy: options.byePolicy ?? 'fair',
+    byes: [],
+    allow_rematches: options.allowRematches ?? false,
   };
-
-  state.comparisons_this_round = generatePairings(state.samples);
-  return state;
 }
 
-export function generatePairings(samples: Sample[]): [number, number][] {
-  if (samples.length < 2) return [];
-
-  const indexed = samples.map((s, i) => ({ sample: s, index: i }));
-  indexed.sort((a, b) => b.sample.score - a.sample.score);
-
-  const pairings: [number, number][] = [];
-  const used = new Set<number>();
-
-  for (let i = 0; i < indexed.length - 1; i++) {
-    if (used.has(indexed[i].index)) continue;
-
-    for (let j = i + 1; j < indexed.length; j++) {
-      if (used.has(indexed[j].index)) continue;
-
-      pairings.push([indexed[i].index, indexed[j].index]);
-      used.add(indexed[i].index);
-      used.add(indexed[j].index);
-      break;
-    }
-  }
-
-  return shuffleArray(pairings);
+export function referenceIndex(state: TournamentState): number {
+  const path = state.reference?.path;
+  return path ? state.samples.findIndex(s => s.path === path) : -1;
 }
 
-function shuffleArray<T>(array: T[]): T[] {
-  const result = [...array];
-  for (let i = result.length - 1; i > 0; i--) {
-    const j = Math.floor(Math.random() * (i + 1));
-    [result[i], result[j]] = [result[j], result[i]];
-  }
-  return result;
+export function isCalibrationPairing(state: TournamentState): boolean {
+  const pairing = state.comparisons_this_round[state.current_comparison_index];
+  const reference = referenceIndex(state);
+  return !!pairing && reference >= 0 && pairing.includes(reference);
 }
 
-export function recordComparison(
-  state: TournamentState,
-  winnerIndex: number
-): TournamentState {
-  const newState = { ...state, samples: [...state.samples] };
-
-  newState.samples[winnerIndex] = {
-    ...newState.samples[winnerIndex],
-    score: newState.samples[winnerIndex].score + 1,
-    comparisons: newState.samples[winnerIndex].comparisons + 1,
-  };
-
-  const currentPairing = state.comparisons_this_round[state.current_comparison_index];
-  const loserIndex = currentPairing[0] === winnerIndex ? currentPairing[1] : currentPairing[0];
-  newState.samples[loserIndex] = {
-    ...newState.samples[loserIndex],
-    comparisons: newState.samples[loserIndex].comparisons + 1,
-  };
-
-  newState.current_comparison_index = state.current_comparison_index + 1;
-
-  return newState;
+// Names the comparison on screen; votes carry it so the backend can refuse one cast
+// on a pair that has moved on
+export function pairId(state: TournamentState): string {
+  return `${state.current_round}-${state.current_comparison_index}`;
 }
 
-export function eliminateBoth(state: TournamentState): TournamentState {
-  const currentPairing = state.comparisons_this_round[state.current_comparison_index];
-  if (!currentPairing) return state;
-
-  const [indexA, indexB] = currentPairing;
-
-  const newSamples = state.samples.map((sample, i) => {
-    if (i === indexA || i === indexB) {
-      return { ...sample, score: -1000, comparisons: sample.comparisons + 1 };
-    }
-    return sample;
-  });
+// Bring a window's copy up to date after an accepted change
+export function applyUpdate(state: TournamentState, update: SessionUpdate): TournamentState {
+  if (update.state) return update.state;
+  const patch = update.patch;
+  if (!patch) return state;
 
-  const newComparisons = state.comparisons_this_round.filter((pair, idx) => {
-    if (idx <= state.current_comparison_index) return true;
-    return !pair.includes(indexA) && !pair.includes(indexB);
+  const samples = [...state.samples];
+  patch.samples.forEach(([index, sample]) => {
+    samples[index] = sample;
   });
-
   return {
     ...state,
-    samples: newSamples,
-    comparisons_this_round: newComparisons,
-    current_comparison_index: state.current_comparison_index + 1,
+    samples,
+    current_comparison_index: patch.current_comparison_index,
+    comparisons_this_round: patch.comparisons_this_round ?? state.comparisons_this_round,
+    history: [...(state.history ?? []), ...patch.history],
+    votes: [...(state.votes ?? []), ...patch.votes],
+    reference: patch.reference,
   };
 }
 
@@ -106,27 +73,6 @@ export function isRoundComplete(state: TournamentState): boolean {
   return state.current_comparison_index >= state.comparisons_this_round.length;
 }
 
-export function advanceToNextRound(state: TournamentState): TournamentState {
-  const sortedSamples = [...state.samples].sort((a, b) => b.score - a.score);
-
-  const keepCount = Math.max(
-    2,
-    Math.ceil(sortedSamples.length * state.advancement_threshold)
-  );
-
-  const advancingSamples = sortedSamples.slice(0, keepCount);
-
-  const newState: TournamentState = {
-    ...state,
-    samples: advancingSamples,
-    current_round: state.current_round + 1,
-    current_comparison_index: 0,
-    comparisons_this_round: generatePairings(advancingSamples),
-  };
-
-  return newState;
-}
-
 export function getCurrentPairing(state: TournamentState): [Sample, Sample] | null {
   if (state.current_comparison_index >= state.comparisons_this_round.length) {
     return null;
@@ -160,7 +106,10 @@ export function getProgress(state: TournamentState): {
     ? (state.current_comparison_index / totalComparisonsThisRound) * 100
     : 100;
 
-  const activeSamples = state.samples.filter(s => s.score > -1000).length;
+  const activeSamples = state.samples.filter(
+    s => s.score > -1000 && s.path !== state.reference?.path && s.pin !== 'eliminate' &&
+      !s.benched
+  ).length;
 
   return {
     currentComparison: Math.min(state.current_comparison_index + 1, totalComparisonsThisRound),
@@ -171,10 +120,17 @@ export function getProgress(state: TournamentState): {
   };
 }
 
+// Keepers first, rejects last, matching the backend's standings
+function pinRank(sample: Sample): number {
+  if (sample.pin === 'always_advance') return 0;
+  return sample.pin === 'eliminate' ? 2 : 1;
+}
+
 export function getSortedResults(state: TournamentState): Sample[] {
   return [...state.samples]
-    .filter(s => s.score > -1000)
+    .filter(s => s.score > -1000 && s.path !== state.reference?.path)
     .sort((a, b) => {
+      if (pinRank(a) !== pinRank(b)) return pinRank(a) - pinRank(b);
       if (b.score !== a.score) return b.score - a.score;
       const aRate = a.comparisons > 0 ? a.score / a.comparisons : 0;
       const bRate = b.comparisons > 0 ? b.score / b.comparisons : 0;
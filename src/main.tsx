@@ -1,9 +1,21 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "./App";
+import { LeaderboardWindow } from "./components/LeaderboardWindow";
+import { MiniWindow } from "./components/MiniWindow";
+import "./App.css";
+
+// Secondary windows load the same bundle with a `view` query parameter
+const view = new URLSearchParams(window.location.search).get("view");
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    <App />
+    {view === "mini" ? (
+      <MiniWindow />
+    ) : view === "leaderboard" ? (
+      <LeaderboardWindow />
+    ) : (
+      <App />
+    )}
   </React.StrictMode>,
 );
@@ -3,6 +3,333 @@ export interface Sample {
   filename: string;
   score: number;
   comparisons: number;
+  provisional?: boolean;
+  online_only?: boolean;
+  analysis?: Analysis | null;
+  variants?: string[];
+  pack?: string | null;
+  instrument?: string | null;
+  channels?: number | null;
+  pin?: Pin | null;
+  // Set aside: not paired and not cut, score and history kept
+  benched?: boolean;
+  uncertainty?: number;
+  tags?: string[];
+}
+
+export type Pin = 'always_advance' | 'eliminate';
+
+export interface PackStats {
+  pack: string | null;
+  samples: number;
+  eliminated: number;
+  mean_score: number;
+  best_score: number;
+  win_rate: number;
+  top_ten: number;
+}
+
+// One collaborative voter next to the majority; preferences are shares of picks (0.5 = none)
+export interface VoterStats {
+  voter_id: string;
+  name: string;
+  ballots: number;
+  skips: number;
+  agreement: number;
+  prefers_louder: number | null;
+  prefers_brighter: number | null;
+  prefers_longer: number | null;
+  ranking_correlation: number | null;
+  favourites: string[];
+}
+
+// `a` and `b` are voter IDs, since two voters may share a name
+export interface VoterAgreement {
+  a: string;
+  b: string;
+  a_name: string;
+  b_name: string;
+  shared: number;
+  rate: number;
+}
+
+export interface Statistics {
+  samples: number;
+  eliminated: number;
+  appearances: number;
+  packs: PackStats[];
+  upsets: Upset[];
+  // Empty unless at least two voters cast ballots
+  voters?: VoterStats[];
+  voter_agreement?: VoterAgreement[];
+}
+
+export type QualityFlag = 'clipping' | 'inter_sample_clipping' | 'dc_offset';
+
+export interface Analysis {
+  sample_rate: number;
+  channels: number;
+  duration_secs: number;
+  peak_dbfs: number;
+  true_peak_dbfs: number;
+  dc_offset: number;
+  flags: QualityFlag[];
+  leading_silence_secs: number;
+  trailing_silence_secs: number;
+  silence_ratio: number;
+  noise_floor_dbfs: number;
+  stereo_correlation: number;
+  stereo_width: number;
+  mono_loss_db: number;
+  spectral_centroid_hz?: number;
+  spectral_rolloff_hz?: number;
+  spectral_flatness?: number;
+  root_hz?: number | null;
+  root_note?: string | null;
+  tempo_bpm?: number | null;
+}
+
+export interface LoopSlice {
+  start_secs: number;
+  end_secs: number;
+  bpm: number | null;
+}
+
+export interface LoopSlices {
+  a: LoopSlice;
+  b: LoopSlice;
+}
+
+export type SortKey = 'score' | 'brightness' | 'rolloff' | 'flatness' | 'duration';
+
+// Which samples the list export includes; unset criteria let everything through
+// Guessed kind of sound, from the analysis
+export type Category =
+  | 'loop'
+  | 'sustained_tonal'
+  | 'bass_or_kick'
+  | 'pitched_one_shot'
+  | 'hi_hat_or_cymbal'
+  | 'noise_or_texture'
+  | 'low_percussion'
+  | 'percussive_one_shot';
+
+export interface ExportFilter {
+  min_score?: number | null;
+  max_score?: number | null;
+  // 1-based positions among the samples passing the other criteria; max_rank 25 = top 25
+  min_rank?: number | null;
+  max_rank?: number | null;
+  category?: Category | null;
+  // Samples must carry every one of these tags; case is ignored
+  tags?: string[];
+  pack?: string | null;
+  folder?: string | null;
+  min_duration_secs?: number | null;
+  max_duration_secs?: number | null;
+}
+
+// One exported file in an export manifest (`<export>.manifest.json`, or
+// vs1-manifest.json inside a copy export)
+export interface ManifestEntry {
+  source: string;
+  // Relative path for copies, entry name for archives, listed path otherwise
+  destination: string;
+  // 0 for eliminated samples
+  rank: number;
+  score: number;
+  size: number;
+  sha256: string;
+  verified: boolean;
+  renamed_from?: string;
+  timestamp?: number;
+}
+
+export interface ExportManifest {
+  kind: 'copy' | 'archive' | 'playlist' | 'ndjson';
+  created: number;
+  files: ManifestEntry[];
+}
+
+// A pool rebuilt from an export manifest, shaped like a scan result
+export interface ManifestImport {
+  samples: Sample[];
+  directory: string;
+  report: ScanReport;
+}
+
+// One line of the append-only audit log of what the app touched
+export interface AuditEntry {
+  timestamp: number;
+  kind: 'grant' | 'denial' | 'write' | 'move' | 'launch';
+  // The path concerned, or the program for launches
+  subject: string;
+  // Where a move or copy came from, or the arguments of a launch
+  detail?: string;
+}
+
+// An open session's backend copy and the revision it is at
+export interface SessionSnapshot {
+  id: string;
+  revision: number;
+  state: TournamentState;
+}
+
+// Every change to a session goes through the backend; votes name the comparison
+// (`<round>-<index>`) they were cast on
+export type SessionChange =
+  | { kind: 'start_round' }
+  | { kind: 'vote'; pair_id: string; winner: number; decision_ms?: number; ballots?: Ballot[] }
+  | { kind: 'eliminate_both'; pair_id: string; ballots?: Ballot[] }
+  | { kind: 'pin'; path: string; pin: Pin | null }
+  | { kind: 'bench'; path: string; benched: boolean }
+  | { kind: 'tag'; path: string; tags: string[] }
+  | { kind: 'reorder'; indices: number[] }
+  | { kind: 'reference'; path: string | null; interval?: number }
+  | { kind: 'merge_duplicates'; keep: string; drop: string[] }
+  | { kind: 'advance_round' }
+  | { kind: 'redemption'; count: number };
+
+// What a vote changed, instead of the whole state
+export interface StatePatch {
+  // Replaced samples, by index
+  samples: [number, Sample][];
+  current_comparison_index: number;
+  // Only when comparisons still to come were dropped
+  comparisons_this_round?: [number, number][];
+  // Appended to `history` and `votes`
+  history: MatchRecord[];
+  votes: VoteRecord[];
+  reference: Reference | null;
+}
+
+// An accepted change: a patch for votes, the whole state for everything else
+export interface SessionUpdate {
+  revision: number;
+  patch?: StatePatch;
+  state?: TournamentState;
+}
+
+// Sent as `session://changed` after every accepted change; without a patch the
+// state has to be fetched with `getSession`
+export interface SessionChanged {
+  id: string;
+  revision: number;
+  patch: StatePatch | null;
+}
+
+// Structured error from commands that report what went wrong, not just a message
+export interface CommandError {
+  kind:
+    | 'path_not_allowed'
+    | 'file_not_found'
+    | 'invalid_argument'
+    | 'rate_limited'
+    | 'program_missing'
+    | 'read_only'
+    | 'conflict'
+    | 'failed';
+  message: string;
+}
+
+export interface SampleQuery {
+  text?: string | null;
+  min_score?: number | null;
+  max_score?: number | null;
+  sort?: SortKey | null;
+  descending?: boolean;
+  limit?: number | null;
+}
+
+export type ReplayFrom = 'start' | 'onset' | 'last_position';
+
+export interface Settings {
+  hooks: unknown[];
+  plugin: string | null;
+  log_level: string | null;
+  replay_from?: ReplayFrom;
+  crossfade_ms?: number | null;
+  output_ceiling_dbfs?: number | null;
+  preview_secs?: number | null;
+  preview_start_secs?: number;
+  stale_after_days?: number | null;
+  language?: string | null;
+  read_only?: boolean;
+  analysis_throttle?: AnalysisThrottle;
+  // Megabytes of cached waveforms and spectrograms; null uses 256
+  thumbnail_cache_mb?: number | null;
+}
+
+export interface CacheUsage {
+  bytes: number;
+  files: number;
+  limit_bytes: number;
+}
+
+// Limits for background analysis, so it doesn't spin up the fans while listening
+export interface AnalysisThrottle {
+  // null uses every core
+  max_threads?: number | null;
+  // Share of each worker's time spent working (10-100)
+  cpu_percent?: number | null;
+  pause_on_battery?: boolean;
+}
+
+export interface ScanOptions {
+  include_archives?: boolean;
+  include_video?: boolean;
+  analyze?: boolean;
+  max_silence_ratio?: number | null;
+  group_variations?: boolean;
+  include_instruments?: boolean;
+  // Folder levels to descend (1 = only the selected folder); null scans everything
+  max_depth?: number | null;
+  include_hidden?: boolean;
+  // Random subset of at most this many files; the seed picks which
+  sample_limit?: number | null;
+  sample_seed?: number | null;
+}
+
+export interface SkippedEntry {
+  path: string;
+  reason: 'unreadable' | 'permission_denied' | 'symlink' | 'bad_archive';
+}
+
+// What a scan passed over and why
+export interface ScanReport {
+  files: number;
+  unreadable: number;
+  permission_denied: number;
+  non_audio: number;
+  symlinks: number;
+  bad_archives: number;
+  hidden: number;
+  too_silent: number;
+  sampling?: ScanSampling | null;
+  // The first problem paths; the counts cover everything
+  skipped: SkippedEntry[];
+}
+
+// Rough size of a scan, counted from file names alone
+export interface ScanEstimate {
+  samples: number;
+  archives: number;
+  instruments: number;
+  // Samples after the sampling cap
+  would_add: number;
+}
+
+// How a capped scan picked its subset
+export interface ScanSampling {
+  limit: number;
+  seed: number;
+  // Matching files before sampling
+  population: number;
+}
+
+export interface ScanOutcome {
+  samples: Sample[];
+  report: ScanReport;
 }
 
 export interface TournamentState {
@@ -12,8 +339,206 @@ export interface TournamentState {
   current_comparison_index: number;
   advancement_threshold: number;
   source_directory: string;
+  votes?: VoteRecord[];
+  reference?: Reference | null;
+  eliminated?: Sample[];
+  seed?: number;
+  // Shared by every window that opens the tournament; given out by the backend
+  id?: string;
+  threshold_schedule?: number[];
+  adaptive_cutoff?: number | null;
+  history?: MatchRecord[];
+  round_summaries?: RoundSummary[];
+  scan_sampling?: ScanSampling | null;
+  // Export settings of the template the session started from
+  export_defaults?: ExportDefaults | null;
+  score_carry?: ScoreCarry;
+  bye_policy?: ByePolicy;
+  // Every bye given, oldest first
+  byes?: Bye[];
+  // Off, pairings avoid matchups already decided until no other opponent is left
+  allow_rematches?: boolean;
 }
 
+// What advancing samples take into the next round: their raw score, the share of
+// this round's matches they won, or nothing
+export type ScoreCarry = 'raw' | 'percentage' | 'reset';
+
+// Who sits out an odd-sized round: the sample with the fewest comparisons (never
+// twice running) or whoever the pairing leaves over
+export type ByePolicy = 'fair' | 'lowest';
+
+export interface Bye {
+  round: number;
+  path: string;
+  // Comparisons it had played when it sat out
+  comparisons: number;
+}
+
+// Conflicted copies of a save folded into one; unapplied comparisons name samples since cut
+export interface ConflictMerge {
+  path: string;
+  copies: number;
+  added: number;
+  unapplied: number;
+  state: TournamentState;
+}
+
+// Temporary link to the standings page; expires_at is Unix seconds
+export interface ShareInfo {
+  url: string;
+  expires_at: number;
+  qr_svg: string;
+}
+
+// Winners rendered back to back into one WAV, with a .cue sheet beside it
+export interface ReelReport {
+  tracks: number;
+  duration_secs: number;
+  cue_path: string;
+  failed: { source: string; error: string }[];
+}
+
+// How winners are put into Ableton's User Library
+export type PlaceMode = 'copy' | 'symlink';
+
+// Folder inside the User Library the winners went to, named by rank
+export interface AbletonExport {
+  folder: string;
+  placed: number;
+  failed: { source: string; error: string }[];
+}
+
+// A playlist folder for a player's watched folder; skipped counts archive entries left out
+export interface PlayerExport {
+  folder: string;
+  playlist_path: string;
+  tracks: number;
+  skipped: number;
+  failed: { source: string; error: string }[];
+}
+
+// Scores seeded from earlier ratings; unmatched_rows counts ratings that found no sample
+export interface RatingImport {
+  samples: Sample[];
+  matched: number;
+  unmatched_rows: number;
+}
+
+// A scheduled comparison; position 0 is the one on screen
+export interface Upcoming {
+  position: number;
+  a: Sample;
+  b: Sample;
+  calibration: boolean;
+}
+
+// One decided comparison, with both scores as they were before it
+export interface MatchRecord {
+  round: number;
+  winner: string;
+  loser: string;
+  winner_score: number;
+  loser_score: number;
+  decision_ms?: number | null;
+}
+
+// A match the lower-rated sample won; `expected` is the winner's chance going in
+export interface Upset extends MatchRecord {
+  expected: number;
+}
+
+// Built by the backend when a round finishes (also sent as `round://complete`)
+export interface RoundSummary {
+  round: number;
+  advancers: string[];
+  eliminated: string[];
+  upsets: Upset[];
+  closest: MatchRecord[];
+}
+
+// Optional setup for a new tournament; omitted values use the defaults
+export interface TournamentOptions {
+  seed?: number;
+  scanSampling?: ScanSampling | null;
+  thresholdSchedule?: number[];
+  adaptiveCutoff?: number | null;
+  // Start from the samples' scores (seeded from a manifest) instead of zero
+  keepScores?: boolean;
+  exportDefaults?: ExportDefaults | null;
+  scoreCarry?: ScoreCarry;
+  byePolicy?: ByePolicy;
+  allowRematches?: boolean;
+}
+
+// Starting values for the results screen's export controls
+export interface ExportDefaults {
+  min_score?: number;
+  // null exports everything past min_score
+  top_n?: number | null;
+  write_manifest?: boolean;
+  note_tagging?: 'filename' | 'sidecar' | null;
+  path_template?: string | null;
+}
+
+export interface Preset {
+  name: string;
+  threshold_schedule: number[];
+  adaptive_cutoff: number | null;
+  score_carry?: ScoreCarry;
+  plugin: string | null;
+  scan: ScanOptions;
+  export?: ExportDefaults;
+}
+
+// A template applied and its folder scanned, ready to start
+export interface TemplateStart {
+  template: Preset;
+  directory: string;
+  outcome: ScanOutcome;
+}
+
+export interface CalibrationCheck {
+  round: number;
+  opponent: string;
+  opponent_score: number;
+  reference_won: boolean;
+}
+
+export interface Reference {
+  path: string;
+  interval: number;
+  checks: CalibrationCheck[];
+}
+
+export interface CalibrationReport {
+  checks: number;
+  baseline_win_rate: number | null;
+  recent_win_rate: number | null;
+  drifting: boolean;
+}
+
+export type VoteChoice = 'a' | 'b' | 'skip';
+
+export interface Ballot {
+  voter_id: string;
+  voter_name: string;
+  choice: VoteChoice;
+}
+
+export interface VoteRecord {
+  pair_id: string;
+  round: number;
+  a: string;
+  b: string;
+  winner: VoteChoice;
+  ballots: Ballot[];
+}
+
+export type DeepLinkAction =
+  | { action: 'open'; save: string }
+  | { action: 'scan'; directory: string };
+
 export type AppView = 'home' | 'battle' | 'results';
 
 export interface AppState {